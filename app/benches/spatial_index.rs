@@ -0,0 +1,69 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nalgebra::{Matrix3, Vector3};
+use sg_geometry::spatial_index::{CubeMapIndex, DEFAULT_RESOLUTION};
+
+const SECONDS_TO_BENCHMARK: u64 = 10;
+const POINTS: usize = 100_000;
+
+fn random_points() -> Vec<Vector3<f32>> {
+    let mut rng = rand::thread_rng();
+    (0..POINTS)
+        .map(|_| {
+            let (ra, dec) = sg_geometry::generate_random_point(&mut rng);
+            sg_geometry::get_point_vector(ra, dec, &Matrix3::identity())
+        })
+        .collect()
+}
+
+pub fn culling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("culling");
+    group.measurement_time(std::time::Duration::from_secs(SECONDS_TO_BENCHMARK));
+
+    let points = random_points();
+    let index = CubeMapIndex::from_points(DEFAULT_RESOLUTION, points.iter().enumerate().map(|(i, point)| (*point, i)));
+    let centre = Vector3::new(0.3, -0.2, 0.9).normalize();
+
+    for radius_deg in [5.0_f32, 30.0, 90.0] {
+        let radius = angle::Rad(radius_deg.to_radians());
+        let min_cos = radius.0.cos();
+        group.bench_function(format!("brute force within {radius_deg}°"), |b| {
+            b.iter(|| points.iter().filter(|point| point.dot(black_box(&centre)) >= min_cos).count())
+        });
+        group.bench_function(format!("cube map within {radius_deg}°"), |b| b.iter(|| index.within(black_box(&centre), radius).count()));
+        group.bench_function(format!("cube map candidates within {radius_deg}°"), |b| {
+            b.iter(|| index.candidates_within(black_box(&centre), radius).count())
+        });
+    }
+
+    group.finish();
+}
+
+pub fn picking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("picking");
+    group.measurement_time(std::time::Duration::from_secs(SECONDS_TO_BENCHMARK));
+
+    let points = random_points();
+    let index = CubeMapIndex::from_points(DEFAULT_RESOLUTION, points.iter().enumerate().map(|(i, point)| (*point, i)));
+    let cursor = Vector3::new(-0.5, 0.5, 0.1).normalize();
+    let max_radius = angle::Rad(0.5_f32.to_radians());
+
+    group.bench_function("brute force nearest", |b| {
+        b.iter(|| {
+            points
+                .iter()
+                .enumerate()
+                .map(|(i, point)| (i, point.dot(black_box(&cursor))))
+                .filter(|(_, cos)| *cos >= max_radius.0.cos())
+                .max_by(|(_, cos_1), (_, cos_2)| cos_1.total_cmp(cos_2))
+        })
+    });
+    group.bench_function("cube map nearest", |b| b.iter(|| index.nearest(black_box(&cursor), max_radius).map(|(i, _)| *i)));
+    group.bench_function("cube map build", |b| {
+        b.iter(|| CubeMapIndex::from_points(DEFAULT_RESOLUTION, points.iter().enumerate().map(|(i, point)| (*point, i))).len())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, culling, picking);
+criterion_main!(benches);
@@ -6,7 +6,9 @@ const SKY_OBJECTS_FOLDER: &str = "./sphere/sky-objects";
 const LINES_FOLDER: &str = "./sphere/lines";
 const MARKERS_FOLDER: &str = "./sphere/markers";
 const STAR_NAMES_FOLDER: &str = "./sphere/named-stars";
+const SHAPES_FOLDER: &str = "./sphere/shapes";
 const CONSTELLATION_NAMES: &str = "./data/constellations.csv";
+const BRIGHT_STARS: &str = "./sphere/stars/stars.csv";
 
 fn zero_nothing(num: i64) -> String {
     String::from(if num < 10 { "0" } else { "" })
@@ -35,6 +37,7 @@ fn main() {
             ["lines", LINES_FOLDER],
             ["markers", MARKERS_FOLDER],
             ["star names", STAR_NAMES_FOLDER],
+            ["shapes", SHAPES_FOLDER],
         ];
         let mut sky_data = Vec::new();
 
@@ -68,6 +71,10 @@ fn main() {
             #[allow(clippy::single_char_pattern)] // No idea why, but `"\""` works while `'"'` does not
             other_sky_data.push([String::from("constellation names"), file_content.replace("\"", "\\\"")])
         };
+        if let Ok(file_content) = fs::read_to_string(BRIGHT_STARS) {
+            #[allow(clippy::single_char_pattern)] // No idea why, but `"\""` works while `'"'` does not
+            other_sky_data.push([String::from("bright stars"), file_content.replace("\"", "\\\"")])
+        };
 
         vec![const_declaration!(pub SKY_DATA_LISTS = sky_data), const_declaration!(pub SKY_DATA_FILES = other_sky_data)]
     } else {
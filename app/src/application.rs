@@ -88,6 +88,7 @@ impl Application {
         let mut graphics_settings = graphics_settings::GraphicsSettings::default(); // Default in case there are no saved graphics settings
         let mut input = input::Input::default();
         let mut initial_setup_stage = initial_setup::InitialSetupStage::default();
+        let mut language = crate::localisation::Language::default();
         if let Some(storage) = cc.storage {
             if let Some(time_spent_restore) = storage.get_string(StorageKeys::TimeSpent.as_ref()) {
                 match time_spent_restore.parse() {
@@ -128,7 +129,14 @@ impl Application {
                     Err(err) => log::error!("Failed to deserialize the state of showing initial setup: {err}"),
                 }
             }
+            if let Some(language_str) = storage.get_string(StorageKeys::Language.as_ref()) {
+                match serde_json::from_str(&language_str) {
+                    Ok(language_loaded) => language = language_loaded,
+                    Err(err) => log::error!("Failed to deserialize the language: {err}"),
+                }
+            }
         }
+        crate::localisation::set_language(language);
         let first_application_launch = time_spent_start == 0;
         let timestamp = chrono::Utc::now().timestamp();
         let mut state = state::State::new(timestamp, time_spent_start);
@@ -183,6 +191,7 @@ impl Application {
 
             initial_setup_stage,
         };
+        app.threads_communication.tiled_stars = app.cellestial_sphere.load_tiles_in_background();
         if crate::config::ENABLE_UPDATES_CHECKS || app.testing_mode {
             server_communication::check_for_updates::check_for_updates(
                 &mut app.threads_communication,
@@ -206,6 +215,9 @@ impl eframe::App for Application {
         self.frames_handler.current_frame.timestamp_ns = chrono::Local::now().timestamp_nanos_opt().expect("Date out of bounds.");
         self.frame_timestamp = chrono::Utc::now().timestamp();
         self.screen_width = ScreenWidth::from_width(ctx.screen_rect().size().x);
+        if self.cellestial_sphere.update_camera_transition(self.frames_handler.current_frame.timestamp_ns) {
+            self.cellestial_sphere.init_renderers();
+        }
         let cursor_within_central_panel = self.render(ctx);
         self.handle_input(cursor_within_central_panel, ctx);
         self.receive_threads_messages();
@@ -213,23 +225,7 @@ impl eframe::App for Application {
         self.frames_handler.handle();
         self.frames_handler.last_frame = chrono::Local::now().timestamp_nanos_opt().expect("Date out of bounds.");
         if self.game_handler.switch_to_next_part {
-            let data = game_handler::QuestionCheckingData {
-                cellestial_sphere: &mut self.cellestial_sphere,
-                theme: &self.theme,
-                game_stage: &mut self.game_handler.stage,
-                score: &mut self.game_handler.score,
-                possible_score: &mut self.game_handler.possible_score,
-                is_scored_mode: self.game_handler.game_settings.is_scored_mode,
-                current_question: self.game_handler.current_question,
-                used_questions: &mut self.game_handler.used_questions,
-                add_marker_on_click: &mut self.game_handler.add_marker_on_click,
-                questions_settings: &self.game_handler.questions_settings,
-                question_number: &mut self.game_handler.question_number,
-                start_next_question: &mut self.game_handler.switch_to_next_question,
-                switch_to_next_part: &mut self.game_handler.switch_to_next_part,
-            };
-            self.game_handler.question_catalog[self.game_handler.current_question].generic_to_next_part(data);
-            self.game_handler.switch_to_next_part = false;
+            self.game_handler.next_part(&mut self.cellestial_sphere, &self.theme);
         }
         if self.game_handler.switch_to_next_question {
             self.game_handler.next_question(&mut self.cellestial_sphere, &self.theme);
@@ -252,6 +248,9 @@ impl eframe::App for Application {
         }
         self.input.input_field_had_focus_last_frame = input_field_has_focus;
 
+        if self.graphics_settings.night_vision {
+            crate::rendering::night_vision::apply_night_vision(ctx, self.graphics_settings.night_vision_brightness);
+        }
         ctx.request_repaint();
     }
 
@@ -296,6 +295,11 @@ impl eframe::App for Application {
             Err(err) => log::error!("Failed to serialize the initial setup stage: {:?}", err),
         }
 
+        match serde_json::to_string(&crate::localisation::language()) {
+            Ok(string) => storage.set_string(StorageKeys::Language.as_ref(), string),
+            Err(err) => log::error!("Failed to serialize the language: {:?}", err),
+        }
+
         let question_packs = self
             .game_handler
             .question_packs
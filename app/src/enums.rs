@@ -2,14 +2,6 @@ use eframe::egui;
 use egui::epaint::Pos2;
 use std::fmt::Display;
 
-pub enum Inputs {
-    AltShiftI,
-    AltShiftO,
-    AltShiftS,
-    MouseMiddle,
-    Space,
-}
-
 pub enum PointerPosition {
     OnScreen(Pos2),
     OffScreen,
@@ -52,7 +44,7 @@ impl LightPollution {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum GameStage {
     Guessing,
     Checked,
@@ -82,6 +74,7 @@ pub enum StorageKeys {
     QuestionPacks,
     InputSettings,
     InitialSetupStage,
+    Language,
 }
 
 impl AsRef<str> for StorageKeys {
@@ -107,6 +100,7 @@ impl AsRef<str> for StorageKeys {
             Self::QuestionPacks => "question_packs",
             Self::InputSettings => "input_settings",
             Self::InitialSetupStage => "initial_setup_stage",
+            Self::Language => "language",
         }
     }
 }
@@ -153,5 +147,6 @@ pub enum RendererCategory {
     Stars,
     Lines,
     Deepskies,
+    Shapes,
     Markers,
 }
@@ -0,0 +1,450 @@
+use eframe::egui::{Color32, Pos2, Vec2};
+use std::fmt::Write;
+
+/// How much wider than its size a character of the chart font is on average, used to centre texts in the PDF where the font metrics are not known
+const AVERAGE_CHARACTER_WIDTH: f32 = 0.5;
+/// The distance between the baselines of two lines of a paragraph, relative to the size of the text
+const LINE_SPACING: f32 = 1.3;
+const FRAME_WIDTH: f32 = 0.8;
+/// The distance of the control points of a cubic Bézier curve approximating a quarter of a circle, relative to its radius
+const CIRCLE_BEZIER_FACTOR: f32 = 0.552_284_8;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TextAnchor {
+    Start,
+    Middle,
+}
+
+pub enum ChartElement {
+    Line {
+        start: Pos2,
+        end: Pos2,
+        width: f32,
+        colour: Color32,
+        dashed: bool,
+    },
+    Circle {
+        centre: Pos2,
+        radius: f32,
+        fill: Option<Color32>,
+        stroke: Option<(f32, Color32)>,
+    },
+    Text {
+        position: Pos2,
+        size: f32,
+        text: String,
+        colour: Color32,
+        anchor: TextAnchor,
+    },
+}
+
+/// A vector drawing independent of the output format, with the origin in the top left corner and the sizes in points (1/72 of an inch)
+pub struct Chart {
+    pub width: f32,
+    pub height: f32,
+    pub background: Color32,
+    pub elements: Vec<ChartElement>,
+}
+
+impl Chart {
+    pub fn new(width: f32, height: f32, background: Color32) -> Self {
+        Self {
+            width,
+            height,
+            background,
+            elements: Vec::new(),
+        }
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}pt" height="{h}pt" viewBox="0 0 {w} {h}">"#,
+            w = self.width,
+            h = self.height
+        );
+        let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, svg_colour(self.background));
+        for element in &self.elements {
+            let _ = match element {
+                ChartElement::Line { start, end, width, colour, dashed } => writeln!(
+                    svg,
+                    r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="{width:.2}" stroke-linecap="round"{}/>"#,
+                    start.x,
+                    start.y,
+                    end.x,
+                    end.y,
+                    svg_colour(*colour),
+                    if *dashed { r#" stroke-dasharray="3 2""# } else { "" }
+                ),
+                ChartElement::Circle { centre, radius, fill, stroke } => writeln!(
+                    svg,
+                    r#"<circle cx="{:.2}" cy="{:.2}" r="{radius:.2}" fill="{}"{}/>"#,
+                    centre.x,
+                    centre.y,
+                    fill.map(svg_colour).unwrap_or(String::from("none")),
+                    match stroke {
+                        Some((width, colour)) => format!(r#" stroke="{}" stroke-width="{width:.2}""#, svg_colour(*colour)),
+                        None => String::new(),
+                    }
+                ),
+                ChartElement::Text { position, size, text, colour, anchor } => writeln!(
+                    svg,
+                    r#"<text x="{:.2}" y="{:.2}" font-family="Helvetica, Arial, sans-serif" font-size="{size:.1}" fill="{}" text-anchor="{}">{}</text>"#,
+                    position.x,
+                    position.y,
+                    svg_colour(*colour),
+                    match anchor {
+                        TextAnchor::Start => "start",
+                        TextAnchor::Middle => "middle",
+                    },
+                    escape_xml(text)
+                ),
+            };
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// A single page PDF using only the standard Helvetica font, so nothing has to be embedded
+    pub fn to_pdf(&self) -> Vec<u8> {
+        pdf_document(std::slice::from_ref(self))
+    }
+
+    /// The content stream of the chart as a page of a PDF
+    fn pdf_content(&self) -> String {
+        // PDF has the origin in the bottom left corner
+        let y = |y: f32| self.height - y;
+        let mut content = String::new();
+        let _ = writeln!(content, "{} rg 0 0 {} {} re f", pdf_colour(self.background), self.width, self.height);
+        let _ = writeln!(content, "0 0 {} {} re W n", self.width, self.height);
+        content.push_str("1 J 1 j\n");
+        for element in &self.elements {
+            match element {
+                ChartElement::Line { start, end, width, colour, dashed } => {
+                    let _ = writeln!(
+                        content,
+                        "{} RG {width:.2} w {} {:.2} {:.2} m {:.2} {:.2} l S",
+                        pdf_colour(*colour),
+                        if *dashed { "[3 2] 0 d" } else { "[] 0 d" },
+                        start.x,
+                        y(start.y),
+                        end.x,
+                        y(end.y)
+                    );
+                }
+                ChartElement::Circle { centre, radius, fill, stroke } => {
+                    let (x, y, r) = (centre.x, y(centre.y), *radius);
+                    let k = r * CIRCLE_BEZIER_FACTOR;
+                    let _ = write!(
+                        content,
+                        "{:.2} {:.2} m {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c ",
+                        x + r,
+                        y,
+                        x + r,
+                        y + k,
+                        x + k,
+                        y + r,
+                        x,
+                        y + r,
+                        x - k,
+                        y + r,
+                        x - r,
+                        y + k,
+                        x - r,
+                        y,
+                        x - r,
+                        y - k,
+                        x - k,
+                        y - r,
+                        x,
+                        y - r,
+                        x + k,
+                        y - r,
+                        x + r,
+                        y - k,
+                        x + r,
+                        y
+                    );
+                    if let Some(fill) = fill {
+                        let _ = write!(content, "{} rg ", pdf_colour(*fill));
+                    }
+                    if let Some((width, colour)) = stroke {
+                        let _ = write!(content, "{} RG {width:.2} w [] 0 d ", pdf_colour(*colour));
+                    }
+                    content.push_str(match (fill, stroke) {
+                        (Some(_), Some(_)) => "B\n",
+                        (Some(_), None) => "f\n",
+                        (None, Some(_)) => "S\n",
+                        (None, None) => "n\n",
+                    });
+                }
+                ChartElement::Text { position, size, text, colour, anchor } => {
+                    let x = match anchor {
+                        TextAnchor::Start => position.x,
+                        TextAnchor::Middle => position.x - text.chars().count() as f32 * size * AVERAGE_CHARACTER_WIDTH / 2.0,
+                    };
+                    let _ = writeln!(
+                        content,
+                        "BT {} rg /F1 {size:.1} Tf {x:.2} {:.2} Td ({}) Tj ET",
+                        pdf_colour(*colour),
+                        y(position.y),
+                        escape_pdf_text(text)
+                    );
+                }
+            }
+        }
+        content
+    }
+
+    /// Adds the elements of the other chart moved by the offset, with a frame around them
+    pub fn insert(&mut self, other: Chart, offset: Vec2) {
+        let corners = [Pos2::ZERO, Pos2::new(other.width, 0.0), Pos2::new(other.width, other.height), Pos2::new(0.0, other.height)].map(|corner| corner + offset);
+        for element in other.elements {
+            self.elements.push(match element {
+                ChartElement::Line { start, end, width, colour, dashed } => ChartElement::Line {
+                    start: start + offset,
+                    end: end + offset,
+                    width,
+                    colour,
+                    dashed,
+                },
+                ChartElement::Circle { centre, radius, fill, stroke } => ChartElement::Circle {
+                    centre: centre + offset,
+                    radius,
+                    fill,
+                    stroke,
+                },
+                ChartElement::Text { position, size, text, colour, anchor } => ChartElement::Text {
+                    position: position + offset,
+                    size,
+                    text,
+                    colour,
+                    anchor,
+                },
+            });
+        }
+        for i in 0..corners.len() {
+            self.elements.push(ChartElement::Line {
+                start: corners[i],
+                end: corners[(i + 1) % corners.len()],
+                width: FRAME_WIDTH,
+                colour: Color32::BLACK,
+                dashed: false,
+            });
+        }
+    }
+
+    /// Adds the text broken into lines no wider than the width, the position is the start of the baseline of the first line. Returns the height taken by the lines.
+    pub fn add_paragraph(&mut self, position: Pos2, width: f32, size: f32, colour: Color32, text: &str) -> f32 {
+        let max_characters = ((width / (size * AVERAGE_CHARACTER_WIDTH)) as usize).max(1);
+        let mut lines: Vec<String> = Vec::new();
+        for word in text.split_whitespace() {
+            match lines.last_mut() {
+                Some(line) if line.chars().count() + 1 + word.chars().count() <= max_characters => {
+                    line.push(' ');
+                    line.push_str(word);
+                }
+                _ => lines.push(word.to_string()),
+            }
+        }
+        let line_height = size * LINE_SPACING;
+        for (i, line) in lines.iter().enumerate() {
+            self.elements.push(ChartElement::Text {
+                position: position + Vec2::new(0.0, i as f32 * line_height),
+                size,
+                text: line.clone(),
+                colour,
+                anchor: TextAnchor::Start,
+            });
+        }
+        lines.len() as f32 * line_height
+    }
+}
+
+/// A PDF with every chart on its own page
+pub fn pdf_document(pages: &[Chart]) -> Vec<u8> {
+    // The catalog, the page tree and the font come first, then the page and its content for every chart
+    let mut objects = vec![
+        String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len()).map(|i| format!("{} 0 R", 4 + 2 * i)).collect::<Vec<String>>().join(" "),
+            pages.len()
+        ),
+        String::from("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"),
+    ];
+    for (i, page) in pages.iter().enumerate() {
+        let content = page.pdf_content();
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            page.width,
+            page.height,
+            5 + 2 * i
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{content}endstream", content.len()));
+    }
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{object}\nendobj\n", i + 1);
+    }
+    let xref_offset = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{offset:010} 00000 n ");
+    }
+    let _ = write!(pdf, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n", objects.len() + 1);
+    pdf.into_bytes()
+}
+
+/// An HTML page showing the charts one below another, each printed on its own sheet
+pub fn html_document(title: &str, pages: &[Chart]) -> String {
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>", escape_xml(title));
+    html.push_str("<style>section { page-break-after: always; margin-bottom: 1em; } svg { max-width: 100%; height: auto; }</style>\n</head>\n<body>\n");
+    for page in pages {
+        let _ = write!(html, "<section>\n{}</section>\n", page.to_svg());
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn svg_colour(colour: Color32) -> String {
+    if colour.a() == 255 {
+        format!("rgb({},{},{})", colour.r(), colour.g(), colour.b())
+    } else {
+        format!("rgba({},{},{},{:.3})", colour.r(), colour.g(), colour.b(), colour.a() as f32 / 255.0)
+    }
+}
+
+fn pdf_colour(colour: Color32) -> String {
+    format!("{:.3} {:.3} {:.3}", colour.r() as f32 / 255.0, colour.g() as f32 / 255.0, colour.b() as f32 / 255.0)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Escapes the text for a PDF string in the WinAnsi encoding of the standard fonts
+///
+/// The encoding only covers Latin-1 and a few extra characters (Š, Ž, Œ, typographic quotes and dashes, €, ...), so most letters of the
+/// Central European alphabets (č, ř, ě, ů, ...) and all of Greek and Cyrillic can not be shown and are replaced by question marks.
+/// The SVG export has no such limitation.
+fn escape_pdf_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            ' '..='~' => escaped.push(character),
+            _ => match win_ansi_code(character) {
+                Some(code) => {
+                    let _ = write!(escaped, "\\{:03o}", code);
+                }
+                None => escaped.push('?'),
+            },
+        }
+    }
+    escaped
+}
+
+/// The code of a non-ASCII character in the WinAnsi encoding, if it has one
+fn win_ansi_code(character: char) -> Option<u8> {
+    let code = match character {
+        '\u{a0}'..='\u{ff}' => character as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8a,
+        '‹' => 0x8b,
+        'Œ' => 0x8c,
+        'Ž' => 0x8e,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9a,
+        '›' => 0x9b,
+        'œ' => 0x9c,
+        'ž' => 0x9e,
+        'Ÿ' => 0x9f,
+        _ => return None,
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chart() -> Chart {
+        let mut chart = Chart::new(200.0, 100.0, Color32::WHITE);
+        chart.elements.push(ChartElement::Line {
+            start: Pos2::new(0.0, 0.0),
+            end: Pos2::new(200.0, 100.0),
+            width: 1.0,
+            colour: Color32::GRAY,
+            dashed: true,
+        });
+        chart.elements.push(ChartElement::Circle {
+            centre: Pos2::new(50.0, 50.0),
+            radius: 3.0,
+            fill: Some(Color32::BLACK),
+            stroke: None,
+        });
+        chart.elements.push(ChartElement::Text {
+            position: Pos2::new(10.0, 20.0),
+            size: 8.0,
+            text: String::from("Sirius (α CMa) <3"),
+            colour: Color32::BLACK,
+            anchor: TextAnchor::Middle,
+        });
+        chart
+    }
+
+    #[test]
+    fn svg() {
+        let svg = chart().to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"stroke-dasharray="3 2""#));
+        assert!(svg.contains(r#"<circle cx="50.00" cy="50.00" r="3.00" fill="rgb(0,0,0)"/>"#));
+        assert!(svg.contains("Sirius (α CMa) &lt;3"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn pdf_cross_references() {
+        let pdf = String::from_utf8(chart().to_pdf()).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("(Sirius \\(? CMa\\) <3) Tj"));
+        let xref_offset: usize = pdf.lines().skip_while(|line| *line != "startxref").nth(1).unwrap().parse().unwrap();
+        assert!(pdf[xref_offset..].starts_with("xref"));
+        // Every entry of the cross-reference table points at the start of its object
+        for (i, line) in pdf[xref_offset..].lines().skip(3).take(5).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+
+    #[test]
+    fn pdf_text_encoding() {
+        assert_eq!(escape_pdf_text("Žluťoučký kůň"), "\\216lu?ou?k\\375 k??");
+        assert_eq!(escape_pdf_text("Šedá – “mlhovina”"), "\\212ed\\341 \\226 \\223mlhovina\\224");
+        assert_eq!(escape_pdf_text("řeřicha"), "?e?icha");
+    }
+}
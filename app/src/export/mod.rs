@@ -0,0 +1,48 @@
+pub mod chart;
+pub mod screenshot;
+pub mod sky_chart;
+pub mod worksheet;
+
+use std::error::Error;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum ChartFormat {
+    Svg,
+    Pdf,
+}
+
+impl ChartFormat {
+    pub fn variants() -> [Self; 2] {
+        [Self::Svg, Self::Pdf]
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+impl std::fmt::Display for ChartFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Svg => write!(f, "SVG"),
+            Self::Pdf => write!(f, "PDF"),
+        }
+    }
+}
+
+pub fn save_chart(chart: &chart::Chart, format: ChartFormat, path: &std::path::Path) -> Result<(), Box<dyn Error>> {
+    let data = match format {
+        ChartFormat::Svg => chart.to_svg().into_bytes(),
+        ChartFormat::Pdf => chart.to_pdf(),
+    };
+    if let Some(folder) = path.parent() {
+        if !folder.as_os_str().is_empty() {
+            std::fs::create_dir_all(folder)?;
+        }
+    }
+    std::fs::write(path, data)?;
+    Ok(())
+}
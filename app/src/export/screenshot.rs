@@ -0,0 +1,350 @@
+use eframe::egui::{
+    self,
+    epaint::{ClippedPrimitive, ImageData, Primitive, TextureId},
+    Color32, Pos2, Rect, Vec2,
+};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error};
+
+use crate::{renderer::CellestialSphere, rendering::themes::Theme};
+
+/// The largest width or height of a screenshot, the finished 8192 × 8192 image takes 256 MiB
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const MAX_SIZE: u32 = 8192;
+/// Phones run out of memory much sooner, a 4096 × 4096 image takes 64 MiB
+#[cfg(any(target_os = "android", target_os = "ios"))]
+const MAX_SIZE: u32 = 4096;
+/// How many rows of pixels are blended at once, the blending buffer uses four f32 channels per pixel so it is kept to a band of the image
+const BAND_HEIGHT: u32 = 256;
+const MAX_TEXTURE_SIDE: usize = 8192;
+/// The space around the answer review and between its heading and text, in points
+const REVIEW_MARGIN: f32 = 8.0;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScreenshotSettings {
+    /// The size of the image in pixels, independent of the size of the window
+    pub width: u32,
+    pub height: u32,
+    /// How many pixels there are in a point, larger values make the lines and the text thicker
+    pub pixels_per_point: f32,
+    /// Whether to include the review of the answer when the screenshot is taken after answering a question
+    pub answer_review: bool,
+}
+
+impl Default for ScreenshotSettings {
+    fn default() -> Self {
+        Self {
+            width: 3840,
+            height: 2160,
+            pixels_per_point: 2.0,
+            answer_review: true,
+        }
+    }
+}
+
+impl ScreenshotSettings {
+    pub fn clamp(&mut self) {
+        self.width = self.width.clamp(1, MAX_SIZE);
+        self.height = self.height.clamp(1, MAX_SIZE);
+        self.pixels_per_point = self.pixels_per_point.clamp(0.25, 16.0);
+    }
+}
+
+/// A texture the rasteriser samples from, the pixels are sRGBA with premultiplied alpha
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Color32>,
+}
+
+impl Texture {
+    fn sample(&self, uv: Pos2) -> [f32; 4] {
+        let x = ((uv.x * self.size[0] as f32) as usize).min(self.size[0].saturating_sub(1));
+        let y = ((uv.y * self.size[1] as f32) as usize).min(self.size[1].saturating_sub(1));
+        match self.pixels.get(y * self.size[0] + x) {
+            Some(texel) => texel.to_normalized_gamma_f32(),
+            None => [1.0; 4],
+        }
+    }
+}
+
+/// The tessellated shapes of a screenshot, which can be sent to another thread to be rasterised there
+pub struct PreparedScreenshot {
+    primitives: Vec<ClippedPrimitive>,
+    textures: HashMap<TextureId, Texture>,
+    pixels_per_point: f32,
+    width: u32,
+    height: u32,
+}
+
+impl PreparedScreenshot {
+    /// Fills the shapes into the image, this is the slow part of taking a screenshot and does not need the sphere anymore
+    pub fn rasterise(&self) -> RgbaImage {
+        rasterise(&self.primitives, &self.textures, self.pixels_per_point, self.width, self.height)
+    }
+}
+
+/// Renders the sky as it is seen now, with the answer review on top of it if given, into an image of the size from the settings. Nothing is drawn on the screen.
+pub fn render_screenshot(cellestial_sphere: &mut CellestialSphere, theme: &Theme, answer_review: Option<(&str, &str)>, settings: &ScreenshotSettings) -> RgbaImage {
+    prepare_screenshot(cellestial_sphere, theme, answer_review, settings).rasterise()
+}
+
+/// Lays out the sky as it is seen now, with the answer review on top of it if given, for an image of the size from the settings, without rasterising it yet
+pub fn prepare_screenshot(cellestial_sphere: &mut CellestialSphere, theme: &Theme, answer_review: Option<(&str, &str)>, settings: &ScreenshotSettings) -> PreparedScreenshot {
+    let pixels_per_point = settings.pixels_per_point;
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(settings.width as f32, settings.height as f32) / pixels_per_point);
+
+    let ctx = egui::Context::default();
+    ctx.set_visuals(theme.egui_visuals.clone());
+    let mut raw_input = egui::RawInput {
+        screen_rect: Some(screen_rect),
+        max_texture_side: Some(MAX_TEXTURE_SIDE),
+        ..Default::default()
+    };
+    raw_input.viewports.entry(egui::ViewportId::ROOT).or_default().native_pixels_per_point = Some(pixels_per_point);
+
+    // The renderers cull everything outside of the viewport, so they have to be set up for the size of the screenshot
+    let viewport_rect = cellestial_sphere.viewport_rect;
+    cellestial_sphere.viewport_rect = screen_rect;
+    cellestial_sphere.init_renderers();
+    let sphere = &*cellestial_sphere;
+    let output = ctx.run(raw_input, |ctx| {
+        let painter = ctx.layer_painter(egui::LayerId::background());
+        painter.rect_filled(screen_rect, 0.0, theme.egui_visuals.panel_fill);
+        sphere.render_sky(&painter);
+        if let Some((heading, text)) = answer_review {
+            paint_answer_review(ctx, screen_rect, heading, text);
+        }
+    });
+    cellestial_sphere.viewport_rect = viewport_rect;
+    cellestial_sphere.init_renderers();
+
+    let mut textures = HashMap::new();
+    for (id, delta) in output.textures_delta.set {
+        let (size, pixels): ([usize; 2], Vec<Color32>) = match &delta.image {
+            ImageData::Color(image) => (image.size, image.pixels.clone()),
+            ImageData::Font(image) => (image.size, image.srgba_pixels(None).collect()),
+        };
+        match (delta.pos, textures.get_mut(&id)) {
+            (
+                Some([x, y]),
+                Some(Texture {
+                    size: [texture_width, _],
+                    pixels: texture_pixels,
+                }),
+            ) => {
+                for row in 0..size[1] {
+                    let start = (y + row) * *texture_width + x;
+                    if let Some(target) = texture_pixels.get_mut(start..start + size[0]) {
+                        target.copy_from_slice(&pixels[row * size[0]..(row + 1) * size[0]]);
+                    }
+                }
+            }
+            _ => {
+                textures.insert(id, Texture { size, pixels });
+            }
+        }
+    }
+    PreparedScreenshot {
+        primitives: ctx.tessellate(output.shapes, output.pixels_per_point),
+        textures,
+        pixels_per_point: output.pixels_per_point,
+        width: settings.width,
+        height: settings.height,
+    }
+}
+
+/// Paints the heading and the text of the answer review in a box in the top left corner, looking like the question window
+fn paint_answer_review(ctx: &egui::Context, screen_rect: Rect, heading: &str, text: &str) {
+    let style = ctx.style();
+    let visuals = &style.visuals;
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("Answer review")));
+    let wrap_width = (screen_rect.width() / 3.0).max(200.0).min(screen_rect.width() - 4.0 * REVIEW_MARGIN);
+    let heading = painter.layout(heading.to_owned(), egui::TextStyle::Heading.resolve(&style), visuals.strong_text_color(), wrap_width);
+    let text = painter.layout(text.to_owned(), egui::TextStyle::Body.resolve(&style), visuals.text_color(), wrap_width);
+
+    let origin = screen_rect.min + Vec2::splat(2.0 * REVIEW_MARGIN);
+    let size = Vec2::new(heading.size().x.max(text.size().x), heading.size().y + REVIEW_MARGIN + text.size().y) + Vec2::splat(2.0 * REVIEW_MARGIN);
+    painter.rect(
+        Rect::from_min_size(origin, size),
+        visuals.window_corner_radius,
+        visuals.window_fill,
+        visuals.window_stroke,
+        egui::StrokeKind::Inside,
+    );
+    let text_position = origin + Vec2::new(REVIEW_MARGIN, 2.0 * REVIEW_MARGIN + heading.size().y);
+    painter.galley(origin + Vec2::splat(REVIEW_MARGIN), heading, visuals.text_color());
+    painter.galley(text_position, text, visuals.text_color());
+}
+
+/// Fills the triangles of the meshes into an image, blending them the same way as the GPU does with premultiplied alpha. The image is blended band by band, so that only one band needs the precise blending buffer.
+fn rasterise(primitives: &[ClippedPrimitive], textures: &HashMap<TextureId, Texture>, pixels_per_point: f32, width: u32, height: u32) -> RgbaImage {
+    let mut image = RgbaImage::new(width, height);
+    let mut buffer = vec![[0.0_f32; 4]; width as usize * BAND_HEIGHT.min(height) as usize];
+    for top in (0..height).step_by(BAND_HEIGHT as usize) {
+        let bottom = (top + BAND_HEIGHT).min(height);
+        buffer.fill([0.0; 4]);
+        rasterise_band(primitives, textures, pixels_per_point, width, top..bottom, &mut buffer);
+        for y in top..bottom {
+            for x in 0..width {
+                let [r, g, b, a] = buffer[(y - top) as usize * width as usize + x as usize];
+                // PNGs are not premultiplied
+                let unmultiply = |channel: f32| if a > 0.0 { (channel / a).clamp(0.0, 1.0) } else { 0.0 };
+                image.put_pixel(
+                    x,
+                    y,
+                    image::Rgba([unmultiply(r), unmultiply(g), unmultiply(b), a.clamp(0.0, 1.0)].map(|channel| (channel * 255.0).round() as u8)),
+                );
+            }
+        }
+    }
+    image
+}
+
+/// Blends the parts of the triangles inside the rows into the buffer, which holds just these rows
+fn rasterise_band(primitives: &[ClippedPrimitive], textures: &HashMap<TextureId, Texture>, pixels_per_point: f32, width: u32, rows: std::ops::Range<u32>, buffer: &mut [[f32; 4]]) {
+    let band_rect = Rect::from_min_max(Pos2::new(0.0, rows.start as f32), Pos2::new(width as f32, rows.end as f32));
+    for primitive in primitives {
+        let Primitive::Mesh(mesh) = &primitive.primitive else {
+            continue;
+        };
+        let clip_rect = Rect::from_min_max(primitive.clip_rect.min * pixels_per_point, primitive.clip_rect.max * pixels_per_point).intersect(band_rect);
+        if !clip_rect.is_positive() {
+            continue;
+        }
+        let texture = textures.get(&mesh.texture_id);
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [Some(a), Some(b), Some(c)] = [0, 1, 2].map(|i| mesh.vertices.get(triangle[i] as usize)) else {
+                continue;
+            };
+            let positions = [a.pos, b.pos, c.pos].map(|pos| pos * pixels_per_point);
+            let mut vertices = [(positions[0], a), (positions[1], b), (positions[2], c)];
+            let mut area = edge(positions[0], positions[1], positions[2]);
+            if area.abs() < f32::EPSILON {
+                continue;
+            }
+            // Makes all the triangles wound the same way, so that the fill rule below works for them
+            if area < 0.0 {
+                vertices.swap(1, 2);
+                area = -area;
+            }
+            let [(p0, v0), (p1, v1), (p2, v2)] = vertices;
+            let bounds = Rect::from_points(&[p0, p1, p2]).intersect(clip_rect);
+            if !bounds.is_positive() {
+                continue;
+            }
+            let colours = [v0, v1, v2].map(|vertex| vertex.color.to_normalized_gamma_f32());
+            for y in (bounds.min.y.floor() as u32).max(rows.start)..(bounds.max.y.ceil() as u32).min(rows.end) {
+                for x in (bounds.min.x.floor() as u32)..(bounds.max.x.ceil() as u32).min(width) {
+                    let point = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+                    if !clip_rect.contains(point) {
+                        continue;
+                    }
+                    let weights = [edge(p1, p2, point), edge(p2, p0, point), edge(p0, p1, point)];
+                    let edges = [(p1, p2), (p2, p0), (p0, p1)];
+                    if weights.iter().zip(edges).any(|(&weight, (start, end))| weight < 0.0 || (weight == 0.0 && !is_top_left(start, end))) {
+                        continue;
+                    }
+                    let weights = weights.map(|weight| weight / area);
+                    let uv = (v0.uv.to_vec2() * weights[0] + v1.uv.to_vec2() * weights[1] + v2.uv.to_vec2() * weights[2]).to_pos2();
+                    let texel = texture.map(|texture| texture.sample(uv)).unwrap_or([1.0; 4]);
+                    let target = &mut buffer[(y - rows.start) as usize * width as usize + x as usize];
+                    let alpha = (colours[0][3] * weights[0] + colours[1][3] * weights[1] + colours[2][3] * weights[2]) * texel[3];
+                    for channel in 0..4 {
+                        let source = (colours[0][channel] * weights[0] + colours[1][channel] * weights[1] + colours[2][channel] * weights[2]) * texel[channel];
+                        target[channel] = source + target[channel] * (1.0 - alpha);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Twice the signed area of the triangle (start, end, point), positive when the point is on the right of the edge in screen coordinates
+fn edge(start: Pos2, end: Pos2, point: Pos2) -> f32 {
+    (end.x - start.x) * (point.y - start.y) - (end.y - start.y) * (point.x - start.x)
+}
+
+/// Pixels lying exactly on an edge shared by two triangles belong to only one of them, otherwise translucent shapes would have visible seams
+fn is_top_left(start: Pos2, end: Pos2) -> bool {
+    end.y < start.y || (end.y == start.y && end.x > start.x)
+}
+
+pub fn save_screenshot(image: &RgbaImage, path: &std::path::Path) -> Result<(), Box<dyn Error>> {
+    if let Some(folder) = path.parent() {
+        if !folder.as_os_str().is_empty() {
+            std::fs::create_dir_all(folder)?;
+        }
+    }
+    image.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::epaint::Mesh;
+
+    fn rectangle(rect: Rect, colour: Color32) -> ClippedPrimitive {
+        let mut mesh = Mesh::default();
+        mesh.add_colored_rect(rect, colour);
+        ClippedPrimitive {
+            clip_rect: Rect::EVERYTHING,
+            primitive: Primitive::Mesh(mesh),
+        }
+    }
+
+    #[test]
+    fn rasterisation() {
+        let red = Color32::from_rgb(255, 0, 0);
+        let primitives = [rectangle(Rect::from_min_max(Pos2::ZERO, Pos2::new(2.0, 4.0)), red)];
+        let image = rasterise(&primitives, &HashMap::new(), 1.0, 4, 4);
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 3).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(2, 0).0, [0, 0, 0, 0]);
+
+        // The diagonal shared by the two triangles of the rectangle is not blended twice
+        let translucent = Color32::from_rgba_unmultiplied(0, 0, 255, 128);
+        let image = rasterise(&[rectangle(Rect::from_min_max(Pos2::ZERO, Pos2::new(4.0, 4.0)), translucent)], &HashMap::new(), 1.0, 4, 4);
+        let first = image.get_pixel(0, 1);
+        assert!(image.pixels().all(|pixel| pixel == first));
+
+        // Points are scaled into pixels
+        let image = rasterise(&primitives, &HashMap::new(), 2.0, 8, 8);
+        assert_eq!(image.get_pixel(3, 7).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(4, 0).0, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn screenshots_fit_into_memory() {
+        let mut settings = ScreenshotSettings {
+            width: 100_000,
+            height: 0,
+            ..Default::default()
+        };
+        settings.clamp();
+        assert_eq!((settings.width, settings.height), (MAX_SIZE, 1));
+
+        // Shapes crossing the bands are filled in all of them, and only once
+        let translucent = Color32::from_rgba_unmultiplied(0, 0, 255, 128);
+        let height = 2 * BAND_HEIGHT + 10;
+        let image = rasterise(
+            &[rectangle(Rect::from_min_max(Pos2::ZERO, Pos2::new(2.0, height as f32)), translucent)],
+            &HashMap::new(),
+            1.0,
+            4,
+            height,
+        );
+        let first = *image.get_pixel(0, 0);
+        assert_ne!(first.0[3], 0);
+        for y in 0..height {
+            assert_eq!(*image.get_pixel(1, y), first);
+            assert_eq!(image.get_pixel(2, y).0, [0, 0, 0, 0]);
+        }
+
+        // The rasterisation runs in its own thread
+        fn assert_send<T: Send + 'static>() {}
+        assert_send::<PreparedScreenshot>();
+    }
+}
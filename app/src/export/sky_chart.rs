@@ -0,0 +1,367 @@
+use angle::Angle;
+use eframe::egui::{self, Color32, Pos2};
+use nalgebra::{Matrix3, Rotation3, Vector3};
+use serde::{Deserialize, Serialize};
+use sg_geometry::{intersections, projection::Projection, LineSegment, Rectangle};
+
+use crate::renderer::CellestialSphere;
+
+use super::chart::{Chart, ChartElement, TextAnchor};
+
+const BACKGROUND_COLOUR: Color32 = Color32::WHITE;
+const STAR_COLOUR: Color32 = Color32::BLACK;
+const LINE_COLOUR: Color32 = Color32::from_gray(150);
+const GRID_COLOUR: Color32 = Color32::from_gray(190);
+const DEEPSKY_COLOUR: Color32 = Color32::from_gray(80);
+const LABEL_COLOUR: Color32 = Color32::from_gray(40);
+const CONSTELLATION_NAME_COLOUR: Color32 = Color32::from_gray(130);
+const MARKER_COLOUR: Color32 = Color32::from_rgb(200, 30, 30);
+
+const STAR_MIN_RADIUS: f32 = 0.5;
+const STAR_MAX_RADIUS: f32 = 6.0;
+const STAR_RADIUS_PER_MAGNITUDE: f32 = 0.5;
+/// The white ring around the stars, so that they stand out from the lines going through them
+const STAR_OUTLINE_WIDTH: f32 = 0.6;
+const LINE_WIDTH: f32 = 0.6;
+const GRID_LINE_WIDTH: f32 = 0.4;
+/// How far apart the points along the grid lines are, in degrees
+const GRID_SAMPLE_STEP: f32 = 1.0;
+const DEEPSKY_RADIUS: f32 = 3.0;
+const MARKER_RADIUS: f32 = 7.0;
+const MARKER_LINE_WIDTH: f32 = 1.2;
+const STAR_LABEL_SIZE: f32 = 6.0;
+const DEEPSKY_LABEL_SIZE: f32 = 5.5;
+const CONSTELLATION_NAME_SIZE: f32 = 8.0;
+const GRID_LABEL_SIZE: f32 = 5.5;
+const MARKER_LABEL_SIZE: f32 = 9.0;
+
+/// What part of the sky goes onto the chart and what is drawn on it
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ChartSettings {
+    /// Takes the orientation and the zoom of the camera instead of the region given below
+    pub current_view: bool,
+    pub centre_ra: angle::Deg<f32>,
+    pub centre_dec: angle::Deg<f32>,
+    /// The field of view across the diagonal of the page in degrees
+    pub fov: f32,
+    pub projection: Projection,
+    /// The faintest stars still drawn
+    pub magnitude_limit: f32,
+    /// The size of the page in points (1/72 of an inch)
+    pub width: f32,
+    pub height: f32,
+    /// Leaves out all the names, for practising with pen and paper
+    pub blank: bool,
+    /// The faintest stars getting their names written next to them
+    pub star_names_magnitude_limit: f32,
+    pub grid: bool,
+    /// The distance between the lines of the grid in degrees
+    pub grid_step: f32,
+    pub lines: bool,
+    pub deepskies: bool,
+}
+
+impl Default for ChartSettings {
+    fn default() -> Self {
+        Self {
+            current_view: true,
+            // Orion
+            centre_ra: angle::Deg(83.8),
+            centre_dec: angle::Deg(0.0),
+            fov: 60.0,
+            projection: Projection::Stereographic,
+            magnitude_limit: 5.5,
+            // A4 landscape
+            width: 842.0,
+            height: 595.0,
+            blank: false,
+            star_names_magnitude_limit: 2.0,
+            grid: true,
+            grid_step: 15.0,
+            lines: true,
+            deepskies: true,
+        }
+    }
+}
+
+impl ChartSettings {
+    pub fn clamp(&mut self) {
+        self.fov = self.fov.clamp(1.0, 360.0);
+        self.width = self.width.clamp(72.0, 14400.0);
+        self.height = self.height.clamp(72.0, 14400.0);
+        self.grid_step = self.grid_step.clamp(1.0, 90.0);
+        self.centre_dec = angle::Deg(self.centre_dec.0.clamp(-90.0, 90.0));
+        self.centre_ra = angle::Deg(self.centre_ra.0.rem_euclid(360.0));
+    }
+}
+
+/// A point marked on the chart, for example the objects asked about on a worksheet
+pub struct ChartMarker {
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    pub label: String,
+}
+
+/// Maps the sky onto the page of the chart
+pub struct ChartView {
+    rotation: Rotation3<f32>,
+    zoom: f32,
+    projection: Projection,
+    rect: egui::Rect,
+}
+
+impl ChartView {
+    pub fn new(cellestial_sphere: &CellestialSphere, settings: &ChartSettings) -> Self {
+        let rect = egui::Rect::from_min_size(Pos2::ZERO, egui::vec2(settings.width, settings.height));
+        if settings.current_view {
+            // The zoom is relative to the diagonal, so the page shows the same field of view as the screen, only the aspect ratio may differ
+            return Self {
+                rotation: cellestial_sphere.rotation,
+                zoom: cellestial_sphere.zoom,
+                projection: cellestial_sphere.sky_settings.projection,
+                rect,
+            };
+        }
+        let centre = sg_geometry::get_point_vector(settings.centre_ra, settings.centre_dec, &Matrix3::identity());
+        Self {
+            rotation: sg_geometry::north_up_rotation(&centre).unwrap_or(Rotation3::identity()),
+            zoom: settings.projection.fov_to_zoom(settings.fov),
+            projection: settings.projection,
+            rect,
+        }
+    }
+
+    fn vector(&self, ra: angle::Deg<f32>, dec: angle::Deg<f32>) -> Vector3<f32> {
+        sg_geometry::get_point_vector(ra, dec, self.rotation.matrix())
+    }
+
+    /// The position of the point on the page, None if it is not on it
+    pub fn project(&self, ra: angle::Deg<f32>, dec: angle::Deg<f32>) -> Option<Pos2> {
+        let vector = self.vector(ra, dec);
+        self.projection.project(&vector)?;
+        let (position, is_on_page) = sg_geometry::project_point(&vector, self.zoom, self.rect, self.projection);
+        is_on_page.then_some(position)
+    }
+
+    /// The ends of the line on the page, None if no part of it is on the page
+    fn segment(&self, start: (angle::Deg<f32>, angle::Deg<f32>), end: (angle::Deg<f32>, angle::Deg<f32>)) -> Option<[Pos2; 2]> {
+        let (start, end) = (self.vector(start.0, start.1), self.vector(end.0, end.1));
+        if self.projection.breaks_line(&start, &end) || self.projection.project(&start).is_none() || self.projection.project(&end).is_none() {
+            return None;
+        }
+        let (start_point, is_start_on_page) = sg_geometry::project_point(&start, self.zoom, self.rect, self.projection);
+        let (end_point, is_end_on_page) = sg_geometry::project_point(&end, self.zoom, self.rect, self.projection);
+        if is_start_on_page || is_end_on_page || intersections::rect_segment(Rectangle::from(self.rect), LineSegment::new(start_point, end_point)) {
+            Some([start_point, end_point])
+        } else {
+            None
+        }
+    }
+
+    /// The (ra, dec) of the centre of the page
+    fn centre(&self) -> (angle::Deg<f32>, angle::Deg<f32>) {
+        let [dec, ra] = sg_geometry::cast_onto_sphere_dec_ra(&self.rect, &self.rect.center(), self.rotation, self.zoom, self.projection);
+        (ra.to_deg(), dec.to_deg())
+    }
+}
+
+/// Draws the sky as seen through the view into a chart meant for printing - dark objects on a white background. The hidden objects are left out.
+pub fn build_chart(cellestial_sphere: &CellestialSphere, settings: &ChartSettings, markers: &[ChartMarker], hidden_objects: &[u64]) -> Chart {
+    let view = ChartView::new(cellestial_sphere, settings);
+    let mut chart = Chart::new(settings.width, settings.height, BACKGROUND_COLOUR);
+
+    if settings.grid {
+        add_grid(&mut chart, &view, settings.grid_step);
+    }
+    if settings.lines {
+        for lines in cellestial_sphere.lines.values().filter(|lines| lines.active) {
+            for line in &lines.lines {
+                if let Some([start, end]) = view.segment((line.ra_start, line.dec_start), (line.ra_end, line.dec_end)) {
+                    chart.elements.push(ChartElement::Line {
+                        start,
+                        end,
+                        width: LINE_WIDTH,
+                        colour: LINE_COLOUR,
+                        dashed: false,
+                    });
+                }
+            }
+        }
+    }
+    if !settings.blank {
+        for constellation in cellestial_sphere.constellations.values() {
+            let Some(centre) = constellation.centre() else {
+                continue;
+            };
+            let (dec, ra) = sg_geometry::cartesian_to_spherical(centre);
+            if let Some(position) = view.project(ra.to_deg(), dec.to_deg()) {
+                chart.elements.push(ChartElement::Text {
+                    position,
+                    size: CONSTELLATION_NAME_SIZE,
+                    text: constellation.display_name().to_uppercase(),
+                    colour: CONSTELLATION_NAME_COLOUR,
+                    anchor: TextAnchor::Middle,
+                });
+            }
+        }
+    }
+    if settings.deepskies {
+        for deepskies in cellestial_sphere.deepskies.values().filter(|deepskies| deepskies.active) {
+            for deepsky in deepskies.deepskies.iter().filter(|deepsky| !hidden_objects.contains(&deepsky.object_id)) {
+                let Some(centre) = view.project(deepsky.ra, deepsky.dec) else {
+                    continue;
+                };
+                chart.elements.push(ChartElement::Circle {
+                    centre,
+                    radius: DEEPSKY_RADIUS,
+                    fill: None,
+                    stroke: Some((LINE_WIDTH, DEEPSKY_COLOUR)),
+                });
+                if let (false, Some(label)) = (settings.blank, deepsky.label()) {
+                    chart.elements.push(ChartElement::Text {
+                        position: centre + egui::vec2(DEEPSKY_RADIUS + 1.0, -DEEPSKY_RADIUS),
+                        size: DEEPSKY_LABEL_SIZE,
+                        text: label,
+                        colour: DEEPSKY_COLOUR,
+                        anchor: TextAnchor::Start,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut stars: Vec<_> = cellestial_sphere
+        .stars
+        .iter()
+        .filter(|(name, _)| *cellestial_sphere.sky_settings.stars_categories_active.get(*name).unwrap_or(&true))
+        .flat_map(|(_, stars)| stars.iter())
+        .filter(|star| star.vmag <= settings.magnitude_limit && !hidden_objects.contains(&star.object_id))
+        .collect();
+    // The brighter stars are drawn last, so that their outlines cut through the fainter ones
+    stars.sort_by(|a, b| b.vmag.total_cmp(&a.vmag));
+    for star in stars {
+        let Some(centre) = view.project(star.ra, star.dec) else {
+            continue;
+        };
+        let radius = (STAR_MIN_RADIUS + (settings.magnitude_limit - star.vmag) * STAR_RADIUS_PER_MAGNITUDE).clamp(STAR_MIN_RADIUS, STAR_MAX_RADIUS);
+        chart.elements.push(ChartElement::Circle {
+            centre,
+            radius,
+            fill: Some(STAR_COLOUR),
+            stroke: Some((STAR_OUTLINE_WIDTH, BACKGROUND_COLOUR)),
+        });
+        if settings.blank || star.vmag > settings.star_names_magnitude_limit {
+            continue;
+        }
+        if let Some(name) = &star.name {
+            chart.elements.push(ChartElement::Text {
+                position: centre + egui::vec2(radius + 1.5, radius / 2.0 + 1.0),
+                size: STAR_LABEL_SIZE,
+                text: crate::localisation::object_name(&name.name).unwrap_or_else(|| name.name.clone()),
+                colour: LABEL_COLOUR,
+                anchor: TextAnchor::Start,
+            });
+        }
+    }
+
+    for marker in markers {
+        let Some(centre) = view.project(marker.ra, marker.dec) else {
+            continue;
+        };
+        chart.elements.push(ChartElement::Circle {
+            centre,
+            radius: MARKER_RADIUS,
+            fill: None,
+            stroke: Some((MARKER_LINE_WIDTH, MARKER_COLOUR)),
+        });
+        chart.elements.push(ChartElement::Text {
+            position: centre + egui::vec2(MARKER_RADIUS, -MARKER_RADIUS),
+            size: MARKER_LABEL_SIZE,
+            text: marker.label.clone(),
+            colour: MARKER_COLOUR,
+            anchor: TextAnchor::Start,
+        });
+    }
+    chart
+}
+
+/// Lines of constant right ascension and declination, labelled where they cross the ones closest to the centre of the page
+fn add_grid(chart: &mut Chart, view: &ChartView, step: f32) {
+    let mut add_polyline = |points: Vec<(angle::Deg<f32>, angle::Deg<f32>)>| {
+        for pair in points.windows(2) {
+            if let Some([start, end]) = view.segment(pair[0], pair[1]) {
+                chart.elements.push(ChartElement::Line {
+                    start,
+                    end,
+                    width: GRID_LINE_WIDTH,
+                    colour: GRID_COLOUR,
+                    dashed: true,
+                });
+            }
+        }
+    };
+    let samples = |from: f32, to: f32| (0..=((to - from) / GRID_SAMPLE_STEP).round() as usize).map(move |i| from + i as f32 * GRID_SAMPLE_STEP);
+
+    let declinations: Vec<f32> = (1..).map(|i| -90.0 + i as f32 * step).take_while(|dec| *dec < 90.0 - f32::EPSILON).collect();
+    let right_ascensions: Vec<f32> = (0..).map(|i| i as f32 * step).take_while(|ra| *ra < 360.0 - f32::EPSILON).collect();
+    for &dec in &declinations {
+        add_polyline(samples(0.0, 360.0).map(|ra| (angle::Deg(ra), angle::Deg(dec))).collect());
+    }
+    for &ra in &right_ascensions {
+        add_polyline(samples(-90.0, 90.0).map(|dec| (angle::Deg(ra), angle::Deg(dec))).collect());
+    }
+
+    let (centre_ra, centre_dec) = view.centre();
+    let closest = |values: &[f32], target: f32| values.iter().copied().min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()));
+    let ra_difference = |ra: f32| {
+        let difference = (ra - centre_ra.0).rem_euclid(360.0);
+        difference.min(360.0 - difference)
+    };
+    let label_ra = right_ascensions.iter().copied().min_by(|a, b| ra_difference(*a).total_cmp(&ra_difference(*b)));
+    let label_dec = closest(&declinations, centre_dec.0);
+    let mut add_label = |ra: f32, dec: f32, text: String| {
+        if let Some(position) = view.project(angle::Deg(ra), angle::Deg(dec)) {
+            chart.elements.push(ChartElement::Text {
+                position: position + egui::vec2(1.5, -1.5),
+                size: GRID_LABEL_SIZE,
+                text,
+                colour: GRID_COLOUR,
+                anchor: TextAnchor::Start,
+            });
+        }
+    };
+    if let Some(label_dec) = label_dec {
+        for &ra in &right_ascensions {
+            add_label(ra, label_dec, format_ra(ra));
+        }
+    }
+    if let Some(label_ra) = label_ra {
+        for &dec in &declinations {
+            add_label(label_ra, dec, format!("{dec:+.0}°"));
+        }
+    }
+}
+
+/// The right ascension in hours and minutes, leaving out zero minutes
+pub fn format_ra(ra: f32) -> String {
+    let minutes = (ra / 15.0 * 60.0).round() as u32;
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    if minutes == 0 {
+        format!("{hours}h")
+    } else {
+        format!("{hours}h{minutes:02}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ra_labels() {
+        assert_eq!(format_ra(0.0), "0h");
+        assert_eq!(format_ra(90.0), "6h");
+        assert_eq!(format_ra(7.5), "0h30m");
+    }
+}
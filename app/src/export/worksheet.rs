@@ -0,0 +1,313 @@
+use eframe::egui::{Color32, Pos2, Vec2};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+use crate::{
+    game::{game_handler::Answer, questions_filter::QuestionPack},
+    localisation::{tr, tr_args},
+    renderer::CellestialSphere,
+};
+
+use super::{
+    chart::{self, Chart, ChartElement, TextAnchor},
+    sky_chart::{self, build_chart, ChartMarker, ChartSettings},
+};
+
+const PAGE_MARGIN: f32 = 40.0;
+/// The space above the chart taken by the title and the task
+const TEXT_AREA_HEIGHT: f32 = 90.0;
+const TITLE_SIZE: f32 = 14.0;
+const TEXT_SIZE: f32 = 10.0;
+const TEXT_COLOUR: Color32 = Color32::BLACK;
+/// How far the chart is moved away from the answer, relative to the field of view, so that the middle of the chart does not give the answer away
+const CENTRE_SHIFT_RANGE: std::ops::Range<f32> = 0.1..0.3;
+
+/// A question as it is printed on a worksheet
+pub struct WorksheetItem {
+    /// The task for the player
+    pub prompt: String,
+    /// The points marked on the chart with the number of the question
+    pub marks: Vec<(angle::Deg<f32>, angle::Deg<f32>)>,
+    /// The (ra, dec) the chart is centred around
+    pub centre: (angle::Deg<f32>, angle::Deg<f32>),
+    /// Whether the centre is what the player is looking for, in which case the chart is moved a bit away from it
+    pub centre_is_answer: bool,
+    /// An object left out of the chart
+    pub hidden_object: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum WorksheetFormat {
+    Pdf,
+    Html,
+}
+
+impl WorksheetFormat {
+    pub fn variants() -> [Self; 2] {
+        [Self::Pdf, Self::Html]
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Pdf => "pdf",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl std::fmt::Display for WorksheetFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pdf => write!(f, "PDF"),
+            Self::Html => write!(f, "HTML"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WorksheetSettings {
+    pub questions_count: usize,
+    /// The same seed and question pack always give the same worksheet
+    pub seed: u64,
+    /// The size of the pages in points (1/72 of an inch)
+    pub page_width: f32,
+    pub page_height: f32,
+    /// What the charts show, their region and size are set for every question
+    pub chart: ChartSettings,
+}
+
+impl Default for WorksheetSettings {
+    fn default() -> Self {
+        Self {
+            questions_count: 10,
+            seed: 0,
+            // A4 portrait
+            page_width: 595.0,
+            page_height: 842.0,
+            chart: ChartSettings {
+                current_view: false,
+                fov: 50.0,
+                blank: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The pages with the questions and the pages with the answers
+pub struct Worksheet {
+    pub pages: Vec<Chart>,
+    pub answer_key: Vec<Chart>,
+}
+
+/// Picks the questions from the pack and puts each of them on its own page together with a chart, questions which can not be asked on paper are skipped
+pub fn generate_worksheet(cellestial_sphere: &CellestialSphere, question_pack: &QuestionPack, settings: &WorksheetSettings) -> Worksheet {
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+    let mut questions = cellestial_sphere.generate_questions_with_rng(&question_pack.question_objects, &mut rng);
+    questions.shuffle(&mut rng);
+
+    let content_width = settings.page_width - 2.0 * PAGE_MARGIN;
+    let mut pages = Vec::new();
+    let mut answers = Vec::new();
+    for question in questions {
+        if pages.len() >= settings.questions_count {
+            break;
+        }
+        let Some(item) = question.worksheet_item() else {
+            continue;
+        };
+        let number = pages.len() + 1;
+        let (mut centre_ra, mut centre_dec) = item.centre;
+        if item.centre_is_answer {
+            (centre_ra, centre_dec) = sg_geometry::offset_point(
+                centre_ra,
+                centre_dec,
+                angle::Deg(settings.chart.fov * rng.gen_range(CENTRE_SHIFT_RANGE)),
+                angle::Deg(rng.gen_range(0.0..360.0)),
+            );
+        }
+        let chart_settings = ChartSettings {
+            current_view: false,
+            centre_ra,
+            centre_dec,
+            width: content_width,
+            height: settings.page_height - 2.0 * PAGE_MARGIN - TEXT_AREA_HEIGHT,
+            ..settings.chart.clone()
+        };
+        let markers: Vec<ChartMarker> = item
+            .marks
+            .iter()
+            .enumerate()
+            .map(|(i, &(ra, dec))| ChartMarker {
+                ra,
+                dec,
+                label: mark_label(number, i, item.marks.len()),
+            })
+            .collect();
+        let sky = build_chart(cellestial_sphere, &chart_settings, &markers, item.hidden_object.as_slice());
+
+        let answer = question.get_correct_answer(cellestial_sphere);
+        let mut page = Chart::new(settings.page_width, settings.page_height, Color32::WHITE);
+        page.elements.push(ChartElement::Text {
+            position: Pos2::new(PAGE_MARGIN, PAGE_MARGIN + TITLE_SIZE),
+            size: TITLE_SIZE,
+            text: tr_args("Question {0}", &[&number]),
+            colour: TEXT_COLOUR,
+            anchor: TextAnchor::Start,
+        });
+        let mut prompt = item.prompt;
+        if matches!(answer, Some(Answer::Text(_))) {
+            prompt.push_str(&tr(" Answer: ____________________"));
+        }
+        page.add_paragraph(Pos2::new(PAGE_MARGIN, PAGE_MARGIN + 2.5 * TITLE_SIZE), content_width, TEXT_SIZE, TEXT_COLOUR, &prompt);
+        page.insert(sky, Vec2::new(PAGE_MARGIN, PAGE_MARGIN + TEXT_AREA_HEIGHT));
+        pages.push(page);
+        answers.push(format!("{number}. {}", answer_text(answer.as_ref())));
+    }
+    Worksheet {
+        pages,
+        answer_key: answer_key_pages(&answers, settings),
+    }
+}
+
+/// The label of a mark, just the number of the question if it is the only mark, otherwise with a letter after it (1A, 1B, ...)
+fn mark_label(number: usize, index: usize, count: usize) -> String {
+    if count <= 1 {
+        number.to_string()
+    } else {
+        format!("{number}{}", (b'A' + (index % 26) as u8) as char)
+    }
+}
+
+fn answer_text(answer: Option<&Answer>) -> String {
+    match answer {
+        Some(Answer::Text(text)) => match text.parse::<f32>() {
+            Ok(number) => format!("{number:.2}"),
+            Err(_) => text.clone(),
+        },
+        Some(Answer::Position { ra, dec }) => format!("RA {}, Dec {:+.1}°", sky_chart::format_ra(ra.0), dec.0),
+        None => tr("No single correct answer"),
+    }
+}
+
+fn answer_key_pages(answers: &[String], settings: &WorksheetSettings) -> Vec<Chart> {
+    let content_width = settings.page_width - 2.0 * PAGE_MARGIN;
+    let new_page = || {
+        let mut page = Chart::new(settings.page_width, settings.page_height, Color32::WHITE);
+        page.elements.push(ChartElement::Text {
+            position: Pos2::new(PAGE_MARGIN, PAGE_MARGIN + TITLE_SIZE),
+            size: TITLE_SIZE,
+            text: tr("Answer key"),
+            colour: TEXT_COLOUR,
+            anchor: TextAnchor::Start,
+        });
+        page
+    };
+    let top = PAGE_MARGIN + 2.5 * TITLE_SIZE;
+    let mut pages = vec![new_page()];
+    let mut y = top;
+    for answer in answers {
+        // An answer is rarely longer than two lines
+        if y + 2.0 * TEXT_SIZE > settings.page_height - PAGE_MARGIN {
+            pages.push(new_page());
+            y = top;
+        }
+        if let Some(page) = pages.last_mut() {
+            y += page.add_paragraph(Pos2::new(PAGE_MARGIN, y), content_width, TEXT_SIZE, TEXT_COLOUR, answer) + TEXT_SIZE / 2.0;
+        }
+    }
+    pages
+}
+
+/// Saves the worksheet into the path and the answer key next to it, with "-answers" added to the name. The extension of the format is added to the path if it has none
+pub fn save_worksheet(worksheet: &Worksheet, format: WorksheetFormat, path: &std::path::Path) -> Result<(), Box<dyn Error>> {
+    let path = &with_extension(path, format);
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or(String::from("worksheet"));
+    let answers_path = path.with_file_name(format!("{stem}-answers.{}", format.extension()));
+    if let Some(folder) = path.parent() {
+        if !folder.as_os_str().is_empty() {
+            std::fs::create_dir_all(folder)?;
+        }
+    }
+    for (pages, path, title) in [(&worksheet.pages, path.as_path(), tr("Worksheet")), (&worksheet.answer_key, answers_path.as_path(), tr("Answer key"))] {
+        let data = match format {
+            WorksheetFormat::Pdf => chart::pdf_document(pages),
+            WorksheetFormat::Html => chart::html_document(&title, pages).into_bytes(),
+        };
+        std::fs::write(path, data)?;
+    }
+    Ok(())
+}
+
+/// File dialogs on some platforms return the name as the user typed it, without the extension of the chosen filter
+fn with_extension(path: &std::path::Path, format: WorksheetFormat) -> std::path::PathBuf {
+    match path.extension() {
+        Some(_) => path.to_path_buf(),
+        None => path.with_extension(format.extension()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(page: &Chart) -> Vec<&str> {
+        page.elements
+            .iter()
+            .filter_map(|element| match element {
+                ChartElement::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mark_labels() {
+        assert_eq!(mark_label(3, 0, 1), "3");
+        assert_eq!(mark_label(3, 0, 2), "3A");
+        assert_eq!(mark_label(3, 1, 2), "3B");
+        assert_eq!(mark_label(12, 2, 3), "12C");
+    }
+
+    #[test]
+    fn answer_texts() {
+        assert_eq!(answer_text(Some(&Answer::Text(String::from("Cygnus")))), "Cygnus");
+        assert_eq!(answer_text(Some(&Answer::Text(String::from("2.456")))), "2.46");
+        assert_eq!(
+            answer_text(Some(&Answer::Position {
+                ra: angle::Deg(90.0),
+                dec: angle::Deg(-12.34)
+            })),
+            "RA 6h, Dec -12.3°"
+        );
+        assert_eq!(answer_text(None), "No single correct answer");
+    }
+
+    #[test]
+    fn answer_key_continues_on_new_pages() {
+        let settings = WorksheetSettings::default();
+        assert_eq!(answer_key_pages(&[String::from("1. Cygnus")], &settings).len(), 1);
+
+        let answers: Vec<String> = (1..=200).map(|number| format!("{number}. Cygnus")).collect();
+        let pages = answer_key_pages(&answers, &settings);
+        assert!(pages.len() > 1);
+        let mut printed = Vec::new();
+        for page in &pages {
+            let texts = texts(page);
+            assert_eq!(texts[0], "Answer key");
+            printed.extend(texts[1..].iter().map(|text| text.to_string()));
+        }
+        assert_eq!(printed, answers);
+    }
+
+    #[test]
+    fn missing_extensions_are_added() {
+        let path = std::path::Path::new("charts/worksheet");
+        assert_eq!(with_extension(path, WorksheetFormat::Pdf), std::path::Path::new("charts/worksheet.pdf"));
+        assert_eq!(with_extension(path, WorksheetFormat::Html), std::path::Path::new("charts/worksheet.html"));
+        let path = std::path::Path::new("charts/worksheet.pdf");
+        assert_eq!(with_extension(path, WorksheetFormat::Pdf), path);
+    }
+}
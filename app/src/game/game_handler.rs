@@ -35,6 +35,8 @@ pub struct QuestionWindowData<'a> {
     pub used_questions: &'a mut Vec<usize>,
     pub questions_settings: &'a questions::Settings,
     pub question_number: &'a mut usize,
+    /// How long the player has been answering the current question, used for the time bonus
+    pub seconds_spent: f32,
 }
 
 pub struct QuestionCheckingData<'a> {
@@ -49,6 +51,7 @@ pub struct QuestionCheckingData<'a> {
     pub add_marker_on_click: &'a mut bool,
     pub questions_settings: &'a questions::Settings,
     pub question_number: &'a mut usize,
+    pub seconds_spent: f32,
     /// Signals that the current question should run the generic_to_next_part() function
     pub switch_to_next_part: &'a mut bool,
     /// Signals that the current question gives up its place and a new one should be picked
@@ -81,6 +84,24 @@ pub trait QuestionTrait {
 
     fn render_display_question(&self, ui: &mut egui::Ui);
 
+    /// Sets the text answer of the question, does nothing for questions which do not take a text input
+    fn set_answer(&mut self, answer: &str);
+
+    /// Checks the answer given so far without going through the question window
+    fn check(&mut self, data: QuestionCheckingData);
+
+    /// Returns an answer that would be evaluated as correct, or None if the question has no single correct answer
+    fn get_correct_answer(&self, cellestial_sphere: &CellestialSphere) -> Option<Answer>;
+
+    /// Returns the heading and the text of the answer review
+    fn get_answer_review(&self) -> (&str, &str);
+
+    /// Reveals the next hint and returns its text, or None if the question has no more hints
+    fn use_hint(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> Option<String>;
+
+    /// What the question looks like on a printed worksheet, None if it can not be asked on paper
+    fn worksheet_item(&self) -> Option<crate::export::worksheet::WorksheetItem>;
+
     fn clone_box(&self) -> Box<dyn QuestionTrait>;
 }
 
@@ -90,6 +111,15 @@ impl Clone for Box<dyn QuestionTrait> {
     }
 }
 
+/// An answer to a question that can be submitted without the UI
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    /// What the player would type into the question input field
+    Text(String),
+    /// Where the player would place a marker on the sky
+    Position { ra: angle::Deg<f32>, dec: angle::Deg<f32> },
+}
+
 #[derive(Clone)]
 pub enum QuestionEnum {
     ObjectQuestion {
@@ -177,6 +207,8 @@ pub struct GameHandler {
     pub request_input_focus: bool,
     pub switch_to_next_part: bool,
     pub switch_to_next_question: bool,
+    /// When the current question was asked, in milliseconds
+    pub question_start_timestamp: i64,
 
     pub active_question_pack: String,
     pub question_packs: HashMap<String, crate::game::questions_filter::QuestionPack>,
@@ -331,23 +363,12 @@ impl GameHandler {
             request_input_focus: false,
             switch_to_next_part: false,
             switch_to_next_question: false,
+            question_start_timestamp: 0,
 
             active_question_pack,
             question_packs,
         }
     }
-    pub fn evaluate_score(distance: angle::Deg<f32>) -> u32 {
-        if distance < angle::Deg(0.2) {
-            3
-        } else if distance < angle::Deg(0.5) {
-            2
-        } else if distance < angle::Deg(1.0) {
-            1
-        } else {
-            0
-        }
-    }
-
     pub fn next_question(&mut self, cellestial_sphere: &mut crate::renderer::CellestialSphere, theme: &Theme) {
         self.answer = String::new();
         let mut possible_questions: Vec<usize> = Vec::new();
@@ -363,20 +384,28 @@ impl GameHandler {
             self.stage = GameStage::ScoredModeFinished;
         } else {
             self.current_question = possible_questions[rand::thread_rng().gen_range(0..possible_questions.len())];
-            self.question_number_text = format!(
-                "Question {}/{}",
-                self.used_questions.len() + self.question_number + 1,
-                possible_questions.len() + self.used_questions.len() + self.question_number
+            self.question_number_text = crate::localisation::tr_args(
+                "Question {0}/{1}",
+                &[
+                    &(self.used_questions.len() + self.question_number + 1),
+                    &(possible_questions.len() + self.used_questions.len() + self.question_number),
+                ],
             );
 
             self.add_marker_on_click = self.question_catalog[self.current_question].add_marker_on_click();
+            cellestial_sphere.game_markers.clear_hints();
             self.question_catalog[self.current_question].start_question(cellestial_sphere, theme);
             self.request_input_focus = true;
             cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+            self.question_start_timestamp = chrono::Local::now().timestamp_millis();
             self.stage = GameStage::Guessing;
         }
     }
 
+    pub fn seconds_spent(&self) -> f32 {
+        (chrono::Local::now().timestamp_millis() - self.question_start_timestamp) as f32 / 1000.0
+    }
+
     pub fn should_display_input(&self) -> bool {
         self.question_catalog[self.current_question].should_display_input()
     }
@@ -444,4 +473,147 @@ impl GameHandler {
     pub fn get_possible_score(&self) -> u32 {
         self.possible_score
     }
+
+    pub fn get_score(&self) -> u32 {
+        self.score
+    }
+
+    /// Regenerates the question catalog from the active question pack and puts the game into the not started state
+    pub fn reload_question_catalog(&mut self, cellestial_sphere: &mut CellestialSphere) {
+        let new_questions = if let Some(active_pack) = self.question_packs.get(&self.active_question_pack) {
+            cellestial_sphere.generate_questions(&active_pack.question_objects)
+        } else {
+            Vec::new()
+        };
+        self.possible_no_of_questions = new_questions.len() as u32;
+        self.question_catalog = new_questions;
+        self.reset_used_questions(cellestial_sphere);
+        self.current_question = 0;
+        self.stage = GameStage::NotStartedYet;
+        self.question_number_text = String::new();
+    }
+
+    /// Starts a new game from the first question, throwing away the progress of the current one
+    pub fn start_game(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        self.reset_used_questions(cellestial_sphere);
+        self.next_question(cellestial_sphere, theme);
+    }
+
+    /// Places a guess marker the same way a click on the sky would
+    pub fn place_marker(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme, dec: angle::Rad<f32>, ra: angle::Rad<f32>) {
+        if self.allow_multiple_player_marker() {
+            self.guess_marker_positions.push([dec, ra]);
+        } else {
+            self.guess_marker_positions = vec![[dec, ra]];
+        }
+        cellestial_sphere.game_markers.markers = self.generate_player_markers(&self.guess_marker_positions, theme);
+        cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+    }
+
+    /// Moves the last placed guess marker (or places one if there is none), for placing markers without a mouse
+    pub fn move_last_marker(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme, dec: angle::Rad<f32>, ra: angle::Rad<f32>) {
+        match self.guess_marker_positions.last_mut() {
+            Some(position) => *position = [dec, ra],
+            None => self.guess_marker_positions.push([dec, ra]),
+        }
+        cellestial_sphere.game_markers.markers = self.generate_player_markers(&self.guess_marker_positions, theme);
+        cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+    }
+
+    /// Submits an answer to the current question, it still has to be checked using check_answer
+    pub fn submit_answer(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme, answer: Answer) {
+        if self.stage != GameStage::Guessing {
+            return;
+        }
+        match answer {
+            Answer::Text(text) => self.question_catalog[self.current_question].set_answer(&text),
+            Answer::Position { ra, dec } => {
+                if self.add_marker_on_click {
+                    self.place_marker(cellestial_sphere, theme, dec.to_rad(), ra.to_rad());
+                } else {
+                    log::warn!("Tried to place a marker in a question that does not accept markers");
+                }
+            }
+        }
+    }
+
+    pub fn get_correct_answer(&self, cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        if !matches!(self.stage, GameStage::Guessing | GameStage::Checked) {
+            return None;
+        }
+        self.question_catalog[self.current_question].get_correct_answer(cellestial_sphere)
+    }
+
+    pub fn check_answer(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        if self.stage != GameStage::Guessing {
+            return;
+        }
+        let (question, data) = self.current_question_with_checking_data(cellestial_sphere, theme);
+        question.check(data);
+    }
+
+    /// Checks the current question as if nothing was answered
+    pub fn give_up(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        if self.stage != GameStage::Guessing {
+            return;
+        }
+        if self.add_marker_on_click {
+            self.guess_marker_positions = Vec::new();
+            cellestial_sphere.game_markers.markers = Vec::new();
+        } else {
+            self.question_catalog[self.current_question].set_answer("");
+        }
+        self.check_answer(cellestial_sphere, theme);
+    }
+
+    /// Does what a press of the 'next' key does - checks the question if it is being guessed, or moves to the next question if it has been checked
+    pub fn next_part(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        if !matches!(self.stage, GameStage::Guessing | GameStage::Checked) {
+            return;
+        }
+        let (question, data) = self.current_question_with_checking_data(cellestial_sphere, theme);
+        question.generic_to_next_part(data);
+        self.switch_to_next_part = false;
+        if self.switch_to_next_question {
+            self.next_question(cellestial_sphere, theme);
+            self.switch_to_next_question = false;
+        }
+    }
+
+    /// Reveals the next hint of the current question, the hint is then reflected in the score
+    pub fn use_hint(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> Option<String> {
+        if self.stage != GameStage::Guessing {
+            return None;
+        }
+        self.question_catalog[self.current_question].use_hint(cellestial_sphere, theme)
+    }
+
+    pub fn get_answer_review(&self) -> Option<(&str, &str)> {
+        if self.stage != GameStage::Checked {
+            return None;
+        }
+        Some(self.question_catalog[self.current_question].get_answer_review())
+    }
+
+    fn current_question_with_checking_data<'a>(&'a mut self, cellestial_sphere: &'a mut CellestialSphere, theme: &'a Theme) -> (&'a mut Box<dyn QuestionTrait>, QuestionCheckingData<'a>) {
+        let seconds_spent = self.seconds_spent();
+        let question = &mut self.question_catalog[self.current_question];
+        let data = QuestionCheckingData {
+            cellestial_sphere,
+            theme,
+            game_stage: &mut self.stage,
+            score: &mut self.score,
+            possible_score: &mut self.possible_score,
+            is_scored_mode: self.game_settings.is_scored_mode,
+            current_question: self.current_question,
+            used_questions: &mut self.used_questions,
+            add_marker_on_click: &mut self.add_marker_on_click,
+            questions_settings: &self.questions_settings,
+            question_number: &mut self.question_number,
+            seconds_spent,
+            start_next_question: &mut self.switch_to_next_question,
+            switch_to_next_part: &mut self.switch_to_next_part,
+        };
+        (question, data)
+    }
 }
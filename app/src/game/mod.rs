@@ -2,6 +2,7 @@ pub mod game_handler;
 pub mod game_settings;
 pub mod questions;
 pub mod questions_filter;
+pub mod scoring;
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
 #[serde(tag = "object_category", content = "object_type")]
@@ -118,6 +119,79 @@ pub struct QuestionObjectRaw {
     pub mag: Option<f32>,
     pub distance: Option<f32>,
     pub bv: Option<f32>,
+
+    // Only present in the double star files - the separation is in arcseconds, the component magnitudes are separated by ';'
+    #[serde(default)]
+    pub separation: Option<f32>,
+    #[serde(default)]
+    pub position_angle: Option<f32>,
+    #[serde(default)]
+    pub component_magnitudes: Option<String>,
+
+    // Only present in the variable star files - the type is the GCVS abbreviation and the period is in days
+    #[serde(default)]
+    pub variable_type: Option<String>,
+    #[serde(default)]
+    pub period: Option<f32>,
+    #[serde(default)]
+    pub mag_max: Option<f32>,
+    #[serde(default)]
+    pub mag_min: Option<f32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DoubleStar {
+    /// In arcseconds
+    pub separation: f32,
+    /// Of the secondary component, measured from the north towards the east, in degrees
+    pub position_angle: Option<f32>,
+    /// From the primary component
+    pub component_magnitudes: Vec<f32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct VariableStar {
+    /// The abbreviation used by the General Catalogue of Variable Stars, like `M` for Mira variables or `EA` for Algol-type eclipsing binaries
+    pub variable_type: String,
+    /// In days, None for irregular variables
+    pub period: Option<f32>,
+    /// The magnitude at the brightest
+    pub mag_max: Option<f32>,
+    /// The magnitude at the faintest
+    pub mag_min: Option<f32>,
+}
+
+impl VariableStar {
+    pub fn type_name(&self) -> String {
+        let variable_type = self.variable_type.to_uppercase();
+        let name = match variable_type.as_str() {
+            "M" => "Mira variable",
+            "DCEP" | "CEP" => "Classical Cepheid",
+            "CW" | "CWA" | "CWB" => "Type II Cepheid",
+            "RR" | "RRAB" | "RRC" => "RR Lyrae variable",
+            "EA" => "Algol-type eclipsing binary",
+            "EB" => "Beta Lyrae-type eclipsing binary",
+            "EW" => "W Ursae Majoris-type eclipsing binary",
+            "SR" | "SRA" | "SRB" | "SRC" | "SRD" => "Semiregular variable",
+            "L" | "LB" | "LC" => "Irregular variable",
+            "RCB" => "R Coronae Borealis variable",
+            "GCAS" => "Gamma Cassiopeiae variable",
+            _ => return variable_type,
+        };
+        String::from(name)
+    }
+
+    /// A short description of the variability, like "Mira variable, 2.0 - 10.1 mag, period 332 days"
+    pub fn describe(&self) -> String {
+        let mut parts = vec![self.type_name()];
+        if let (Some(mag_max), Some(mag_min)) = (self.mag_max, self.mag_min) {
+            parts.push(format!("{mag_max:.1} - {mag_min:.1} mag"));
+        }
+        if let Some(period) = self.period {
+            parts.push(format!("period {period} days"));
+        }
+        parts.join(", ")
+    }
 }
 
 pub struct QuestionObject {
@@ -128,6 +202,8 @@ pub struct QuestionObject {
     pub proper_names_raw: Vec<String>,
     pub proper_names_full: Vec<String>,
     pub proper_names_all: Vec<String>,
+    /// The proper names in all the languages there are translations for
+    pub proper_names_localised: Vec<String>,
     pub bayer_designation_raw: Option<String>,
     pub bayer_designation_full: Option<String>,
     pub flamsteed_designation_raw: Option<String>,
@@ -144,6 +220,10 @@ pub struct QuestionObject {
     pub distance: Option<f32>,
     pub bv: Option<f32>,
     pub images: Vec<crate::structs::image_info::ImageInfo>,
+    pub double_star: Option<DoubleStar>,
+    pub variable_star: Option<VariableStar>,
+    /// The position of a star among the stars of its constellation ordered by their brightness, 1 being the brightest
+    pub brightness_rank: Option<u32>,
 }
 
 impl QuestionObject {
@@ -158,6 +238,7 @@ impl QuestionObject {
             }
             proper_names_all.extend(names);
         }
+        let proper_names_localised = proper_names_full.iter().flat_map(|name| crate::localisation::all_object_names(name)).collect();
 
         let bayer_designation_raw = raw.bayer_designation;
         let bayer_designation_full = if let Some(bayer_raw) = &bayer_designation_raw {
@@ -176,6 +257,17 @@ impl QuestionObject {
         } else {
             None
         };
+        let double_star = raw.separation.map(|separation| DoubleStar {
+            separation,
+            position_angle: raw.position_angle,
+            component_magnitudes: raw.component_magnitudes.as_deref().unwrap_or_default().split(';').filter_map(|mag| mag.trim().parse().ok()).collect(),
+        });
+        let variable_star = raw.variable_type.filter(|variable_type| !variable_type.is_empty()).map(|variable_type| VariableStar {
+            variable_type,
+            period: raw.period,
+            mag_max: raw.mag_max,
+            mag_min: raw.mag_min,
+        });
         Self {
             object_id: raw.object_id,
             object_type: raw.object_type,
@@ -184,6 +276,7 @@ impl QuestionObject {
             proper_names_raw,
             proper_names_full,
             proper_names_all,
+            proper_names_localised,
             bayer_designation_raw,
             bayer_designation_full,
             flamsteed_designation_raw,
@@ -200,6 +293,61 @@ impl QuestionObject {
             distance: raw.distance,
             bv: raw.bv,
             images,
+            double_star,
+            variable_star,
+            brightness_rank: None,
         }
     }
+
+    /// All the catalogue designations of the object, written the same way as in the questions
+    pub fn designations(&self) -> Vec<String> {
+        let mut designations = Vec::new();
+        designations.extend(self.bayer_designation_full.clone());
+        designations.extend(self.flamsteed_designation_full.clone());
+        designations.extend(self.messier_number.map(|number| format!("M{number}")));
+        designations.extend(self.caldwell_number.map(|number| format!("C{number}")));
+        designations.extend(self.ngc_number.map(|number| format!("NGC{number}")));
+        designations.extend(self.ic_number.map(|number| format!("IC{number}")));
+        designations.extend(self.hd_number.map(|number| format!("HD{number}")));
+        designations.extend(self.hipparcos_number.map(|number| format!("HIP{number}")));
+        designations
+    }
+
+    /// The proper name in the current language if there is one, otherwise the first designation
+    pub fn display_name(&self) -> String {
+        self.proper_names_full
+            .first()
+            .map(|name| crate::localisation::object_name(name).unwrap_or_else(|| name.clone()))
+            .or_else(|| self.designations().into_iter().next())
+            .unwrap_or(format!("Object {}", self.object_id))
+    }
+
+    pub fn type_name(&self) -> String {
+        match &self.object_type {
+            ObjectType::Star(star_type) => star_type.display_name(),
+            ObjectType::Deepsky(deepsky_type) => deepsky_type.display_name(),
+        }
+    }
+}
+
+/// Stars from different catalogues whose magnitudes differ by less than this are taken to be the same star
+const SAME_STAR_MAGNITUDE_TOLERANCE: f32 = 0.05;
+
+/// Ranks the stars among the question objects by their brightness in their constellations. The catalogue holds the constellation abbreviation and the magnitude of every star to compare with, the question objects themselves included.
+pub fn assign_brightness_ranks(question_objects: &mut [QuestionObject], catalogue: &[(String, f32)]) {
+    let mut magnitudes: std::collections::HashMap<String, Vec<f32>> = std::collections::HashMap::new();
+    for (constellation, mag) in catalogue {
+        magnitudes.entry(constellation.to_lowercase()).or_default().push(*mag);
+    }
+    for object in question_objects.iter_mut().filter(|object| matches!(object.object_type, ObjectType::Star(_))) {
+        let Some(mag) = object.mag else {
+            continue;
+        };
+        object.brightness_rank = object
+            .constellations_abbreviations
+            .iter()
+            .filter_map(|abbreviation| magnitudes.get(&abbreviation.to_lowercase()))
+            .map(|constellation_magnitudes| 1 + constellation_magnitudes.iter().filter(|&&other| other < mag - SAME_STAR_MAGNITUDE_TOLERANCE).count() as u32)
+            .min();
+    }
 }
@@ -1,14 +1,24 @@
 use crate::enums::GameStage;
-use crate::game::game_handler::{self, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::export::worksheet::WorksheetItem;
+use crate::game::game_handler::{self, Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
 use angle::{Angle, Deg};
 use eframe::egui;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct SmallSettings {
     pub rotate_to_midpoint: bool,
+    #[serde(default = "default_scoring")]
+    pub scoring: ScoringRules,
+}
+
+/// The error is in percent of the real distance
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::stepwise([3.0, 5.0, 10.0])
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -17,6 +27,7 @@ pub struct Settings {
     pub show: bool,
     pub rotate_to_midpoint: bool,
     pub limit_to_toggled_constellations: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -25,6 +36,7 @@ impl Default for Settings {
             show: true,
             rotate_to_midpoint: true,
             limit_to_toggled_constellations: false,
+            scoring: default_scoring(),
         }
     }
 }
@@ -50,47 +62,54 @@ pub struct Question {
 
 impl Question {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if self.should_display_input() {
-                let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
-                if *data.request_input_focus {
-                    text_input_response.request_focus();
-                    *data.request_input_focus = false;
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
                 }
-            }
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn check_answer(&mut self, data: QuestionCheckingData) {
@@ -104,15 +123,10 @@ impl Question {
                 let error_percent = 1.0 - answer.value() / distance.value();
                 self.state.answer_review_text = format!("The real distance was {:.1}°. Your error is equal to {:.1}% of the distance.", distance.value(), error_percent * 100.0);
                 if data.is_scored_mode {
-                    let error = (1.0 - answer.value() / distance.value()).abs();
-                    if error < 0.03 {
-                        *data.score += 3;
-                    } else if error < 0.05 {
-                        *data.score += 2;
-                    } else if error < 0.1 {
-                        *data.score += 1;
-                    }
-                    *data.possible_score += 3;
+                    let score = self.small_settings.scoring.evaluate(error_percent.abs() * 100.0, data.seconds_spent, 0);
+                    *data.score += score.total();
+                    *data.possible_score += self.small_settings.scoring.max_points();
+                    self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "%");
                 }
             }
             Err(_) => {
@@ -196,7 +210,7 @@ impl crate::game::game_handler::QuestionTrait for Question {
             let end_2 = sg_geometry::get_point_vector(ra2, dec2, &nalgebra::Matrix3::<f32>::identity());
             if (end_1 + end_2).magnitude_squared() > 10e-4 {
                 let final_vector = (end_1 + end_2).normalize();
-                cellestial_sphere.look_at_point(&final_vector);
+                cellestial_sphere.transition_to_point(&final_vector, None);
                 cellestial_sphere.init_renderers();
             }
         }
@@ -206,6 +220,43 @@ impl crate::game::game_handler::QuestionTrait for Question {
         ui.heading("What is the angular distance between these markers?");
     }
 
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        let (ra1, dec1) = self.point1;
+        let (ra2, dec2) = self.point2;
+        let distance = sg_geometry::angular_distance((ra1.to_rad(), dec1.to_rad()), (ra2.to_rad(), dec2.to_rad())).to_deg();
+        Some(Answer::Text(distance.value().to_string()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        let (ra1, dec1) = self.point1;
+        let (ra2, dec2) = self.point2;
+        let (dec, ra) =
+            sg_geometry::cartesian_to_spherical(sg_geometry::get_point_vector(ra1, dec1, &nalgebra::Matrix3::identity()) + sg_geometry::get_point_vector(ra2, dec2, &nalgebra::Matrix3::identity()));
+        Some(WorksheetItem {
+            prompt: localisation::tr("What is the angular distance between the marked points?"),
+            marks: vec![self.point1, self.point2],
+            centre: (ra.to_deg(), dec.to_deg()),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
         Box::new(self.clone())
     }
@@ -0,0 +1,366 @@
+use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
+use crate::game::game_handler;
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
+use crate::renderer::CellestialSphere;
+use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
+use crate::rendering::themes::Theme;
+use angle::Deg;
+use eframe::egui;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct SmallSettings {
+    /// How many stars are compared in one question, 2 or 3
+    pub stars_per_question: u32,
+    /// The stars of a question differ in their magnitude by at least this much, so the lower it is, the harder the question
+    pub min_magnitude_difference: f32,
+    pub max_magnitude_difference: f32,
+    /// In degrees, so that all the stars of a question fit on the screen
+    pub max_separation: f32,
+    pub rotate_to_stars: bool,
+    pub replay_incorrect: bool,
+    /// Applies to each pair of stars separately
+    pub scoring: ScoringRules,
+}
+
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::correct_answer(1.0)
+}
+
+impl Default for SmallSettings {
+    fn default() -> Self {
+        Self {
+            stars_per_question: 2,
+            min_magnitude_difference: 0.5,
+            max_magnitude_difference: 3.0,
+            max_separation: 30.0,
+            rotate_to_stars: true,
+            replay_incorrect: true,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub show: bool,
+    pub limit_to_toggled_constellations: bool,
+    pub magnitude_cutoff: f32,
+    pub stars_per_question: u32,
+    pub min_magnitude_difference: f32,
+    pub max_magnitude_difference: f32,
+    pub max_separation: f32,
+    pub rotate_to_stars: bool,
+    pub replay_incorrect: bool,
+    pub scoring: ScoringRules,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let small_settings = SmallSettings::default();
+        Self {
+            show: false,
+            limit_to_toggled_constellations: false,
+            magnitude_cutoff: 4.0,
+            stars_per_question: small_settings.stars_per_question,
+            min_magnitude_difference: small_settings.min_magnitude_difference,
+            max_magnitude_difference: small_settings.max_magnitude_difference,
+            max_separation: small_settings.max_separation,
+            rotate_to_stars: small_settings.rotate_to_stars,
+            replay_incorrect: small_settings.replay_incorrect,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ComparedStar {
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    pub mag: f32,
+    pub name: String,
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    answer: String,
+
+    answer_review_text_heading: String,
+    answer_review_text: String,
+}
+
+/// Marks two or three stars and asks the player to order them by their brightness
+#[derive(Clone)]
+pub struct Question {
+    pub stars: Vec<ComparedStar>,
+
+    pub state: State,
+    pub small_settings: SmallSettings,
+}
+
+/// The letter the star is marked with on the sky
+fn label(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+impl Question {
+    /// The magnitudes the player actually sees, including the dimming by the clouds
+    fn apparent_magnitudes(&self, cellestial_sphere: &CellestialSphere) -> Vec<f32> {
+        self.stars.iter().map(|star| star.mag + cellestial_sphere.magnitude_offset(star.ra, star.dec)).collect()
+    }
+
+    /// The labels of the stars from the brightest to the faintest
+    fn correct_order(&self, cellestial_sphere: &CellestialSphere) -> Vec<usize> {
+        let magnitudes = self.apparent_magnitudes(cellestial_sphere);
+        let mut order = (0..self.stars.len()).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| magnitudes[a].total_cmp(&magnitudes[b]));
+        order
+    }
+
+    /// Reads the order typed by the player, like "B A" or "c,a,b". Anything else than each of the labels exactly once is not an order.
+    fn parse_order(&self, answer: &str) -> Option<Vec<usize>> {
+        let order = answer
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .map(|c| (0..self.stars.len()).find(|&i| label(i) == c.to_ascii_uppercase()))
+            .collect::<Option<Vec<usize>>>()?;
+        let mut sorted = order.clone();
+        sorted.sort();
+        (sorted == (0..self.stars.len()).collect::<Vec<usize>>()).then_some(order)
+    }
+
+    fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
+                }
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn check_answer(&mut self, data: QuestionCheckingData) {
+        let magnitudes = self.apparent_magnitudes(data.cellestial_sphere);
+        let correct_order = self.correct_order(data.cellestial_sphere);
+        let order = self.parse_order(&self.state.answer);
+        let mut pairs = 0;
+        let mut correct_pairs = 0;
+        let mut points = 0;
+        for first in 0..self.stars.len() {
+            for second in first + 1..self.stars.len() {
+                pairs += 1;
+                // Stars of the same brightness can be ordered either way
+                let correct = magnitudes[first] == magnitudes[second]
+                    || order.as_ref().is_some_and(|order| {
+                        let position = |star: usize| order.iter().position(|&i| i == star);
+                        (position(first) < position(second)) == (magnitudes[first] < magnitudes[second])
+                    });
+                if correct {
+                    correct_pairs += 1;
+                }
+                points += self.small_settings.scoring.evaluate(if correct { 0.0 } else { f32::INFINITY }, data.seconds_spent, 0).total();
+            }
+        }
+        self.state.answer_review_text_heading = localisation::tr(if correct_pairs == pairs { "Correct!" } else { "Incorrect!" });
+        let stars = correct_order
+            .iter()
+            .map(|&i| {
+                let offset = magnitudes[i] - self.stars[i].mag;
+                if offset > 0.0 {
+                    localisation::tr_args(
+                        "{0} - {1} ({2} mag, {3} mag without the clouds)",
+                        &[&label(i), &self.stars[i].name, &format!("{:.2}", magnitudes[i]), &format!("{:.2}", self.stars[i].mag)],
+                    )
+                } else {
+                    localisation::tr_args("{0} - {1} ({2} mag)", &[&label(i), &self.stars[i].name, &format!("{:.2}", magnitudes[i])])
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.state.answer_review_text = localisation::tr_args(
+            "Your answer was: {0}\nFrom the brightest to the faintest:\n{1}\nYou ordered {2} of {3} pairs correctly",
+            &[&self.state.answer, &stars, &correct_pairs, &pairs],
+        );
+        if data.is_scored_mode {
+            self.state.answer_review_text += &self.small_settings.scoring.review_text_per_pair(points, "");
+        }
+        *data.score += points;
+        *data.possible_score += pairs * self.small_settings.scoring.max_points();
+        if !self.small_settings.replay_incorrect || correct_pairs == pairs {
+            data.used_questions.push(data.current_question);
+        } else {
+            *data.question_number += 1;
+        }
+        *data.game_stage = GameStage::Checked;
+    }
+}
+
+impl crate::game::game_handler::QuestionTrait for Question {
+    fn render_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        if *data.game_stage == GameStage::Guessing {
+            self.render_question_window(data)
+        } else if *data.game_stage == GameStage::Checked {
+            self.render_answer_review_window(data)
+        } else {
+            None
+        }
+    }
+
+    fn generic_to_next_part(&mut self, data: QuestionCheckingData) {
+        match data.game_stage {
+            GameStage::Guessing => {
+                if !self.should_display_input() {
+                    self.check_answer(data);
+                }
+            }
+            GameStage::Checked => {
+                *data.start_next_question = true;
+            }
+            GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
+        }
+    }
+
+    fn reset(self: Box<Self>) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(Self {
+            stars: self.stars,
+            state: Default::default(),
+            small_settings: self.small_settings,
+        })
+    }
+
+    fn show_tolerance_marker(&self) -> bool {
+        false
+    }
+
+    fn show_circle_marker(&self) -> bool {
+        true
+    }
+
+    fn get_question_distance_tolerance(&self) -> Deg<f32> {
+        angle::Deg(0.0)
+    }
+
+    fn allow_multiple_player_markers(&self) -> bool {
+        false
+    }
+
+    fn add_marker_on_click(&self) -> bool {
+        false
+    }
+
+    fn should_display_input(&self) -> bool {
+        true
+    }
+
+    fn start_question(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        self.state = Default::default();
+        cellestial_sphere.game_markers.markers = self
+            .stars
+            .iter()
+            .enumerate()
+            .map(|(i, star)| GameMarker::new(GameMarkerType::Task, star.ra, star.dec, 2.0, 5.0, true, false, &theme.game_visuals.game_markers_colours).with_label(label(i)))
+            .collect();
+        if self.small_settings.rotate_to_stars {
+            let sum = self
+                .stars
+                .iter()
+                .map(|star| sg_geometry::get_point_vector(star.ra, star.dec, &nalgebra::Matrix3::<f32>::identity()))
+                .fold(nalgebra::Vector3::zeros(), |sum, vector| sum + vector);
+            if sum.magnitude_squared() > 10e-4 {
+                cellestial_sphere.transition_to_point(&sum.normalize(), None);
+                cellestial_sphere.init_renderers();
+            }
+        }
+    }
+
+    fn render_display_question(&self, ui: &mut egui::Ui) {
+        ui.heading(localisation::tr("Order the marked stars from the brightest to the faintest"));
+        ui.label(localisation::tr_args(
+            "Type the letters of the stars, for example '{0}'",
+            &[&(0..self.stars.len()).rev().map(label).collect::<String>()],
+        ));
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Text(self.correct_order(cellestial_sphere).into_iter().map(label).collect()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        let first = self.stars.first()?;
+        Some(WorksheetItem {
+            prompt: localisation::tr_args(
+                "Order the marked stars from the brightest to the faintest ({0})",
+                &[&(0..self.stars.len()).map(|i| label(i).to_string()).collect::<Vec<String>>().join(", ")],
+            ),
+            marks: self.stars.iter().map(|star| (star.ra, star.dec)).collect(),
+            centre: (first.ra, first.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(self.clone())
+    }
+}
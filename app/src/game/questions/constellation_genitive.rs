@@ -0,0 +1,232 @@
+use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
+use crate::game::game_handler;
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
+use crate::renderer::CellestialSphere;
+use crate::rendering::themes::Theme;
+use angle::Deg;
+use eframe::egui;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct SmallSettings {
+    pub replay_incorrect: bool,
+    #[serde(default = "default_scoring")]
+    pub scoring: ScoringRules,
+}
+
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::correct_answer(1.0)
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub show: bool,
+    pub limit_to_toggled_constellations: bool,
+    pub replay_incorrect: bool,
+    pub scoring: ScoringRules,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show: false,
+            limit_to_toggled_constellations: true,
+            replay_incorrect: true,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    answer: String,
+
+    answer_review_text_heading: String,
+    answer_review_text: String,
+}
+
+/// Asks for the latin genitive of a constellation, as used in the Bayer and Flamsteed designations of its stars
+#[derive(Clone)]
+pub struct Question {
+    pub abbreviation: String,
+    pub name_latin: String,
+    pub genitive: String,
+
+    pub state: State,
+    pub small_settings: SmallSettings,
+}
+
+impl Question {
+    fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
+                }
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn check_answer(&mut self, data: QuestionCheckingData) {
+        let correct = localisation::normalise_answer(&self.state.answer) == localisation::normalise_answer(&self.genitive);
+        let score = self.small_settings.scoring.evaluate(if correct { 0.0 } else { f32::INFINITY }, data.seconds_spent, 0);
+        self.state.answer_review_text_heading = localisation::tr(if correct { "Correct!" } else { "Incorrect!" });
+        *data.score += score.total();
+        *data.possible_score += self.small_settings.scoring.max_points();
+        self.state.answer_review_text = localisation::tr_args(
+            "Your answer was: {0}\nThe genitive of {1} is {2}, as in 'alpha {2}' (alpha {3})",
+            &[&self.state.answer, &self.name_latin, &self.genitive, &self.abbreviation],
+        );
+        if data.is_scored_mode {
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "");
+        }
+        if !self.small_settings.replay_incorrect || correct {
+            data.used_questions.push(data.current_question);
+        } else {
+            *data.question_number += 1;
+        }
+        *data.game_stage = GameStage::Checked;
+    }
+}
+
+impl crate::game::game_handler::QuestionTrait for Question {
+    fn render_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        if *data.game_stage == GameStage::Guessing {
+            self.render_question_window(data)
+        } else if *data.game_stage == GameStage::Checked {
+            self.render_answer_review_window(data)
+        } else {
+            None
+        }
+    }
+
+    fn generic_to_next_part(&mut self, data: QuestionCheckingData) {
+        match data.game_stage {
+            GameStage::Guessing => {
+                if !self.should_display_input() {
+                    self.check_answer(data);
+                }
+            }
+            GameStage::Checked => {
+                *data.start_next_question = true;
+            }
+            GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
+        }
+    }
+
+    fn reset(self: Box<Self>) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(Self {
+            abbreviation: self.abbreviation,
+            name_latin: self.name_latin,
+            genitive: self.genitive,
+            state: Default::default(),
+            small_settings: self.small_settings,
+        })
+    }
+
+    fn show_tolerance_marker(&self) -> bool {
+        false
+    }
+
+    fn show_circle_marker(&self) -> bool {
+        false
+    }
+
+    fn get_question_distance_tolerance(&self) -> Deg<f32> {
+        angle::Deg(0.0)
+    }
+
+    fn allow_multiple_player_markers(&self) -> bool {
+        false
+    }
+
+    fn add_marker_on_click(&self) -> bool {
+        false
+    }
+
+    fn should_display_input(&self) -> bool {
+        true
+    }
+
+    fn start_question(&mut self, cellestial_sphere: &mut CellestialSphere, _theme: &Theme) {
+        self.state = Default::default();
+        cellestial_sphere.game_markers.markers = Vec::new();
+    }
+
+    fn render_display_question(&self, ui: &mut egui::Ui) {
+        ui.heading(localisation::tr_args("What is the genitive of {0}?", &[&self.name_latin]));
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Text(self.genitive.clone()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        // There is nothing to mark on a chart
+        None
+    }
+
+    fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(self.clone())
+    }
+}
@@ -1,6 +1,9 @@
 use crate::enums::{GameStage, RendererCategory};
+use crate::export::worksheet::WorksheetItem;
 use crate::game::game_handler;
-use crate::game::game_handler::{GameHandler, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::{Score, ScoringRules};
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
@@ -8,7 +11,7 @@ use angle::{Angle, Deg};
 use eframe::egui;
 use rand::Rng;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 #[serde(default)]
 pub struct SmallSettings {
     pub correctness_threshold: angle::Deg<f32>,
@@ -24,6 +27,46 @@ pub struct SmallSettings {
     pub ask_bayer: bool,
     pub ask_flamsteed: bool,
     pub ask_proper: bool,
+    pub scoring: ScoringRules,
+    /// The hints the player can ask for, in the order in which they are revealed
+    pub hints: Vec<Hint>,
+}
+
+/// The error is the distance from the object in degrees
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules {
+        hint_penalty: 0.2,
+        ..ScoringRules::stepwise([0.2, 0.5, 1.0])
+    }
+}
+
+pub fn default_hints() -> Vec<Hint> {
+    vec![
+        Hint::Constellation,
+        Hint::ConstellationBorder,
+        Hint::ObjectType,
+        Hint::BrightestNearbyStar,
+        Hint::Circle { radius: angle::Deg(20.0) },
+        Hint::Circle { radius: angle::Deg(10.0) },
+        Hint::Circle { radius: angle::Deg(5.0) },
+    ]
+}
+
+/// How far from the object the brightest nearby star is looked for
+const NEARBY_STAR_DISTANCE: angle::Deg<f32> = angle::Deg(10.0);
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+pub enum Hint {
+    /// Tells the player which constellation the object is in
+    Constellation,
+    /// Draws the border of the constellation the object is in
+    ConstellationBorder,
+    /// Draws a circle of the given radius somewhere around the object, replacing the previous circle
+    Circle { radius: angle::Deg<f32> },
+    /// Tells the player what kind of object they are looking for
+    ObjectType,
+    /// Tells the player which star is the brightest one near the object and marks it
+    BrightestNearbyStar,
 }
 
 impl Default for SmallSettings {
@@ -41,6 +84,8 @@ impl Default for SmallSettings {
             ask_bayer: false,
             ask_flamsteed: false,
             ask_proper: false,
+            scoring: default_scoring(),
+            hints: default_hints(),
         }
     }
 }
@@ -60,6 +105,7 @@ pub struct Settings {
     pub correctness_threshold: angle::Deg<f32>,
     pub replay_incorrect: bool,
     pub show: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -77,6 +123,7 @@ impl Default for Settings {
             correctness_threshold: angle::Deg(0.2),
             replay_incorrect: true,
             show: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -87,6 +134,11 @@ pub struct State {
 
     answer_review_text_heading: String,
     answer_review_text: String,
+
+    /// The texts of the hints revealed so far
+    hints_used: Vec<String>,
+    /// Index of the next hint in the settings
+    next_hint: usize,
 }
 
 #[derive(Clone)]
@@ -111,46 +163,66 @@ pub struct Question {
 
 impl Question {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                for hint in &self.state.hints_used {
+                    ui.label(hint);
+                }
+                if self.state.next_hint < self.small_settings.hints.len() {
+                    let hint_text = if data.is_scored_mode && self.small_settings.scoring.hint_penalty > 0.0 {
+                        localisation::tr_args("Hint (-{0}% of the points)", &[&(self.small_settings.scoring.hint_penalty * 100.0).round()])
+                    } else {
+                        localisation::tr("Hint")
+                    };
+                    if ui.button(hint_text).clicked() {
+                        self.reveal_next_hint(data.cellestial_sphere, data.theme);
+                    }
+                }
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if let Some(image) = &self.state.answer_image {
-                ui.add(egui::Image::new(&image.path).max_width(600.0));
-                if let Some(image_source) = &image.source {
-                    ui.hyperlink_to("Image source", image_source);
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
                 }
-            }
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+                ui.label(&self.state.answer_review_text);
+                if let Some(image) = &self.state.answer_image {
+                    ui.add(egui::Image::new(&image.path).max_width(600.0));
+                    if let Some(image_source) = &image.source {
+                        ui.hyperlink_to(localisation::tr("Image source"), image_source);
+                    }
+                }
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn check_answer(&mut self, data: QuestionCheckingData) {
@@ -160,38 +232,55 @@ impl Question {
         if !self.images.is_empty() {
             self.state.answer_image = Some(self.images[rand::thread_rng().gen_range(0..self.images.len())].clone());
         }
+        if data.is_scored_mode {
+            *data.possible_score += self.small_settings.scoring.max_points();
+        }
+        let mut score = Score::default();
         let (answer_dec_text, answer_ra_text, distance, answer_review_text_heading) = if !markers.is_empty() {
             let answer_dec = markers[0].dec;
             let answer_ra = markers[0].ra;
             let distance = sg_geometry::angular_distance((self.ra.to_rad(), self.dec.to_rad()), (answer_ra.to_rad(), answer_dec.to_rad())).to_deg();
-            if data.is_scored_mode {
-                *data.score += GameHandler::evaluate_score(distance);
-            }
+            score = self.small_settings.scoring.evaluate(distance.value(), data.seconds_spent, self.state.hints_used.len() as u32);
             (
                 answer_dec.value().to_string(),
                 answer_ra.value().to_string(),
                 distance.value().to_string(),
                 if distance < self.small_settings.correctness_threshold {
                     correct = true;
-                    String::from("Correct!")
+                    localisation::tr("Correct!")
                 } else {
-                    format!("You were {} degrees away from {} !", (distance.value() * 100.0).round() / 100.0, self.name)
+                    localisation::tr_args("You were {0} degrees away from {1}!", &[&((distance.value() * 100.0).round() / 100.0), &self.name])
                 },
             )
         } else {
-            (String::from("-"), String::from("-"), String::from("-"), format!("You didn't guess where {} is", self.name))
+            (
+                String::from("-"),
+                String::from("-"),
+                String::from("-"),
+                localisation::tr_args("You didn't guess where {0} is", &[&self.name]),
+            )
         };
         self.state.answer_review_text_heading = answer_review_text_heading;
-        self.state.answer_review_text = format!(
-            "Your coordinates: [dec = {}°; ra = {}°]\nCorrect coordinates: [dec = {}°; ra = {}°]\nFully precise distance: {}°\nYou can see the correct place marked with a new {}.\nObject type: {}",
-            answer_dec_text,
-            answer_ra_text,
-            self.dec.value(),
-            self.ra.value(),
-            distance,
-            if self.is_bayer || self.is_starname { "circle" } else { "cross" },
-            self.object_type
+        self.state.answer_review_text = localisation::tr_args(
+            "Your coordinates: [dec = {0}°; ra = {1}°]\nCorrect coordinates: [dec = {2}°; ra = {3}°]\nFully precise distance: {4}°",
+            &[&answer_dec_text, &answer_ra_text, &self.dec.value(), &self.ra.value(), &distance],
         );
+        self.state.answer_review_text += "\n";
+        self.state.answer_review_text += &if self.is_bayer || self.is_starname {
+            localisation::tr("You can see the correct place marked with a new circle.")
+        } else {
+            localisation::tr("You can see the correct place marked with a new cross.")
+        };
+        self.state.answer_review_text += "\n";
+        self.state.answer_review_text += &localisation::tr_args("Object type: {0}", &[&self.object_type]);
+        if !self.state.hints_used.is_empty() {
+            self.state.answer_review_text += "\n";
+            self.state.answer_review_text += &localisation::tr_args("Hints used: {0}", &[&self.state.hints_used.join(" ")]);
+        }
+        if data.is_scored_mode {
+            *data.score += score.total();
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "°");
+        }
         markers.push(GameMarker::new(
             GameMarkerType::CorrectAnswer,
             self.ra,
@@ -209,13 +298,97 @@ impl Question {
         }
         if self.small_settings.rotate_to_answer {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            data.cellestial_sphere.look_at_point(&final_vector);
+            data.cellestial_sphere.transition_to_point(&final_vector, None);
             data.cellestial_sphere.init_renderers();
         } else {
             data.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
         }
         *data.game_stage = GameStage::Checked;
     }
+
+    fn reveal_next_hint(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> Option<String> {
+        while let Some(&hint) = self.small_settings.hints.get(self.state.next_hint) {
+            self.state.next_hint += 1;
+            // Hints which cannot be given for this object (for example when it has no named stars around) are skipped without a penalty
+            if let Some(text) = self.give_hint(hint, cellestial_sphere, theme) {
+                self.state.hints_used.push(text.clone());
+                cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+                return Some(text);
+            }
+        }
+        None
+    }
+
+    fn give_hint(&self, hint: Hint, cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> Option<String> {
+        let colours = &theme.game_visuals.game_markers_colours;
+        match hint {
+            Hint::Constellation => {
+                let constellation = cellestial_sphere.constellations.get(&self.constellation_abbreviation.to_lowercase())?;
+                Some(localisation::tr_args("The object is in {0}.", &[&constellation.display_name()]))
+            }
+            Hint::ConstellationBorder => {
+                let constellation = cellestial_sphere.constellations.get(&self.constellation_abbreviation.to_lowercase())?;
+                let lines = constellation.border_lines(2.0);
+                if lines.is_empty() {
+                    return None;
+                }
+                let text = localisation::tr_args("The border of {0} is highlighted.", &[&constellation.display_name()]);
+                cellestial_sphere.game_markers.hint_lines = lines;
+                cellestial_sphere.game_markers.hint_lines_colour = colours.hint;
+                Some(text)
+            }
+            Hint::Circle { radius } => {
+                let mut rng = rand::thread_rng();
+                // Do not centre the circle on the object, that would give the answer away
+                let (ra, dec) = sg_geometry::offset_point(self.ra, self.dec, angle::Deg(radius.value() * rng.gen_range(0.0..0.7)), angle::Deg(rng.gen_range(0.0..360.0)));
+                cellestial_sphere.game_markers.hints.retain(|marker| marker.angular_radius.is_none());
+                cellestial_sphere
+                    .game_markers
+                    .hints
+                    .push(GameMarker::new(GameMarkerType::Hint, ra, dec, 2.0, radius.value(), true, true, colours));
+                Some(localisation::tr_args("The object is within the highlighted circle with a radius of {0}°.", &[&radius.value()]))
+            }
+            Hint::ObjectType => Some(match self.magnitude {
+                Some(magnitude) => localisation::tr_args("The object is a {0} of magnitude {1}.", &[&self.object_type.to_lowercase(), &format!("{magnitude:.1}")]),
+                None => localisation::tr_args("The object is a {0}.", &[&self.object_type.to_lowercase()]),
+            }),
+            Hint::BrightestNearbyStar => {
+                let (ra, dec, text) = cellestial_sphere
+                    .objects_within(self.ra, self.dec, NEARBY_STAR_DISTANCE)
+                    .into_iter()
+                    .filter(|(object, _)| matches!(object.object_type, crate::game::ObjectType::Star(_)))
+                    .filter_map(|(object, distance)| {
+                        let magnitude = object.mag?;
+                        let name = object
+                            .proper_names_full
+                            .first()
+                            .or(object.bayer_designation_full.as_ref())
+                            .or(object.flamsteed_designation_full.as_ref())?;
+                        // The object itself is not a hint
+                        if distance < angle::Deg(0.01) {
+                            return None;
+                        }
+                        Some((object, name, magnitude, distance))
+                    })
+                    .min_by(|(_, _, magnitude_1, _), (_, _, magnitude_2, _)| magnitude_1.total_cmp(magnitude_2))
+                    .map(|(object, name, magnitude, distance)| {
+                        (
+                            object.ra,
+                            object.dec,
+                            localisation::tr_args(
+                                "The brightest star nearby is {0} (magnitude {1}), {2}° away from the object.",
+                                &[name, &format!("{magnitude:.1}"), &format!("{:.1}", distance.value())],
+                            ),
+                        )
+                    })?;
+                cellestial_sphere
+                    .game_markers
+                    .hints
+                    .push(GameMarker::new(GameMarkerType::Hint, ra, dec, 2.0, 5.0, false, false, colours));
+                Some(text)
+            }
+        }
+    }
 }
 
 impl crate::game::game_handler::QuestionTrait for Question {
@@ -237,6 +410,7 @@ impl crate::game::game_handler::QuestionTrait for Question {
             GameStage::Checked => {
                 *data.start_next_question = true;
                 data.cellestial_sphere.game_markers.markers = Vec::new();
+                data.cellestial_sphere.game_markers.clear_hints();
             }
             GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
         }
@@ -293,7 +467,35 @@ impl crate::game::game_handler::QuestionTrait for Question {
     }
 
     fn render_display_question(&self, ui: &mut egui::Ui) {
-        ui.heading(format!("Find {}", self.name));
+        ui.heading(localisation::tr_args("Find {0}", &[&self.name]));
+    }
+
+    fn set_answer(&mut self, _answer: &str) {}
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Position { ra: self.ra, dec: self.dec })
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> Option<String> {
+        self.reveal_next_hint(cellestial_sphere, theme)
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr_args("Mark {0} on the chart", &[&self.name]),
+            marks: Vec::new(),
+            centre: (self.ra, self.dec),
+            centre_is_answer: true,
+            hidden_object: None,
+        })
     }
 
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
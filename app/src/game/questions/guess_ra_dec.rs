@@ -1,15 +1,25 @@
 use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
 use crate::game::game_handler;
-use crate::game::game_handler::{QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
 use angle::{Angle, Deg};
 use eframe::egui;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct SmallSettings {
     pub rotate_to_point: bool,
+    #[serde(default = "default_scoring")]
+    pub scoring: ScoringRules,
+}
+
+/// The error is in degrees
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::stepwise([3.0, 5.0, 10.0])
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -18,6 +28,7 @@ pub struct Settings {
     pub show: bool,
     pub rotate_to_point: bool,
     pub limit_to_toggled_constellations: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -26,6 +37,7 @@ impl Default for Settings {
             show: true,
             rotate_to_point: true,
             limit_to_toggled_constellations: false,
+            scoring: default_scoring(),
         }
     }
 }
@@ -49,47 +61,54 @@ pub struct RaQuestion {
 
 impl RaQuestion {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if self.should_display_input() {
-                let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
-                if *data.request_input_focus {
-                    text_input_response.request_focus();
-                    *data.request_input_focus = false;
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
                 }
-            }
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
     fn check_answer(&mut self, data: QuestionCheckingData) {
         match self.state.answer.parse::<f32>() {
@@ -101,14 +120,10 @@ impl RaQuestion {
                 self.state.answer_review_text = format!("The real right ascension was {:.1}h", self.ra.value() / 360.0 * 24.0);
 
                 if data.is_scored_mode {
-                    if error_deg < angle::Deg(3.0) {
-                        *data.score += 3;
-                    } else if error_deg < angle::Deg(5.0) {
-                        *data.score += 2;
-                    } else if error_deg < angle::Deg(10.0) {
-                        *data.score += 1;
-                    }
-                    *data.possible_score += 3;
+                    let score = self.small_settings.scoring.evaluate(error_deg.value(), data.seconds_spent, 0);
+                    *data.score += score.total();
+                    *data.possible_score += self.small_settings.scoring.max_points();
+                    self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "°");
                 }
             }
             Err(_) => {
@@ -193,7 +208,7 @@ impl crate::game::game_handler::QuestionTrait for RaQuestion {
         )];
         if self.small_settings.rotate_to_point {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            cellestial_sphere.look_at_point(&final_vector);
+            cellestial_sphere.transition_to_point(&final_vector, None);
             cellestial_sphere.init_renderers();
         }
     }
@@ -202,6 +217,36 @@ impl crate::game::game_handler::QuestionTrait for RaQuestion {
         ui.heading("What is the right ascension (in hours) of this point?");
     }
 
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Text((self.ra.value() / 360.0 * 24.0).to_string()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr("What is the right ascension (in hours) of the marked point?"),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
         Box::new(self.clone())
     }
@@ -218,47 +263,54 @@ pub struct DecQuestion {
 
 impl DecQuestion {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if self.should_display_input() {
-                let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
-                if *data.request_input_focus {
-                    text_input_response.request_focus();
-                    *data.request_input_focus = false;
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
                 }
-            }
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
     fn check_answer(&mut self, data: QuestionCheckingData) {
         match self.state.answer.parse::<f32>() {
@@ -270,14 +322,10 @@ impl DecQuestion {
                 self.state.answer_review_text = format!("The declination was {:.1}°", self.dec.value());
 
                 if data.is_scored_mode {
-                    if error < angle::Deg(3.0) {
-                        *data.score += 3;
-                    } else if error < angle::Deg(5.0) {
-                        *data.score += 2;
-                    } else if error < angle::Deg(10.0) {
-                        *data.score += 1;
-                    }
-                    *data.possible_score += 3;
+                    let score = self.small_settings.scoring.evaluate(error.value(), data.seconds_spent, 0);
+                    *data.score += score.total();
+                    *data.possible_score += self.small_settings.scoring.max_points();
+                    self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "°");
                 }
             }
             Err(_) => {
@@ -362,7 +410,7 @@ impl crate::game::game_handler::QuestionTrait for DecQuestion {
         )];
         if self.small_settings.rotate_to_point {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            cellestial_sphere.look_at_point(&final_vector);
+            cellestial_sphere.transition_to_point(&final_vector, None);
             cellestial_sphere.init_renderers();
         }
     }
@@ -371,6 +419,36 @@ impl crate::game::game_handler::QuestionTrait for DecQuestion {
         ui.heading("What is the declination of this point?");
     }
 
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Text(self.dec.value().to_string()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr("What is the declination of the marked point?"),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
         Box::new(self.clone())
     }
@@ -1,16 +1,26 @@
 use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
 use crate::game::game_handler;
-use crate::game::game_handler::{QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
 use angle::Deg;
 use eframe::egui;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct SmallSettings {
     pub rotate_to_point: bool,
     pub replay_incorrect: bool,
+    #[serde(default = "default_scoring")]
+    pub scoring: ScoringRules,
+}
+
+/// The error is in magnitudes
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::stepwise([0.3, 0.7, 1.5])
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -21,6 +31,7 @@ pub struct Settings {
     pub magnitude_cutoff: f32,
     pub replay_incorrect: bool,
     pub show: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -31,6 +42,7 @@ impl Default for Settings {
             magnitude_cutoff: 6.0,
             replay_incorrect: true,
             show: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -55,47 +67,54 @@ pub struct Question {
 
 impl Question {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if self.should_display_input() {
-                let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
-                if *data.request_input_focus {
-                    text_input_response.request_focus();
-                    *data.request_input_focus = false;
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
                 }
-            }
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
     fn check_answer(&mut self, data: QuestionCheckingData) {
         match self.state.answer.parse::<f32>() {
@@ -106,14 +125,10 @@ impl Question {
                 self.state.answer_review_text = format!("The magnitude was {:.1}.", self.mag);
 
                 if data.is_scored_mode {
-                    if error < 0.3 {
-                        *data.score += 3;
-                    } else if error < 0.7 {
-                        *data.score += 2;
-                    } else if error < 1.5 {
-                        *data.score += 1;
-                    }
-                    *data.possible_score += 3;
+                    let score = self.small_settings.scoring.evaluate(error, data.seconds_spent, 0);
+                    *data.score += score.total();
+                    *data.possible_score += self.small_settings.scoring.max_points();
+                    self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, " mag");
                 }
             }
             Err(_) => {
@@ -200,7 +215,7 @@ impl crate::game::game_handler::QuestionTrait for Question {
         )];
         if self.small_settings.rotate_to_point {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            cellestial_sphere.look_at_point(&final_vector);
+            cellestial_sphere.transition_to_point(&final_vector, None);
             cellestial_sphere.init_renderers();
         }
     }
@@ -209,6 +224,36 @@ impl crate::game::game_handler::QuestionTrait for Question {
         ui.heading("What is the magnitude of this object?");
     }
 
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Text(self.mag.to_string()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr("What is the magnitude of the marked object?"),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
         Box::new(self.clone())
     }
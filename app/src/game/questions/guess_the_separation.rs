@@ -0,0 +1,285 @@
+use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
+use crate::game::game_handler;
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
+use crate::renderer::CellestialSphere;
+use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
+use crate::rendering::themes::Theme;
+use angle::Deg;
+use eframe::egui;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct SmallSettings {
+    pub rotate_to_point: bool,
+    pub replay_incorrect: bool,
+    #[serde(default = "default_scoring")]
+    pub scoring: ScoringRules,
+}
+
+/// The error is in percent of the separation, as the separations go from about an arcsecond to several arcminutes
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::stepwise([15.0, 35.0, 70.0])
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub rotate_to_point: bool,
+    pub limit_to_toggled_constellations: bool,
+    pub replay_incorrect: bool,
+    pub show: bool,
+    pub scoring: ScoringRules,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rotate_to_point: true,
+            limit_to_toggled_constellations: false,
+            replay_incorrect: true,
+            show: false,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    answer: String,
+
+    answer_review_text_heading: String,
+    answer_review_text: String,
+}
+
+#[derive(Clone)]
+pub struct Question {
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    pub name: String,
+    /// In arcseconds
+    pub separation: f32,
+    pub position_angle: Option<f32>,
+    pub component_magnitudes: Vec<f32>,
+
+    pub state: State,
+    pub small_settings: SmallSettings,
+}
+
+impl Question {
+    fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
+                }
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn describe_double(&self) -> String {
+        let mut description = format!("The separation of {} was {}\".", self.name, self.separation);
+        if let Some(position_angle) = self.position_angle {
+            description += &format!("\nPosition angle: {position_angle}°");
+        }
+        if !self.component_magnitudes.is_empty() {
+            description += &format!(
+                "\nComponent magnitudes: {}",
+                self.component_magnitudes.iter().map(|mag| format!("{mag:.1}")).collect::<Vec<String>>().join(", ")
+            );
+        }
+        description
+    }
+
+    fn check_answer(&mut self, data: QuestionCheckingData) {
+        let mut correct = false;
+        match self.state.answer.trim().trim_end_matches('"').parse::<f32>() {
+            Ok(answer) => {
+                let error = (self.separation - answer).abs() / self.separation * 100.0;
+                self.state.answer_review_text_heading = format!("You were {error:.0} % off!");
+                self.state.answer_review_text = self.describe_double();
+
+                let score = self.small_settings.scoring.evaluate(error, data.seconds_spent, 0);
+                correct = score.base > 0.0;
+                if data.is_scored_mode {
+                    *data.score += score.total();
+                    *data.possible_score += self.small_settings.scoring.max_points();
+                    self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, " %");
+                }
+            }
+            Err(_) => {
+                self.state.answer_review_text_heading = "You didn't guess".to_string();
+                self.state.answer_review_text = self.describe_double();
+            }
+        };
+        if !self.small_settings.replay_incorrect || correct {
+            data.used_questions.push(data.current_question);
+        } else {
+            *data.question_number += 1;
+        }
+        *data.game_stage = GameStage::Checked;
+    }
+}
+
+impl crate::game::game_handler::QuestionTrait for Question {
+    fn render_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        if *data.game_stage == GameStage::Guessing {
+            self.render_question_window(data)
+        } else if *data.game_stage == GameStage::Checked {
+            self.render_answer_review_window(data)
+        } else {
+            None
+        }
+    }
+
+    fn generic_to_next_part(&mut self, data: QuestionCheckingData) {
+        match data.game_stage {
+            GameStage::Guessing => {
+                if !self.should_display_input() {
+                    self.check_answer(data);
+                }
+            }
+            GameStage::Checked => {
+                *data.start_next_question = true;
+            }
+            GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
+        }
+    }
+
+    fn reset(self: Box<Self>) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(Self {
+            ra: self.ra,
+            dec: self.dec,
+            name: self.name,
+            separation: self.separation,
+            position_angle: self.position_angle,
+            component_magnitudes: self.component_magnitudes,
+
+            state: State::default(),
+            small_settings: self.small_settings,
+        })
+    }
+
+    fn show_tolerance_marker(&self) -> bool {
+        false
+    }
+
+    fn show_circle_marker(&self) -> bool {
+        true
+    }
+
+    fn get_question_distance_tolerance(&self) -> Deg<f32> {
+        angle::Deg(0.0)
+    }
+
+    fn allow_multiple_player_markers(&self) -> bool {
+        false
+    }
+
+    fn add_marker_on_click(&self) -> bool {
+        false
+    }
+
+    fn should_display_input(&self) -> bool {
+        true
+    }
+
+    fn start_question(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        self.state = Default::default();
+        cellestial_sphere.game_markers.markers = vec![GameMarker::new(
+            GameMarkerType::Task,
+            self.ra,
+            self.dec,
+            2.0,
+            5.0,
+            true,
+            false,
+            &theme.game_visuals.game_markers_colours,
+        )];
+        if self.small_settings.rotate_to_point {
+            let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
+            cellestial_sphere.transition_to_point(&final_vector, None);
+            cellestial_sphere.init_renderers();
+        }
+    }
+
+    fn render_display_question(&self, ui: &mut egui::Ui) {
+        ui.heading(format!("What is the separation of {} (in arcseconds)?", self.name));
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Text(self.separation.to_string()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr_args("Estimate the separation of the marked double star {0} (in arcseconds).", &[&self.name]),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(self.clone())
+    }
+}
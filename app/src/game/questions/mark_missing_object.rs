@@ -1,6 +1,9 @@
 use crate::enums::{GameStage, RendererCategory};
+use crate::export::worksheet::WorksheetItem;
 use crate::game::game_handler;
-use crate::game::game_handler::{GameHandler, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::{Score, ScoringRules};
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
@@ -8,12 +11,18 @@ use angle::{Angle, Deg};
 use eframe::egui;
 use rand::Rng;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 #[serde(default)]
 pub struct SmallSettings {
     pub correctness_threshold: angle::Deg<f32>,
     pub rotate_to_answer: bool,
     pub replay_incorrect: bool,
+    pub scoring: ScoringRules,
+}
+
+/// The error is the distance from the object in degrees
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::stepwise([0.2, 0.5, 1.0])
 }
 
 impl Default for SmallSettings {
@@ -22,6 +31,7 @@ impl Default for SmallSettings {
             correctness_threshold: angle::Deg(1.0),
             rotate_to_answer: true,
             replay_incorrect: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -41,6 +51,7 @@ pub struct Settings {
     pub correctness_threshold: angle::Deg<f32>,
     pub replay_incorrect: bool,
     pub show: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -58,6 +69,7 @@ impl Default for Settings {
             correctness_threshold: angle::Deg(0.2),
             replay_incorrect: true,
             show: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -93,46 +105,53 @@ pub struct Question {
 
 impl Question {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if let Some(image) = &self.state.answer_image {
-                ui.add(egui::Image::new(&image.path).max_width(600.0));
-                if let Some(image_source) = &image.source {
-                    ui.hyperlink_to("Image source", image_source);
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
                 }
-            }
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+                ui.label(&self.state.answer_review_text);
+                if let Some(image) = &self.state.answer_image {
+                    ui.add(egui::Image::new(&image.path).max_width(600.0));
+                    if let Some(image_source) = &image.source {
+                        ui.hyperlink_to(localisation::tr("Image source"), image_source);
+                    }
+                }
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn check_answer(&mut self, data: QuestionCheckingData) {
@@ -142,13 +161,15 @@ impl Question {
         if !self.images.is_empty() {
             self.state.answer_image = Some(self.images[rand::thread_rng().gen_range(0..self.images.len())].clone());
         }
+        if data.is_scored_mode {
+            *data.possible_score += self.small_settings.scoring.max_points();
+        }
+        let mut score = Score::default();
         let (answer_dec_text, answer_ra_text, distance, answer_review_text_heading) = if !markers.is_empty() {
             let answer_dec = markers[0].dec;
             let answer_ra = markers[0].ra;
             let distance = sg_geometry::angular_distance((self.ra.to_rad(), self.dec.to_rad()), (answer_ra.to_rad(), answer_dec.to_rad())).to_deg();
-            if data.is_scored_mode {
-                *data.score += GameHandler::evaluate_score(distance);
-            }
+            score = self.small_settings.scoring.evaluate(distance.value(), data.seconds_spent, 0);
             (
                 answer_dec.value().to_string(),
                 answer_ra.value().to_string(),
@@ -175,6 +196,10 @@ impl Question {
             if self.is_bayer || self.is_starname { "circle" } else { "cross" },
             self.object_type
         );
+        if data.is_scored_mode {
+            *data.score += score.total();
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "°");
+        }
         markers.push(GameMarker::new(
             GameMarkerType::CorrectAnswer,
             self.ra,
@@ -192,7 +217,7 @@ impl Question {
         }
         if self.small_settings.rotate_to_answer {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            data.cellestial_sphere.look_at_point(&final_vector);
+            data.cellestial_sphere.transition_to_point(&final_vector, None);
             data.cellestial_sphere.init_renderers();
         } else {
             data.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
@@ -281,6 +306,34 @@ impl crate::game::game_handler::QuestionTrait for Question {
         ui.heading("Find the object that is missing in the sky");
     }
 
+    fn set_answer(&mut self, _answer: &str) {}
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        Some(Answer::Position { ra: self.ra, dec: self.dec })
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr("Mark the object that is missing from the chart"),
+            marks: Vec::new(),
+            centre: (self.ra, self.dec),
+            centre_is_answer: true,
+            hidden_object: Some(self.object_id),
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
         Box::new(self.clone())
     }
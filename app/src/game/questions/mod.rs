@@ -1,23 +1,33 @@
 pub mod angular_separation;
+pub mod compare_brightness;
+pub mod constellation_genitive;
 pub mod find_this_object;
 pub mod guess_ra_dec;
 pub mod guess_the_magnitude;
+pub mod guess_the_separation;
 pub mod mark_missing_object;
+pub mod which_bayer_letter;
 pub mod which_constellation_is_point_in;
 pub mod which_object_is_here;
 pub mod which_object_is_missing;
+pub mod which_variable_star;
 
 #[derive(serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct Settings {
     pub angular_separation: angular_separation::Settings,
+    pub compare_brightness: compare_brightness::Settings,
+    pub constellation_genitive: constellation_genitive::Settings,
     pub find_this_object: find_this_object::Settings,
     pub guess_rad_dec: guess_ra_dec::Settings,
     pub guess_the_magnitude: guess_the_magnitude::Settings,
+    pub guess_the_separation: guess_the_separation::Settings,
     pub mark_missing_object: mark_missing_object::Settings,
     pub what_constellation_is_this_point_in: which_constellation_is_point_in::Settings,
     pub what_is_this_object: which_object_is_here::Settings,
+    pub which_bayer_letter: which_bayer_letter::Settings,
     pub which_object_is_missing: which_object_is_missing::Settings,
+    pub which_variable_star: which_variable_star::Settings,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -25,13 +35,18 @@ impl Default for Settings {
     fn default() -> Self {
         Self {
             angular_separation: angular_separation::Settings::default(),
+            compare_brightness: compare_brightness::Settings::default(),
+            constellation_genitive: constellation_genitive::Settings::default(),
             find_this_object: find_this_object::Settings::default(),
             guess_rad_dec: guess_ra_dec::Settings::default(),
             guess_the_magnitude: guess_the_magnitude::Settings::default(),
+            guess_the_separation: guess_the_separation::Settings::default(),
             mark_missing_object: mark_missing_object::Settings::default(),
             what_constellation_is_this_point_in: which_constellation_is_point_in::Settings::default(),
             what_is_this_object: which_object_is_here::Settings::default(),
+            which_bayer_letter: which_bayer_letter::Settings::default(),
             which_object_is_missing: which_object_is_missing::Settings::default(),
+            which_variable_star: which_variable_star::Settings::default(),
         }
     }
 }
@@ -39,14 +54,19 @@ impl Default for Settings {
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub enum QuestionType {
     AngularSeparation(angular_separation::SmallSettings),
+    CompareBrightness(compare_brightness::SmallSettings),
+    ConstellationGenitive(constellation_genitive::SmallSettings),
     FindThisObject(find_this_object::SmallSettings),
     GuessDec(guess_ra_dec::SmallSettings),
     GuessRa(guess_ra_dec::SmallSettings),
     GuessTheMagnitude(guess_the_magnitude::SmallSettings),
+    GuessTheSeparation(guess_the_separation::SmallSettings),
     MarkMissingObject(mark_missing_object::SmallSettings),
     WhatIsThisObject(which_object_is_here::SmallSettings),
+    WhichBayerLetter(which_bayer_letter::SmallSettings),
     WhichConstellationIsThisPointIn(which_constellation_is_point_in::SmallSettings),
     WhichObjectIsMissing(which_object_is_missing::SmallSettings),
+    WhichVariableStar(which_variable_star::SmallSettings),
 }
 
 pub fn question_pack_to_string(name: &str, question_pack: &crate::game::questions_filter::QuestionPack) -> String {
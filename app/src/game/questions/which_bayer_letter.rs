@@ -0,0 +1,302 @@
+use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
+use crate::game::game_handler;
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
+use crate::renderer::CellestialSphere;
+use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
+use crate::rendering::caspr::star_names::GREEK_LETTERS;
+use crate::rendering::themes::Theme;
+use angle::Deg;
+use eframe::egui;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct SmallSettings {
+    pub rotate_to_point: bool,
+    pub replay_incorrect: bool,
+    pub scoring: ScoringRules,
+}
+
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::correct_answer(1.0)
+}
+
+impl Default for SmallSettings {
+    fn default() -> Self {
+        Self {
+            rotate_to_point: true,
+            replay_incorrect: true,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub show: bool,
+    pub rotate_to_point: bool,
+    pub limit_to_toggled_constellations: bool,
+    /// Only the stars among this many brightest stars of their constellation are asked about
+    pub brightest_in_constellation: u32,
+    pub replay_incorrect: bool,
+    pub scoring: ScoringRules,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show: false,
+            rotate_to_point: true,
+            limit_to_toggled_constellations: true,
+            brightest_in_constellation: 5,
+            replay_incorrect: true,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+/// Splits a Bayer designation from the sky objects files, like "theta{1}{ Eridani}", into the index into [`GREEK_LETTERS`] and the superscript. Designations using Latin letters give None.
+pub fn greek_letter(bayer_designation_raw: &str) -> Option<(usize, Option<String>)> {
+    let letter_end = bayer_designation_raw.find('{').unwrap_or(bayer_designation_raw.len());
+    let index = GREEK_LETTERS.iter().position(|(_, name, _)| *name == bayer_designation_raw[..letter_end].trim())?;
+    let superscript = bayer_designation_raw[letter_end..]
+        .strip_prefix('{')
+        .and_then(|rest| rest.split_once('}'))
+        .map(|(superscript, _)| superscript.to_owned())
+        .filter(|superscript| !superscript.is_empty() && !superscript.starts_with(' '));
+    Some((index, superscript))
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    answer: String,
+
+    answer_review_text_heading: String,
+    answer_review_text: String,
+}
+
+/// Marks a star and asks for its Bayer letter, the constellation being given
+#[derive(Clone)]
+pub struct Question {
+    pub small_settings: SmallSettings,
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    /// Index into [`GREEK_LETTERS`]
+    pub letter: usize,
+    /// For the stars like theta¹ Eridani
+    pub superscript: Option<String>,
+    /// The full name of the star, shown after answering
+    pub star_name: String,
+    pub constellation_name: String,
+
+    pub state: State,
+}
+
+impl Question {
+    /// The name of the letter, the letter itself and its abbreviation are all accepted, both with and without the superscript
+    fn possible_answers(&self) -> Vec<String> {
+        let (abbreviation, name, symbol) = GREEK_LETTERS[self.letter];
+        let mut answers = Vec::new();
+        for letter in [name, symbol, abbreviation] {
+            if let Some(superscript) = &self.superscript {
+                answers.push(format!("{letter}{superscript}"));
+            }
+            answers.push(letter.to_owned());
+        }
+        answers
+    }
+
+    fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
+                }
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn check_answer(&mut self, data: QuestionCheckingData) {
+        let answer = localisation::normalise_answer(&self.state.answer);
+        let correct = self.possible_answers().iter().any(|possible_answer| localisation::normalise_answer(possible_answer) == answer);
+        let score = self.small_settings.scoring.evaluate(if correct { 0.0 } else { f32::INFINITY }, data.seconds_spent, 0);
+        self.state.answer_review_text_heading = localisation::tr(if correct { "Correct!" } else { "Incorrect!" });
+        self.state.answer_review_text = localisation::tr_args("Your answer was: {0}\nThe star is {1}", &[&self.state.answer, &self.star_name]);
+        if data.is_scored_mode {
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "");
+        }
+        *data.score += score.total();
+        *data.possible_score += self.small_settings.scoring.max_points();
+        if !self.small_settings.replay_incorrect || correct {
+            data.used_questions.push(data.current_question);
+        } else {
+            *data.question_number += 1;
+        }
+        *data.game_stage = GameStage::Checked;
+    }
+}
+
+impl crate::game::game_handler::QuestionTrait for Question {
+    fn render_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        if *data.game_stage == GameStage::Guessing {
+            self.render_question_window(data)
+        } else if *data.game_stage == GameStage::Checked {
+            self.render_answer_review_window(data)
+        } else {
+            None
+        }
+    }
+
+    fn generic_to_next_part(&mut self, data: QuestionCheckingData) {
+        match data.game_stage {
+            GameStage::Guessing => {
+                if !self.should_display_input() {
+                    self.check_answer(data);
+                }
+            }
+            GameStage::Checked => {
+                *data.start_next_question = true;
+            }
+            GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
+        }
+    }
+
+    fn reset(self: Box<Self>) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(Self {
+            small_settings: self.small_settings,
+            ra: self.ra,
+            dec: self.dec,
+            letter: self.letter,
+            superscript: self.superscript,
+            star_name: self.star_name,
+            constellation_name: self.constellation_name,
+
+            state: State::default(),
+        })
+    }
+
+    fn show_tolerance_marker(&self) -> bool {
+        false
+    }
+
+    fn show_circle_marker(&self) -> bool {
+        true
+    }
+
+    fn get_question_distance_tolerance(&self) -> Deg<f32> {
+        angle::Deg(0.0)
+    }
+
+    fn allow_multiple_player_markers(&self) -> bool {
+        false
+    }
+
+    fn add_marker_on_click(&self) -> bool {
+        false
+    }
+
+    fn should_display_input(&self) -> bool {
+        true
+    }
+
+    fn start_question(&mut self, cellestial_sphere: &mut CellestialSphere, theme: &Theme) {
+        self.state = Default::default();
+        cellestial_sphere.game_markers.markers = vec![GameMarker::new(
+            GameMarkerType::Task,
+            self.ra,
+            self.dec,
+            2.0,
+            5.0,
+            true,
+            false,
+            &theme.game_visuals.game_markers_colours,
+        )];
+        if self.small_settings.rotate_to_point {
+            let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
+            cellestial_sphere.transition_to_point(&final_vector, None);
+            cellestial_sphere.init_renderers();
+        }
+    }
+
+    fn render_display_question(&self, ui: &mut egui::Ui) {
+        ui.heading(localisation::tr_args("Which Bayer letter does this star have in {0}?", &[&self.constellation_name]));
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        self.possible_answers().into_iter().next().map(Answer::Text)
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr_args("Which Bayer letter does the marked star have in {0}?", &[&self.constellation_name]),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(self.clone())
+    }
+}
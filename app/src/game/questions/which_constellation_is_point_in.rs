@@ -1,15 +1,24 @@
 use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
 use crate::game::game_handler;
-use crate::game::game_handler::{QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
 use angle::{Angle, Deg};
 use eframe::egui;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct SmallSettings {
     pub rotate_to_point: bool,
+    #[serde(default = "default_scoring")]
+    pub scoring: ScoringRules,
+}
+
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::correct_answer(1.0)
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -18,6 +27,7 @@ pub struct Settings {
     pub show: bool,
     pub rotate_to_point: bool,
     pub limit_to_toggled_constellations: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -26,6 +36,7 @@ impl Default for Settings {
             show: true,
             rotate_to_point: true,
             limit_to_toggled_constellations: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -49,47 +60,54 @@ pub struct Question {
 
 impl Question {
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if self.should_display_input() {
-                let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
-                if *data.request_input_focus {
-                    text_input_response.request_focus();
-                    *data.request_input_focus = false;
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
                 }
-            }
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn check_answer(&mut self, data: QuestionCheckingData) {
@@ -97,21 +115,20 @@ impl Question {
         let mut possible_constellation_names = Vec::new();
         for abbrev in possible_abbrevs {
             if let Some(constellation) = data.cellestial_sphere.constellations.get(&abbrev) {
-                possible_constellation_names.extend(constellation.possible_names.iter().map(|name| name.replace(' ', "").to_lowercase()));
+                possible_constellation_names.extend(constellation.possible_names.iter().cloned());
+                possible_constellation_names.push(constellation.genitive.clone());
             };
         }
-        let correct = possible_constellation_names.contains(&self.state.answer.replace(' ', "").to_lowercase());
-        self.state.answer_review_text_heading = format!(
-            "{}orrect!",
-            if correct {
-                *data.score += 1;
-                "C"
-            } else {
-                "Inc"
-            }
-        );
-        *data.possible_score += 1;
-        self.state.answer_review_text = format!("Your answer was: {}\nThe right answers were: {}", self.state.answer, possible_constellation_names.join(", "));
+        let answer = localisation::normalise_answer(&self.state.answer);
+        let correct = possible_constellation_names.iter().any(|name| localisation::normalise_answer(name) == answer);
+        let score = self.small_settings.scoring.evaluate(if correct { 0.0 } else { f32::INFINITY }, data.seconds_spent, 0);
+        self.state.answer_review_text_heading = localisation::tr(if correct { "Correct!" } else { "Incorrect!" });
+        *data.score += score.total();
+        *data.possible_score += self.small_settings.scoring.max_points();
+        self.state.answer_review_text = localisation::tr_args("Your answer was: {0}\nThe right answers were: {1}", &[&self.state.answer, &possible_constellation_names.join(", ")]);
+        if data.is_scored_mode {
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "");
+        }
         data.used_questions.push(data.current_question);
         *data.game_stage = GameStage::Checked;
     }
@@ -189,13 +206,48 @@ impl crate::game::game_handler::QuestionTrait for Question {
         )];
         if self.small_settings.rotate_to_point {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            cellestial_sphere.look_at_point(&final_vector);
+            cellestial_sphere.transition_to_point(&final_vector, None);
             cellestial_sphere.init_renderers();
         }
     }
 
     fn render_display_question(&self, ui: &mut egui::Ui) {
-        ui.heading("What constellation does this point lie in?");
+        ui.heading(localisation::tr("What constellation does this point lie in?"));
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        cellestial_sphere
+            .determine_constellation((self.ra.to_rad(), self.dec.to_rad()))
+            .iter()
+            .find_map(|abbrev| cellestial_sphere.constellations.get(abbrev))
+            .and_then(|constellation| constellation.possible_names.first())
+            .map(|name| Answer::Text(name.clone()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr("What constellation does the marked point lie in?"),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
     }
 
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
@@ -1,6 +1,9 @@
 use crate::enums::GameStage;
+use crate::export::worksheet::WorksheetItem;
 use crate::game::game_handler;
-use crate::game::game_handler::{QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
 use crate::renderer::CellestialSphere;
 use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
 use crate::rendering::themes::Theme;
@@ -8,7 +11,7 @@ use angle::Deg;
 use eframe::egui;
 use rand::Rng;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 #[serde(default)]
 pub struct SmallSettings {
     pub rotate_to_point: bool,
@@ -22,6 +25,11 @@ pub struct SmallSettings {
     pub accept_proper: bool,
     pub accept_bayer: bool,
     pub accept_flamsteed: bool,
+    pub scoring: ScoringRules,
+}
+
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::correct_answer(1.0)
 }
 
 impl Default for SmallSettings {
@@ -38,6 +46,7 @@ impl Default for SmallSettings {
             accept_proper: true,
             accept_bayer: true,
             accept_flamsteed: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -57,6 +66,7 @@ pub struct Settings {
     pub correctness_threshold: f32,
     pub replay_incorrect: bool,
     pub show: bool,
+    pub scoring: ScoringRules,
 }
 
 impl Default for Settings {
@@ -74,6 +84,7 @@ impl Default for Settings {
             correctness_threshold: 0.2,
             replay_incorrect: true,
             show: true,
+            scoring: default_scoring(),
         }
     }
 }
@@ -108,78 +119,113 @@ pub struct Question {
 }
 
 impl Question {
+    /// The kinds of names the player can answer with
+    fn accepted_names(&self) -> Vec<&'static str> {
+        let mut accepted = Vec::new();
+        if self.small_settings.accept_bayer {
+            accepted.push("Bayer");
+        }
+        if self.small_settings.accept_caldwell {
+            accepted.push("Caldwell");
+        }
+        if self.small_settings.accept_flamsteed {
+            accepted.push("Flamsteed");
+        }
+        if self.small_settings.accept_hd {
+            accepted.push("HD");
+        }
+        if self.small_settings.accept_hip {
+            accepted.push("HIP");
+        }
+        if self.small_settings.accept_ic {
+            accepted.push("IC");
+        }
+        if self.small_settings.accept_messier {
+            accepted.push("Messier");
+        }
+        if self.small_settings.accept_ngc {
+            accepted.push("NGC");
+        }
+        if self.small_settings.accept_proper {
+            accepted.push("Proper name");
+        }
+        accepted
+    }
+
     fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            self.render_display_question(ui);
-            if self.should_display_input() {
-                let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
-                if *data.request_input_focus {
-                    text_input_response.request_focus();
-                    *data.request_input_focus = false;
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
                 }
-            }
-            if ui.button("Check").clicked() {
-                self.check_answer(QuestionCheckingData {
-                    cellestial_sphere: data.cellestial_sphere,
-                    theme: data.theme,
-                    game_stage: data.game_stage,
-                    score: data.score,
-                    possible_score: data.possible_score,
-                    is_scored_mode: data.is_scored_mode,
-                    current_question: data.current_question,
-                    used_questions: data.used_questions,
-                    add_marker_on_click: data.add_marker_on_click,
-                    questions_settings: data.questions_settings,
-                    question_number: data.question_number,
-                    start_next_question: data.start_next_question,
-                    switch_to_next_part: data.switch_to_next_part,
-                });
-            }
-            ui.label(data.question_number_text);
-        })
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
-        egui::Window::new("Question").open(data.game_question_opened).show(data.ctx, |ui| {
-            if !self.state.answer_review_text_heading.is_empty() {
-                ui.heading(&self.state.answer_review_text_heading);
-            }
-            ui.label(&self.state.answer_review_text);
-            if let Some(image) = &self.state.answer_image {
-                ui.add(egui::Image::new(&image.path).max_width(600.0));
-                if let Some(image_source) = &image.source {
-                    ui.hyperlink_to("Image source", image_source);
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
                 }
-            }
-            if ui.button("Next").clicked() {
-                *data.switch_to_next_part = true;
-            }
-            ui.label(data.question_number_text);
-        })
+                ui.label(&self.state.answer_review_text);
+                if let Some(image) = &self.state.answer_image {
+                    ui.add(egui::Image::new(&image.path).max_width(600.0));
+                    if let Some(image_source) = &image.source {
+                        ui.hyperlink_to(localisation::tr("Image source"), image_source);
+                    }
+                }
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
     }
 
     fn check_answer(&mut self, data: QuestionCheckingData) {
         if !self.images.is_empty() {
             self.state.answer_image = Some(self.images[rand::thread_rng().gen_range(0..self.images.len())].clone());
         }
-        let possible_names_edited = self.possible_names.iter().map(|name| name.replace(' ', "").to_lowercase()).collect::<Vec<String>>();
-        let correct = possible_names_edited.contains(&self.state.answer.replace(' ', "").to_lowercase());
-        self.state.answer_review_text_heading = format!(
-            "{}orrect!",
-            if correct {
-                *data.score += 1;
-                "C"
-            } else {
-                "Inc"
-            }
+        let possible_names_edited = self.possible_names.iter().map(|name| localisation::normalise_answer(name)).collect::<Vec<String>>();
+        let correct = possible_names_edited.contains(&localisation::normalise_answer(&self.state.answer));
+        let score = self.small_settings.scoring.evaluate(if correct { 0.0 } else { f32::INFINITY }, data.seconds_spent, 0);
+        self.state.answer_review_text_heading = localisation::tr(if correct { "Correct!" } else { "Incorrect!" });
+        self.state.answer_review_text = localisation::tr_args(
+            "Your answer was: {0}\nPossible answers: {1}\nObject type: {2}",
+            &[&self.state.answer, &self.possible_names.join(", "), &self.object_type],
         );
-        self.state.answer_review_text = format!(
-            "Your answer was: {}\nPossible answers: {}\nObject type: {}",
-            self.state.answer,
-            self.possible_names.join(", "),
-            self.object_type
-        );
-        *data.possible_score += 1;
+        if data.is_scored_mode {
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "");
+        }
+        *data.score += score.total();
+        *data.possible_score += self.small_settings.scoring.max_points();
         if !self.small_settings.replay_incorrect || correct {
             data.used_questions.push(data.current_question);
         } else {
@@ -286,42 +332,44 @@ impl crate::game::game_handler::QuestionTrait for Question {
         };
         if self.small_settings.rotate_to_point {
             let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
-            cellestial_sphere.look_at_point(&final_vector);
+            cellestial_sphere.transition_to_point(&final_vector, None);
             cellestial_sphere.init_renderers();
         }
     }
 
     fn render_display_question(&self, ui: &mut egui::Ui) {
-        let mut accepted = Vec::new();
-        if self.small_settings.accept_bayer {
-            accepted.push("Bayer");
-        }
-        if self.small_settings.accept_caldwell {
-            accepted.push("Caldwell");
-        }
-        if self.small_settings.accept_flamsteed {
-            accepted.push("Flamsteed");
-        }
-        if self.small_settings.accept_hd {
-            accepted.push("HD");
-        }
-        if self.small_settings.accept_hip {
-            accepted.push("HIP");
-        }
-        if self.small_settings.accept_ic {
-            accepted.push("IC");
-        }
-        if self.small_settings.accept_messier {
-            accepted.push("Messier");
-        }
-        if self.small_settings.accept_ngc {
-            accepted.push("NGC");
-        }
-        if self.small_settings.accept_proper {
-            accepted.push("Proper name");
-        }
-        ui.heading("What is this object?");
-        ui.label(format!("Accepted names: {}", accepted.join(", ")));
+        ui.heading(localisation::tr("What is this object?"));
+        ui.label(localisation::tr_args("Accepted names: {0}", &[&self.accepted_names().join(", ")]));
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        self.possible_names.first().map(|name| Answer::Text(name.clone()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr_args("What is the marked object? Accepted names: {0}", &[&self.accepted_names().join(", ")]),
+            marks: vec![(self.ra, self.dec)],
+            centre: (self.ra, self.dec),
+            centre_is_answer: false,
+            hidden_object: None,
+        })
     }
 
     fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
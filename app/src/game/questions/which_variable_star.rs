@@ -0,0 +1,286 @@
+use crate::enums::{GameStage, RendererCategory};
+use crate::export::worksheet::WorksheetItem;
+use crate::game::game_handler;
+use crate::game::game_handler::{Answer, QuestionCheckingData, QuestionTrait, QuestionWindowData};
+use crate::game::scoring::ScoringRules;
+use crate::localisation;
+use crate::renderer::CellestialSphere;
+use crate::rendering::caspr::markers::game_markers::{GameMarker, GameMarkerType};
+use crate::rendering::themes::Theme;
+use angle::Deg;
+use eframe::egui;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct SmallSettings {
+    pub rotate_to_answer: bool,
+    pub replay_incorrect: bool,
+    /// Whether to tell the constellation of the star together with its variability
+    pub show_constellation: bool,
+    pub scoring: ScoringRules,
+}
+
+pub fn default_scoring() -> ScoringRules {
+    ScoringRules::correct_answer(1.0)
+}
+
+impl Default for SmallSettings {
+    fn default() -> Self {
+        Self {
+            rotate_to_answer: true,
+            replay_incorrect: true,
+            show_constellation: true,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub rotate_to_answer: bool,
+    pub limit_to_toggled_constellations: bool,
+    pub show_constellation: bool,
+    pub replay_incorrect: bool,
+    pub show: bool,
+    pub scoring: ScoringRules,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rotate_to_answer: true,
+            limit_to_toggled_constellations: false,
+            show_constellation: true,
+            replay_incorrect: true,
+            show: false,
+            scoring: default_scoring(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    answer: String,
+
+    answer_review_text_heading: String,
+    answer_review_text: String,
+}
+
+/// Describes the variability of a star and asks which star it is
+#[derive(Clone)]
+pub struct Question {
+    pub small_settings: SmallSettings,
+    pub possible_names: Vec<String>,
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    /// For example "Mira variable, 2.0 - 10.1 mag, period 332 days"
+    pub variability: String,
+    pub constellation_abbreviation: String,
+
+    pub state: State,
+}
+
+impl Question {
+    fn clues(&self) -> String {
+        if self.small_settings.show_constellation {
+            format!("{}\n{}", self.variability, localisation::tr_args("Constellation: {0}", &[&self.constellation_abbreviation]))
+        } else {
+            self.variability.clone()
+        }
+    }
+
+    fn render_question_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                self.render_display_question(ui);
+                if self.should_display_input() {
+                    let text_input_response = ui.text_edit_singleline(&mut self.state.answer);
+                    if *data.request_input_focus {
+                        text_input_response.request_focus();
+                        *data.request_input_focus = false;
+                    }
+                }
+                if ui.button(localisation::tr("Check")).clicked() {
+                    self.check_answer(QuestionCheckingData {
+                        cellestial_sphere: data.cellestial_sphere,
+                        theme: data.theme,
+                        game_stage: data.game_stage,
+                        score: data.score,
+                        possible_score: data.possible_score,
+                        is_scored_mode: data.is_scored_mode,
+                        current_question: data.current_question,
+                        used_questions: data.used_questions,
+                        add_marker_on_click: data.add_marker_on_click,
+                        questions_settings: data.questions_settings,
+                        question_number: data.question_number,
+                        seconds_spent: data.seconds_spent,
+                        start_next_question: data.start_next_question,
+                        switch_to_next_part: data.switch_to_next_part,
+                    });
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn render_answer_review_window(&self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        egui::Window::new(localisation::tr("Question"))
+            .id(egui::Id::new("Question"))
+            .open(data.game_question_opened)
+            .show(data.ctx, |ui| {
+                if !self.state.answer_review_text_heading.is_empty() {
+                    ui.heading(&self.state.answer_review_text_heading);
+                }
+                ui.label(&self.state.answer_review_text);
+                if ui.button(localisation::tr("Next")).clicked() {
+                    *data.switch_to_next_part = true;
+                }
+                ui.label(data.question_number_text);
+            })
+    }
+
+    fn check_answer(&mut self, data: QuestionCheckingData) {
+        let possible_names_edited = self.possible_names.iter().map(|name| localisation::normalise_answer(name)).collect::<Vec<String>>();
+        let correct = possible_names_edited.contains(&localisation::normalise_answer(&self.state.answer));
+        let score = self.small_settings.scoring.evaluate(if correct { 0.0 } else { f32::INFINITY }, data.seconds_spent, 0);
+        self.state.answer_review_text_heading = localisation::tr(if correct { "Correct!" } else { "Incorrect!" });
+        self.state.answer_review_text = format!("Your answer was: {}\nPossible answers: {}\n{}", self.state.answer, self.possible_names.join(", "), self.variability);
+        if data.is_scored_mode {
+            self.state.answer_review_text += &self.small_settings.scoring.review_text(&score, "");
+        }
+        data.cellestial_sphere.game_markers.markers.push(GameMarker::new(
+            GameMarkerType::CorrectAnswer,
+            self.ra,
+            self.dec,
+            2.0,
+            5.0,
+            true,
+            false,
+            &data.theme.game_visuals.game_markers_colours,
+        ));
+        if self.small_settings.rotate_to_answer {
+            let final_vector = sg_geometry::get_point_vector(self.ra, self.dec, &nalgebra::Matrix3::<f32>::identity());
+            data.cellestial_sphere.transition_to_point(&final_vector, None);
+            data.cellestial_sphere.init_renderers();
+        } else {
+            data.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+        }
+        *data.score += score.total();
+        *data.possible_score += self.small_settings.scoring.max_points();
+        if !self.small_settings.replay_incorrect || correct {
+            data.used_questions.push(data.current_question);
+        } else {
+            *data.question_number += 1;
+        }
+        *data.game_stage = GameStage::Checked;
+    }
+}
+
+impl crate::game::game_handler::QuestionTrait for Question {
+    fn render_window(&mut self, data: QuestionWindowData) -> Option<egui::InnerResponse<Option<()>>> {
+        if *data.game_stage == GameStage::Guessing {
+            self.render_question_window(data)
+        } else if *data.game_stage == GameStage::Checked {
+            self.render_answer_review_window(data)
+        } else {
+            None
+        }
+    }
+
+    fn generic_to_next_part(&mut self, data: QuestionCheckingData) {
+        match data.game_stage {
+            GameStage::Guessing => {
+                if !self.should_display_input() {
+                    self.check_answer(data);
+                }
+            }
+            GameStage::Checked => {
+                *data.start_next_question = true;
+            }
+            GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
+        }
+    }
+
+    fn reset(self: Box<Self>) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(Self {
+            small_settings: self.small_settings,
+            possible_names: self.possible_names,
+            ra: self.ra,
+            dec: self.dec,
+            variability: self.variability,
+            constellation_abbreviation: self.constellation_abbreviation,
+
+            state: State::default(),
+        })
+    }
+
+    fn show_tolerance_marker(&self) -> bool {
+        false
+    }
+
+    fn show_circle_marker(&self) -> bool {
+        true
+    }
+
+    fn get_question_distance_tolerance(&self) -> Deg<f32> {
+        angle::Deg(0.0)
+    }
+
+    fn allow_multiple_player_markers(&self) -> bool {
+        false
+    }
+
+    fn add_marker_on_click(&self) -> bool {
+        false
+    }
+
+    fn should_display_input(&self) -> bool {
+        true
+    }
+
+    fn start_question(&mut self, cellestial_sphere: &mut CellestialSphere, _theme: &Theme) {
+        self.state = Default::default();
+        cellestial_sphere.game_markers.markers = Vec::new();
+    }
+
+    fn render_display_question(&self, ui: &mut egui::Ui) {
+        ui.heading("Which variable star is this?");
+        ui.label(self.clues());
+    }
+
+    fn set_answer(&mut self, answer: &str) {
+        self.state.answer = answer.to_owned();
+    }
+
+    fn check(&mut self, data: QuestionCheckingData) {
+        self.check_answer(data);
+    }
+
+    fn get_correct_answer(&self, _cellestial_sphere: &CellestialSphere) -> Option<Answer> {
+        self.possible_names.first().map(|name| Answer::Text(name.clone()))
+    }
+
+    fn get_answer_review(&self) -> (&str, &str) {
+        (&self.state.answer_review_text_heading, &self.state.answer_review_text)
+    }
+
+    fn use_hint(&mut self, _cellestial_sphere: &mut CellestialSphere, _theme: &Theme) -> Option<String> {
+        None
+    }
+
+    fn worksheet_item(&self) -> Option<WorksheetItem> {
+        Some(WorksheetItem {
+            prompt: localisation::tr_args("Which variable star is this? {0}", &[&self.clues().replace('\n', ", ")]),
+            marks: Vec::new(),
+            centre: (self.ra, self.dec),
+            centre_is_answer: true,
+            hidden_object: None,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn game_handler::QuestionTrait> {
+        Box::new(self.clone())
+    }
+}
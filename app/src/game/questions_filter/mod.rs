@@ -42,7 +42,7 @@ pub fn check(expression: &parser::Keyword, object: &QuestionObject) -> bool {
         parser::Keyword::CatalogueDesignation(catalogue_designations) => catalogue_designations.iter().any(|(catalogue, designation)| match *catalogue {
             parser::Catalogue::Bayer => {
                 if let Some(raw) = &object.bayer_designation_raw {
-                    let names: Vec<String> = crate::rendering::caspr::generate_name_combinations(raw, crate::rendering::caspr::SpecificName::None)
+                    let names: Vec<String> = crate::rendering::caspr::designation_name_combinations(raw, &object.constellations_abbreviations)
                         .iter()
                         .map(|name| name.to_lowercase())
                         .collect();
@@ -53,7 +53,7 @@ pub fn check(expression: &parser::Keyword, object: &QuestionObject) -> bool {
             }
             parser::Catalogue::Flamsteed => {
                 if let Some(raw) = &object.flamsteed_designation_raw {
-                    let names: Vec<String> = crate::rendering::caspr::generate_name_combinations(raw, crate::rendering::caspr::SpecificName::None)
+                    let names: Vec<String> = crate::rendering::caspr::designation_name_combinations(raw, &object.constellations_abbreviations)
                         .iter()
                         .map(|name| name.to_lowercase())
                         .collect();
@@ -112,6 +112,17 @@ pub fn check(expression: &parser::Keyword, object: &QuestionObject) -> bool {
             }
         }
         &parser::Keyword::ObjectId(object_id) => object.object_id == object_id,
+        &parser::Keyword::Separation(min, max) => object.double_star.as_ref().is_some_and(|double_star| (min..=max).contains(&double_star.separation)),
+        parser::Keyword::Variable(variable_types) => object.variable_star.as_ref().is_some_and(|variable_star| {
+            let object_type = variable_star.variable_type.to_uppercase();
+            variable_types.iter().any(|variable_type| variable_type == "ANY" || object_type.starts_with(variable_type.as_str()))
+        }),
+        &parser::Keyword::Period(min, max) => object
+            .variable_star
+            .as_ref()
+            .and_then(|variable_star| variable_star.period)
+            .is_some_and(|period| (min..=max).contains(&period)),
+        &parser::Keyword::BrightestInConstellation(count) => object.brightness_rank.is_some_and(|rank| rank <= count),
     }
 }
 
@@ -137,6 +148,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                         ask_bayer: false,
                         ask_flamsteed: false,
                         ask_proper: false,
+                        scoring: find_this_object::default_scoring(),
+                        hints: find_this_object::default_hints(),
                     }),
                     vec![
                         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46,
@@ -168,6 +181,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                         ask_bayer: false,
                         ask_flamsteed: false,
                         ask_proper: false,
+                        scoring: find_this_object::default_scoring(),
+                        hints: find_this_object::default_hints(),
                     }),
                     vec![
                         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46,
@@ -199,6 +214,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                         ask_bayer: false,
                         ask_flamsteed: false,
                         ask_proper: false,
+                        scoring: find_this_object::default_scoring(),
+                        hints: find_this_object::default_hints(),
                     }),
                     vec![
                         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46,
@@ -230,6 +247,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                         ask_bayer: false,
                         ask_flamsteed: false,
                         ask_proper: false,
+                        scoring: find_this_object::default_scoring(),
+                        hints: find_this_object::default_hints(),
                     }),
                     vec![
                         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46,
@@ -260,6 +279,7 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                         accept_proper: false,
                         accept_bayer: false,
                         accept_flamsteed: false,
+                        scoring: which_object_is_here::default_scoring(),
                     }),
                     vec![
                         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46,
@@ -294,6 +314,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             ask_bayer: true,
                             ask_flamsteed: false,
                             ask_proper: true,
+                            scoring: find_this_object::default_scoring(),
+                            hints: find_this_object::default_hints(),
                         }),
                         vec![
                             7793, 11953, 16044, 21587, 24597, 24768, 25494, 25585, 26465, 26881, 28138, 28507, 30470, 30584, 31821, 32484, 33712, 34573, 36964, 37392, 37938, 40059, 41140, 43013,
@@ -314,6 +336,7 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             accept_proper: true,
                             accept_bayer: true,
                             accept_flamsteed: true,
+                            scoring: which_object_is_here::default_scoring(),
                         }),
                         vec![
                             7793, 11953, 16044, 21587, 24597, 24768, 25494, 25585, 26465, 26881, 28138, 28507, 30470, 30584, 31821, 32484, 33712, 34573, 36964, 37392, 37938, 40059, 41140, 43013,
@@ -349,6 +372,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             ask_bayer: false,
                             ask_flamsteed: false,
                             ask_proper: true,
+                            scoring: find_this_object::default_scoring(),
+                            hints: find_this_object::default_hints(),
                         }),
                         vec![7793, 21587, 24597, 24768, 28138, 30584, 32484, 37392, 60753, 65490, 68704, 69672, 71678, 91200, 97559],
                     ),
@@ -365,6 +390,7 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             accept_proper: true,
                             accept_bayer: true,
                             accept_flamsteed: true,
+                            scoring: which_object_is_here::default_scoring(),
                         }),
                         vec![7793, 21587, 24597, 24768, 28138, 30584, 32484, 37392, 60753, 65490, 68704, 69672, 71678, 91200, 97559],
                     ),
@@ -396,6 +422,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             ask_bayer: false,
                             ask_flamsteed: false,
                             ask_proper: true,
+                            scoring: find_this_object::default_scoring(),
+                            hints: find_this_object::default_hints(),
                         }),
                         vec![
                             7793, 21587, 24597, 24768, 28138, 30584, 32484, 37392, 37938, 49748, 60753, 62460, 65490, 68704, 69672, 71675, 71678, 80740, 91200, 97559, 101988, 113229,
@@ -414,6 +442,7 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             accept_proper: true,
                             accept_bayer: true,
                             accept_flamsteed: true,
+                            scoring: which_object_is_here::default_scoring(),
                         }),
                         vec![
                             7793, 21587, 24597, 24768, 28138, 30584, 32484, 37392, 37938, 49748, 60753, 62460, 65490, 68704, 69672, 71675, 71678, 80740, 91200, 97559, 101988, 113229,
@@ -453,6 +482,8 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             ask_bayer: true,
                             ask_flamsteed: false,
                             ask_proper: true,
+                            scoring: find_this_object::default_scoring(),
+                            hints: find_this_object::default_hints(),
                         }),
                         vec![
                             896, 963, 2295, 3391, 3632, 4636, 5655, 6891, 7793, 9087, 9838, 10080, 11953, 14319, 14759, 16044, 21587, 24597, 24768, 25494, 25585, 26086, 26465, 26881, 27517, 28138,
@@ -475,6 +506,7 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             accept_proper: true,
                             accept_bayer: false,
                             accept_flamsteed: false,
+                            scoring: which_object_is_here::default_scoring(),
                         }),
                         vec![
                             896, 963, 2295, 3391, 3632, 5655, 6891, 7793, 9087, 9838, 10080, 11953, 14319, 14759, 16044, 21587, 24597, 24768, 25494, 25585, 26086, 26465, 26881, 27517, 28138, 28507,
@@ -496,6 +528,7 @@ pub fn default_packs() -> [(String, QuestionPack); 9] {
                             accept_proper: false,
                             accept_bayer: true,
                             accept_flamsteed: false,
+                            scoring: which_object_is_here::default_scoring(),
                         }),
                         vec![
                             896, 963, 2295, 3391, 3632, 4636, 5655, 6891, 7793, 9087, 9838, 10080, 11953, 14319, 14759, 16044, 21587, 24597, 24768, 25494, 25585, 26086, 26465, 26881, 27517, 28138,
@@ -17,6 +17,10 @@ enum KeywordRaw {
     Mag,
     ObjectId,
     CatalogueDesignation,
+    Separation,
+    Variable,
+    Period,
+    BrightestInConstellation,
 }
 
 #[derive(Debug)]
@@ -35,6 +39,12 @@ pub enum Keyword {
     Mag(f32, f32),
     ObjectId(u64),
     CatalogueDesignation(Vec<(Catalogue, String)>),
+    Separation(f32, f32),
+    /// The uppercase GCVS type prefixes, or ANY
+    Variable(Vec<String>),
+    Period(f32, f32),
+    /// The number of the brightest stars of each constellation to include
+    BrightestInConstellation(u32),
 }
 
 impl Keyword {
@@ -160,7 +170,7 @@ impl Keyword {
                 for arg in args {
                     match arg {
                         Node::Keyword(_) => return Err(format!("Keyword 'CONSTELLATION' can only take values, not other keywords (position {ident_pos})")),
-                        Node::Value(value) => new_args.push(value),
+                        Node::Value(value) => new_args.push(value.trim().to_owned()),
                     }
                 }
                 if new_args.is_empty() {
@@ -345,6 +355,75 @@ impl Keyword {
                 };
                 Self::ObjectId(val)
             }
+            KeywordRaw::BrightestInConstellation => {
+                let mut new_args = Vec::new();
+                for arg in args {
+                    match arg {
+                        Node::Keyword(_) => return Err(format!("Keyword 'BRIGHTEST_IN_CONSTELLATION' can only take values, not other keywords (position {ident_pos})")),
+                        Node::Value(value) => new_args.push(value),
+                    }
+                }
+                if new_args.len() != 1 {
+                    return Err(format!(
+                        "Keyword 'BRIGHTEST_IN_CONSTELLATION' at position {} expects exactly 1 argument, found {}",
+                        ident_pos,
+                        new_args.len()
+                    ));
+                }
+                let val = new_args[0].clone();
+                let val = match val.trim().parse() {
+                    Ok(val) => val,
+                    Err(err) => {
+                        return Err(format!(
+                            "Keyword 'BRIGHTEST_IN_CONSTELLATION' at position {ident_pos} expects a whole number as argument, found '{val}' ('{err}')"
+                        ))
+                    }
+                };
+                Self::BrightestInConstellation(val)
+            }
+            KeywordRaw::Separation | KeywordRaw::Period => {
+                let name = if matches!(keyword_raw, KeywordRaw::Separation) { "SEPARATION" } else { "PERIOD" };
+                let mut new_args = Vec::new();
+                for arg in args {
+                    match arg {
+                        Node::Keyword(_) => return Err(format!("Keyword '{name}' can only take values, not other keywords (position {ident_pos})")),
+                        Node::Value(value) => new_args.push(value),
+                    }
+                }
+                if new_args.len() != 2 {
+                    return Err(format!("Keyword '{}' at position {} expects exactly 2 arguments, found {}", name, ident_pos, new_args.len()));
+                }
+                let (min, max) = (new_args[0].clone(), new_args[1].clone());
+                let mut min = match min.trim().parse() {
+                    Ok(min) => min,
+                    Err(err) => return Err(format!("Keyword '{name}' at position {ident_pos} expects numbers as arguments, found '{min}' ('{err}')")),
+                };
+                let mut max = match max.trim().parse() {
+                    Ok(max) => max,
+                    Err(err) => return Err(format!("Keyword '{name}' at position {ident_pos} expects numbers as arguments, found '{max}' ('{err}')")),
+                };
+                if min > max {
+                    std::mem::swap(&mut min, &mut max);
+                }
+                if matches!(keyword_raw, KeywordRaw::Separation) {
+                    Self::Separation(min, max)
+                } else {
+                    Self::Period(min, max)
+                }
+            }
+            KeywordRaw::Variable => {
+                let mut new_args = Vec::new();
+                for arg in args {
+                    match arg {
+                        Node::Keyword(_) => return Err(format!("Keyword 'VARIABLE' can only take values, not other keywords (position {ident_pos})")),
+                        Node::Value(value) => new_args.push(value.trim().to_uppercase()),
+                    }
+                }
+                if new_args.is_empty() {
+                    return Err(format!("Keyword 'VARIABLE' at position {ident_pos} expects at least 1 argument, found 0"));
+                }
+                Self::Variable(new_args)
+            }
         };
         Ok(keyword)
     }
@@ -423,6 +502,10 @@ impl<'a> Parser<'a> {
                     "MAG" => KeywordRaw::Mag,
                     "OBJECT_ID" => KeywordRaw::ObjectId,
                     "CATALOGUE_DESIGNATION" => KeywordRaw::CatalogueDesignation,
+                    "SEPARATION" => KeywordRaw::Separation,
+                    "VARIABLE" => KeywordRaw::Variable,
+                    "PERIOD" => KeywordRaw::Period,
+                    "BRIGHTEST_IN_CONSTELLATION" => KeywordRaw::BrightestInConstellation,
                     _ => return Err(format!("Unknown keyword '{ident}' at position {ident_pos}")),
                 };
                 self.chars.next(); // Consume '('
@@ -501,6 +584,14 @@ pub fn parse_question_type_and_settings(question_type: &str, question_settings:
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::AngularSeparation(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
         },
+        "COMPARE_BRIGHTNESS" => match serde_json::from_str(question_settings) {
+            Ok(question_settings) => Ok(crate::game::questions::QuestionType::CompareBrightness(question_settings)),
+            Err(err) => Err(format!("Error when parsing question settings ({err})")),
+        },
+        "CONSTELLATION_GENITIVE" => match serde_json::from_str(question_settings) {
+            Ok(question_settings) => Ok(crate::game::questions::QuestionType::ConstellationGenitive(question_settings)),
+            Err(err) => Err(format!("Error when parsing question settings ({err})")),
+        },
         "FIND_THIS_OBJECT" => match serde_json::from_str(question_settings) {
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::FindThisObject(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
@@ -517,6 +608,10 @@ pub fn parse_question_type_and_settings(question_type: &str, question_settings:
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::GuessTheMagnitude(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
         },
+        "GUESS_THE_SEPARATION" => match serde_json::from_str(question_settings) {
+            Ok(question_settings) => Ok(crate::game::questions::QuestionType::GuessTheSeparation(question_settings)),
+            Err(err) => Err(format!("Error when parsing question settings ({err})")),
+        },
         "MARK_MISSING_OBJECT" => match serde_json::from_str(question_settings) {
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::MarkMissingObject(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
@@ -525,6 +620,10 @@ pub fn parse_question_type_and_settings(question_type: &str, question_settings:
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::WhatIsThisObject(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
         },
+        "WHICH_BAYER_LETTER" => match serde_json::from_str(question_settings) {
+            Ok(question_settings) => Ok(crate::game::questions::QuestionType::WhichBayerLetter(question_settings)),
+            Err(err) => Err(format!("Error when parsing question settings ({err})")),
+        },
         "WHICH_CONSTELLATION_IS_THIS_POINT_IN" => match serde_json::from_str(question_settings) {
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::WhichConstellationIsThisPointIn(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
@@ -533,6 +632,10 @@ pub fn parse_question_type_and_settings(question_type: &str, question_settings:
             Ok(question_settings) => Ok(crate::game::questions::QuestionType::WhichObjectIsMissing(question_settings)),
             Err(err) => Err(format!("Error when parsing question settings ({err})")),
         },
+        "WHICH_VARIABLE_STAR" => match serde_json::from_str(question_settings) {
+            Ok(question_settings) => Ok(crate::game::questions::QuestionType::WhichVariableStar(question_settings)),
+            Err(err) => Err(format!("Error when parsing question settings ({err})")),
+        },
         _ => Err(String::from("Error when parsing the query, the question type could not be matched")),
     }
 }
@@ -0,0 +1,254 @@
+use crate::localisation::tr_args;
+
+/// How many points an answer is worth, configurable for each set of a question pack
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScoringRules {
+    pub curve: ScoreCurve,
+    pub time_bonus: TimeBonus,
+    /// The fraction of the points lost for every hint used, between 0 and 1
+    pub hint_penalty: f32,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self::correct_answer(1.0)
+    }
+}
+
+/// Maps the error of an answer to the base number of points
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub enum ScoreCurve {
+    /// The points of the first step whose limit the error is below. The steps should be sorted by their limit.
+    Stepwise(Vec<ScoreStep>),
+    /// Decreases linearly from `max_points` for a perfect answer to 0 for an error of `max_error`
+    Continuous { max_points: f32, max_error: f32 },
+    /// `points` for a correct answer, nothing otherwise
+    Correct { points: f32 },
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+pub struct ScoreStep {
+    pub below: f32,
+    pub points: f32,
+}
+
+/// Extra points for answering quickly, decreasing linearly from `max_points` for an instant answer to 0 after `within_seconds`
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct TimeBonus {
+    pub max_points: f32,
+    pub within_seconds: f32,
+}
+
+impl Default for TimeBonus {
+    fn default() -> Self {
+        Self {
+            max_points: 0.0,
+            within_seconds: 30.0,
+        }
+    }
+}
+
+/// The points awarded for a single answer, split into their parts
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Score {
+    pub base: f32,
+    pub time_bonus: f32,
+    pub hint_penalty: f32,
+}
+
+impl Score {
+    pub fn total(&self) -> u32 {
+        (self.base + self.time_bonus - self.hint_penalty).max(0.0).round() as u32
+    }
+}
+
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_points_with_unit(self.total() as f32))?;
+        if self.time_bonus > 0.0 || self.hint_penalty > 0.0 {
+            let mut parts = vec![tr_args("{0} for the answer", &[&format_points(self.base)])];
+            if self.time_bonus > 0.0 {
+                parts.push(tr_args("+{0} time bonus", &[&format_points(self.time_bonus)]));
+            }
+            if self.hint_penalty > 0.0 {
+                parts.push(tr_args("-{0} for hints", &[&format_points(self.hint_penalty)]));
+            }
+            write!(f, " ({})", parts.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl ScoringRules {
+    /// 3/2/1 points for an error below the given limits
+    pub fn stepwise(limits: [f32; 3]) -> Self {
+        Self {
+            curve: ScoreCurve::Stepwise(limits.iter().zip([3.0, 2.0, 1.0]).map(|(&below, points)| ScoreStep { below, points }).collect()),
+            time_bonus: TimeBonus::default(),
+            hint_penalty: 0.0,
+        }
+    }
+
+    /// A fixed number of points for a correct answer
+    pub fn correct_answer(points: f32) -> Self {
+        Self {
+            curve: ScoreCurve::Correct { points },
+            time_bonus: TimeBonus::default(),
+            hint_penalty: 0.0,
+        }
+    }
+
+    /// Evaluates an answer with the given error. Questions using the `Correct` curve should pass an error of 0 for a correct answer.
+    pub fn evaluate(&self, error: f32, seconds_spent: f32, hints_used: u32) -> Score {
+        let base = match &self.curve {
+            ScoreCurve::Stepwise(steps) => steps.iter().find(|step| error < step.below).map(|step| step.points).unwrap_or(0.0),
+            ScoreCurve::Continuous { max_points, max_error } => {
+                if *max_error > 0.0 {
+                    max_points * (1.0 - error / max_error).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                }
+            }
+            ScoreCurve::Correct { points } => {
+                if error == 0.0 {
+                    *points
+                } else {
+                    0.0
+                }
+            }
+        };
+        // Guessing instantly should not pay off, so the bonus is only given for answers that are worth something
+        let time_bonus = if base > 0.0 && self.time_bonus.within_seconds > 0.0 {
+            self.time_bonus.max_points * (1.0 - seconds_spent / self.time_bonus.within_seconds).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let hint_penalty = (base + time_bonus) * (self.hint_penalty * hints_used as f32).clamp(0.0, 1.0);
+        Score { base, time_bonus, hint_penalty }
+    }
+
+    /// The most points an answer can get, used for the possible score
+    pub fn max_points(&self) -> u32 {
+        let base = match &self.curve {
+            ScoreCurve::Stepwise(steps) => steps.iter().map(|step| step.points).fold(0.0, f32::max),
+            ScoreCurve::Continuous { max_points, .. } => *max_points,
+            ScoreCurve::Correct { points } => *points,
+        };
+        (base + self.time_bonus.max_points.max(0.0)).max(0.0).round() as u32
+    }
+
+    /// A human readable description of the rules, `unit` is appended to the error limits
+    pub fn describe(&self, unit: &str) -> String {
+        let mut description = match &self.curve {
+            ScoreCurve::Stepwise(steps) => steps
+                .iter()
+                .map(|step| tr_args("{0} for an error below {1}", &[&format_points_with_unit(step.points), &format!("{}{unit}", step.below)]))
+                .collect::<Vec<String>>()
+                .join(", "),
+            ScoreCurve::Continuous { max_points, max_error } => tr_args("Up to {0}, decreasing to 0 at an error of {1}", &[&format_points_with_unit(*max_points), &format!("{max_error}{unit}")]),
+            ScoreCurve::Correct { points } => tr_args("{0} for a correct answer", &[&format_points_with_unit(*points)]),
+        };
+        if self.time_bonus.max_points > 0.0 {
+            description += "; ";
+            description += &tr_args(
+                "up to {0} bonus for answering within {1} s",
+                &[&format_points_with_unit(self.time_bonus.max_points), &self.time_bonus.within_seconds],
+            );
+        }
+        if self.hint_penalty > 0.0 {
+            description += "; ";
+            description += &tr_args("-{0}% per hint", &[&(self.hint_penalty * 100.0).round()]);
+        }
+        description
+    }
+
+    /// The text appended to the answer review in scored mode
+    pub fn review_text(&self, score: &Score, unit: &str) -> String {
+        format!("\n{}", tr_args("You got {0}.\nScoring: {1}", &[score, &self.describe(unit)]))
+    }
+
+    /// The text appended to the answer review in scored mode for questions scoring each pair of their parts separately, `points` are the sum over all the pairs
+    pub fn review_text_per_pair(&self, points: u32, unit: &str) -> String {
+        format!(
+            "\n{}",
+            tr_args("You got {0}.\nScoring for each pair: {1}", &[&format_points_with_unit(points as f32), &self.describe(unit)])
+        )
+    }
+}
+
+fn format_points(points: f32) -> String {
+    let rounded = (points * 10.0).round() / 10.0;
+    rounded.to_string()
+}
+
+fn format_points_with_unit(points: f32) -> String {
+    tr_args(if points == 1.0 { "{0} point" } else { "{0} points" }, &[&format_points(points)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stepwise() {
+        let rules = ScoringRules::stepwise([0.2, 0.5, 1.0]);
+        assert_eq!(rules.evaluate(0.0, 0.0, 0).total(), 3);
+        assert_eq!(rules.evaluate(0.3, 0.0, 0).total(), 2);
+        assert_eq!(rules.evaluate(0.5, 0.0, 0).total(), 1);
+        assert_eq!(rules.evaluate(1.0, 0.0, 0).total(), 0);
+        assert_eq!(rules.max_points(), 3);
+    }
+
+    #[test]
+    fn continuous() {
+        let rules = ScoringRules {
+            curve: ScoreCurve::Continuous { max_points: 10.0, max_error: 2.0 },
+            ..Default::default()
+        };
+        assert_eq!(rules.evaluate(0.0, 0.0, 0).total(), 10);
+        assert_eq!(rules.evaluate(1.0, 0.0, 0).total(), 5);
+        assert_eq!(rules.evaluate(5.0, 0.0, 0).total(), 0);
+        assert_eq!(rules.max_points(), 10);
+    }
+
+    #[test]
+    fn time_bonus_and_hints() {
+        let rules = ScoringRules {
+            curve: ScoreCurve::Correct { points: 2.0 },
+            time_bonus: TimeBonus {
+                max_points: 2.0,
+                within_seconds: 10.0,
+            },
+            hint_penalty: 0.5,
+        };
+        assert_eq!(rules.max_points(), 4);
+        assert_eq!(rules.evaluate(0.0, 5.0, 0).total(), 3);
+        assert_eq!(rules.evaluate(0.0, 20.0, 1).total(), 1);
+        assert_eq!(rules.evaluate(0.0, 0.0, 3).total(), 0);
+        // No time bonus for a wrong answer
+        assert_eq!(rules.evaluate(f32::INFINITY, 0.0, 0).total(), 0);
+    }
+
+    #[test]
+    fn scores_are_described_in_the_chosen_language() {
+        let rules = ScoringRules {
+            hint_penalty: 0.2,
+            ..ScoringRules::stepwise([0.2, 0.5, 1.0])
+        };
+        let score = rules.evaluate(0.0, 0.0, 1);
+        assert_eq!(score.to_string(), "2 points (3 for the answer, -0.6 for hints)");
+        crate::localisation::set_language(crate::localisation::Language::Czech);
+        assert_eq!(score.to_string(), "2 b. (3 za odpověď, -0.6 za nápovědy)");
+        crate::localisation::set_language(crate::localisation::Language::English);
+    }
+
+    #[test]
+    fn old_packs_get_the_defaults() {
+        let rules: ScoringRules = serde_json::from_str("{}").unwrap();
+        assert_eq!(rules, ScoringRules::default());
+        let rules: ScoringRules = serde_json::from_str(r#"{"curve":{"Stepwise":[{"below":1.0,"points":5.0}]}}"#).unwrap();
+        assert_eq!(rules.max_points(), 5);
+    }
+}
@@ -1,55 +1,144 @@
+use angle::Angle;
 use eframe::egui;
 use egui::epaint::Pos2;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
-use crate::game::game_handler::QuestionCheckingData;
 use crate::{
-    enums::{self, GameStage, PointerPosition, RendererCategory},
+    enums::{GameStage, PointerPosition, RendererCategory},
+    game::game_handler::Answer,
+    keybindings::{Action, Keybindings},
     Application,
 };
-const KEY_COMBINATIONS: [&str; 6] = ["alt+shift+g", "alt+shift+i", "alt+shift+o", "alt+shift+s", "mouse-middle", "space"];
+/// How far the view moves on a single press of a rotation key, in points on the screen
+const KEY_ROTATION_STEP: f32 = 10.0;
+/// How far the guess marker moves on a single press of a marker key, in points on the screen
+const KEY_MARKER_STEP: f32 = 5.0;
+/// How much a single press of a zoom key zooms
+const KEY_ZOOM_FACTOR: f32 = 1.28;
+/// How long a finger has to rest on the screen to place a marker
+const LONG_PRESS_SECONDS: f64 = 0.5;
+/// How far a finger can move (in points) for the touch to still count as a long press
+const LONG_PRESS_TOLERANCE: f32 = 10.0;
+/// The time (in seconds) in which the speed of the inertial panning drops to about a third
+const INERTIA_TIME_CONSTANT: f32 = 0.35;
+/// Below this speed (in points per second) the inertial panning stops
+const INERTIA_MIN_SPEED: f32 = 30.0;
 
 impl Application {
     pub fn handle_input(&mut self, cursor_within_central_panel: bool, ctx: &egui::Context) {
         self.input.handle(cursor_within_central_panel, ctx);
-        for input_to_handle in &self.input.to_handle {
-            match input_to_handle {
-                enums::Inputs::AltShiftI => self.state.windows.app_info.opened = !self.state.windows.app_info.opened,
-                enums::Inputs::AltShiftO => self.state.windows.settings.opened = !self.state.windows.settings.opened,
-                enums::Inputs::AltShiftS => self.state.windows.stats.opened = !self.state.windows.stats.opened,
-                enums::Inputs::Space | enums::Inputs::MouseMiddle => {
-                    if matches!(*input_to_handle, enums::Inputs::Space) && ctx.wants_keyboard_input() {
-                        continue;
-                    }
+        let mut reinitialise_stars = false;
+        for action in self.input.to_handle.clone() {
+            match action {
+                Action::ToggleAppInfo => self.state.windows.app_info.opened = !self.state.windows.app_info.opened,
+                Action::ToggleSettings => self.state.windows.settings.opened = !self.state.windows.settings.opened,
+                Action::ToggleStats => self.state.windows.stats.opened = !self.state.windows.stats.opened,
+                Action::NextPart => {
                     if !self.game_handler.no_more_questions() {
                         match self.game_handler.stage {
                             GameStage::Guessing | GameStage::Checked => {
                                 if (self.game_handler.stage == GameStage::Guessing && !self.game_handler.should_display_input()) || self.game_handler.stage == GameStage::Checked {
-                                    self.game_handler.question_catalog[self.game_handler.current_question].generic_to_next_part(QuestionCheckingData {
-                                        cellestial_sphere: &mut self.cellestial_sphere,
-                                        theme: &self.theme,
-                                        game_stage: &mut self.game_handler.stage,
-                                        score: &mut self.game_handler.score,
-                                        possible_score: &mut self.game_handler.possible_score,
-                                        is_scored_mode: self.game_handler.game_settings.is_scored_mode,
-                                        current_question: self.game_handler.current_question,
-                                        used_questions: &mut self.game_handler.used_questions,
-                                        add_marker_on_click: &mut self.game_handler.add_marker_on_click,
-                                        questions_settings: &self.game_handler.questions_settings,
-                                        question_number: &mut self.game_handler.question_number,
-                                        start_next_question: &mut self.game_handler.switch_to_next_question,
-                                        switch_to_next_part: &mut self.game_handler.switch_to_next_part,
-                                    });
+                                    self.game_handler.next_part(&mut self.cellestial_sphere, &self.theme);
                                 }
                             }
                             GameStage::NotStartedYet | GameStage::NoMoreQuestions | GameStage::ScoredModeFinished => {}
                         }
                     }
                 }
+                Action::Check => self.game_handler.check_answer(&mut self.cellestial_sphere, &self.theme),
+                Action::GiveUp => self.game_handler.give_up(&mut self.cellestial_sphere, &self.theme),
+                Action::ZoomIn | Action::ZoomOut => {
+                    self.cellestial_sphere.cancel_camera_transition();
+                    let velocity = if action == Action::ZoomIn { KEY_ZOOM_FACTOR - 1.0 } else { 1.0 / KEY_ZOOM_FACTOR - 1.0 };
+                    reinitialise_stars |= self.cellestial_sphere.zoom(velocity);
+                }
+                Action::RotateLeft | Action::RotateRight | Action::RotateUp | Action::RotateDown => {
+                    let offset = match action {
+                        Action::RotateLeft => egui::vec2(-KEY_ROTATION_STEP, 0.0),
+                        Action::RotateRight => egui::vec2(KEY_ROTATION_STEP, 0.0),
+                        Action::RotateUp => egui::vec2(0.0, -KEY_ROTATION_STEP),
+                        _ => egui::vec2(0.0, KEY_ROTATION_STEP),
+                    };
+                    let centre = self.cellestial_sphere.viewport_rect.center();
+                    let initial_vector = self.cellestial_sphere.project_screen_pos(centre - offset);
+                    let final_vector = self.cellestial_sphere.project_screen_pos(centre);
+                    self.cellestial_sphere.cancel_camera_transition();
+                    self.cellestial_sphere.rotate_between_points(&initial_vector, &final_vector);
+                    self.cellestial_sphere.init_renderers();
+                }
+                Action::CentreOnAnswer => {
+                    // Only after checking, so that it cannot be used to cheat
+                    if self.game_handler.stage == GameStage::Checked {
+                        if let Some(Answer::Position { ra, dec }) = self.game_handler.get_correct_answer(&self.cellestial_sphere) {
+                            let final_vector = sg_geometry::get_point_vector(ra, dec, &nalgebra::Matrix3::<f32>::identity());
+                            self.cellestial_sphere.transition_to_point(&final_vector, None);
+                        }
+                    }
+                }
+                Action::ToggleConstellationLines | Action::ToggleConstellationBorders => {
+                    if let Some(name) = action.toggled_lines() {
+                        self.cellestial_sphere.toggle_lines(name);
+                    }
+                }
+                Action::PlaceMarker => {
+                    if self.game_handler.add_marker_on_click && self.game_handler.stage == GameStage::Guessing {
+                        let [dec, ra] = self.cellestial_sphere.screen_pos_to_dec_ra(self.cellestial_sphere.viewport_rect.center());
+                        self.game_handler.place_marker(&mut self.cellestial_sphere, &self.theme, dec, ra);
+                    }
+                }
+                Action::MoveMarkerLeft | Action::MoveMarkerRight | Action::MoveMarkerUp | Action::MoveMarkerDown => {
+                    if self.game_handler.add_marker_on_click && self.game_handler.stage == GameStage::Guessing {
+                        let offset = match action {
+                            Action::MoveMarkerLeft => egui::vec2(-KEY_MARKER_STEP, 0.0),
+                            Action::MoveMarkerRight => egui::vec2(KEY_MARKER_STEP, 0.0),
+                            Action::MoveMarkerUp => egui::vec2(0.0, -KEY_MARKER_STEP),
+                            _ => egui::vec2(0.0, KEY_MARKER_STEP),
+                        };
+                        // A marker which is not on the screen is brought back to the centre of it
+                        let marker_screen_pos = self
+                            .game_handler
+                            .guess_marker_positions
+                            .last()
+                            .and_then(|[dec, ra]| self.cellestial_sphere.dec_ra_to_screen_pos(dec.to_deg(), ra.to_deg()))
+                            .unwrap_or(self.cellestial_sphere.viewport_rect.center() - offset);
+                        let [dec, ra] = self.cellestial_sphere.screen_pos_to_dec_ra(marker_screen_pos + offset);
+                        self.game_handler.move_last_marker(&mut self.cellestial_sphere, &self.theme, dec, ra);
+                    }
+                }
             }
         }
-        let reinitialise_stars = if cursor_within_central_panel { self.cellestial_sphere.zoom(self.input.zoom) } else { false };
+        if cursor_within_central_panel && self.input.zoom != 0.0 {
+            self.cellestial_sphere.cancel_camera_transition();
+        }
+        let mut view_moved = false;
+        if let Some(gesture) = &self.input.touch_gesture {
+            if cursor_within_central_panel {
+                reinitialise_stars |= self.cellestial_sphere.apply_touch_gesture(gesture.centre, gesture.translation, gesture.zoom, gesture.rotation);
+                view_moved = true;
+            }
+        }
+        if self.input.inertia_drag != egui::Vec2::ZERO {
+            let centre = self.cellestial_sphere.viewport_rect.center();
+            let initial_vector = self.cellestial_sphere.project_screen_pos(centre - self.input.inertia_drag);
+            let final_vector = self.cellestial_sphere.project_screen_pos(centre);
+            self.cellestial_sphere.rotate_between_points(&initial_vector, &final_vector);
+            view_moved = true;
+        }
+        if let Some(position) = self.input.long_press {
+            if self.state.exploration_mode {
+                self.select_object_at(position);
+            } else if self.game_handler.add_marker_on_click && self.game_handler.stage == GameStage::Guessing {
+                let [dec, ra] = self.cellestial_sphere.screen_pos_to_dec_ra(position);
+                self.game_handler.place_marker(&mut self.cellestial_sphere, &self.theme, dec, ra);
+            }
+        }
+        if view_moved {
+            self.cellestial_sphere.init_renderers();
+            reinitialise_stars = false;
+        }
+        if cursor_within_central_panel {
+            reinitialise_stars |= self.cellestial_sphere.zoom(self.input.zoom);
+        }
 
         let pointer_position: Pos2 = match self.input.pointer_position {
             PointerPosition::OnScreen(position) => position,
@@ -57,7 +146,11 @@ impl Application {
         };
         let all_reinitialised = if cursor_within_central_panel {
             let mut all_reinitialised = false;
-            if self.game_handler.add_marker_on_click && self.input.primary_released && !self.input.primary_dragging_last_frame {
+            let clicked = self.input.primary_released && !self.input.primary_dragging_last_frame && !self.input.gesture_during_touch;
+            if self.state.exploration_mode && clicked {
+                self.select_object_at(pointer_position);
+            } else if self.game_handler.add_marker_on_click && clicked && !self.input.touch_used {
+                // With touch, markers are placed by a long press, so that lifting the finger after moving the sky does not place one
                 /*let sphere_position = geometry::cast_onto_sphere(&self.cellestial_sphere, &pointer_position);
                 let (dec, ra) = geometry::cartesian_to_spherical(sphere_position);*/
                 let marker_pos = sg_geometry::cast_onto_sphere_dec_ra(
@@ -65,15 +158,9 @@ impl Application {
                     &pointer_position,
                     self.cellestial_sphere.rotation,
                     self.cellestial_sphere.get_zoom(),
+                    self.cellestial_sphere.sky_settings.projection,
                 );
-                if self.game_handler.allow_multiple_player_marker() {
-                    self.game_handler.guess_marker_positions.push(marker_pos);
-                } else {
-                    self.game_handler.guess_marker_positions = vec![marker_pos];
-                }
-                let new_markers = self.game_handler.generate_player_markers(&self.game_handler.guess_marker_positions, &self.theme);
-                self.cellestial_sphere.game_markers.markers = new_markers; // vec![Marker::new(ra / PI * 180.0, dec / PI * 180.0, Color32::RED, 2.0, 5.0, self.game_handler.show_circle_marker(), false)];
-                self.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+                self.game_handler.place_marker(&mut self.cellestial_sphere, &self.theme, marker_pos[0], marker_pos[1]);
             }
             let initial_vector = self.cellestial_sphere.project_screen_pos(pointer_position - self.input.dragged);
             let final_vector = self.cellestial_sphere.project_screen_pos(pointer_position);
@@ -81,6 +168,8 @@ impl Application {
             if initial_vector != final_vector {
                 // Some rotation this frame
 
+                // Dragging takes over the camera from any transition in progress
+                self.cellestial_sphere.cancel_camera_transition();
                 self.cellestial_sphere.rotate_between_points(&initial_vector, &final_vector);
                 self.cellestial_sphere.init_renderers();
                 all_reinitialised = true;
@@ -91,19 +180,43 @@ impl Application {
         };
         if !all_reinitialised && reinitialise_stars {
             self.cellestial_sphere.reinit_renderer_category(RendererCategory::Stars);
+            // Fainter deepsky objects are shown when zoomed in
+            self.cellestial_sphere.reinit_renderer_category(RendererCategory::Deepskies);
         }
     }
 }
 
 #[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InputSettings {
     pub display_onscreen_keyboard: bool,
+    pub keybindings: Keybindings,
+}
+
+/// A gesture made with two or more fingers during the last frame
+pub struct TouchGesture {
+    pub centre: Pos2,
+    pub translation: egui::Vec2,
+    /// The change of the distance between the fingers, 1.0 for no change
+    pub zoom: f32,
+    /// Positive for a clockwise rotation of the fingers
+    pub rotation: angle::Rad<f32>,
+}
+
+enum LongPress {
+    Idle,
+    Pressing {
+        start_time: f64,
+        position: Pos2,
+    },
+    /// Either the long press was already used, or the finger moved too far for it to be one
+    Done,
 }
 
 pub struct Input {
     pub dragged: egui::Vec2,
     pub pointer_position: PointerPosition,
-    pub to_handle: Vec<enums::Inputs>,
+    pub to_handle: Vec<Action>,
     pub zoom: f32,
     pub secondary_released: bool,
     pub primary_clicked: bool,
@@ -113,7 +226,20 @@ pub struct Input {
     pub primary_dragging_last_frame: bool,
 
     pointer_down_outside_subwindow: bool,
-    currently_held: HashMap<&'static str, bool>,
+    middle_held: bool,
+
+    pub touch_gesture: Option<TouchGesture>,
+    /// Whether a multi-touch gesture happened since the fingers touched the screen, so that lifting them after it is not a click
+    pub gesture_during_touch: bool,
+    /// Whether the last interaction with the app was by touch
+    pub touch_used: bool,
+    /// The position of a long press which happened this frame
+    pub long_press: Option<Pos2>,
+    long_press_state: LongPress,
+    /// How far the view should move this frame due to the inertia of the last drag
+    pub inertia_drag: egui::Vec2,
+    drag_velocity: egui::Vec2,
+    inertia_velocity: egui::Vec2,
 
     pub text_from_keys: String,
     pub input_field_had_focus_last_frame: bool,
@@ -123,10 +249,6 @@ pub struct Input {
 
 impl Default for Input {
     fn default() -> Self {
-        let mut currently_held: HashMap<&str, bool> = HashMap::new();
-        for combination in KEY_COMBINATIONS {
-            currently_held.insert(combination, false);
-        }
         Self {
             dragged: egui::Vec2::new(0.0, 0.0),
             pointer_position: PointerPosition::OnScreen(egui::Pos2::new(0.0, 0.0)),
@@ -134,7 +256,16 @@ impl Default for Input {
             zoom: 1.0,
 
             pointer_down_outside_subwindow: false,
-            currently_held,
+            middle_held: false,
+
+            touch_gesture: None,
+            gesture_during_touch: false,
+            touch_used: false,
+            long_press: None,
+            long_press_state: LongPress::Idle,
+            inertia_drag: egui::Vec2::ZERO,
+            drag_velocity: egui::Vec2::ZERO,
+            inertia_velocity: egui::Vec2::ZERO,
             secondary_released: false,
             primary_clicked: false,
             primary_down: false,
@@ -190,26 +321,32 @@ impl Input {
         if cursor_within_central_panel || !primary_down {
             self.pointer_down_outside_subwindow = primary_down;
         }
-        let mut to_handle: Vec<enums::Inputs> = Vec::new();
+        let mut to_handle: Vec<Action> = Vec::new();
         let middle_down = ctx.input(|i| i.pointer.middle_down());
-
-        if let Some(pressed) = self.currently_held.get("mouse-middle") {
-            let pressed = *pressed;
-            let held = self.currently_held.entry("mouse-middle").or_insert(true);
-            if !pressed && middle_down {
-                *held = true;
-                to_handle.push(enums::Inputs::MouseMiddle);
-            } else if pressed && !middle_down {
-                *held = false;
-            }
-        } else {
-            println!("The mouse-middle combination was not in the 'currently_held' hashmap");
+        if !self.middle_held && middle_down {
+            to_handle.push(Action::NextPart);
         }
+        self.middle_held = middle_down;
+        let typing = ctx.wants_keyboard_input();
         self.zoom = 0.0;
         let mut tap_position = Pos2::new(0.0, 0.0);
         let mut tap_released = false;
         let mut touch_detected = false;
         for event in &input_events {
+            if let egui::Event::Key {
+                key,
+                pressed: true,
+                repeat,
+                modifiers,
+                ..
+            } = event
+            {
+                if let Some((action, binding)) = self.settings.keybindings.action_for(*key, *modifiers) {
+                    if (!repeat || action.repeats()) && (!typing || binding.works_while_typing()) {
+                        to_handle.push(action);
+                    }
+                }
+            }
             match event {
                 egui::Event::Zoom(zoom) => {
                     if *zoom > 1.0 {
@@ -239,183 +376,6 @@ impl Input {
                     }
                     tap_position = *pos;
                 }
-                // Press of Shift + UpArrow
-                egui::Event::Key {
-                    key: egui::Key::ArrowUp,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: false,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: true,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    self.zoom = 1.28;
-                }
-                // Press of Shift + DownArrow
-                egui::Event::Key {
-                    key: egui::Key::ArrowDown,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: false,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: true,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    self.zoom = 0.75;
-                }
-                // Press of LeftArrow
-                egui::Event::Key {
-                    key: egui::Key::ArrowLeft,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    self.dragged.x -= 1.0;
-                }
-                // Press of RightArrow
-                egui::Event::Key {
-                    key: egui::Key::ArrowRight,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    self.dragged.x += 1.0;
-                }
-                // Press of UpArrow
-                egui::Event::Key {
-                    key: egui::Key::ArrowUp,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    self.dragged.y -= 1.0;
-                }
-                // Press of DownArrow
-                egui::Event::Key {
-                    key: egui::Key::ArrowDown,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    self.dragged.y += 1.0;
-                }
-                // Press of Alt + Shift + I
-                egui::Event::Key {
-                    key: egui::Key::I,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: false,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: true,
-                            ctrl: _,
-                            shift: true,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(pressed) = self.currently_held.get("alt+shift+i") {
-                        if !pressed {
-                            let held = self.currently_held.entry("alt+shift+i").or_insert(true);
-                            *held = true;
-                            to_handle.push(enums::Inputs::AltShiftI);
-                        }
-                    } else {
-                        println!("The alt+shift+i combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Press of Alt + Shift + O
-                egui::Event::Key {
-                    key: egui::Key::O,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: false,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: true,
-                            ctrl: _,
-                            shift: true,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(pressed) = self.currently_held.get("alt+shift+o") {
-                        if !pressed {
-                            let held = self.currently_held.entry("alt+shift+o").or_insert(true);
-                            *held = true;
-                            to_handle.push(enums::Inputs::AltShiftO);
-                        }
-                    } else {
-                        println!("The alt+shift+o combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Press of Alt + Shift + S
-                egui::Event::Key {
-                    key: egui::Key::S,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: false,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: true,
-                            ctrl: _,
-                            shift: true,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(pressed) = self.currently_held.get("alt+shift+s") {
-                        if !pressed {
-                            let held = self.currently_held.entry("alt+shift+s").or_insert(true);
-                            *held = true;
-                            to_handle.push(enums::Inputs::AltShiftS);
-                        }
-                    } else {
-                        println!("The alt+shift+s combination was not in the 'currently_held' hashmap");
-                    }
-                }
                 // All unhandled, unmodified keys - construct the text edit string by hand
                 #[cfg(any(target_os = "ios", target_os = "android"))]
                 egui::Event::Key {
@@ -541,170 +501,12 @@ impl Input {
                         | egui::Key::F34
                         | egui::Key::F35 => "",
                     };
-                    // Could probably have a bit more fun with it, but having it doubled does work well enough...
-                    if *key == egui::Key::Space && !ctx.wants_keyboard_input() {
-                        // If you are typing, then you don't want this to fire
-                        if let Some(pressed) = self.currently_held.get("space") {
-                            if !pressed {
-                                let held = self.currently_held.entry("space").or_insert(true);
-                                *held = true;
-                                to_handle.push(enums::Inputs::Space);
-                            }
-                        } else {
-                            println!("The space combination was not in the 'currently_held' hashmap");
-                        }
-                    }
                     let c = character.to_uppercase();
                     if *shift {
                         character = &c;
                     }
                     self.text_from_keys += character;
                 }
-                // Press of Space
-                egui::Event::Key {
-                    key: egui::Key::Space,
-                    physical_key: _,
-                    pressed: true,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(pressed) = self.currently_held.get("space") {
-                        if !pressed {
-                            let held = self.currently_held.entry("space").or_insert(true);
-                            *held = true;
-                            to_handle.push(enums::Inputs::Space);
-                        }
-                    } else {
-                        println!("The space combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Release of G
-                egui::Event::Key {
-                    key: egui::Key::G,
-                    physical_key: _,
-                    pressed: false,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(&pressed) = self.currently_held.get("alt+shift+g") {
-                        if pressed {
-                            let held = self.currently_held.entry("alt+shift+g").or_insert(false);
-                            *held = false;
-                        }
-                    } else {
-                        println!("The alt+shift+g combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Release of I
-                egui::Event::Key {
-                    key: egui::Key::I,
-                    physical_key: _,
-                    pressed: false,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(&pressed) = self.currently_held.get("alt+shift+i") {
-                        if pressed {
-                            let held = self.currently_held.entry("alt+shift+i").or_insert(false);
-                            *held = false;
-                        }
-                    } else {
-                        println!("The alt+shift+i combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Release of O
-                egui::Event::Key {
-                    key: egui::Key::O,
-                    physical_key: _,
-                    pressed: false,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(&pressed) = self.currently_held.get("alt+shift+o") {
-                        if pressed {
-                            let held = self.currently_held.entry("alt+shift+o").or_insert(false);
-                            *held = false;
-                        }
-                    } else {
-                        println!("The alt+shift+o combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Release of S
-                egui::Event::Key {
-                    key: egui::Key::S,
-                    physical_key: _,
-                    pressed: false,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(&pressed) = self.currently_held.get("alt+shift+s") {
-                        if pressed {
-                            let held = self.currently_held.entry("alt+shift+s").or_insert(false);
-                            *held = false;
-                        }
-                    } else {
-                        println!("The alt+shift+s combination was not in the 'currently_held' hashmap");
-                    }
-                }
-                // Release of Space
-                egui::Event::Key {
-                    key: egui::Key::Space,
-                    physical_key: _,
-                    pressed: false,
-                    repeat: _,
-                    modifiers:
-                        egui::Modifiers {
-                            alt: _,
-                            ctrl: _,
-                            shift: _,
-                            mac_cmd: _,
-                            command: _,
-                        },
-                } => {
-                    if let Some(&pressed) = self.currently_held.get("space") {
-                        if pressed {
-                            let held = self.currently_held.entry("space").or_insert(false);
-                            *held = false;
-                        }
-                    } else {
-                        println!("The space combination was not in the 'currently_held' hashmap");
-                    }
-                }
                 _ => {}
             }
         }
@@ -716,5 +518,75 @@ impl Input {
             self.pointer_position = PointerPosition::OnScreen(tap_position);
             self.primary_released |= tap_released;
         }
+        if touch_detected {
+            self.touch_used = true;
+        } else if input_events.iter().any(|event| matches!(event, egui::Event::PointerButton { .. })) {
+            self.touch_used = false;
+        }
+        self.handle_touch_gestures(cursor_within_central_panel, ctx);
+    }
+
+    fn handle_touch_gestures(&mut self, cursor_within_central_panel: bool, ctx: &egui::Context) {
+        let (time, dt, any_touches, multi_touch) = ctx.input(|i| (i.time, i.stable_dt.max(f32::EPSILON), i.any_touches(), i.multi_touch()));
+        if multi_touch.is_some() {
+            self.gesture_during_touch = true;
+        } else if !any_touches && !self.primary_released {
+            self.gesture_during_touch = false;
+        }
+        self.touch_gesture = multi_touch.map(|touch| TouchGesture {
+            centre: touch.center_pos,
+            translation: touch.translation_delta,
+            zoom: touch.zoom_delta,
+            rotation: angle::Rad(touch.rotation_delta),
+        });
+        if self.touch_gesture.is_some() {
+            // The fingers move the sky by themselves, the pointer (following only one of them) must not also drag it
+            self.dragged = egui::Vec2::ZERO;
+            self.zoom = 0.0;
+        }
+
+        self.long_press = None;
+        let touch_position = ctx.input(|i| i.pointer.hover_pos());
+        match (&self.long_press_state, touch_position) {
+            _ if !any_touches || !self.primary_down || !cursor_within_central_panel => self.long_press_state = LongPress::Idle,
+            _ if self.touch_gesture.is_some() => self.long_press_state = LongPress::Done,
+            (LongPress::Idle, Some(position)) => self.long_press_state = LongPress::Pressing { start_time: time, position },
+            (&LongPress::Pressing { start_time, position }, Some(current_position)) => {
+                if (current_position - position).length() > LONG_PRESS_TOLERANCE {
+                    self.long_press_state = LongPress::Done;
+                } else if time - start_time >= LONG_PRESS_SECONDS {
+                    self.long_press = Some(position);
+                    self.long_press_state = LongPress::Done;
+                } else {
+                    // Nothing happens while the finger rests, so the frame has to be requested
+                    ctx.request_repaint_after(std::time::Duration::from_secs_f64(LONG_PRESS_SECONDS - (time - start_time)));
+                }
+            }
+            _ => {}
+        }
+
+        self.inertia_drag = egui::Vec2::ZERO;
+        if any_touches {
+            self.inertia_velocity = egui::Vec2::ZERO;
+            if self.primary_dragging && self.touch_gesture.is_none() {
+                // Smoothed, so that a single jittery frame at the end of the drag does not decide the speed
+                self.drag_velocity = self.drag_velocity * 0.5 + self.dragged / dt * 0.5;
+            } else {
+                self.drag_velocity = egui::Vec2::ZERO;
+            }
+        } else if self.drag_velocity != egui::Vec2::ZERO {
+            // The finger was lifted during a drag
+            if self.drag_velocity.length() > INERTIA_MIN_SPEED {
+                self.inertia_velocity = self.drag_velocity;
+            }
+            self.drag_velocity = egui::Vec2::ZERO;
+        } else if self.inertia_velocity != egui::Vec2::ZERO {
+            self.inertia_drag = self.inertia_velocity * dt;
+            self.inertia_velocity *= (-dt / INERTIA_TIME_CONSTANT).exp();
+            if self.inertia_velocity.length() < INERTIA_MIN_SPEED {
+                self.inertia_velocity = egui::Vec2::ZERO;
+            }
+            ctx.request_repaint();
+        }
     }
 }
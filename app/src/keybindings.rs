@@ -0,0 +1,317 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The line files toggled by the line and border shortcuts
+const CONSTELLATION_LINES: &str = "constellation-connections.csv";
+const CONSTELLATION_BORDERS: &str = "constellation-borders.csv";
+
+/// Everything that can be done using a keyboard shortcut
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    ToggleAppInfo,
+    ToggleSettings,
+    ToggleStats,
+    /// Checks the question if it does not have an input field, or moves to the next question if it has been checked
+    NextPart,
+    Check,
+    GiveUp,
+    ZoomIn,
+    ZoomOut,
+    RotateLeft,
+    RotateRight,
+    RotateUp,
+    RotateDown,
+    CentreOnAnswer,
+    ToggleConstellationLines,
+    ToggleConstellationBorders,
+    /// Places the guess marker in the centre of the screen
+    PlaceMarker,
+    MoveMarkerLeft,
+    MoveMarkerRight,
+    MoveMarkerUp,
+    MoveMarkerDown,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::ToggleAppInfo => write!(f, "Toggle the app info window"),
+            Self::ToggleSettings => write!(f, "Toggle the settings window"),
+            Self::ToggleStats => write!(f, "Toggle the statistics window"),
+            Self::NextPart => write!(f, "Next (check or go to the next question)"),
+            Self::Check => write!(f, "Check the answer"),
+            Self::GiveUp => write!(f, "Give up the question"),
+            Self::ZoomIn => write!(f, "Zoom in"),
+            Self::ZoomOut => write!(f, "Zoom out"),
+            Self::RotateLeft => write!(f, "Rotate the view left"),
+            Self::RotateRight => write!(f, "Rotate the view right"),
+            Self::RotateUp => write!(f, "Rotate the view up"),
+            Self::RotateDown => write!(f, "Rotate the view down"),
+            Self::CentreOnAnswer => write!(f, "Centre on the correct answer"),
+            Self::ToggleConstellationLines => write!(f, "Toggle the constellation lines"),
+            Self::ToggleConstellationBorders => write!(f, "Toggle the constellation borders"),
+            Self::PlaceMarker => write!(f, "Place a marker in the centre of the screen"),
+            Self::MoveMarkerLeft => write!(f, "Move the marker left"),
+            Self::MoveMarkerRight => write!(f, "Move the marker right"),
+            Self::MoveMarkerUp => write!(f, "Move the marker up"),
+            Self::MoveMarkerDown => write!(f, "Move the marker down"),
+        }
+    }
+}
+
+impl Action {
+    pub const fn variants() -> [Self; 20] {
+        [
+            Self::ToggleAppInfo,
+            Self::ToggleSettings,
+            Self::ToggleStats,
+            Self::NextPart,
+            Self::Check,
+            Self::GiveUp,
+            Self::ZoomIn,
+            Self::ZoomOut,
+            Self::RotateLeft,
+            Self::RotateRight,
+            Self::RotateUp,
+            Self::RotateDown,
+            Self::CentreOnAnswer,
+            Self::ToggleConstellationLines,
+            Self::ToggleConstellationBorders,
+            Self::PlaceMarker,
+            Self::MoveMarkerLeft,
+            Self::MoveMarkerRight,
+            Self::MoveMarkerUp,
+            Self::MoveMarkerDown,
+        ]
+    }
+
+    /// Whether holding the key down should repeat the action
+    pub fn repeats(&self) -> bool {
+        matches!(
+            *self,
+            Self::ZoomIn
+                | Self::ZoomOut
+                | Self::RotateLeft
+                | Self::RotateRight
+                | Self::RotateUp
+                | Self::RotateDown
+                | Self::MoveMarkerLeft
+                | Self::MoveMarkerRight
+                | Self::MoveMarkerUp
+                | Self::MoveMarkerDown
+        )
+    }
+
+    /// The file of the lines turned on and off by the action, the lines are keyed by their file names
+    pub fn toggled_lines(&self) -> Option<&'static str> {
+        match *self {
+            Self::ToggleConstellationLines => Some(CONSTELLATION_LINES),
+            Self::ToggleConstellationBorders => Some(CONSTELLATION_BORDERS),
+            _ => None,
+        }
+    }
+
+    fn default_bindings(&self) -> Vec<KeyBinding> {
+        match *self {
+            Self::ToggleAppInfo => vec![KeyBinding::new(egui::Key::I, true, false, true)],
+            Self::ToggleSettings => vec![KeyBinding::new(egui::Key::O, true, false, true)],
+            Self::ToggleStats => vec![KeyBinding::new(egui::Key::S, true, false, true)],
+            Self::NextPart => vec![KeyBinding::new(egui::Key::Space, false, false, false)],
+            Self::Check => vec![KeyBinding::new(egui::Key::Enter, false, true, false)],
+            Self::GiveUp => vec![KeyBinding::new(egui::Key::G, true, false, true)],
+            Self::ZoomIn => vec![KeyBinding::new(egui::Key::ArrowUp, false, false, true), KeyBinding::new(egui::Key::PageUp, false, false, false)],
+            Self::ZoomOut => vec![KeyBinding::new(egui::Key::ArrowDown, false, false, true), KeyBinding::new(egui::Key::PageDown, false, false, false)],
+            Self::RotateLeft => vec![KeyBinding::new(egui::Key::ArrowLeft, false, false, false)],
+            Self::RotateRight => vec![KeyBinding::new(egui::Key::ArrowRight, false, false, false)],
+            Self::RotateUp => vec![KeyBinding::new(egui::Key::ArrowUp, false, false, false)],
+            Self::RotateDown => vec![KeyBinding::new(egui::Key::ArrowDown, false, false, false)],
+            Self::CentreOnAnswer => vec![KeyBinding::new(egui::Key::C, true, false, true)],
+            Self::ToggleConstellationLines => vec![KeyBinding::new(egui::Key::L, true, false, true)],
+            Self::ToggleConstellationBorders => vec![KeyBinding::new(egui::Key::B, true, false, true)],
+            Self::PlaceMarker => vec![KeyBinding::new(egui::Key::Enter, false, false, false)],
+            Self::MoveMarkerLeft => vec![KeyBinding::new(egui::Key::ArrowLeft, false, true, false)],
+            Self::MoveMarkerRight => vec![KeyBinding::new(egui::Key::ArrowRight, false, true, false)],
+            Self::MoveMarkerUp => vec![KeyBinding::new(egui::Key::ArrowUp, false, true, false)],
+            Self::MoveMarkerDown => vec![KeyBinding::new(egui::Key::ArrowDown, false, true, false)],
+        }
+    }
+}
+
+/// A key together with the modifiers which have to be held, saved as for example "Alt+Shift+I"
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyBinding {
+    pub key: egui::Key,
+    pub alt: bool,
+    pub ctrl: bool,
+    pub shift: bool,
+}
+
+impl KeyBinding {
+    pub const fn new(key: egui::Key, alt: bool, ctrl: bool, shift: bool) -> Self {
+        Self { key, alt, ctrl, shift }
+    }
+
+    pub fn from_event(key: egui::Key, modifiers: egui::Modifiers) -> Self {
+        Self::new(key, modifiers.alt, modifiers.ctrl, modifiers.shift)
+    }
+
+    pub fn matches(&self, key: egui::Key, modifiers: egui::Modifiers) -> bool {
+        self.key == key
+            && modifiers.matches_exact(egui::Modifiers {
+                alt: self.alt,
+                ctrl: self.ctrl,
+                shift: self.shift,
+                ..Default::default()
+            })
+    }
+
+    /// Shortcuts without Alt or Ctrl would collide with typing into text fields
+    pub fn works_while_typing(&self) -> bool {
+        self.alt || self.ctrl
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key.name())
+    }
+}
+
+impl From<KeyBinding> for String {
+    fn from(binding: KeyBinding) -> Self {
+        binding.to_string()
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut parts = value.split('+').collect::<Vec<&str>>();
+        let key_name = parts.pop().unwrap_or_default();
+        let key = egui::Key::from_name(key_name).ok_or(format!("Unknown key '{key_name}' in '{value}'"))?;
+        let mut binding = Self::new(key, false, false, false);
+        for modifier in parts {
+            match modifier {
+                "Ctrl" => binding.ctrl = true,
+                "Alt" => binding.alt = true,
+                "Shift" => binding.shift = true,
+                _ => return Err(format!("Unknown modifier '{modifier}' in '{value}'")),
+            }
+        }
+        Ok(binding)
+    }
+}
+
+/// The key bindings of all the actions. Actions missing from the saved settings (for example added in a newer version) get their default bindings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "HashMap<Action, Vec<KeyBinding>>", into = "HashMap<Action, Vec<KeyBinding>>")]
+pub struct Keybindings {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self::from(HashMap::new())
+    }
+}
+
+impl From<HashMap<Action, Vec<KeyBinding>>> for Keybindings {
+    fn from(mut bindings: HashMap<Action, Vec<KeyBinding>>) -> Self {
+        for action in Action::variants() {
+            bindings.entry(action).or_insert_with(|| action.default_bindings());
+        }
+        Self { bindings }
+    }
+}
+
+impl From<Keybindings> for HashMap<Action, Vec<KeyBinding>> {
+    fn from(keybindings: Keybindings) -> Self {
+        keybindings.bindings
+    }
+}
+
+impl Keybindings {
+    pub fn get(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map(|bindings| bindings.as_slice()).unwrap_or_default()
+    }
+
+    pub fn get_mut(&mut self, action: Action) -> &mut Vec<KeyBinding> {
+        self.bindings.entry(action).or_default()
+    }
+
+    /// The action bound to the key press, with the binding that matched
+    pub fn action_for(&self, key: egui::Key, modifiers: egui::Modifiers) -> Option<(Action, KeyBinding)> {
+        Action::variants()
+            .into_iter()
+            .find_map(|action| self.get(action).iter().find(|binding| binding.matches(key, modifiers)).map(|binding| (action, *binding)))
+    }
+
+    /// All the actions except the given one which use the binding
+    pub fn conflicts(&self, action: Action, binding: &KeyBinding) -> Vec<Action> {
+        Action::variants().into_iter().filter(|&other| other != action && self.get(other).contains(binding)).collect()
+    }
+
+    pub fn reset(&mut self, action: Action) {
+        self.bindings.insert(action, action.default_bindings());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_survive_saving() {
+        let mut keybindings = Keybindings::default();
+        keybindings.get_mut(Action::GiveUp).push(KeyBinding::new(egui::Key::Escape, false, true, true));
+        let saved = serde_json::to_string(&keybindings).expect("Failed to serialize the key bindings");
+        assert!(saved.contains("\"Ctrl+Shift+Escape\""));
+        let loaded: Keybindings = serde_json::from_str(&saved).expect("Failed to deserialize the key bindings");
+        assert_eq!(loaded, keybindings);
+    }
+
+    #[test]
+    fn missing_actions_get_default_bindings() {
+        let loaded: Keybindings = serde_json::from_str(r#"{"NextPart": ["N"]}"#).expect("Failed to deserialize the key bindings");
+        assert_eq!(loaded.get(Action::NextPart), &[KeyBinding::new(egui::Key::N, false, false, false)]);
+        assert_eq!(loaded.get(Action::ToggleSettings), Action::ToggleSettings.default_bindings().as_slice());
+        assert!(serde_json::from_str::<Keybindings>(r#"{"NextPart": ["Hyper+N"]}"#).is_err());
+    }
+
+    #[test]
+    fn modifiers_have_to_match_exactly() {
+        let keybindings = Keybindings::default();
+        let shift = egui::Modifiers { shift: true, ..Default::default() };
+        let ctrl = egui::Modifiers {
+            ctrl: true,
+            command: true,
+            ..Default::default()
+        };
+        assert_eq!(keybindings.action_for(egui::Key::ArrowUp, egui::Modifiers::NONE).map(|(action, _)| action), Some(Action::RotateUp));
+        assert_eq!(keybindings.action_for(egui::Key::ArrowUp, shift).map(|(action, _)| action), Some(Action::ZoomIn));
+        assert_eq!(keybindings.action_for(egui::Key::ArrowUp, ctrl).map(|(action, _)| action), Some(Action::MoveMarkerUp));
+        assert_eq!(keybindings.action_for(egui::Key::I, shift), None);
+    }
+
+    #[test]
+    fn only_the_line_actions_toggle_lines() {
+        let toggled: Vec<(Action, &str)> = Action::variants().into_iter().filter_map(|action| Some((action, action.toggled_lines()?))).collect();
+        assert_eq!(
+            toggled,
+            vec![(Action::ToggleConstellationLines, CONSTELLATION_LINES), (Action::ToggleConstellationBorders, CONSTELLATION_BORDERS)]
+        );
+    }
+}
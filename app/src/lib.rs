@@ -12,10 +12,13 @@ pub use rendering::caspr::renderer;
 pub mod application;
 pub mod config;
 pub mod enums;
+pub mod export;
 pub mod files;
 pub mod game;
 pub mod graphics;
 pub mod input;
+pub mod keybindings;
+pub mod localisation;
 mod public_constants;
 pub mod rendering;
 pub mod server_communication;
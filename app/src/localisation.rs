@@ -0,0 +1,201 @@
+use std::{cell::Cell, collections::HashMap, fmt::Display};
+
+use once_cell::sync::Lazy;
+
+/// The languages the user interface and the object names can be shown in. English is the language of the source code and the data files, so it needs no translation file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Language {
+    #[default]
+    English,
+    Czech,
+}
+
+impl Display for Language {
+    /// The name of the language in the language itself, so that it can be found even when the rest of the interface is in a language the user does not understand
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::English => write!(f, "English"),
+            Self::Czech => write!(f, "Čeština"),
+        }
+    }
+}
+
+impl Language {
+    pub const fn variants() -> [Self; 2] {
+        [Self::English, Self::Czech]
+    }
+
+    /// The ISO 639-1 code of the language, also used as the name of its translation file
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Czech => "cs",
+        }
+    }
+
+    fn translations(&self) -> Option<&'static Translations> {
+        match self {
+            Self::English => None,
+            Self::Czech => Some(&CZECH),
+        }
+    }
+}
+
+/// The contents of a translation file in the `locales` folder. Everything is keyed by the English text, anything missing falls back to English.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct Translations {
+    /// The strings of the user interface
+    ui: HashMap<String, String>,
+    /// The names of the constellations, keyed by their abbreviation. The first name is the one shown, all of them are accepted as answers.
+    constellations: HashMap<String, Vec<String>>,
+    /// The names of stars and deep-sky objects, keyed by their English proper name. The first name is the one shown, all of them are accepted as answers.
+    object_names: HashMap<String, Vec<String>>,
+}
+
+fn load_translations(language: Language, contents: &str) -> Translations {
+    match serde_json::from_str(contents) {
+        Ok(translations) => translations,
+        Err(err) => {
+            log::error!("Failed to read the translations into {language}: {err}");
+            Translations::default()
+        }
+    }
+}
+
+static CZECH: Lazy<Translations> = Lazy::new(|| load_translations(Language::Czech, include_str!("../locales/cs.json")));
+
+thread_local! {
+    // The interface is only ever drawn from a single thread, so keeping this per thread means tests running in parallel do not affect each other
+    static LANGUAGE: Cell<Language> = const { Cell::new(Language::English) };
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.with(|current| current.set(language));
+}
+
+pub fn language() -> Language {
+    LANGUAGE.with(|current| current.get())
+}
+
+/// Translates a string of the user interface into the current language, returning the English text if there is no translation
+pub fn tr(text: &str) -> String {
+    language().translations().and_then(|translations| translations.ui.get(text)).cloned().unwrap_or_else(|| text.to_owned())
+}
+
+/// Translates a string containing the placeholders `{0}`, `{1}`, ... and fills them in with the arguments, as their order may differ between languages
+pub fn tr_args(text: &str, args: &[&dyn Display]) -> String {
+    let mut translated = tr(text);
+    for (i, arg) in args.iter().enumerate() {
+        translated = translated.replace(&format!("{{{i}}}"), &arg.to_string());
+    }
+    translated
+}
+
+/// The name of the constellation in the current language, if it has a translation
+pub fn constellation_name(abbreviation: &str) -> Option<String> {
+    language()
+        .translations()
+        .and_then(|translations| translations.constellations.get(abbreviation))
+        .and_then(|names| names.first())
+        .cloned()
+}
+
+/// The name of an object in the current language, if it has a translation
+pub fn object_name(english_name: &str) -> Option<String> {
+    language()
+        .translations()
+        .and_then(|translations| translations.object_names.get(english_name))
+        .and_then(|names| names.first())
+        .cloned()
+}
+
+/// The names of the constellation in all the languages, so that the answers are accepted regardless of the language chosen
+pub fn all_constellation_names(abbreviation: &str) -> Vec<String> {
+    Language::variants()
+        .iter()
+        .filter_map(|language| language.translations())
+        .filter_map(|translations| translations.constellations.get(abbreviation))
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+/// The names of an object in all the languages, see [`all_constellation_names`]
+pub fn all_object_names(english_name: &str) -> Vec<String> {
+    Language::variants()
+        .iter()
+        .filter_map(|language| language.translations())
+        .filter_map(|translations| translations.object_names.get(english_name))
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+/// Prepares a typed answer for the comparison with the accepted names - the case, the spaces and the diacritics are ignored, so that 'Velká medvědice' can also be typed on a keyboard without Czech letters
+pub fn normalise_answer(answer: &str) -> String {
+    answer
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' => 'a',
+            'č' | 'ç' => 'c',
+            'ď' => 'd',
+            'é' | 'ě' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ľ' | 'ĺ' => 'l',
+            'ň' => 'n',
+            'ó' | 'ô' | 'ö' => 'o',
+            'ř' => 'r',
+            'š' => 's',
+            'ť' => 't',
+            'ú' | 'ů' | 'ü' | 'û' => 'u',
+            'ý' => 'y',
+            'ž' => 'z',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn czech_translations_are_complete() {
+        // A broken translation file only logs an error, so make sure it actually loaded
+        assert_eq!(CZECH.constellations.len(), 88);
+        assert!(CZECH.ui.values().all(|text| !text.is_empty()));
+        for (key, text) in &CZECH.ui {
+            for i in 0..4 {
+                let placeholder = format!("{{{i}}}");
+                assert_eq!(key.contains(&placeholder), text.contains(&placeholder), "The placeholders of '{key}' do not match");
+            }
+        }
+
+        set_language(Language::Czech);
+        assert_eq!(tr("Start"), "Začít");
+        assert_eq!(tr("Some text that is not translated"), "Some text that is not translated");
+        assert_eq!(constellation_name("UMa").as_deref(), Some("Velká medvědice"));
+        set_language(Language::English);
+        assert_eq!(tr("Start"), "Start");
+        assert_eq!(constellation_name("UMa"), None);
+        assert!(all_object_names("Polaris").contains(&String::from("Polárka")));
+    }
+
+    #[test]
+    fn placeholders_are_filled_in_the_translation() {
+        set_language(Language::Czech);
+        assert_eq!(tr_args("The object is in {0}.", &[&"Orion"]), "Objekt leží v souhvězdí Orion.");
+        set_language(Language::English);
+        assert_eq!(tr_args("The object is in {0}.", &[&"Orion"]), "The object is in Orion.");
+    }
+
+    #[test]
+    fn answers_are_normalised() {
+        assert_eq!(normalise_answer("Velká Medvědice"), normalise_answer("velka medvedice"));
+        assert_eq!(normalise_answer("Žirafa"), "zirafa");
+        assert_eq!(normalise_answer(" M 31 "), "m31");
+    }
+}
@@ -4,11 +4,19 @@ pub const OBJECT_IMAGES_ADDON_FOLDER: &str = "./addons/object-images";
 pub const THEMES_FOLDER: &str = "./addons/themes";
 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
 pub const QUESTION_PACKS_FOLDER: &str = "./addons/question-packs";
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub const CHARTS_FOLDER: &str = "./charts";
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub const SCREENSHOTS_FOLDER: &str = "./screenshots";
 #[cfg(target_os = "android")]
 pub const OBJECT_IMAGES_ADDON_FOLDER: &str = "/storage/emulated/0/Android/data/com.github.noreply.users.stellar_guesser/files/addons/object-images"; // For whatever reason the Documents folder wouldn't work...
 #[cfg(target_os = "android")]
 pub const THEMES_FOLDER: &str = "/storage/emulated/0/Android/data/com.github.noreply.users.stellar_guesser/files/addons/themes";
 #[cfg(target_os = "android")]
 pub const QUESTION_PACKS_FOLDER: &str = "/storage/emulated/0/Android/data/com.github.noreply.users.stellar_guesser/files/addons/question-packs";
+#[cfg(target_os = "android")]
+pub const CHARTS_FOLDER: &str = "/storage/emulated/0/Android/data/com.github.noreply.users.stellar_guesser/files/charts";
+#[cfg(target_os = "android")]
+pub const SCREENSHOTS_FOLDER: &str = "/storage/emulated/0/Android/data/com.github.noreply.users.stellar_guesser/files/screenshots";
 pub const ANDROID_PACKAGE_NAME: &str = "com.github.noreply.users.stellar_guesser";
 pub const DESKTOP_PACKAGE_NAME: &str = "stellar_guesser";
@@ -0,0 +1,31 @@
+/// How the camera can be rotated by dragging
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum CameraMode {
+    /// The sky can be rotated freely in any direction, including turning it upside down
+    #[default]
+    Trackball,
+    /// The north celestial pole is always up, the sky can only be turned around the pole and towards or away from it
+    NorthUp,
+}
+
+impl CameraMode {
+    pub fn variants() -> [Self; 2] {
+        [Self::Trackball, Self::NorthUp]
+    }
+
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            Self::Trackball => "The sky can be rotated freely, so after some dragging the constellations may be sideways or upside down",
+            Self::NorthUp => "The north celestial pole stays up, like on printed star charts. The view stops at the poles.",
+        }
+    }
+}
+
+impl std::fmt::Display for CameraMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Trackball => write!(f, "Free (trackball)"),
+            Self::NorthUp => write!(f, "North up"),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use nalgebra::Rotation3;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Easing {
+    Linear,
+    /// Starts and ends slowly
+    EaseInOut,
+    /// Starts quickly and slows down towards the end
+    EaseOut,
+}
+
+impl Easing {
+    pub fn variants() -> [Self; 3] {
+        [Self::Linear, Self::EaseInOut, Self::EaseOut]
+    }
+
+    /// Maps the linear progress (from 0 to 1) to the eased one
+    pub fn apply(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => progress,
+            Self::EaseInOut => progress * progress * (3.0 - 2.0 * progress),
+            Self::EaseOut => 1.0 - (1.0 - progress).powi(3),
+        }
+    }
+}
+
+impl std::fmt::Display for Easing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Linear => write!(f, "Linear"),
+            Self::EaseInOut => write!(f, "Ease in and out"),
+            Self::EaseOut => write!(f, "Ease out"),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy)]
+#[serde(default)]
+pub struct CameraTransitionSettings {
+    /// How long a transition takes in seconds, 0 moves the camera instantly
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Default for CameraTransitionSettings {
+    fn default() -> Self {
+        Self {
+            duration: 0.8,
+            easing: Easing::EaseInOut,
+        }
+    }
+}
+
+impl CameraTransitionSettings {
+    pub fn clamp(&mut self) {
+        self.duration = self.duration.clamp(0.0, 10.0);
+    }
+
+    fn duration_ns(&self) -> i64 {
+        (self.duration as f64 * 1e9) as i64
+    }
+}
+
+/// A smooth movement of the camera from one orientation and zoom to another, driven by the frame timestamps
+pub struct CameraTransition {
+    start_rotation: Rotation3<f32>,
+    target_rotation: Rotation3<f32>,
+    start_zoom: f32,
+    target_zoom: f32,
+    /// Set when the transition is first advanced, so that transitions can be started from places which do not know the time of the frame
+    start_ns: Option<i64>,
+    duration_ns: i64,
+    easing: Easing,
+}
+
+impl CameraTransition {
+    pub fn new(start_rotation: Rotation3<f32>, target_rotation: Rotation3<f32>, start_zoom: f32, target_zoom: f32, settings: &CameraTransitionSettings) -> Self {
+        Self {
+            start_rotation,
+            target_rotation,
+            start_zoom,
+            target_zoom,
+            start_ns: None,
+            duration_ns: settings.duration_ns(),
+            easing: settings.easing,
+        }
+    }
+
+    /// The rotation and the zoom of the camera at the given time, and whether the transition has finished
+    pub fn advance(&mut self, timestamp_ns: i64) -> (Rotation3<f32>, f32, bool) {
+        let start_ns = *self.start_ns.get_or_insert(timestamp_ns);
+        let progress = if self.duration_ns <= 0 {
+            1.0
+        } else {
+            ((timestamp_ns - start_ns) as f64 / self.duration_ns as f64).clamp(0.0, 1.0) as f32
+        };
+        if progress >= 1.0 {
+            return (self.target_rotation, self.target_zoom, true);
+        }
+        let eased = self.easing.apply(progress);
+        // The zoom is interpolated exponentially, so that zooming in by the same factor always takes the same time
+        let zoom = self.start_zoom * (self.target_zoom / self.start_zoom).powf(eased);
+        (self.start_rotation.slerp(&self.target_rotation, eased), zoom, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn reaches_the_target() {
+        let start = Rotation3::identity();
+        let target = Rotation3::from_axis_angle(&Vector3::y_axis(), 1.0);
+        let settings = CameraTransitionSettings {
+            duration: 1.0,
+            easing: Easing::EaseInOut,
+        };
+        let mut transition = CameraTransition::new(start, target, 1.0, 4.0, &settings);
+        // The transition starts on the first frame it is advanced in
+        let (before, zoom, finished) = transition.advance(100);
+        assert!(!finished);
+        assert!(before.angle() < 1e-4);
+        assert!((zoom - 1.0).abs() < 1e-4);
+        let (halfway, zoom, finished) = transition.advance(100 + 500_000_000);
+        assert!(!finished);
+        assert!((halfway.angle() - 0.5).abs() < 1e-4);
+        assert!((zoom - 2.0).abs() < 1e-4);
+        assert_eq!(transition.advance(100 + 1_000_000_000), (target, 4.0, true));
+    }
+
+    #[test]
+    fn easing_keeps_the_ends() {
+        for easing in Easing::variants() {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert!(easing.apply(0.25) < easing.apply(0.75));
+        }
+    }
+}
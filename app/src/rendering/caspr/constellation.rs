@@ -1,7 +1,10 @@
 use angle::Angle;
+use nalgebra::{Matrix3, Vector3};
 use serde::Deserialize;
 use spherical_geometry::{Polygon, SphericalPoint};
 
+use super::lines::SkyLine;
+
 #[derive(Clone, Deserialize)]
 pub struct BorderVertex {
     pub constellation: String,
@@ -19,14 +22,19 @@ impl BorderVertex {
 pub struct ConstellationRaw {
     pub name_latin: String,
     pub abbreviation: String,
+    pub name_genitive: String,
     pub polygons: String,
 }
 
 pub struct Constellation {
     pub abbreviation: String,
-    /// \[abbreviation, latin name, ...\]
+    /// \[abbreviation, latin name, names in the other languages...\]
     pub possible_names: Vec<String>,
+    /// The latin genitive used in the star designations, e.g. 'Orionis' for Orion
+    pub genitive: String,
     pub polygons: Vec<Polygon>,
+    /// The vertices of each of the polygons as (ra, dec), used for drawing the border
+    pub borders: Vec<Vec<(angle::Rad<f32>, angle::Rad<f32>)>>,
 }
 
 impl Constellation {
@@ -41,14 +49,67 @@ impl Constellation {
                 return Err(Box::from(format!("Failed to create polygons for the {} constellation: {:?}", raw.name_latin, err)));
             }
         };
+        // The polygons are stored as [vertices, edges direction], only the vertices are needed for the border
+        let borders = match serde_json::from_str::<Vec<(Vec<[f32; 2]>, serde::de::IgnoredAny)>>(&raw.polygons) {
+            Ok(polygons) => polygons
+                .into_iter()
+                .map(|(vertices, _)| vertices.into_iter().map(|[ra, dec]| (angle::Rad(ra), angle::Rad(dec))).collect())
+                .collect(),
+            Err(err) => {
+                log::warn!("Failed to read the border of the {} constellation: {:?}", raw.name_latin, err);
+                Vec::new()
+            }
+        };
         let abbreviation = raw.abbreviation;
+        let mut possible_names = vec![abbreviation.clone(), raw.name_latin];
+        possible_names.extend(crate::localisation::all_constellation_names(&abbreviation));
         Ok((
             Self {
                 abbreviation: abbreviation.clone(),
-                possible_names: vec![abbreviation.clone(), raw.name_latin],
+                possible_names,
+                genitive: raw.name_genitive,
                 polygons,
+                borders,
             },
             abbreviation,
         ))
     }
+
+    /// The name of the constellation in the current language, or the latin name if there is no translation
+    pub fn display_name(&self) -> String {
+        crate::localisation::constellation_name(&self.abbreviation).unwrap_or_else(|| self.possible_names[1].clone())
+    }
+
+    /// The lines along the border of the constellation
+    pub fn border_lines(&self, width: f32) -> Vec<SkyLine> {
+        let mut lines = Vec::new();
+        for vertices in &self.borders {
+            for i in 0..vertices.len() {
+                let (ra_start, dec_start) = vertices[i];
+                let (ra_end, dec_end) = vertices[(i + 1) % vertices.len()];
+                lines.push(SkyLine {
+                    ra_start: ra_start.to_deg(),
+                    dec_start: dec_start.to_deg(),
+                    ra_end: ra_end.to_deg(),
+                    dec_end: dec_end.to_deg(),
+                    width,
+                });
+            }
+        }
+        lines
+    }
+
+    /// A rough centre of the constellation (the normalised average of the vertices of its border), None if the border is not known
+    pub fn centre(&self) -> Option<Vector3<f32>> {
+        let sum = self
+            .borders
+            .iter()
+            .flatten()
+            .map(|(ra, dec)| sg_geometry::get_point_vector(ra.to_deg(), dec.to_deg(), &Matrix3::identity()))
+            .fold(Vector3::zeros(), |sum, point| sum + point);
+        if sum.magnitude_squared() < f32::EPSILON {
+            return None;
+        }
+        Some(sum.normalize())
+    }
 }
@@ -5,6 +5,7 @@ use serde::Deserialize;
 
 use crate::graphics::parse_colour_option;
 
+use super::markers::MarkerShape;
 use super::renderer::CellestialSphere;
 
 pub struct Deepskies {
@@ -49,14 +50,24 @@ pub struct DeepskyRaw {
 
 impl Deepsky {
     pub fn get_renderer(&self, rotation_matrix: &Matrix3<f32>, colour: Color32) -> DeepskyRenderer {
-        let name = match self.messier {
-            Some(num) => Some(format!("M {num}")),
-            None => self.caldwell.map(|num| format!("C {num}")),
-        };
+        let name = self.label();
 
         DeepskyRenderer::new(self.object_id, sg_geometry::get_point_vector(self.ra, self.dec, rotation_matrix), colour, name, false)
     }
 
+    /// The magnitude as a number, None if the object does not have a known magnitude
+    pub fn magnitude(&self) -> Option<f32> {
+        self.mag.parse().ok()
+    }
+
+    /// The short designation shown next to the object, the Messier number if there is one, otherwise the Caldwell number
+    pub fn label(&self) -> Option<String> {
+        match self.messier {
+            Some(num) => Some(format!("M {num}")),
+            None => self.caldwell.map(|num| format!("C {num}")),
+        }
+    }
+
     pub fn from_raw(raw_deepsky: DeepskyRaw, images_data: Vec<crate::structs::image_info::ImageInfo>) -> (Self, Option<Color32>) {
         let names = raw_deepsky.names.map(|raw_names| raw_names.split(';').map(|s| s.to_owned()).filter(|s| !s.is_empty()).collect());
         let colour = parse_colour_option(raw_deepsky.colour);
@@ -105,6 +116,6 @@ impl DeepskyRenderer {
             return;
         }
         //cellestial_sphere.render_circle(&self.unit_vector, cellestial_sphere.mag_to_radius(self.vmag - magnitude_decrease), self.colour, painter);
-        cellestial_sphere.render_marker(&self.unit_vector, &None, false, Some(5.0), self.colour, 1.5, painter, self.label.clone());
+        cellestial_sphere.render_marker(&self.unit_vector, &None, MarkerShape::Cross, Some(5.0), self.colour, 1.5, painter, self.label.clone(), false);
     }
 }
@@ -1,3 +1,4 @@
+use angle::Angle;
 use eframe::egui;
 use egui::epaint::Color32;
 use nalgebra::{Matrix3, Vector3};
@@ -32,6 +33,18 @@ impl SkyLine {
         )
     }
 
+    /// The point halfway along the segment, not normalised
+    pub fn midpoint(&self) -> Vector3<f32> {
+        let identity = Matrix3::identity();
+        sg_geometry::get_point_vector(self.ra_start, self.dec_start, &identity) + sg_geometry::get_point_vector(self.ra_end, self.dec_end, &identity)
+    }
+
+    /// How far the ends of the segment are from its midpoint, which is as far as any of its points gets from it
+    pub fn half_length(&self) -> angle::Rad<f32> {
+        let length = sg_geometry::angular_distance((self.ra_start.to_rad(), self.dec_start.to_rad()), (self.ra_end.to_rad(), self.dec_end.to_rad()));
+        angle::Rad(length.value() / 2.0)
+    }
+
     pub fn from_raw(raw_line: SkyLineRaw) -> (Self, Option<Color32>) {
         let colour = parse_colour_option(raw_line.colour);
         (
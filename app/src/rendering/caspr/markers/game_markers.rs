@@ -4,11 +4,37 @@ use nalgebra::Matrix3;
 
 use crate::rendering::themes::GameMarkersColours;
 
-use super::{Marker, MarkerRenderer};
+use super::{Marker, MarkerRenderer, MarkerShape};
+use crate::rendering::caspr::lines::SkyLine;
 
 pub struct GameMarkers {
     pub active: bool,
     pub markers: Vec<GameMarker>,
+    /// Markers revealed by hints, kept separate so that placing a guess does not remove them
+    pub hints: Vec<GameMarker>,
+    pub hint_lines: Vec<SkyLine>,
+    pub hint_lines_colour: Color32,
+    /// Highlights the object selected in the exploration mode
+    pub selection: Option<GameMarker>,
+    /// Highlights the constellation found by searching
+    pub selection_lines: Vec<SkyLine>,
+    pub selection_lines_colour: Color32,
+}
+
+impl GameMarkers {
+    pub fn clear_hints(&mut self) {
+        self.hints = Vec::new();
+        self.hint_lines = Vec::new();
+    }
+
+    /// Changes the colours of all the markers, so that a change of the theme shows immediately
+    pub fn recolour(&mut self, game_markers_colours: &GameMarkersColours) {
+        for marker in self.markers.iter_mut().chain(self.hints.iter_mut()).chain(self.selection.iter_mut()) {
+            marker.colour = GameMarker::get_colour(marker.marker_type, game_markers_colours);
+        }
+        self.hint_lines_colour = game_markers_colours.hint;
+        self.selection_lines_colour = game_markers_colours.selection;
+    }
 }
 
 pub struct GameMarker {
@@ -22,6 +48,8 @@ pub struct GameMarker {
     pub pixel_radius: Option<f32>,
     pub angular_width: Option<angle::Deg<f32>>,
     pub pixel_width: Option<f32>,
+    /// Tells apart several markers of the same question, shown regardless of the label settings
+    pub label: Option<String>,
 }
 
 impl GameMarker {
@@ -70,9 +98,15 @@ impl GameMarker {
             pixel_radius,
             angular_width: angular_width.map(angle::Deg),
             pixel_width,
+            label: None,
         }
     }
 
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
     pub fn get_renderer(&self, rotation_matrix: &Matrix3<f32>) -> Option<MarkerRenderer> {
         if self.angular_radius.is_none() && self.pixel_radius.is_none() && self.angular_width.is_none() && self.pixel_width.is_none() {
             return None;
@@ -100,12 +134,13 @@ impl GameMarker {
                 )
             })
         };
-        Some(MarkerRenderer::new(
-            sg_geometry::get_point_vector(self.ra, self.dec, rotation_matrix),
-            other_vec,
-            &self.to_general_marker(),
-            self.colour,
-        ))
+        let mut renderer = MarkerRenderer::new(sg_geometry::get_point_vector(self.ra, self.dec, rotation_matrix), other_vec, &self.to_general_marker(), self.colour);
+        renderer.shape = self.marker_type.shape(renderer.circle);
+        if let Some(label) = &self.label {
+            renderer.label = Some(label.clone());
+            renderer.label_always_shown = true;
+        }
+        Some(renderer)
     }
 
     pub fn to_general_marker(&self) -> Marker {
@@ -127,11 +162,13 @@ impl GameMarker {
             GameMarkerType::Tolerance => game_markers_colours.tolerance,
             GameMarkerType::Task => game_markers_colours.task,
             GameMarkerType::CorrectAnswer => game_markers_colours.correct_answer,
+            GameMarkerType::Hint => game_markers_colours.hint,
+            GameMarkerType::Selection => game_markers_colours.selection,
         }
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum GameMarkerType {
     /// A marker showing the exact chosen location
     Exact,
@@ -141,4 +178,72 @@ pub enum GameMarkerType {
     Task,
     /// A marker marking the correct answer
     CorrectAnswer,
+    /// A marker revealed by a hint
+    Hint,
+    /// A marker highlighting the object inspected in the exploration mode
+    Selection,
+}
+
+impl GameMarkerType {
+    pub fn variants() -> [Self; 6] {
+        [Self::Exact, Self::Tolerance, Self::Task, Self::CorrectAnswer, Self::Hint, Self::Selection]
+    }
+
+    /// The shape of the marker, depending on whether it was placed as a circle or as a cross. No two types share a shape, so that they do not have to be told apart by the colour.
+    pub fn shape(&self, circular: bool) -> MarkerShape {
+        match (self, circular) {
+            (Self::Exact, true) => MarkerShape::Circle,
+            (Self::Exact, false) => MarkerShape::Cross,
+            (Self::Tolerance, _) => MarkerShape::DashedCircle,
+            (Self::Task, true) => MarkerShape::DoubleCircle,
+            (Self::Task, false) => MarkerShape::DiagonalCross,
+            (Self::CorrectAnswer, true) => MarkerShape::CrosshairCircle,
+            (Self::CorrectAnswer, false) => MarkerShape::Diamond,
+            (Self::Hint, true) => MarkerShape::DottedCircle,
+            (Self::Hint, false) => MarkerShape::Square,
+            // The selection is only shown in the exploration mode, where there are no other game markers
+            (Self::Selection, true) => MarkerShape::Circle,
+            (Self::Selection, false) => MarkerShape::Cross,
+        }
+    }
+}
+
+impl std::fmt::Display for GameMarkerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Exact => "Guess",
+                Self::Tolerance => "Guess tolerance",
+                Self::Task => "Task",
+                Self::CorrectAnswer => "Correct answer",
+                Self::Hint => "Hint",
+                Self::Selection => "Selection",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_types_differ_in_shape() {
+        // The selection is never shown together with the other markers
+        let types = GameMarkerType::variants()
+            .into_iter()
+            .filter(|marker_type| *marker_type != GameMarkerType::Selection)
+            .collect::<Vec<GameMarkerType>>();
+        for (index, first) in types.iter().enumerate() {
+            for second in &types[index + 1..] {
+                for first_circular in [true, false] {
+                    for second_circular in [true, false] {
+                        assert_ne!(first.shape(first_circular), second.shape(second_circular), "{first} and {second}");
+                    }
+                }
+            }
+        }
+    }
 }
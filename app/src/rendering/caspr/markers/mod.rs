@@ -123,6 +123,92 @@ impl Marker {
     }
 }
 
+/// How a marker is drawn. Game markers of different types also differ in their shape, so that they can be told apart without relying on the colours.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MarkerShape {
+    /// An upright cross (+)
+    Cross,
+    /// A cross rotated by 45° (×)
+    DiagonalCross,
+    Diamond,
+    Square,
+    Circle,
+    DashedCircle,
+    DottedCircle,
+    /// Two concentric circles
+    DoubleCircle,
+    /// A circle with a small cross in its centre
+    CrosshairCircle,
+}
+
+impl MarkerShape {
+    /// Points on a circle, with the first point repeated at the end to close it
+    fn circle_points(centre: egui::Pos2, radius: f32) -> Vec<egui::Pos2> {
+        let segments = (radius * 0.5).clamp(16.0, 128.0) as usize;
+        (0..=segments)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / segments as f32;
+                centre + radius * egui::vec2(angle.cos(), angle.sin())
+            })
+            .collect()
+    }
+
+    fn paint_cross(painter: &egui::Painter, centre: egui::Pos2, size: f32, diagonal: bool, stroke: egui::Stroke) {
+        let [horizontal, vertical] = if diagonal {
+            let half_diagonal = size * std::f32::consts::FRAC_1_SQRT_2;
+            [egui::vec2(half_diagonal, half_diagonal), egui::vec2(half_diagonal, -half_diagonal)]
+        } else {
+            [egui::vec2(size, 0.0), egui::vec2(0.0, size)]
+        };
+        painter.line_segment([centre - horizontal, centre + horizontal], stroke);
+        painter.line_segment([centre - vertical, centre + vertical], stroke);
+    }
+
+    /// Paints the shape, `size` being the radius for circles and the distance from the centre to the end of the arms for the other shapes
+    pub fn paint(&self, painter: &egui::Painter, centre: egui::Pos2, size: f32, stroke: egui::Stroke) {
+        match self {
+            Self::Cross => Self::paint_cross(painter, centre, size, false, stroke),
+            Self::DiagonalCross => Self::paint_cross(painter, centre, size, true, stroke),
+            Self::Diamond => {
+                let points = vec![
+                    centre - egui::vec2(size, 0.0),
+                    centre - egui::vec2(0.0, size),
+                    centre + egui::vec2(size, 0.0),
+                    centre + egui::vec2(0.0, size),
+                ];
+                painter.add(egui::Shape::closed_line(points, stroke));
+            }
+            Self::Square => {
+                painter.rect_stroke(egui::Rect::from_center_size(centre, egui::Vec2::splat(size * 1.6)), 0.0, stroke, egui::StrokeKind::Middle);
+            }
+            Self::Circle => {
+                painter.circle_stroke(centre, size, stroke);
+            }
+            Self::DashedCircle => {
+                let circumference = std::f32::consts::TAU * size;
+                let dash_period = circumference / (circumference / 10.0).round().max(8.0);
+                painter.extend(egui::Shape::dashed_line(&Self::circle_points(centre, size), stroke, dash_period * 0.6, dash_period * 0.4));
+            }
+            Self::DottedCircle => {
+                let circumference = std::f32::consts::TAU * size;
+                let spacing = circumference / (circumference / (3.0 * stroke.width).max(4.0)).round().max(8.0);
+                painter.extend(egui::Shape::dotted_line(&Self::circle_points(centre, size), stroke.color, spacing, stroke.width * 0.75));
+            }
+            Self::DoubleCircle => {
+                painter.circle_stroke(centre, size, stroke);
+                // Small circles get the second circle around them rather than inside them
+                let gap = 2.5 * stroke.width;
+                let second_radius = if size > 3.0 * gap { size - gap } else { size + gap };
+                painter.circle_stroke(centre, second_radius, stroke);
+            }
+            Self::CrosshairCircle => {
+                painter.circle_stroke(centre, size, stroke);
+                Self::paint_cross(painter, centre, (size * 0.4).max(2.0), false, stroke);
+            }
+        }
+    }
+}
+
 pub struct MarkerRenderer {
     pub unit_vector: Vector3<f32>,
     pub unit_vector_other_point: Option<Vector3<f32>>,
@@ -133,7 +219,10 @@ pub struct MarkerRenderer {
     pub angular_width: Option<angle::Deg<f32>>,
     pub pixel_width: Option<f32>,
     pub circle: bool,
+    pub shape: MarkerShape,
     pub label: Option<String>,
+    /// Shows the label even when the labels are turned off in the sky settings, for the labels the game depends on
+    pub label_always_shown: bool,
 }
 
 impl MarkerRenderer {
@@ -148,7 +237,13 @@ impl MarkerRenderer {
             angular_width: marker.angular_width,
             pixel_width: marker.pixel_width,
             circle: marker.angular_radius.is_some() || marker.pixel_radius.is_some(),
+            shape: if marker.angular_radius.is_some() || marker.pixel_radius.is_some() {
+                MarkerShape::Circle
+            } else {
+                MarkerShape::Cross
+            },
             label: marker.label.map(|a| a.iter().collect()),
+            label_always_shown: false,
         }
     }
 
@@ -156,12 +251,13 @@ impl MarkerRenderer {
         cellestial_sphere.render_marker(
             &self.unit_vector,
             &self.unit_vector_other_point,
-            self.circle,
+            self.shape,
             if self.circle { self.pixel_radius } else { self.pixel_width },
             self.colour,
             self.line_width,
             painter,
             self.label.clone(),
+            self.label_always_shown,
         )
     }
 }
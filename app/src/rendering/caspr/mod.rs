@@ -1,12 +1,17 @@
+pub mod camera_mode;
+pub mod camera_transition;
 pub mod clouds;
 pub mod constellation;
 pub mod deepsky;
 pub mod lines;
 pub mod markers;
 pub mod renderer;
+pub mod search;
+pub mod shapes;
 pub mod sky_settings;
 pub mod star_names;
 pub mod stars;
+pub mod tiled_stars;
 
 pub enum SpecificName {
     /// Gives back all name combinations
@@ -70,6 +75,27 @@ pub fn generate_name_combinations(s: &str, specific_name: SpecificName) -> Vec<S
     results
 }
 
+/// All the variants of a Bayer or Flamsteed designation like "alpha{ Orionis}", where the genitive of the constellation in the last optional part can also be replaced by the abbreviation of the constellation, so that both "alpha Orionis" and "alpha Ori" are accepted
+pub fn designation_name_combinations(designation: &str, constellation_abbreviations: &[String]) -> Vec<String> {
+    let mut names = generate_name_combinations(designation, SpecificName::None);
+    let Some(genitive_start) = designation.rfind("{ ") else {
+        return names;
+    };
+    let genitive = &designation[genitive_start + 1..];
+    if !genitive.ends_with('}') || genitive[..genitive.len() - 1].contains(['{', '}']) {
+        return names;
+    }
+    for abbreviation in constellation_abbreviations.iter().filter(|abbreviation| !abbreviation.is_empty()) {
+        let abbreviated = format!("{}{{ {abbreviation}}}", &designation[..genitive_start]);
+        for name in generate_name_combinations(&abbreviated, SpecificName::None) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -110,4 +136,13 @@ mod tests {
         let combinations = super::generate_name_combinations(input, super::SpecificName::AllOptional);
         assert_eq!(combinations, vec![String::from("Optional starting text Some text inner text more ending text"),]);
     }
+
+    #[test]
+    fn designations_with_abbreviations() {
+        let names = super::designation_name_combinations("gamma{2}{ Arietis}", &[String::from("Ari")]);
+        assert_eq!(names, vec!["gamma", "gamma2", "gamma Arietis", "gamma2 Arietis", "gamma Ari", "gamma2 Ari"]);
+
+        let names = super::designation_name_combinations("61{ Cygni}", &[]);
+        assert_eq!(names, vec!["61", "61 Cygni"]);
+    }
 }
@@ -6,16 +6,32 @@ use crate::{
 use angle::Angle;
 use eframe::egui::{self, Align2, FontFamily, FontId};
 use egui::epaint::Color32;
-use nalgebra::{Rotation3, Vector3};
-use sg_geometry::{intersections, LineSegment, Rectangle};
-use std::{collections::HashMap, error::Error, f32::consts::PI, fs};
+use nalgebra::{Matrix3, Rotation3, Vector3};
+use sg_geometry::{intersections, projection::Projection, spatial_index::CubeMapIndex, LineSegment, Rectangle};
+use std::{collections::HashMap, error::Error, f32::consts::PI, fs, sync::mpsc::Receiver};
 
 const SKY_OBJECTS_FOLDER: &str = "./sphere/sky-objects";
+/// The sky objects file made by the NGC/IC generator, its thousands of faint objects are only drawn after being turned on in the settings
+pub const NGC_IC_CATALOGUE: &str = "ngc-ic-catalogue.csv";
 const LINES_FOLDER: &str = "./sphere/lines";
 const MARKERS_FOLDER: &str = "./sphere/markers";
 const STAR_NAMES_FOLDER: &str = "./sphere/named-stars";
+const SHAPES_FOLDER: &str = "./sphere/shapes";
+const TILED_STARS_FOLDER: &str = "./sphere/tiled-stars";
 const CONSTELLATION_NAMES: &str = "./data/constellations.csv";
+/// All the stars down to about the sixth magnitude, used to tell how bright the question stars are within their constellations
+const BRIGHT_STARS: &str = "./sphere/stars/stars.csv";
 const ZOOM_CAP: f32 = 100.0;
+/// How much further than the edge of the screen objects are still treated as visible, so that the discs of stars just off the screen are not cut off
+const CULLING_MARGIN: angle::Deg<f32> = angle::Deg(1.0);
+/// The number of parts each edge of the screen is split into when looking for the point furthest from the centre of the view
+const VIEW_CIRCLE_EDGE_SAMPLES: usize = 16;
+/// How far a star drawn from the catalogues can be from the position of a question object and still be the same star, as the positions come from different catalogues
+const DRAWN_STAR_TOLERANCE: angle::Deg<f32> = angle::Deg(0.01);
+/// Up to this FOV (in degrees) the deepsky objects are limited by the magnitude set in the settings, in narrower views fainter objects are shown as well
+const DEEPSKY_MAGNITUDE_LIMIT_FOV: f32 = 90.0;
+/// How far apart two rows of the same object may be placed before they are reported as disagreeing, the catalogues round the positions differently
+const REPEATED_OBJECT_TOLERANCE: angle::Deg<f32> = angle::Deg(0.2);
 
 #[cfg(any(target_os = "android", target_os = "ios"))]
 use crate::{SKY_DATA_FILES, SKY_DATA_LISTS};
@@ -47,20 +63,25 @@ pub const MAG_TO_LIGHT_POLLUTION_RAW: [(LightPollution, [Option<stars::Magnitude
 
 // use geometry::{cast_onto_sphere, project_point};
 
-use super::markers::{Marker, MarkerRaw, MarkerRenderer, Markers};
+use super::camera_mode::CameraMode;
+use super::markers::{Marker, MarkerRaw, MarkerRenderer, MarkerShape, Markers};
 use super::sky_settings;
 use super::star_names::{StarName, StarNameRaw};
 use super::stars::{Star, StarRaw, StarRenderer};
+use super::tiled_stars::{LoadedTile, TiledStarCatalogue};
 use super::{
     deepsky::{Deepskies, Deepsky, DeepskyRaw, DeepskyRenderer},
     markers::game_markers::GameMarkers,
 };
 use super::{
     lines::{LineRenderer, SkyLine, SkyLineRaw, SkyLines},
+    shapes::{ShapeRenderer, SkyShape, SkyShapeRaw, SkyShapes},
     stars,
 };
 
+use super::camera_transition::CameraTransition;
 use super::constellation::{Constellation, ConstellationRaw};
+use super::search::SearchIndex;
 
 const _MERIDIAN_CONSTELLATIONS: [&str; 10] = ["cep", "cas", "and", "peg", "pis", "cet", "scl", "phe", "tuc", "oct"];
 const OBJECT_IMAGES_FOLDER: &str = crate::OBJECT_IMAGES_ADDON_FOLDER;
@@ -69,19 +90,37 @@ pub struct CellestialSphere {
     pub sky_settings: sky_settings::SkySettings,
 
     pub stars: HashMap<String, Vec<Star>>,
+    pub tiled_stars: Vec<TiledStarCatalogue>,
     pub lines: HashMap<String, SkyLines>,
     pub deepskies: HashMap<String, Deepskies>,
+    pub shapes: HashMap<String, SkyShapes>,
     pub markers: HashMap<String, Markers>,
     pub question_objects: Vec<QuestionObject>,
     pub game_markers: GameMarkers,
     pub star_names: HashMap<String, Vec<StarName>>,
     pub constellations: HashMap<String, Constellation>,
+    /// All the names the question objects and constellations can be searched by
+    pub search_index: SearchIndex,
     pub zoom: f32,
     pub fov: f32,
     pub camera_z: f32,
+    /// Indices into the star vectors with the same key
+    star_indices: HashMap<String, CubeMapIndex<usize>>,
+    /// Indices into the deepsky vectors with the same key
+    deepsky_indices: HashMap<String, CubeMapIndex<usize>>,
+    /// Indices of the midpoints of the line segments with the same key, with the furthest a point of any of the segments gets from its midpoint
+    line_indices: HashMap<String, (CubeMapIndex<usize>, angle::Rad<f32>)>,
+    /// The objects hidden from the sky, kept apart from the renderers since those are only built for the objects on the screen
+    disabled_objects: std::collections::HashSet<u64>,
+    /// The indices of the drawn stars hidden together with a question object at their position, for each catalogue
+    disabled_stars: HashMap<String, std::collections::HashSet<usize>>,
+    /// Indices into `question_objects`
+    object_index: CubeMapIndex<usize>,
     star_renderers: HashMap<String, Vec<StarRenderer>>,
+    tiled_star_renderers: Vec<StarRenderer>,
     line_renderers: HashMap<String, Vec<LineRenderer>>,
     deepsky_renderers: HashMap<String, Vec<DeepskyRenderer>>,
+    shape_renderers: HashMap<String, Vec<ShapeRenderer>>,
     marker_renderers: HashMap<String, Vec<MarkerRenderer>>,
 
     pub light_pollution_place: LightPollution,
@@ -90,12 +129,58 @@ pub struct CellestialSphere {
     pub viewport_rect: egui::Rect,
 
     pub rotation: Rotation3<f32>,
+    /// The camera movement currently in progress, if any
+    pub camera_transition: Option<CameraTransition>,
+}
+
+/// What an object repeated in several sky objects files is compared by
+struct SkyObjectIdentity {
+    ra: angle::Deg<f32>,
+    dec: angle::Deg<f32>,
+    /// The Messier, Caldwell, NGC and IC numbers
+    designations: [Option<u32>; 4],
+}
+
+impl SkyObjectIdentity {
+    fn of(object: &QuestionObjectRaw) -> Self {
+        Self {
+            ra: object.ra,
+            dec: object.dec,
+            designations: [object.messier_number, object.caldwell_number, object.ngc_number, object.ic_number],
+        }
+    }
+
+    /// The rows agree if they are close to each other and no designation given in both of them differs
+    fn matches(&self, other: &Self) -> bool {
+        let distance = sg_geometry::angular_distance((self.ra.to_rad(), self.dec.to_rad()), (other.ra.to_rad(), other.dec.to_rad())).to_deg();
+        distance.value() <= REPEATED_OBJECT_TOLERANCE.value() && self.designations.iter().zip(other.designations).all(|(a, b)| a.is_none() || b.is_none() || *a == b)
+    }
+}
+
+/// The double and variable star files only add their data to stars which the other catalogues may already draw (under other object ids), so their stars are not drawn a second time where another catalogue has a star
+fn remove_star_data_copies(catalog: &mut HashMap<String, Vec<Star>>, star_data_ids: &std::collections::HashSet<u64>) {
+    let other_stars = CubeMapIndex::from_points(
+        sg_geometry::spatial_index::DEFAULT_RESOLUTION,
+        catalog
+            .values()
+            .flatten()
+            .filter(|star| !star_data_ids.contains(&star.object_id))
+            .map(|star| (sg_geometry::get_point_vector(star.ra, star.dec, &Matrix3::identity()), ())),
+    );
+    for stars in catalog.values_mut() {
+        stars.retain(|star| {
+            !star_data_ids.contains(&star.object_id)
+                || other_stars
+                    .nearest(&sg_geometry::get_point_vector(star.ra, star.dec, &Matrix3::identity()), DRAWN_STAR_TOLERANCE.to_rad())
+                    .is_none()
+        });
+    }
 }
 
 impl CellestialSphere {
     //Renders a circle based on its current normal (does NOT account for the rotation of the sphere)
     pub fn render_circle(&self, normal: &Vector3<f32>, radius: f32, color: egui::epaint::Color32, painter: &egui::Painter) {
-        let (projected_point, is_within_bounds) = sg_geometry::project_point(normal, self.zoom, self.viewport_rect);
+        let (projected_point, is_within_bounds) = sg_geometry::project_point(normal, self.zoom, self.viewport_rect, self.sky_settings.projection);
 
         if is_within_bounds {
             painter.circle_filled(projected_point, radius, color);
@@ -103,8 +188,13 @@ impl CellestialSphere {
     }
 
     pub fn render_line(&self, start: &Vector3<f32>, end: &Vector3<f32>, colour: Color32, width: f32, painter: &egui::Painter) {
-        let (start_point, is_start_within_bounds) = sg_geometry::project_point(start, self.zoom, self.viewport_rect);
-        let (end_point, is_end_within_bounds) = sg_geometry::project_point(end, self.zoom, self.viewport_rect);
+        let projection = self.sky_settings.projection;
+        // Lines going out of what the projection can show, or torn apart by it, are left out
+        if projection.breaks_line(start, end) || projection.project(start).is_none() || projection.project(end).is_none() {
+            return;
+        }
+        let (start_point, is_start_within_bounds) = sg_geometry::project_point(start, self.zoom, self.viewport_rect, projection);
+        let (end_point, is_end_within_bounds) = sg_geometry::project_point(end, self.zoom, self.viewport_rect, projection);
 
         let screen_rect = Rectangle::from(self.viewport_rect);
 
@@ -129,19 +219,20 @@ impl CellestialSphere {
         &self,
         centre_vector: &Vector3<f32>,
         other_vector: &Option<Vector3<f32>>,
-        circle: bool,
+        shape: MarkerShape,
         pixel_size: Option<f32>,
         colour: Color32,
         width: f32,
         painter: &egui::Painter,
         label: Option<String>,
+        label_always_shown: bool,
     ) {
-        let (centre_point, is_centre_within_bounds) = sg_geometry::project_point(centre_vector, self.zoom, self.viewport_rect);
+        let (centre_point, is_centre_within_bounds) = sg_geometry::project_point(centre_vector, self.zoom, self.viewport_rect, self.sky_settings.projection);
         if !is_centre_within_bounds {
             return;
         }
         let size = if let Some(other_point_vec) = other_vector {
-            let (other_point, _) = sg_geometry::project_point(other_point_vec, self.zoom, self.viewport_rect);
+            let (other_point, _) = sg_geometry::project_point(other_point_vec, self.zoom, self.viewport_rect, self.sky_settings.projection);
             let vec_to = other_point - centre_point;
             vec_to.length()
         } else if let Some(pixel_size) = pixel_size {
@@ -149,19 +240,8 @@ impl CellestialSphere {
         } else {
             return;
         };
-        if circle {
-            painter.circle(centre_point, size, Color32::TRANSPARENT, egui::Stroke::new(width, colour));
-        } else {
-            painter.line_segment(
-                [egui::pos2(centre_point.x, centre_point.y - size), egui::pos2(centre_point.x, centre_point.y + size)],
-                egui::Stroke::new(width, colour),
-            );
-            painter.line_segment(
-                [egui::pos2(centre_point.x - size, centre_point.y), egui::pos2(centre_point.x + size, centre_point.y)],
-                egui::Stroke::new(width, colour),
-            );
-        }
-        if self.sky_settings.render_labels {
+        shape.paint(painter, centre_point, size, egui::Stroke::new(width, colour));
+        if self.sky_settings.render_labels || label_always_shown {
             if let Some(text) = label {
                 _ = painter.text(
                     egui::pos2(centre_point.x + size + 0.1, centre_point.y + size + 0.1),
@@ -181,11 +261,19 @@ impl CellestialSphere {
                 line_renderer.render(self, painter);
             }
         }
+        for shape_renderers in self.shape_renderers.values() {
+            for shape_renderer in shape_renderers {
+                shape_renderer.render(self, painter);
+            }
+        }
         for star_renderers in self.star_renderers.values() {
             for star_renderer in star_renderers {
                 star_renderer.render(painter);
             }
         }
+        for star_renderer in &self.tiled_star_renderers {
+            star_renderer.render(painter);
+        }
         for deepsky_renderers in self.deepsky_renderers.values() {
             for deepsky_renderer in deepsky_renderers {
                 deepsky_renderer.render(self, painter);
@@ -209,6 +297,11 @@ impl CellestialSphere {
     }
 
     pub fn generate_questions(&self, question_pack: &Vec<(crate::game::questions::QuestionType, Vec<u64>)>) -> Vec<Box<dyn QuestionTrait>> {
+        self.generate_questions_with_rng(question_pack, &mut rand::thread_rng())
+    }
+
+    /// Like `generate_questions`, but shuffling the objects with the given generator, so that a seeded one gives the same questions every time
+    pub fn generate_questions_with_rng(&self, question_pack: &Vec<(crate::game::questions::QuestionType, Vec<u64>)>, rng: &mut impl rand::Rng) -> Vec<Box<dyn QuestionTrait>> {
         use rand::seq::SliceRandom;
 
         let mut questions: Vec<Box<dyn QuestionTrait>> = Vec::new();
@@ -221,8 +314,8 @@ impl CellestialSphere {
                     objects.push(&self.question_objects[i]);
                 }
             }
-            objects.shuffle(&mut rand::thread_rng());
-            match *question_type {
+            objects.shuffle(rng);
+            match question_type {
                 crate::game::questions::QuestionType::AngularSeparation(small_settings) => {
                     for i in (0..objects.len()).step_by(2) {
                         if i + 1 >= objects.len() {
@@ -232,14 +325,65 @@ impl CellestialSphere {
                             state: Default::default(),
                             point1: (objects[i].ra, objects[i].dec),
                             point2: (objects[i + 1].ra, objects[i + 1].dec),
-                            small_settings,
+                            small_settings: small_settings.clone(),
+                        }));
+                    }
+                }
+                crate::game::questions::QuestionType::CompareBrightness(small_settings) => {
+                    // Greedily groups the stars so that every two stars of a group are near each other and differ enough, but not too much, in brightness
+                    let stars_per_question = small_settings.stars_per_question.clamp(2, 3) as usize;
+                    let candidates = objects
+                        .iter()
+                        .filter(|object| matches!(object.object_type, crate::game::ObjectType::Star(_)))
+                        .filter_map(|object| object.mag.map(|mag| (*object, mag + self.magnitude_offset(object.ra, object.dec))))
+                        .collect::<Vec<(&crate::game::QuestionObject, f32)>>();
+                    let compatible = |a: &(&crate::game::QuestionObject, f32), b: &(&crate::game::QuestionObject, f32)| {
+                        let difference = (a.1 - b.1).abs();
+                        let separation = sg_geometry::angular_distance((a.0.ra.to_rad(), a.0.dec.to_rad()), (b.0.ra.to_rad(), b.0.dec.to_rad())).to_deg();
+                        difference >= small_settings.min_magnitude_difference && difference <= small_settings.max_magnitude_difference && separation.0 <= small_settings.max_separation
+                    };
+                    let mut used = vec![false; candidates.len()];
+                    for first in 0..candidates.len() {
+                        if used[first] {
+                            continue;
+                        }
+                        let mut group = vec![first];
+                        for other in first + 1..candidates.len() {
+                            if group.len() == stars_per_question {
+                                break;
+                            }
+                            if !used[other] && group.iter().all(|&member| compatible(&candidates[member], &candidates[other])) {
+                                group.push(other);
+                            }
+                        }
+                        if group.len() < stars_per_question {
+                            continue;
+                        }
+                        for &member in &group {
+                            used[member] = true;
+                        }
+                        questions.push(Box::new(crate::game::questions::compare_brightness::Question {
+                            stars: group
+                                .iter()
+                                .map(|&member| {
+                                    let (object, _) = candidates[member];
+                                    crate::game::questions::compare_brightness::ComparedStar {
+                                        ra: object.ra,
+                                        dec: object.dec,
+                                        mag: object.mag.unwrap_or_default(),
+                                        name: object.display_name(),
+                                    }
+                                })
+                                .collect(),
+                            state: Default::default(),
+                            small_settings: small_settings.clone(),
                         }));
                     }
                 }
                 crate::game::questions::QuestionType::FindThisObject(small_settings) => {
                     for object in objects {
                         let question = crate::game::questions::find_this_object::Question {
-                            small_settings,
+                            small_settings: small_settings.clone(),
                             ra: object.ra,
                             dec: object.dec,
                             state: Default::default(),
@@ -329,7 +473,7 @@ impl CellestialSphere {
                             ra: object.ra,
                             dec: object.dec,
                             state: Default::default(),
-                            small_settings,
+                            small_settings: small_settings.clone(),
                         }));
                     }
                 }
@@ -339,7 +483,7 @@ impl CellestialSphere {
                             ra: object.ra,
                             dec: object.dec,
                             state: Default::default(),
-                            small_settings,
+                            small_settings: small_settings.clone(),
                         }));
                     }
                 }
@@ -351,7 +495,23 @@ impl CellestialSphere {
                                 dec: object.dec,
                                 mag,
                                 state: Default::default(),
-                                small_settings,
+                                small_settings: small_settings.clone(),
+                            }));
+                        }
+                    }
+                }
+                crate::game::questions::QuestionType::GuessTheSeparation(small_settings) => {
+                    for object in objects {
+                        if let Some(double_star) = &object.double_star {
+                            questions.push(Box::new(crate::game::questions::guess_the_separation::Question {
+                                ra: object.ra,
+                                dec: object.dec,
+                                name: object.display_name(),
+                                separation: double_star.separation,
+                                position_angle: double_star.position_angle,
+                                component_magnitudes: double_star.component_magnitudes.clone(),
+                                state: Default::default(),
+                                small_settings: small_settings.clone(),
                             }));
                         }
                     }
@@ -360,14 +520,14 @@ impl CellestialSphere {
                     for object in objects {
                         let mut possible_names = Vec::new();
                         if let Some(designation) = &object.bayer_designation_raw {
-                            let names = super::generate_name_combinations(designation, super::SpecificName::None);
+                            let names = super::designation_name_combinations(designation, &object.constellations_abbreviations);
                             possible_names.extend(names);
                         }
                         if let Some(designation) = object.caldwell_number {
                             possible_names.push(format!("C{designation}"));
                         }
                         if let Some(designation) = &object.flamsteed_designation_raw {
-                            let names = super::generate_name_combinations(designation, super::SpecificName::None);
+                            let names = super::designation_name_combinations(designation, &object.constellations_abbreviations);
                             possible_names.extend(names);
                         }
                         if let Some(designation) = &object.hd_number {
@@ -389,8 +549,9 @@ impl CellestialSphere {
                             let names = super::generate_name_combinations(name, super::SpecificName::None);
                             possible_names.extend(names);
                         }
+                        possible_names.extend(object.proper_names_localised.iter().cloned());
                         let question = crate::game::questions::mark_missing_object::Question {
-                            small_settings,
+                            small_settings: small_settings.clone(),
                             ra: object.ra,
                             dec: object.dec,
                             state: Default::default(),
@@ -418,7 +579,7 @@ impl CellestialSphere {
                         let mut possible_names = Vec::new();
                         if small_settings.accept_bayer {
                             if let Some(designation) = &object.bayer_designation_raw {
-                                let names = super::generate_name_combinations(designation, super::SpecificName::None);
+                                let names = super::designation_name_combinations(designation, &object.constellations_abbreviations);
                                 possible_names.extend(names);
                             }
                         }
@@ -429,7 +590,7 @@ impl CellestialSphere {
                         }
                         if small_settings.accept_flamsteed {
                             if let Some(designation) = &object.flamsteed_designation_raw {
-                                let names = super::generate_name_combinations(designation, super::SpecificName::None);
+                                let names = super::designation_name_combinations(designation, &object.constellations_abbreviations);
                                 possible_names.extend(names);
                             }
                         }
@@ -463,10 +624,11 @@ impl CellestialSphere {
                                 let names = super::generate_name_combinations(name, super::SpecificName::None);
                                 possible_names.extend(names);
                             }
+                            possible_names.extend(object.proper_names_localised.iter().cloned());
                         }
                         if !possible_names.is_empty() {
                             questions.push(Box::new(crate::game::questions::which_object_is_here::Question {
-                                small_settings,
+                                small_settings: small_settings.clone(),
                                 possible_names,
                                 ra: object.ra,
                                 dec: object.dec,
@@ -488,13 +650,63 @@ impl CellestialSphere {
                         }
                     }
                 }
+                crate::game::questions::QuestionType::ConstellationGenitive(small_settings) => {
+                    // One question for each constellation the chosen objects lie in
+                    let mut asked = std::collections::HashSet::new();
+                    for object in objects {
+                        for abbreviation in &object.constellations_abbreviations {
+                            let Some(constellation) = self.constellations.get(&abbreviation.to_lowercase()) else {
+                                continue;
+                            };
+                            if constellation.genitive.is_empty() || !asked.insert(constellation.abbreviation.clone()) {
+                                continue;
+                            }
+                            questions.push(Box::new(crate::game::questions::constellation_genitive::Question {
+                                abbreviation: constellation.abbreviation.clone(),
+                                name_latin: constellation.possible_names[1].clone(),
+                                genitive: constellation.genitive.clone(),
+                                state: Default::default(),
+                                small_settings: small_settings.clone(),
+                            }));
+                        }
+                    }
+                }
+                crate::game::questions::QuestionType::WhichBayerLetter(small_settings) => {
+                    for object in objects {
+                        let Some((letter, superscript)) = object.bayer_designation_raw.as_deref().and_then(crate::game::questions::which_bayer_letter::greek_letter) else {
+                            continue;
+                        };
+                        let Some(constellation) = object
+                            .constellations_abbreviations
+                            .first()
+                            .and_then(|abbreviation| self.constellations.get(&abbreviation.to_lowercase()))
+                        else {
+                            continue;
+                        };
+                        let designation = object.bayer_designation_full.clone().unwrap_or_default();
+                        let star_name = match object.proper_names_full.first() {
+                            Some(_) => format!("{designation} ({})", object.display_name()),
+                            None => designation,
+                        };
+                        questions.push(Box::new(crate::game::questions::which_bayer_letter::Question {
+                            small_settings: small_settings.clone(),
+                            ra: object.ra,
+                            dec: object.dec,
+                            letter,
+                            superscript,
+                            star_name,
+                            constellation_name: constellation.display_name(),
+                            state: Default::default(),
+                        }));
+                    }
+                }
                 crate::game::questions::QuestionType::WhichConstellationIsThisPointIn(small_settings) => {
                     for object in objects {
                         questions.push(Box::new(crate::game::questions::which_constellation_is_point_in::Question {
                             ra: object.ra,
                             dec: object.dec,
                             state: Default::default(),
-                            small_settings,
+                            small_settings: small_settings.clone(),
                         }));
                     }
                 }
@@ -503,7 +715,7 @@ impl CellestialSphere {
                         let mut possible_names = Vec::new();
                         if small_settings.accept_bayer {
                             if let Some(designation) = &object.bayer_designation_raw {
-                                let names = super::generate_name_combinations(designation, super::SpecificName::None);
+                                let names = super::designation_name_combinations(designation, &object.constellations_abbreviations);
                                 possible_names.extend(names);
                             }
                         }
@@ -514,7 +726,7 @@ impl CellestialSphere {
                         }
                         if small_settings.accept_flamsteed {
                             if let Some(designation) = &object.flamsteed_designation_raw {
-                                let names = super::generate_name_combinations(designation, super::SpecificName::None);
+                                let names = super::designation_name_combinations(designation, &object.constellations_abbreviations);
                                 possible_names.extend(names);
                             }
                         }
@@ -548,10 +760,11 @@ impl CellestialSphere {
                                 let names = super::generate_name_combinations(name, super::SpecificName::None);
                                 possible_names.extend(names);
                             }
+                            possible_names.extend(object.proper_names_localised.iter().cloned());
                         }
                         if !possible_names.is_empty() {
                             questions.push(Box::new(crate::game::questions::which_object_is_missing::Question {
-                                small_settings,
+                                small_settings: small_settings.clone(),
                                 possible_names,
                                 ra: object.ra,
                                 dec: object.dec,
@@ -574,6 +787,38 @@ impl CellestialSphere {
                         }
                     }
                 }
+                crate::game::questions::QuestionType::WhichVariableStar(small_settings) => {
+                    for object in objects {
+                        let Some(variable_star) = &object.variable_star else {
+                            continue;
+                        };
+                        let mut possible_names = Vec::new();
+                        for name in &object.proper_names_raw {
+                            possible_names.extend(super::generate_name_combinations(name, super::SpecificName::None));
+                        }
+                        possible_names.extend(object.proper_names_localised.iter().cloned());
+                        if let Some(designation) = &object.bayer_designation_raw {
+                            possible_names.extend(super::designation_name_combinations(designation, &object.constellations_abbreviations));
+                        }
+                        if let Some(designation) = &object.flamsteed_designation_raw {
+                            possible_names.extend(super::designation_name_combinations(designation, &object.constellations_abbreviations));
+                        }
+                        if let Some(designation) = &object.hipparcos_number {
+                            possible_names.push(format!("HIP{designation}"));
+                        }
+                        if !possible_names.is_empty() {
+                            questions.push(Box::new(crate::game::questions::which_variable_star::Question {
+                                small_settings: small_settings.clone(),
+                                possible_names,
+                                ra: object.ra,
+                                dec: object.dec,
+                                variability: variable_star.describe(),
+                                constellation_abbreviation: object.constellations_abbreviations.first().cloned().unwrap_or(String::from("Unknown")),
+                                state: Default::default(),
+                            }));
+                        }
+                    }
+                }
             }
         }
         questions
@@ -679,6 +924,7 @@ impl CellestialSphere {
             ["lines", LINES_FOLDER],
             ["markers", MARKERS_FOLDER],
             ["star names", STAR_NAMES_FOLDER],
+            ["shapes", SHAPES_FOLDER],
         ];
 
         #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
@@ -744,6 +990,10 @@ impl CellestialSphere {
                 #[allow(clippy::single_char_pattern)] // No idea why, but `"\""` works while `'"'` does not
                 other_sky_data.push([String::from("constellation names"), file_content.replace("\"", "\\\"")])
             };
+            if let Ok(file_content) = fs::read_to_string(BRIGHT_STARS) {
+                #[allow(clippy::single_char_pattern)] // No idea why, but `"\""` works while `'"'` does not
+                other_sky_data.push([String::from("bright stars"), file_content.replace("\"", "\\\"")])
+            };
             other_sky_data
         };
         #[cfg(any(target_os = "android", target_os = "ios"))]
@@ -757,6 +1007,26 @@ impl CellestialSphere {
             .map(|[file_name, file_content]| [file_name, file_content.replace("\\\"", "\"")])
             .collect::<Vec<[String; 2]>>();
 
+        Self::from_sky_data(storage, theme, object_images, sky_data_lists, sky_data_files)
+    }
+
+    /// Builds the sphere from the contents of the data files, which are given as [file name, file contents] pairs for each kind of data (like "sky objects" or "lines") and for the single files ("constellation names" and "bright stars")
+    pub fn from_sky_data(
+        storage: Option<&dyn eframe::Storage>,
+        theme: &mut Theme,
+        object_images: Option<HashMap<u64, Vec<crate::structs::image_info::DeepskyObjectImageInfo>>>,
+        sky_data_lists: Vec<(&str, Vec<[String; 2]>)>,
+        sky_data_files: Vec<[String; 2]>,
+    ) -> Result<Self, Box<dyn Error>> {
+        // The files are read in the order of their names everywhere, so that it is always the same object which is kept when more files contain it
+        let sky_data_lists = sky_data_lists
+            .into_iter()
+            .map(|(id, mut list)| {
+                list.sort();
+                (id, list)
+            })
+            .collect::<Vec<(&str, Vec<[String; 2]>)>>();
+
         let mut sky_settings = sky_settings::SkySettings::from_raw(&sky_settings::SkySettingsRaw::default());
         if let Some(storage) = storage {
             if let Some(sky_settings_raw_str) = storage.get_string(StorageKeys::SkySettings.as_ref()) {
@@ -769,11 +1039,26 @@ impl CellestialSphere {
 
         let star_color = egui::epaint::Color32::WHITE;
         let mut catalog: HashMap<String, Vec<Star>> = HashMap::new();
+        // The object ids of the stars loaded from the rows with the double or variable star data
+        let mut star_data_ids: std::collections::HashSet<u64> = std::collections::HashSet::new();
+
+        // The tiled catalogues are read from the disk as needed, which is not possible with the embedded data
+        #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+        let mut tiled_stars = super::tiled_stars::open_catalogues(TILED_STARS_FOLDER);
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        let mut tiled_stars: Vec<TiledStarCatalogue> = Vec::new();
+        for catalogue in &mut tiled_stars {
+            if theme.game_visuals.use_overriden_star_colour {
+                catalogue.override_colour = Some(theme.game_visuals.override_star_colour);
+            }
+            sky_settings.stars_categories_active.entry(catalogue.name.clone()).or_insert(true);
+        }
 
         let mut lines: HashMap<String, SkyLines> = HashMap::new();
 
         let mut deepskies: HashMap<String, Deepskies> = HashMap::new();
-        let objects_images = object_images.unwrap_or(std::collections::HashMap::new());
+        let mut shapes: HashMap<String, SkyShapes> = HashMap::new();
+        let objects_images = object_images.unwrap_or_default();
 
         let mut star_names: HashMap<String, Vec<StarName>> = HashMap::new();
 
@@ -823,6 +1108,8 @@ impl CellestialSphere {
                 } else {
                     None
                 };
+                // Larger catalogues (like NGC/IC) repeat objects from the smaller ones under the same object ID, these are loaded only once
+                let mut loaded_objects: HashMap<u64, (&str, SkyObjectIdentity)> = HashMap::new();
                 for [file_name, file_contents] in &data {
                     let mut reader = csv::ReaderBuilder::new().delimiter(b',').from_reader(file_contents.as_bytes());
                     let mut deepskies_colour = None;
@@ -833,6 +1120,18 @@ impl CellestialSphere {
                             continue;
                         }
                         let object_raw: QuestionObjectRaw = object_raw?;
+                        if let Some((loaded_file_name, loaded)) = loaded_objects.get(&object_raw.object_id) {
+                            if loaded.matches(&SkyObjectIdentity::of(&object_raw)) {
+                                log::debug!("Skipping the object with id {} from file {file_name}, as it has already been loaded", object_raw.object_id);
+                            } else {
+                                log::warn!(
+                                    "Skipping the object with id {} from file {file_name}, as an object with the same id but a different position or designation has already been loaded from {loaded_file_name}",
+                                    object_raw.object_id
+                                );
+                            }
+                            continue;
+                        }
+                        loaded_objects.insert(object_raw.object_id, (file_name.as_str(), SkyObjectIdentity::of(&object_raw)));
                         let names = object_raw.proper_names.clone();
                         let constellations = object_raw.constellations_abbreviations.clone();
                         let object_id = object_raw.object_id;
@@ -866,6 +1165,9 @@ impl CellestialSphere {
                                     constellations,
                                 };
                                 let star = Star::from_raw(star_raw, star_color, override_star_colour);
+                                if object.double_star.is_some() || object.variable_star.is_some() {
+                                    star_data_ids.insert(star.object_id);
+                                }
                                 let entry = catalog.entry(file_name.clone()).or_default();
                                 entry.push(star);
                                 if !sky_settings.stars_categories_active.contains_key(file_name) {
@@ -919,6 +1221,37 @@ impl CellestialSphere {
                         theme.game_visuals.deepskies_colours.insert(file_name.clone(), deepskies_colour);
                     }
                 }
+            } else if id == "shapes" {
+                for [file_name, file_contents] in &data {
+                    let mut reader = csv::ReaderBuilder::new().delimiter(b',').from_reader(file_contents.as_bytes());
+                    let mut shapes_raw = Vec::new();
+                    for shape_raw in reader.deserialize() {
+                        let shape_raw: SkyShapeRaw = shape_raw?;
+                        shapes_raw.push(shape_raw);
+                    }
+                    let (shapes_vec, shapes_colour) = SkyShape::from_raw(shapes_raw);
+                    // Try to get the colour from the theme, then if the theme does not handle these shapes, try to use the colour found in the shapes declaration file. Only if that does not exist, use the default colour.
+                    let shapes_colour = theme
+                        .game_visuals
+                        .shapes_colours
+                        .get(file_name)
+                        .cloned()
+                        .unwrap_or(shapes_colour.unwrap_or(theme.game_visuals.default_colour));
+                    shapes.insert(
+                        file_name.clone(),
+                        SkyShapes {
+                            colour: shapes_colour,
+                            active: *sky_settings.shapes_categories_active.get(file_name).unwrap_or(&true),
+                            shapes: shapes_vec,
+                        },
+                    );
+                    if !sky_settings.shapes_categories_active.contains_key(file_name) {
+                        sky_settings.shapes_categories_active.insert(file_name.clone(), true);
+                    }
+                    if !theme.game_visuals.shapes_colours.contains_key(file_name) {
+                        theme.game_visuals.shapes_colours.insert(file_name.clone(), shapes_colour);
+                    }
+                }
             } else if id == "star names" {
                 //TODO: Add linking between stars and their names
                 for [file_name, file_contents] in &data {
@@ -975,9 +1308,9 @@ impl CellestialSphere {
                 }
             }
         }
-        question_objects.sort_by_key(|k| k.object_id);
 
         let mut constellations = HashMap::new();
+        let mut bright_stars = Vec::new();
         for [id, file_contents] in sky_data_files {
             let mut reader = csv::ReaderBuilder::new().delimiter(b',').from_reader(file_contents.as_bytes());
             if id == "constellation names" {
@@ -986,8 +1319,92 @@ impl CellestialSphere {
                     let (constellation, abbreviation) = Constellation::from_raw(constellation_raw)?;
                     constellations.insert(abbreviation.to_lowercase(), constellation);
                 }
+            } else if id == "bright stars" {
+                for bright_star_raw in reader.deserialize() {
+                    let bright_star_raw: StarRaw = bright_star_raw?;
+                    bright_stars.extend(bright_star_raw.constellations.split(';').map(|abbreviation| (abbreviation.to_owned(), bright_star_raw.vmag)));
+                }
+            }
+        }
+
+        // The named stars with a Bayer or Flamsteed designation can be asked about as well, unless a sky objects file already contains them
+        let mut loaded_object_ids: std::collections::HashSet<u64> = question_objects.iter().map(|object| object.object_id).collect();
+        for star_name in star_names.values().flatten() {
+            let Some(constellation) = constellations.get(&star_name.con.to_lowercase()) else {
+                log::warn!("Unknown constellation {} of the star {}", star_name.con, star_name.name);
+                continue;
+            };
+            let Some(object_raw) = star_name.to_question_object_raw(&constellation.genitive) else {
+                continue;
+            };
+            if !loaded_object_ids.insert(object_raw.object_id) {
+                continue;
             }
+            let images = objects_images
+                .get(&object_raw.object_id)
+                .cloned()
+                .unwrap_or_default()
+                .iter()
+                .map(|image_data| crate::structs::image_info::ImageInfo {
+                    path: image_data.image.clone(),
+                    source: image_data.image_source.clone(),
+                })
+                .collect();
+            question_objects.push(QuestionObject::from_raw(object_raw, images));
+        }
+        if bright_stars.is_empty() {
+            log::warn!("The catalogue of bright stars could not be loaded, the stars are only ranked by their brightness among the question objects");
+            bright_stars = question_objects
+                .iter()
+                .filter(|object| matches!(object.object_type, crate::game::ObjectType::Star(_)))
+                .filter_map(|object| Some((object.constellations_abbreviations.first()?.clone(), object.mag?)))
+                .collect();
         }
+        crate::game::assign_brightness_ranks(&mut question_objects, &bright_stars);
+        question_objects.sort_by_key(|k| k.object_id);
+        let object_index = CubeMapIndex::from_points(
+            sg_geometry::spatial_index::DEFAULT_RESOLUTION,
+            question_objects
+                .iter()
+                .enumerate()
+                .map(|(i, object)| (sg_geometry::get_point_vector(object.ra, object.dec, &Matrix3::identity()), i)),
+        );
+        remove_star_data_copies(&mut catalog, &star_data_ids);
+        let star_indices = catalog
+            .iter()
+            .map(|(name, stars)| {
+                let index = CubeMapIndex::from_points(
+                    sg_geometry::spatial_index::DEFAULT_RESOLUTION,
+                    stars.iter().enumerate().map(|(i, star)| (sg_geometry::get_point_vector(star.ra, star.dec, &Matrix3::identity()), i)),
+                );
+                (name.clone(), index)
+            })
+            .collect();
+        let deepsky_indices = deepskies
+            .iter()
+            .map(|(name, deepskies)| {
+                let index = CubeMapIndex::from_points(
+                    sg_geometry::spatial_index::DEFAULT_RESOLUTION,
+                    deepskies
+                        .deepskies
+                        .iter()
+                        .enumerate()
+                        .map(|(i, deepsky)| (sg_geometry::get_point_vector(deepsky.ra, deepsky.dec, &Matrix3::identity()), i)),
+                );
+                (name.clone(), index)
+            })
+            .collect();
+
+        let line_indices = lines
+            .iter()
+            .map(|(name, lines)| {
+                let index = CubeMapIndex::from_points(sg_geometry::spatial_index::DEFAULT_RESOLUTION, lines.lines.iter().enumerate().map(|(i, line)| (line.midpoint(), i)));
+                let max_half_length = lines.lines.iter().map(|line| line.half_length().value()).fold(0.0, f32::max);
+                (name.clone(), (index, angle::Rad(max_half_length)))
+            })
+            .collect();
+
+        let search_index = SearchIndex::new(&question_objects, &constellations);
 
         let mut light_pollution_place_to_mag: HashMap<LightPollution, [Option<stars::MagnitudeToRadius>; stars::MAGNITUDE_TO_RADIUS_OPTIONS]> =
             HashMap::with_capacity(MAG_TO_LIGHT_POLLUTION_RAW.len());
@@ -999,23 +1416,43 @@ impl CellestialSphere {
 
         let viewport_rect = egui::Rect::from_two_pos(egui::pos2(0.0, 0.0), egui::pos2(0.0, 0.0));
         let zoom = 3.0_f32.sqrt();
-        let fov = Self::zoom_to_fov(zoom);
+        let fov = sky_settings.projection.zoom_to_fov(zoom);
         Ok(Self {
             sky_settings,
             stars: catalog,
+            tiled_stars,
             lines,
             deepskies,
+            shapes,
             markers,
             question_objects,
-            game_markers: GameMarkers { active: true, markers: Vec::new() },
+            game_markers: GameMarkers {
+                active: true,
+                markers: Vec::new(),
+                hints: Vec::new(),
+                hint_lines: Vec::new(),
+                hint_lines_colour: theme.game_visuals.game_markers_colours.hint,
+                selection: None,
+                selection_lines: Vec::new(),
+                selection_lines_colour: theme.game_visuals.game_markers_colours.selection,
+            },
             star_names,
             constellations,
+            search_index,
             zoom,
             fov,
             camera_z: Self::fov_to_camera_z(fov),
+            star_indices,
+            deepsky_indices,
+            line_indices,
+            disabled_objects: std::collections::HashSet::new(),
+            disabled_stars: HashMap::new(),
+            object_index,
             star_renderers: HashMap::new(),
+            tiled_star_renderers: Vec::new(),
             line_renderers: HashMap::new(),
             deepsky_renderers: HashMap::new(),
+            shape_renderers: HashMap::new(),
             marker_renderers: HashMap::new(),
 
             light_pollution_place,
@@ -1024,6 +1461,7 @@ impl CellestialSphere {
             viewport_rect,
 
             rotation: Rotation3::new(Vector3::new(0.0, 0.0, 0.0)),
+            camera_transition: None,
         })
     }
 
@@ -1036,20 +1474,32 @@ impl CellestialSphere {
         let future_zoom = self.zoom + velocity * self.zoom;
         //A check is needed since negative zoom breaks everything
         if ZOOM_CAP > future_zoom && future_zoom > 0.0 {
-            self.zoom = future_zoom;
-            self.fov = Self::zoom_to_fov(self.zoom);
-            self.camera_z = Self::fov_to_camera_z(self.fov);
+            self.set_zoom(future_zoom);
             return true;
         }
         false
     }
 
+    fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom;
+        self.fov = self.sky_settings.projection.zoom_to_fov(self.zoom);
+        self.camera_z = Self::fov_to_camera_z(self.fov);
+    }
+
+    /// Switches to another projection, keeping the zoom
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.sky_settings.projection = projection;
+        self.set_zoom(self.zoom);
+        self.init_renderers();
+    }
+
     pub fn get_zoom(&self) -> f32 {
         self.zoom
     }
 
-    pub fn zoom_to_fov(zoom: f32) -> f32 {
-        4.0 * (1.0 / zoom).atan() / PI * 180.0
+    /// The zoom giving the FOV (in degrees) in the current projection
+    pub fn fov_to_zoom(&self, fov_deg: f32) -> f32 {
+        self.sky_settings.projection.fov_to_zoom(fov_deg)
     }
 
     pub fn fov_to_camera_z(fov_deg: f32) -> f32 {
@@ -1062,48 +1512,23 @@ impl CellestialSphere {
         if self.sky_settings.cloud_settings.enabled {
             crate::rendering::caspr::clouds::apply_dimming(&mut self.stars, &self.sky_settings.cloud_settings);
         }
+        self.constrain_rotation(None);
         self.init_renderers();
     }
 
-    /// Preserves disabled renderers - will reinitialise them, but will also keep them disabled
+    /// Disabled objects stay disabled
     pub fn init_renderers(&mut self) {
-        {
-            let mut old_renderers = HashMap::new();
-            std::mem::swap(&mut self.star_renderers, &mut old_renderers);
-            let mut active_star_groups = Vec::new();
-            let mut all_disabled_renderers = std::collections::HashMap::new();
-            for name in self.stars.keys() {
-                let active = self.sky_settings.stars_categories_active.entry(name.to_owned()).or_insert(true);
-                if !*active {
-                    continue;
-                }
-                active_star_groups.push(name.to_owned());
-
-                let mut disabled_renderers = std::collections::HashSet::new();
-                if let Some(renderers) = old_renderers.get(name) {
-                    for renderer in renderers {
-                        if renderer.disabled {
-                            disabled_renderers.insert(renderer.object_id);
-                        }
-                    }
-                }
-                if !disabled_renderers.is_empty() {
-                    all_disabled_renderers.insert(name.to_owned(), disabled_renderers);
-                }
-            }
-            for name in active_star_groups {
-                self.init_single_renderer_group(RendererCategory::Stars, &name);
-                if let Some(disabled_renderers) = all_disabled_renderers.get(&name) {
-                    if let Some(renderers) = self.star_renderers.get_mut(&name) {
-                        for renderer in renderers {
-                            if disabled_renderers.contains(&renderer.object_id) {
-                                renderer.disabled = true;
-                            }
-                        }
-                    }
-                }
-            }
+        self.star_renderers = HashMap::new();
+        let active_star_groups: Vec<String> = self
+            .stars
+            .keys()
+            .filter(|name| *self.sky_settings.stars_categories_active.entry(name.to_string()).or_insert(true))
+            .cloned()
+            .collect();
+        for name in active_star_groups {
+            self.init_single_renderer_group(RendererCategory::Stars, &name);
         }
+        self.init_tiled_star_renderers();
 
         self.line_renderers = HashMap::new();
         let mut active_line_groups = Vec::new();
@@ -1117,42 +1542,20 @@ impl CellestialSphere {
             self.init_single_renderer_group(RendererCategory::Lines, &name);
         }
 
-        {
-            let mut old_renderers = HashMap::new();
-            std::mem::swap(&mut self.deepsky_renderers, &mut old_renderers);
-            let mut active_deepsky_groups = Vec::new();
-            let mut all_disabled_renderers = std::collections::HashMap::new();
-            for name in self.stars.keys() {
-                let active = self.sky_settings.deepskies_categories_active.entry(name.to_owned()).or_insert(true);
-                if !*active {
-                    continue;
-                }
-                active_deepsky_groups.push(name.to_owned());
+        self.deepsky_renderers = HashMap::new();
+        let active_deepsky_groups: Vec<String> = self
+            .stars
+            .keys()
+            .filter(|name| *self.sky_settings.deepskies_categories_active.entry(name.to_string()).or_insert(true))
+            .cloned()
+            .collect();
+        for name in active_deepsky_groups {
+            self.init_single_renderer_group(RendererCategory::Deepskies, &name);
+        }
 
-                let mut disabled_renderers = std::collections::HashSet::new();
-                if let Some(renderers) = old_renderers.get(name) {
-                    for renderer in renderers {
-                        if renderer.disabled {
-                            disabled_renderers.insert(renderer.object_id);
-                        }
-                    }
-                }
-                if !disabled_renderers.is_empty() {
-                    all_disabled_renderers.insert(name.to_owned(), disabled_renderers);
-                }
-            }
-            for name in active_deepsky_groups {
-                self.init_single_renderer_group(RendererCategory::Deepskies, &name);
-                if let Some(disabled_renderers) = all_disabled_renderers.get(&name) {
-                    if let Some(renderers) = self.deepsky_renderers.get_mut(&name) {
-                        for renderer in renderers {
-                            if disabled_renderers.contains(&renderer.object_id) {
-                                renderer.disabled = true;
-                            }
-                        }
-                    }
-                }
-            }
+        let active_shape_groups: Vec<String> = self.shapes.iter().filter(|(_, shapes)| shapes.active).map(|(name, _)| name.to_owned()).collect();
+        for name in active_shape_groups {
+            self.init_single_renderer_group(RendererCategory::Shapes, &name);
         }
 
         self.marker_renderers = HashMap::new();
@@ -1175,43 +1578,21 @@ impl CellestialSphere {
         }
     }
 
-    /// Preserves disabled renderers - will reinitialise them, but will also keep them disabled
+    /// Disabled objects stay disabled
     pub fn reinit_renderer_category(&mut self, category: RendererCategory) {
         match category {
             RendererCategory::Stars => {
-                let mut old_renderers = HashMap::new();
-                std::mem::swap(&mut self.star_renderers, &mut old_renderers);
-                let mut active_star_groups = Vec::new();
-                let mut all_disabled_renderers = std::collections::HashMap::new();
-                for name in self.stars.keys() {
-                    let active = self.sky_settings.stars_categories_active.entry(name.to_owned()).or_insert(true);
-                    if !*active {
-                        continue;
-                    }
-                    active_star_groups.push(name.to_owned());
-
-                    let mut disabled_renderers = std::collections::HashSet::new();
-                    if let Some(renderers) = old_renderers.get(name) {
-                        for renderer in renderers {
-                            if renderer.disabled {
-                                disabled_renderers.insert(renderer.object_id);
-                            }
-                        }
-                    }
-                    all_disabled_renderers.insert(name.to_owned(), disabled_renderers);
-                }
+                self.star_renderers = HashMap::new();
+                let active_star_groups: Vec<String> = self
+                    .stars
+                    .keys()
+                    .filter(|name| *self.sky_settings.stars_categories_active.entry(name.to_string()).or_insert(true))
+                    .cloned()
+                    .collect();
                 for name in active_star_groups {
                     self.init_single_renderer_group(RendererCategory::Stars, &name);
-                    if let Some(disabled_renderers) = all_disabled_renderers.get(&name) {
-                        if let Some(renderers) = self.star_renderers.get_mut(&name) {
-                            for renderer in renderers {
-                                if disabled_renderers.contains(&renderer.object_id) {
-                                    renderer.disabled = true;
-                                }
-                            }
-                        }
-                    }
                 }
+                self.init_tiled_star_renderers();
             }
             RendererCategory::Lines => {
                 self.line_renderers = HashMap::new();
@@ -1225,42 +1606,27 @@ impl CellestialSphere {
                 for name in active_line_groups {
                     self.init_single_renderer_group(RendererCategory::Lines, &name);
                 }
+                // The hint lines are a part of the game markers group
+                if self.game_markers.active {
+                    self.init_single_renderer_group(RendererCategory::Markers, "game");
+                }
             }
             RendererCategory::Deepskies => {
-                let mut old_renderers = HashMap::new();
-                std::mem::swap(&mut self.deepsky_renderers, &mut old_renderers);
-                let mut active_deepsky_groups = Vec::new();
-                let mut all_disabled_renderers = std::collections::HashMap::new();
-                for name in self.stars.keys() {
-                    let active = self.sky_settings.deepskies_categories_active.entry(name.to_owned()).or_insert(true);
-                    if !*active {
-                        continue;
-                    }
-                    active_deepsky_groups.push(name.to_owned());
-
-                    let mut disabled_renderers = std::collections::HashSet::new();
-                    if let Some(renderers) = old_renderers.get(name) {
-                        for renderer in renderers {
-                            if renderer.disabled {
-                                disabled_renderers.insert(renderer.object_id);
-                            }
-                        }
-                    }
-                    if !disabled_renderers.is_empty() {
-                        all_disabled_renderers.insert(name.to_owned(), disabled_renderers);
-                    }
-                }
+                self.deepsky_renderers = HashMap::new();
+                let active_deepsky_groups: Vec<String> = self
+                    .stars
+                    .keys()
+                    .filter(|name| *self.sky_settings.deepskies_categories_active.entry(name.to_string()).or_insert(true))
+                    .cloned()
+                    .collect();
                 for name in active_deepsky_groups {
                     self.init_single_renderer_group(RendererCategory::Deepskies, &name);
-                    if let Some(disabled_renderers) = all_disabled_renderers.get(&name) {
-                        if let Some(renderers) = self.deepsky_renderers.get_mut(&name) {
-                            for renderer in renderers {
-                                if disabled_renderers.contains(&renderer.object_id) {
-                                    renderer.disabled = true;
-                                }
-                            }
-                        }
-                    }
+                }
+            }
+            RendererCategory::Shapes => {
+                let active_shape_groups: Vec<String> = self.shapes.iter().filter(|(_, shapes)| shapes.active).map(|(name, _)| name.to_owned()).collect();
+                for name in active_shape_groups {
+                    self.init_single_renderer_group(RendererCategory::Shapes, &name);
                 }
             }
             RendererCategory::Markers => {
@@ -1286,22 +1652,221 @@ impl CellestialSphere {
         }
     }
 
+    /// Moves the reading of the tiled catalogues to their own thread, the returned receiver gets the loaded tiles, which are then passed to `add_loaded_tiles`. None if there are no tiled catalogues.
+    pub fn load_tiles_in_background(&mut self) -> Option<Receiver<LoadedTile>> {
+        if self.tiled_stars.is_empty() {
+            return None;
+        }
+        let (loader, receiver) = super::tiled_stars::spawn_loader();
+        for catalogue in &mut self.tiled_stars {
+            catalogue.load_in_background(loader.clone());
+        }
+        Some(receiver)
+    }
+
+    /// Adds the stars read in the background to their catalogues and draws them
+    pub fn add_loaded_tiles(&mut self, tiles: impl Iterator<Item = LoadedTile>) {
+        let mut added = false;
+        for tile in tiles {
+            if let Some(catalogue) = self.tiled_stars.iter_mut().find(|catalogue| catalogue.name == tile.catalogue) {
+                catalogue.add_loaded_tile(tile);
+                added = true;
+            }
+        }
+        if added {
+            self.init_tiled_star_renderers();
+        }
+    }
+
+    /// Only the tiles in view are looked at, and only their stars bright enough for the current FOV, so this stays cheap even for huge catalogues
+    pub fn init_tiled_star_renderers(&mut self) {
+        self.tiled_star_renderers.clear();
+        let Some(limiting_magnitude) = self.sky_settings.tiled_stars_settings.limiting_magnitude(self.fov) else {
+            return;
+        };
+        let Some((centre, radius)) = self.view_circle() else {
+            return;
+        };
+        let (centre_dec, centre_ra) = sg_geometry::cartesian_to_spherical(centre);
+        let radius = radius.to_deg();
+        let magnitude_to_radius = self.sky_settings.mag_to_radius_settings[self.sky_settings.mag_to_radius_id];
+        // The stars of the loaded catalogues are drawn instead of their copies in the tiled ones
+        let loaded_star_indices: Vec<&CubeMapIndex<usize>> = self
+            .star_indices
+            .iter()
+            .filter(|(name, _)| *self.sky_settings.stars_categories_active.get(*name).unwrap_or(&true))
+            .map(|(_, index)| index)
+            .collect();
+        for catalogue in &mut self.tiled_stars {
+            if !*self.sky_settings.stars_categories_active.get(&catalogue.name).unwrap_or(&true) {
+                continue;
+            }
+            let override_colour = catalogue.override_colour;
+            for star in catalogue.stars_in_view((centre_ra.to_deg(), centre_dec.to_deg()), radius, limiting_magnitude) {
+                let point = sg_geometry::get_point_vector(star.ra, star.dec, &Matrix3::identity());
+                if loaded_star_indices.iter().any(|index| index.nearest(&point, DRAWN_STAR_TOLERANCE.to_rad()).is_some()) {
+                    continue;
+                }
+                if let Some(renderer) = star.get_renderer(
+                    self.rotation.matrix(),
+                    magnitude_to_radius,
+                    angle::Deg(self.fov),
+                    self.zoom,
+                    self.viewport_rect,
+                    self.sky_settings.projection,
+                    override_colour,
+                ) {
+                    self.tiled_star_renderers.push(renderer);
+                }
+            }
+        }
+    }
+
+    /// The circle on the sphere (without rotations) containing the whole screen, given by its centre and radius. None if the viewport is not known yet.
+    pub fn view_circle(&self) -> Option<(Vector3<f32>, angle::Rad<f32>)> {
+        let rect_size = self.viewport_rect.size();
+        if rect_size.x <= 0.0 || rect_size.y <= 0.0 {
+            return None;
+        }
+        let centre = self.project_screen_pos(egui::pos2(rect_size.x / 2.0, rect_size.y / 2.0)).normalize();
+        // In the azimuthal projections the corners are the furthest from the centre, but in the others it can be any point of the edge, so the whole edge is walked along
+        let mut radius: f32 = 0.0;
+        for i in 0..=VIEW_CIRCLE_EDGE_SAMPLES {
+            let t = i as f32 / VIEW_CIRCLE_EDGE_SAMPLES as f32;
+            for point in [
+                egui::pos2(t * rect_size.x, 0.0),
+                egui::pos2(t * rect_size.x, rect_size.y),
+                egui::pos2(0.0, t * rect_size.y),
+                egui::pos2(rect_size.x, t * rect_size.y),
+            ] {
+                // When a point of the edge is outside of the projected sphere, the whole sky may be visible
+                let edge = sg_geometry::try_cast_onto_sphere(&self.viewport_rect, &point, self.rotation, self.zoom, self.sky_settings.projection)?.normalize();
+                radius = radius.max(centre.dot(&edge).clamp(-1.0, 1.0).acos());
+            }
+        }
+        let radius = radius + CULLING_MARGIN.to_rad().value();
+        if radius.is_nan() {
+            return None;
+        }
+        Some((centre, angle::Rad(radius)))
+    }
+
+    /// The faintest magnitude of the deepsky objects drawn at the current FOV - it is the set limit when zoomed out, and it grows as the view gets narrower
+    pub fn deepsky_magnitude_limit(&self) -> f32 {
+        self.sky_settings.deepsky_magnitude_limit + 5.0 * (DEEPSKY_MAGNITUDE_LIMIT_FOV / self.fov).log10().max(0.0)
+    }
+
+    /// How much the clouds dim the star drawn at the given position. The star is looked up by its position, as the question objects (like the named stars) do not share their object ids with the drawn stars. 0 where no star from the loaded catalogues is drawn, the tiled catalogues are not dimmed.
+    pub fn magnitude_offset(&self, ra: angle::Deg<f32>, dec: angle::Deg<f32>) -> f32 {
+        self.drawn_stars_at(ra, dec)
+            .min_by(|(_, _, distance_1), (_, _, distance_2)| distance_1.0.total_cmp(&distance_2.0))
+            .and_then(|(name, i, _)| self.stars.get(name)?.get(i))
+            .map_or(0.0, |star| star.magnitude_offset)
+    }
+
+    /// The stars drawn at the given position in each of the catalogues, with their catalogue, index and distance from the position
+    fn drawn_stars_at(&self, ra: angle::Deg<f32>, dec: angle::Deg<f32>) -> impl Iterator<Item = (&str, usize, angle::Rad<f32>)> {
+        let point = sg_geometry::get_point_vector(ra, dec, &Matrix3::identity());
+        self.star_indices.iter().filter_map(move |(name, index)| {
+            let (&i, distance) = index.nearest(&point, DRAWN_STAR_TOLERANCE.to_rad())?;
+            Some((name.as_str(), i, distance))
+        })
+    }
+
+    /// Hides or shows the drawn stars at the positions of the question stars with the object id, which is needed as the question stars (like the named stars) do not share their object ids with the drawn stars
+    fn set_drawn_stars_disabled(&mut self, object_id: u64, disabled: bool) {
+        let positions: Vec<(angle::Deg<f32>, angle::Deg<f32>)> = self
+            .question_objects
+            .iter()
+            .filter(|object| object.object_id == object_id && matches!(object.object_type, crate::game::ObjectType::Star(_)))
+            .map(|object| (object.ra, object.dec))
+            .collect();
+        let mut changed = Vec::new();
+        for (ra, dec) in positions {
+            for (name, i, _) in self.drawn_stars_at(ra, dec) {
+                changed.push((name.to_string(), i));
+            }
+        }
+        for (name, i) in &changed {
+            let disabled_stars = self.disabled_stars.entry(name.clone()).or_default();
+            if disabled {
+                disabled_stars.insert(*i);
+            } else {
+                disabled_stars.remove(i);
+            }
+        }
+        let changed_catalogues: std::collections::HashSet<String> = changed.into_iter().map(|(name, _)| name).collect();
+        for name in changed_catalogues {
+            self.init_single_renderer_group(RendererCategory::Stars, &name);
+        }
+    }
+
+    /// Marks which of the objects in the index might be on the screen, everything is marked if there is no index or the view is not known.
+    /// The margin is added to the radius of the view, for the objects which reach further than the indexed point.
+    fn visible_mask(&self, index: Option<&CubeMapIndex<usize>>, margin: angle::Rad<f32>, len: usize) -> Vec<bool> {
+        let (Some(index), Some((centre, radius))) = (index, self.view_circle()) else {
+            return vec![true; len];
+        };
+        let mut visible = vec![false; len];
+        for &(_, i) in index.candidates_within(&centre, angle::Rad(radius.value() + margin.value())) {
+            if let Some(visible) = visible.get_mut(i) {
+                *visible = true;
+            }
+        }
+        visible
+    }
+
+    /// The question objects within the given distance from the point, together with their distances
+    pub fn objects_within(&self, ra: angle::Deg<f32>, dec: angle::Deg<f32>, radius: angle::Deg<f32>) -> Vec<(&QuestionObject, angle::Deg<f32>)> {
+        let centre = sg_geometry::get_point_vector(ra, dec, &Matrix3::identity());
+        let normalised_centre = centre.normalize();
+        self.object_index
+            .within(&centre, radius.to_rad())
+            .filter_map(|(point, i)| Some((self.question_objects.get(*i)?, angle::Rad(normalised_centre.dot(point).clamp(-1.0, 1.0).acos()).to_deg())))
+            .collect()
+    }
+
+    /// The object closest to the screen position which is at most `max_distance` points away from it and passes the filter
+    pub fn object_at_screen_pos(&self, screen_pos: egui::Pos2, max_distance: f32, filter: impl Fn(&QuestionObject) -> bool) -> Option<&QuestionObject> {
+        let point = self.project_screen_pos(screen_pos).normalize();
+        let edge = self.project_screen_pos(screen_pos + egui::vec2(max_distance, 0.0)).normalize();
+        let radius = angle::Rad(point.dot(&edge).clamp(-1.0, 1.0).acos());
+        if radius.value().is_nan() {
+            return None;
+        }
+        self.object_index
+            .within(&point, radius)
+            .filter_map(|(object_point, i)| Some((self.question_objects.get(*i)?, point.dot(object_point))))
+            .filter(|(object, _)| filter(object))
+            .max_by(|(_, cos_1), (_, cos_2)| cos_1.total_cmp(cos_2))
+            .map(|(object, _)| object)
+    }
+
     pub fn init_single_renderer_group(&mut self, category: RendererCategory, name: &str) {
         match category {
             RendererCategory::Stars => {
                 if let Some(stars) = self.stars.get(name) {
+                    // Projecting stars is costly, so only the ones which could be on the screen get a renderer
+                    let visible = self.visible_mask(self.star_indices.get(name), angle::Rad(0.0), stars.len());
+                    let disabled_stars = self.disabled_stars.get(name);
                     self.star_renderers.insert(
                         name.to_string(),
                         stars
                             .iter()
-                            .map(|star| {
-                                star.get_renderer(
+                            .enumerate()
+                            .zip(visible)
+                            .filter(|(_, visible)| *visible)
+                            .map(|((i, star), _)| {
+                                let mut renderer = star.get_renderer(
                                     self.rotation.matrix(),
                                     self.sky_settings.mag_to_radius_settings[self.sky_settings.mag_to_radius_id],
                                     angle::Deg(self.fov),
                                     self.zoom,
                                     self.viewport_rect,
-                                )
+                                    self.sky_settings.projection,
+                                );
+                                renderer.disabled = self.disabled_objects.contains(&star.object_id) || disabled_stars.is_some_and(|disabled_stars| disabled_stars.contains(&i));
+                                renderer
                             })
                             .collect(),
                     );
@@ -1309,30 +1874,55 @@ impl CellestialSphere {
             }
             RendererCategory::Lines => {
                 if let Some(lines) = self.lines.get(name) {
-                    self.line_renderers
-                        .insert(name.to_string(), lines.lines.iter().map(|line| line.get_renderer(self.rotation.matrix(), lines.colour)).collect());
+                    let (index, margin) = match self.line_indices.get(name) {
+                        Some((index, max_half_length)) => (Some(index), *max_half_length),
+                        None => (None, angle::Rad(0.0)),
+                    };
+                    let visible = self.visible_mask(index, margin, lines.lines.len());
+                    self.line_renderers.insert(
+                        name.to_string(),
+                        lines
+                            .lines
+                            .iter()
+                            .zip(visible)
+                            .filter(|(_, visible)| *visible)
+                            .map(|(line, _)| line.get_renderer(self.rotation.matrix(), lines.colour))
+                            .collect(),
+                    );
                 }
             }
             RendererCategory::Deepskies => {
                 if let Some(deepskies) = self.deepskies.get(name) {
-                    let mut disabled_renderers = std::collections::HashSet::new();
-                    if let Some(renderers) = self.deepsky_renderers.get(name) {
-                        for renderer in renderers {
-                            if renderer.disabled {
-                                disabled_renderers.insert(renderer.object_id);
-                            }
-                        }
-                    }
+                    // Big catalogues have too many objects to show all of them at once, so only those on the screen and bright enough for the zoom get a renderer
+                    let visible = self.visible_mask(self.deepsky_indices.get(name), angle::Rad(0.0), deepskies.deepskies.len());
+                    let magnitude_limit = self.deepsky_magnitude_limit();
                     self.deepsky_renderers.insert(
                         name.to_string(),
                         deepskies
                             .deepskies
                             .iter()
-                            .map(|deepsky| {
+                            .zip(visible)
+                            .filter(|(deepsky, visible)| *visible && !deepsky.magnitude().is_some_and(|mag| mag - self.sky_settings.deepsky_render_mag_decrease > magnitude_limit))
+                            .map(|(deepsky, _)| {
                                 let mut renderer = deepsky.get_renderer(self.rotation.matrix(), deepskies.colour);
-                                if disabled_renderers.contains(&deepsky.object_id) {
-                                    renderer.disabled = true;
-                                }
+                                renderer.disabled = self.disabled_objects.contains(&deepsky.object_id);
+                                renderer
+                            })
+                            .collect(),
+                    );
+                }
+            }
+            RendererCategory::Shapes => {
+                if let Some(shapes) = self.shapes.get(name) {
+                    // The shapes of hidden objects stay hidden
+                    self.shape_renderers.insert(
+                        name.to_string(),
+                        shapes
+                            .shapes
+                            .iter()
+                            .map(|shape| {
+                                let mut renderer = shape.get_renderer(self.rotation.matrix(), shapes.colour);
+                                renderer.disabled = shape.object_id.is_some_and(|object_id| self.disabled_objects.contains(&object_id));
                                 renderer
                             })
                             .collect(),
@@ -1343,7 +1933,27 @@ impl CellestialSphere {
                 if name == "game" {
                     self.marker_renderers.insert(
                         name.to_string(),
-                        self.game_markers.markers.iter().filter_map(|marker| marker.get_renderer(self.rotation.matrix())).collect(),
+                        self.game_markers
+                            .hints
+                            .iter()
+                            .chain(self.game_markers.markers.iter())
+                            .chain(self.game_markers.selection.iter())
+                            .filter_map(|marker| marker.get_renderer(self.rotation.matrix()))
+                            .collect(),
+                    );
+                    self.line_renderers.insert(
+                        name.to_string(),
+                        self.game_markers
+                            .hint_lines
+                            .iter()
+                            .map(|line| line.get_renderer(self.rotation.matrix(), self.game_markers.hint_lines_colour))
+                            .chain(
+                                self.game_markers
+                                    .selection_lines
+                                    .iter()
+                                    .map(|line| line.get_renderer(self.rotation.matrix(), self.game_markers.selection_lines_colour)),
+                            )
+                            .collect(),
                     );
                 } else if let Some(markers) = self.markers.get(name) {
                     self.marker_renderers.insert(
@@ -1366,13 +1976,21 @@ impl CellestialSphere {
             RendererCategory::Deepskies => {
                 self.deepsky_renderers.insert(name.to_string(), Vec::new());
             }
+            RendererCategory::Shapes => {
+                self.shape_renderers.insert(name.to_string(), Vec::new());
+            }
             RendererCategory::Markers => {
                 self.marker_renderers.insert(name.to_string(), Vec::new());
+                if name == "game" {
+                    self.line_renderers.insert(name.to_string(), Vec::new());
+                }
             }
         }
     }
 
     pub fn enable_single_renderer(&mut self, object_id: u64) {
+        self.disabled_objects.remove(&object_id);
+        self.set_drawn_stars_disabled(object_id, false);
         for renderer_group in self.star_renderers.values_mut() {
             for renderer in renderer_group {
                 if renderer.object_id == object_id {
@@ -1387,9 +2005,18 @@ impl CellestialSphere {
                 }
             }
         }
+        for renderer_group in self.shape_renderers.values_mut() {
+            for renderer in renderer_group {
+                if renderer.object_id == Some(object_id) {
+                    renderer.disabled = false;
+                }
+            }
+        }
     }
 
     pub fn disable_single_renderer(&mut self, object_id: u64) {
+        self.disabled_objects.insert(object_id);
+        self.set_drawn_stars_disabled(object_id, true);
         for renderer_group in self.star_renderers.values_mut() {
             for renderer in renderer_group {
                 if renderer.object_id == object_id {
@@ -1404,6 +2031,13 @@ impl CellestialSphere {
                 }
             }
         }
+        for renderer_group in self.shape_renderers.values_mut() {
+            for renderer in renderer_group {
+                if renderer.object_id == Some(object_id) {
+                    renderer.disabled = true;
+                }
+            }
+        }
     }
 
     /*pub fn mag_to_radius(&self, vmag: f32) -> f32 {
@@ -1416,7 +2050,34 @@ impl CellestialSphere {
     }*/
 
     pub fn project_screen_pos(&self, screen_pos: egui::Pos2) -> Vector3<f32> {
-        sg_geometry::cast_onto_sphere(&self.viewport_rect, &screen_pos, self.rotation, self.get_zoom())
+        sg_geometry::cast_onto_sphere(&self.viewport_rect, &screen_pos, self.rotation, self.get_zoom(), self.sky_settings.projection)
+    }
+
+    pub fn screen_pos_to_dec_ra(&self, screen_pos: egui::Pos2) -> [angle::Rad<f32>; 2] {
+        sg_geometry::cast_onto_sphere_dec_ra(&self.viewport_rect, &screen_pos, self.rotation, self.get_zoom(), self.sky_settings.projection)
+    }
+
+    /// Where the point is drawn on the screen, None if it is not on the screen
+    pub fn dec_ra_to_screen_pos(&self, dec: angle::Deg<f32>, ra: angle::Deg<f32>) -> Option<egui::Pos2> {
+        let vector = sg_geometry::get_point_vector(ra, dec, self.rotation.matrix());
+        let (screen_pos, visible) = sg_geometry::project_point(&vector, self.get_zoom(), self.viewport_rect, self.sky_settings.projection);
+        visible.then_some(screen_pos)
+    }
+
+    /// Turns the rendering of the lines from the given file on or off, the same way the checkbox in the sky settings does
+    pub fn toggle_lines(&mut self, name: &str) {
+        let Some(lines) = self.lines.get_mut(name) else {
+            log::warn!("Tried to toggle the nonexistent lines '{name}'");
+            return;
+        };
+        lines.active = !lines.active;
+        let active = lines.active;
+        self.sky_settings.lines_categories_active.insert(name.to_owned(), active);
+        if active {
+            self.init_single_renderer_group(RendererCategory::Lines, name);
+        } else {
+            self.deinit_single_renderer_group(RendererCategory::Lines, name);
+        }
     }
 
     pub fn mag_settings_to_light_pollution_place(
@@ -1467,15 +2128,54 @@ impl CellestialSphere {
             if rotation_matrix.matrix()[0].is_nan() {
                 return None;
             }
+            let previous_rotation = self.rotation;
             self.rotation *= rotation_matrix;
+            self.constrain_rotation(Some(previous_rotation));
         } else {
             return None;
         }
         Some(())
     }
 
+    /// The point (without rotations) in the centre of the screen
+    pub fn view_centre(&self) -> Vector3<f32> {
+        self.rotation.inverse() * Vector3::new(0.0, 0.0, -1.0)
+    }
+
+    pub fn set_camera_mode(&mut self, camera_mode: CameraMode) {
+        self.sky_settings.camera_mode = camera_mode;
+        self.constrain_rotation(None);
+        self.init_renderers();
+    }
+
+    /// In the north up camera mode, turns the view around its centre so that north is up.
+    /// If the rotation moved the view over a pole, it is undone by going back to the previous rotation instead.
+    fn constrain_rotation(&mut self, previous_rotation: Option<Rotation3<f32>>) {
+        if self.sky_settings.camera_mode != CameraMode::NorthUp {
+            return;
+        }
+        if let Some(previous_rotation) = previous_rotation {
+            // With north up, the north celestial pole is never below the centre of the screen, unless the view went over a pole
+            if (self.rotation * Vector3::z()).y > 0.0 {
+                self.rotation = previous_rotation;
+                return;
+            }
+        }
+        if let Some(rotation) = sg_geometry::north_up_rotation(&self.view_centre()) {
+            self.rotation = rotation;
+        }
+    }
+
     /// Rotates the view to look at the point. It has to be taken without rotations.
     pub fn look_at_point(&mut self, point: &Vector3<f32>) -> Option<()> {
+        self.rotation = self.rotation_looking_at(point)?;
+        Some(())
+    }
+
+    fn rotation_looking_at(&self, point: &Vector3<f32>) -> Option<Rotation3<f32>> {
+        if self.sky_settings.camera_mode == CameraMode::NorthUp {
+            return sg_geometry::north_up_rotation(point);
+        }
         let z_axis = Vector3::new(0.0, 0.0, -1.0);
         let y_axis = Vector3::new(0.0, 1.0, 0.0);
         let axis = if point.cross(&z_axis).magnitude_squared() < 0.05 { y_axis } else { z_axis };
@@ -1483,7 +2183,184 @@ impl CellestialSphere {
         if rotation_matrix.matrix()[0].is_nan() {
             return None;
         }
-        self.rotation = rotation_matrix;
+        Some(rotation_matrix)
+    }
+
+    /// Starts smoothly moving the camera so that it ends up looking at the point, optionally also changing the zoom.
+    /// The camera starts moving when the transition is first updated, so the renderers do not have to be reinitialised here.
+    pub fn transition_to_point(&mut self, point: &Vector3<f32>, zoom: Option<f32>) -> Option<()> {
+        let target_rotation = self.rotation_looking_at(point)?;
+        let target_zoom = zoom.unwrap_or(self.zoom).clamp(f32::EPSILON, ZOOM_CAP);
+        self.camera_transition = Some(CameraTransition::new(
+            self.rotation,
+            target_rotation,
+            self.zoom,
+            target_zoom,
+            &self.sky_settings.camera_transition_settings,
+        ));
         Some(())
     }
+
+    /// Moves the camera along the transition in progress, returns if the renderers should be reinitialised
+    pub fn update_camera_transition(&mut self, timestamp_ns: i64) -> bool {
+        let Some(transition) = &mut self.camera_transition else {
+            return false;
+        };
+        let (rotation, zoom, finished) = transition.advance(timestamp_ns);
+        self.rotation = rotation;
+        self.constrain_rotation(None);
+        self.set_zoom(zoom);
+        if finished {
+            self.camera_transition = None;
+        }
+        true
+    }
+
+    /// Stops the transition in progress where it is, for example when the player starts moving the camera
+    pub fn cancel_camera_transition(&mut self) {
+        self.camera_transition = None;
+    }
+
+    /// Rotates the view around the point shown at the position on the screen, positive angles turn the sky clockwise on the screen. The north up camera does not roll.
+    pub fn roll_around(&mut self, screen_pos: egui::Pos2, angle: angle::Rad<f32>) {
+        if self.sky_settings.camera_mode == CameraMode::NorthUp {
+            return;
+        }
+        let axis = self.rotation * self.project_screen_pos(screen_pos);
+        if let Some(axis) = nalgebra::Unit::try_new(axis, f32::EPSILON) {
+            self.rotation = Rotation3::from_axis_angle(&axis, -angle.value()) * self.rotation;
+        }
+    }
+
+    /// Applies a two finger gesture - the sky is moved along with the fingers, zoomed around the centre of the gesture and rolled around it. Returns if star renderers should be reinitialised.
+    pub fn apply_touch_gesture(&mut self, centre: egui::Pos2, translation: egui::Vec2, zoom: f32, rotation: angle::Rad<f32>) -> bool {
+        self.cancel_camera_transition();
+        let initial_vector = self.project_screen_pos(centre - translation);
+        let final_vector = self.project_screen_pos(centre);
+        self.rotate_between_points(&initial_vector, &final_vector);
+
+        // The point under the fingers stays there while zooming
+        let before_zoom = self.project_screen_pos(centre);
+        let reinitialise_stars = self.zoom(zoom - 1.0);
+        let after_zoom = self.project_screen_pos(centre);
+        self.rotate_between_points(&before_zoom, &after_zoom);
+
+        self.roll_around(centre, rotation);
+        reinitialise_stars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "object_id,object_category,object_type,dec,ra,proper_names,bayer_designation,flamsteed_designation,hipparcos_number,hd_number,messier_number,caldwell_number,ngc_number,ic_number,constellations_abbreviations,mag,bv,distance,colour,object_type_text";
+
+    #[test]
+    fn clicking_works_in_every_projection() {
+        let messier = format!("{HEADER}\n42,Deepsky,DiffuseNebula,-5.45,83.85,Great Orion Nebula,,,,,42,,1976,,Ori,4,,1600,6BEEFFFF,Diffuse Nebula\n");
+        let sky_data = vec![("sky objects", vec![["messier-catalogue.csv".to_owned(), messier]])];
+        let mut cellestial_sphere = CellestialSphere::from_sky_data(None, &mut Theme::dark(), None, sky_data, Vec::new()).expect("Failed to load the cellestial sphere");
+        cellestial_sphere.viewport_rect = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(800.0, 600.0));
+        // Off the centre of the screen, where the projections differ the most
+        let point = sg_geometry::get_point_vector(angle::Deg(83.85), angle::Deg(-5.45), &Matrix3::identity());
+        cellestial_sphere
+            .look_at_point(&sg_geometry::get_point_vector(angle::Deg(95.0), angle::Deg(10.0), &Matrix3::identity()))
+            .unwrap();
+        for projection in Projection::variants() {
+            cellestial_sphere.set_projection(projection);
+            let (screen_pos, visible) = sg_geometry::project_point(&(cellestial_sphere.rotation * point), cellestial_sphere.zoom, cellestial_sphere.viewport_rect, projection);
+            assert!(visible, "{projection}");
+            let selected = cellestial_sphere.object_at_screen_pos(screen_pos, 20.0, |object| object.messier_number.is_some());
+            assert_eq!(selected.and_then(|object| object.messier_number), Some(42), "{projection}");
+        }
+    }
+
+    #[test]
+    fn repeated_objects_are_compared_by_position_and_designations() {
+        let crab = SkyObjectIdentity {
+            ra: angle::Deg(83.625),
+            dec: angle::Deg(22.016_667),
+            designations: [Some(1), None, Some(1952), None],
+        };
+        // The same object from a catalogue with more precise positions and without the Messier numbers
+        let ngc_1952 = SkyObjectIdentity {
+            ra: angle::Deg(83.633_08),
+            dec: angle::Deg(22.0145),
+            designations: [None, None, Some(1952), None],
+        };
+        assert!(crab.matches(&ngc_1952));
+        let other_number = SkyObjectIdentity {
+            designations: [None, None, Some(1953), None],
+            ..ngc_1952
+        };
+        assert!(!crab.matches(&other_number));
+        let elsewhere = SkyObjectIdentity { ra: angle::Deg(84.0), ..ngc_1952 };
+        assert!(!crab.matches(&elsewhere));
+    }
+
+    #[test]
+    fn repeated_sky_objects_are_loaded_once() {
+        let messier = format!("{HEADER}\n1,Deepsky,SupernovaRemnant,22.01666667,83.625,Crab Nebula,,,,,1,,1952,,Tau,8.4,,6300,6BEEFFFF,Supernova Remnant\n");
+        // M 1 again under its own object ID, next to an object which is only in this file
+        let ngc_ic = format!(
+            "{HEADER}\n1,Deepsky,SupernovaRemnant,22.01450000,83.63308333,,,,,,1,,1952,,Tau,8.4,,,8FA8C8FF,Supernova Remnant\n200,Deepsky,Galaxy,27.70808333,1.81600000,,,,,,,,1,,Peg,12.93,,,8FA8C8FF,Galaxy\n"
+        );
+        let mut theme = Theme::dark();
+        // The files are given in the opposite order than their names, the Messier catalogue is still the one which is kept
+        let sky_data = vec![("sky objects", vec![[NGC_IC_CATALOGUE.to_owned(), ngc_ic], ["messier-catalogue.csv".to_owned(), messier]])];
+        let cellestial_sphere = CellestialSphere::from_sky_data(None, &mut theme, None, sky_data, Vec::new()).expect("Failed to load the cellestial sphere");
+
+        let mut object_ids: Vec<u64> = cellestial_sphere.question_objects.iter().map(|object| object.object_id).collect();
+        object_ids.sort();
+        assert_eq!(object_ids, vec![1, 200]);
+        let crab = cellestial_sphere.question_objects.iter().find(|object| object.object_id == 1).unwrap();
+        assert_eq!(crab.proper_names_raw, vec!["Crab Nebula"]);
+        assert_eq!(cellestial_sphere.deepskies["messier-catalogue.csv"].deepskies.len(), 1);
+        let ngc_ic = &cellestial_sphere.deepskies[NGC_IC_CATALOGUE];
+        assert_eq!(ngc_ic.deepskies.len(), 1);
+        assert!(!ngc_ic.active, "The NGC/IC catalogue should be hidden by default");
+        assert!(cellestial_sphere.deepskies["messier-catalogue.csv"].active);
+    }
+
+    #[test]
+    fn faint_deepskies_are_shown_when_zoomed_in() {
+        let mut cellestial_sphere = CellestialSphere::from_sky_data(None, &mut Theme::dark(), None, Vec::new(), Vec::new()).expect("Failed to load the cellestial sphere");
+        cellestial_sphere.sky_settings.deepsky_magnitude_limit = 8.0;
+        cellestial_sphere.set_zoom(cellestial_sphere.fov_to_zoom(100.0));
+        assert_eq!(cellestial_sphere.deepsky_magnitude_limit(), 8.0);
+        // Zooming in ten times reveals objects five magnitudes fainter
+        cellestial_sphere.set_zoom(cellestial_sphere.fov_to_zoom(9.0));
+        assert!((cellestial_sphere.deepsky_magnitude_limit() - 13.0).abs() < 1e-3);
+    }
+
+    fn star(object_id: u64, ra: f32, dec: f32) -> Star {
+        let raw = StarRaw {
+            object_id,
+            ra: angle::Deg(ra),
+            dec: angle::Deg(dec),
+            vmag: 2.0,
+            colour: None,
+            name: None,
+            bv: None,
+            constellations: String::from("UMi"),
+        };
+        Star::from_raw(raw, Color32::WHITE, None)
+    }
+
+    #[test]
+    fn stars_with_their_data_are_not_drawn_twice() {
+        let mut catalog = HashMap::from([
+            (String::from("stars"), vec![star(1, 37.9546, 89.2641), star(2, 47.0422, 40.9556)]),
+            (String::from("double-stars"), vec![star(1_011_767, 37.9546, 89.2641), star(1_104_214, 239.3, 33.86)]),
+            (String::from("variable-stars"), vec![star(1_014_576, 47.0422, 40.9556)]),
+        ]);
+        let star_data_ids = std::collections::HashSet::from([1_011_767, 1_104_214, 1_014_576]);
+        remove_star_data_copies(&mut catalog, &star_data_ids);
+        let object_ids = |name: &str| catalog[name].iter().map(|star| star.object_id).collect::<Vec<u64>>();
+        assert_eq!(object_ids("stars"), vec![1, 2]);
+        // Only the star which no other catalogue draws is left
+        assert_eq!(object_ids("double-stars"), vec![1_104_214]);
+        assert!(object_ids("variable-stars").is_empty());
+    }
 }
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::game::QuestionObject;
+
+use super::constellation::Constellation;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SearchTarget {
+    /// The ID of a question object
+    Object(u64),
+    /// The lowercase abbreviation of a constellation
+    Constellation(String),
+}
+
+pub struct SearchEntry {
+    /// The name as it is shown to the player
+    pub name: String,
+    pub target: SearchTarget,
+    /// The name in the form used for comparing
+    key: String,
+    /// Brighter objects are suggested first when the names match equally well
+    magnitude: f32,
+}
+
+/// All the names and designations an object or a constellation can be found by
+pub struct SearchIndex {
+    entries: Vec<SearchEntry>,
+}
+
+impl SearchIndex {
+    pub fn new(question_objects: &[QuestionObject], constellations: &HashMap<String, Constellation>) -> Self {
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+        let mut push = |name: String, target: SearchTarget, magnitude: f32| {
+            let key = Self::normalise(&name);
+            if key.is_empty() || !seen.insert((key.clone(), target.clone())) {
+                return;
+            }
+            entries.push(SearchEntry { name, target, key, magnitude });
+        };
+        for object in question_objects {
+            let magnitude = object.mag.unwrap_or(f32::MAX);
+            let mut names = object.proper_names_all.clone();
+            for designation in object.bayer_designation_raw.iter().chain(object.flamsteed_designation_raw.iter()) {
+                names.extend(super::designation_name_combinations(designation, &object.constellations_abbreviations));
+            }
+            names.extend(object.designations());
+            for name in names {
+                push(name, SearchTarget::Object(object.object_id), magnitude);
+            }
+        }
+        for (abbreviation, constellation) in constellations {
+            for name in &constellation.possible_names {
+                // Constellations go before the objects when they match equally well, since there are far fewer of them
+                push(name.clone(), SearchTarget::Constellation(abbreviation.clone()), f32::MIN);
+            }
+        }
+        Self { entries }
+    }
+
+    /// Lowercase and without spaces, dots and dashes, so that for example "M 42", "m42" and "M42" are all the same
+    fn normalise(name: &str) -> String {
+        name.chars().filter(|c| !c.is_whitespace() && *c != '.' && *c != '-').flat_map(|c| c.to_lowercase()).collect()
+    }
+
+    /// The entries matching the query, the exact matches first, then the ones starting with the query, then the ones containing it.
+    /// Only the best matching name of every target is given back.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&SearchEntry> {
+        let query = Self::normalise(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<(u8, &SearchEntry)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let rank = if entry.key == query {
+                    0
+                } else if entry.key.starts_with(&query) {
+                    1
+                } else if entry.key.contains(&query) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, entry))
+            })
+            .collect();
+        matches.sort_by(|(rank_1, entry_1), (rank_2, entry_2)| rank_1.cmp(rank_2).then(entry_1.magnitude.total_cmp(&entry_2.magnitude)).then(entry_1.key.len().cmp(&entry_2.key.len())));
+        let mut targets = Vec::new();
+        let mut results = Vec::new();
+        for (_, entry) in matches {
+            if results.len() >= limit {
+                break;
+            }
+            if !targets.contains(&&entry.target) {
+                targets.push(&entry.target);
+                results.push(entry);
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(object_id: u64, proper_names: &str, bayer_designation: Option<&str>, messier_number: Option<u32>, mag: f32) -> QuestionObject {
+        QuestionObject::from_raw(
+            crate::game::QuestionObjectRaw {
+                object_id,
+                object_type: crate::game::ObjectType::Star(crate::game::StarType::Single),
+                dec: angle::Deg(0.0),
+                ra: angle::Deg(0.0),
+                proper_names: proper_names.to_string(),
+                bayer_designation: bayer_designation.map(|designation| designation.to_string()),
+                flamsteed_designation: None,
+                hipparcos_number: None,
+                hd_number: None,
+                messier_number,
+                caldwell_number: None,
+                ngc_number: None,
+                ic_number: None,
+                constellations_abbreviations: String::from("Ori"),
+                colour: None,
+                mag: Some(mag),
+                distance: None,
+                bv: None,
+                separation: None,
+                position_angle: None,
+                component_magnitudes: None,
+                variable_type: None,
+                period: None,
+                mag_max: None,
+                mag_min: None,
+            },
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn finds_names_and_designations() {
+        let objects = [
+            object(1, "Betelgeuse", Some("alpha{ Orionis}"), None, 0.5),
+            object(2, "Bellatrix", Some("gamma{ Orionis}"), None, 1.6),
+            object(3, "Orion Nebula", None, Some(42), 4.0),
+        ];
+        let index = SearchIndex::new(&objects, &HashMap::new());
+
+        let targets = |query: &str| index.search(query, 10).into_iter().map(|entry| entry.target.clone()).collect::<Vec<SearchTarget>>();
+        assert_eq!(targets("m 42"), vec![SearchTarget::Object(3)]);
+        assert_eq!(targets("alpha Orionis"), vec![SearchTarget::Object(1)]);
+        assert_eq!(targets("alpha"), vec![SearchTarget::Object(1)]);
+        // Both stars start with "Be", the brighter one goes first
+        assert_eq!(targets("be"), vec![SearchTarget::Object(1), SearchTarget::Object(2)]);
+        assert_eq!(targets("orion"), vec![SearchTarget::Object(3), SearchTarget::Object(1), SearchTarget::Object(2)]);
+        assert!(targets("").is_empty());
+        assert_eq!(targets("orion").len(), index.search("orion", 2).len() + 1);
+    }
+}
@@ -0,0 +1,178 @@
+use eframe::egui;
+use egui::epaint::Color32;
+use nalgebra::{Matrix3, Vector3};
+use serde::Deserialize;
+
+use crate::graphics::parse_colour_option;
+
+use super::renderer::CellestialSphere;
+
+/// Into how many segments the outline of an ellipse is split
+const ELLIPSE_VERTICES: usize = 48;
+const OUTLINE_WIDTH: f32 = 1.0;
+
+pub struct SkyShapes {
+    pub colour: Color32,
+    pub active: bool,
+    pub shapes: Vec<SkyShape>,
+}
+
+/// A closed outline on the sky, either of an extended object (the ellipse of a galaxy, the outline of a nebula) or of a region like the Milky Way
+pub struct SkyShape {
+    /// The object the shape belongs to, so that it is hidden together with the object
+    pub object_id: Option<u64>,
+    pub vertices: Vec<(angle::Deg<f32>, angle::Deg<f32>)>,
+}
+
+/// A row of a shapes file, which is either a whole ellipse (when the axes are given), or a vertex of an outline. The vertices of an outline follow each other and share the same object ID and contour number.
+#[derive(Clone, Deserialize)]
+pub struct SkyShapeRaw {
+    pub object_id: Option<u64>,
+    /// Tells apart the outlines of the same object, or the outlines not belonging to any object
+    pub contour: Option<u32>,
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    /// The apparent size in arcminutes
+    pub major_axis: Option<f32>,
+    pub minor_axis: Option<f32>,
+    /// Measured from the north towards the east, in degrees
+    pub position_angle: Option<f32>,
+    pub colour: Option<String>,
+}
+
+impl SkyShape {
+    pub fn get_renderer(&self, rotation_matrix: &Matrix3<f32>, colour: Color32) -> ShapeRenderer {
+        ShapeRenderer::new(
+            self.object_id,
+            self.vertices.iter().map(|&(ra, dec)| sg_geometry::get_point_vector(ra, dec, rotation_matrix)).collect(),
+            colour,
+            false,
+        )
+    }
+
+    /// The outline of an ellipse with the given axes (in arcminutes) and position angle around (ra, dec)
+    pub fn ellipse(object_id: Option<u64>, ra: angle::Deg<f32>, dec: angle::Deg<f32>, major_axis: f32, minor_axis: f32, position_angle: angle::Deg<f32>) -> Self {
+        let (a, b) = (major_axis / 120.0, minor_axis / 120.0);
+        let vertices = (0..ELLIPSE_VERTICES)
+            .map(|i| {
+                // Stepping evenly around the eccentric anomaly keeps the vertices dense at the pointed ends of thin ellipses
+                let anomaly = 2.0 * std::f32::consts::PI * i as f32 / ELLIPSE_VERTICES as f32;
+                let (along, across) = (a * anomaly.cos(), b * anomaly.sin());
+                sg_geometry::offset_point(ra, dec, angle::Deg(along.hypot(across)), angle::Deg(position_angle.0 + across.atan2(along).to_degrees()))
+            })
+            .collect();
+        Self { object_id, vertices }
+    }
+
+    /// Turns the rows of a shapes file into shapes, returning the first colour found in the file as well
+    pub fn from_raw(raw_shapes: Vec<SkyShapeRaw>) -> (Vec<Self>, Option<Color32>) {
+        let mut colour = None;
+        let mut shapes = Vec::new();
+        // The outline being read, together with its contour number
+        let mut outline: Option<(Option<u32>, Self)> = None;
+        for raw_shape in raw_shapes {
+            if colour.is_none() {
+                colour = parse_colour_option(raw_shape.colour);
+            }
+            if let Some(major_axis) = raw_shape.major_axis {
+                shapes.extend(outline.take().map(|(_, shape)| shape));
+                shapes.push(Self::ellipse(
+                    raw_shape.object_id,
+                    raw_shape.ra,
+                    raw_shape.dec,
+                    major_axis,
+                    raw_shape.minor_axis.unwrap_or(major_axis),
+                    angle::Deg(raw_shape.position_angle.unwrap_or(0.0)),
+                ));
+                continue;
+            }
+            match &mut outline {
+                Some((contour, shape)) if shape.object_id == raw_shape.object_id && *contour == raw_shape.contour => shape.vertices.push((raw_shape.ra, raw_shape.dec)),
+                _ => {
+                    let shape = Self {
+                        object_id: raw_shape.object_id,
+                        vertices: vec![(raw_shape.ra, raw_shape.dec)],
+                    };
+                    shapes.extend(outline.replace((raw_shape.contour, shape)).map(|(_, shape)| shape));
+                }
+            }
+        }
+        shapes.extend(outline.map(|(_, shape)| shape));
+        (shapes, colour)
+    }
+}
+
+pub struct ShapeRenderer {
+    pub object_id: Option<u64>,
+    pub vertices: Vec<Vector3<f32>>,
+    pub colour: Color32,
+    pub disabled: bool,
+}
+
+impl ShapeRenderer {
+    pub fn new(object_id: Option<u64>, vertices: Vec<Vector3<f32>>, colour: Color32, disabled: bool) -> Self {
+        Self {
+            object_id,
+            vertices,
+            colour,
+            disabled,
+        }
+    }
+
+    pub fn render(&self, cellestial_sphere: &CellestialSphere, painter: &egui::Painter) {
+        if self.disabled || self.vertices.len() < 2 {
+            return;
+        }
+        for (i, start) in self.vertices.iter().enumerate() {
+            let end = &self.vertices[(i + 1) % self.vertices.len()];
+            cellestial_sphere.render_line(start, end, self.colour, OUTLINE_WIDTH, painter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use angle::Angle;
+
+    fn raw(object_id: Option<u64>, contour: Option<u32>, major_axis: Option<f32>) -> SkyShapeRaw {
+        SkyShapeRaw {
+            object_id,
+            contour,
+            ra: angle::Deg(10.0),
+            dec: angle::Deg(40.0),
+            major_axis,
+            minor_axis: major_axis.map(|axis| axis / 2.0),
+            position_angle: Some(30.0),
+            colour: None,
+        }
+    }
+
+    #[test]
+    fn ellipses_and_outlines() {
+        let ellipse = SkyShape::ellipse(None, angle::Deg(10.0), angle::Deg(40.0), 120.0, 60.0, angle::Deg(30.0));
+        let distance = |(ra, dec): (angle::Deg<f32>, angle::Deg<f32>)| {
+            sg_geometry::angular_distance((angle::Deg(10.0).to_rad(), angle::Deg(40.0).to_rad()), (ra.to_rad(), dec.to_rad()))
+                .to_deg()
+                .0
+        };
+        // The first vertex lies on the major axis, the one a quarter of the way around on the minor axis
+        assert!((distance(ellipse.vertices[0]) - 1.0).abs() < 1e-3);
+        assert!((distance(ellipse.vertices[ELLIPSE_VERTICES / 4]) - 0.5).abs() < 1e-3);
+        let (ra, dec) = sg_geometry::offset_point(angle::Deg(10.0), angle::Deg(40.0), angle::Deg(1.0), angle::Deg(30.0));
+        assert!((ellipse.vertices[0].0 .0 - ra.0).abs() < 1e-3 && (ellipse.vertices[0].1 .0 - dec.0).abs() < 1e-3);
+
+        let (shapes, _) = SkyShape::from_raw(vec![
+            raw(None, Some(1), None),
+            raw(None, Some(1), None),
+            raw(None, Some(2), None),
+            raw(Some(31), None, Some(190.0)),
+            raw(Some(42), None, None),
+            raw(Some(42), None, None),
+        ]);
+        assert_eq!(
+            shapes.iter().map(|shape| (shape.object_id, shape.vertices.len())).collect::<Vec<_>>(),
+            vec![(None, 2), (None, 1), (Some(31), ELLIPSE_VERTICES), (Some(42), 2)]
+        );
+    }
+}
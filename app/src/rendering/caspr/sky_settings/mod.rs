@@ -7,13 +7,22 @@ pub struct SkySettings {
     pub stars_categories_active: HashMap<String, bool>,
     pub lines_categories_active: HashMap<String, bool>,
     pub deepskies_categories_active: HashMap<String, bool>,
+    pub shapes_categories_active: HashMap<String, bool>,
     pub markers_categories_active: HashMap<String, bool>,
     pub star_names_categories_active: HashMap<String, bool>,
     pub mag_to_radius_id: usize,
     pub mag_to_radius_settings: [stars::MagnitudeToRadius; 2],
     pub deepsky_render_mag_decrease: f32,
+    /// The faintest deepsky objects drawn when zoomed out
+    pub deepsky_magnitude_limit: f32,
     pub render_labels: bool,
     pub cloud_settings: crate::rendering::caspr::clouds::CloudSettings,
+    pub tiled_stars_settings: crate::rendering::caspr::tiled_stars::TiledStarsSettings,
+    pub camera_transition_settings: crate::rendering::caspr::camera_transition::CameraTransitionSettings,
+    pub projection: sg_geometry::projection::Projection,
+    pub camera_mode: crate::rendering::caspr::camera_mode::CameraMode,
+    /// Whether to draw the arrows pointing to the north and east in the corner of the screen
+    pub show_compass: bool,
 }
 
 impl SkySettings {
@@ -22,13 +31,20 @@ impl SkySettings {
             stars_categories_active: string_to_partial_hash_map(&sky_settings.star_files_to_not_render),
             lines_categories_active: string_to_partial_hash_map(&sky_settings.line_files_to_not_render),
             deepskies_categories_active: string_to_partial_hash_map(&sky_settings.deepsky_files_to_not_render),
+            shapes_categories_active: string_to_partial_hash_map(&sky_settings.shape_files_to_not_render),
             markers_categories_active: string_to_partial_hash_map(&sky_settings.markers_files_to_not_render),
             star_names_categories_active: string_to_partial_hash_map(&sky_settings.star_names_files_to_not_use),
             mag_to_radius_id: sky_settings.mag_to_radius_id.min(crate::rendering::caspr::stars::MAGNITUDE_TO_RADIUS_OPTIONS - 1),
             mag_to_radius_settings: sky_settings.mag_to_radius_settings,
             deepsky_render_mag_decrease: sky_settings.deepsky_render_mag_decrease,
+            deepsky_magnitude_limit: sky_settings.deepsky_magnitude_limit,
             render_labels: sky_settings.render_labels,
             cloud_settings: sky_settings.cloud_settings,
+            tiled_stars_settings: sky_settings.tiled_stars_settings,
+            camera_transition_settings: sky_settings.camera_transition_settings,
+            projection: sky_settings.projection,
+            camera_mode: sky_settings.camera_mode,
+            show_compass: sky_settings.show_compass,
         }
     }
 }
@@ -39,13 +55,22 @@ pub struct SkySettingsRaw {
     pub star_files_to_not_render: String,
     pub line_files_to_not_render: String,
     pub deepsky_files_to_not_render: String,
+    pub shape_files_to_not_render: String,
     pub markers_files_to_not_render: String,
     pub star_names_files_to_not_use: String,
     pub mag_to_radius_id: usize,
     pub mag_to_radius_settings: [stars::MagnitudeToRadius; 2],
     pub deepsky_render_mag_decrease: f32,
+    /// The faintest deepsky objects drawn when zoomed out
+    pub deepsky_magnitude_limit: f32,
     pub render_labels: bool,
     pub cloud_settings: crate::rendering::caspr::clouds::CloudSettings,
+    pub tiled_stars_settings: crate::rendering::caspr::tiled_stars::TiledStarsSettings,
+    pub camera_transition_settings: crate::rendering::caspr::camera_transition::CameraTransitionSettings,
+    pub projection: sg_geometry::projection::Projection,
+    pub camera_mode: crate::rendering::caspr::camera_mode::CameraMode,
+    /// Whether to draw the arrows pointing to the north and east in the corner of the screen
+    pub show_compass: bool,
 }
 
 impl Default for SkySettingsRaw {
@@ -53,14 +78,21 @@ impl Default for SkySettingsRaw {
         Self {
             star_files_to_not_render: String::new(),
             line_files_to_not_render: String::new(),
-            deepsky_files_to_not_render: String::new(),
+            deepsky_files_to_not_render: String::from(crate::renderer::NGC_IC_CATALOGUE),
+            shape_files_to_not_render: String::new(),
             markers_files_to_not_render: String::new(),
             star_names_files_to_not_use: String::new(),
             mag_to_radius_id: 1.min(crate::rendering::caspr::stars::MAGNITUDE_TO_RADIUS_OPTIONS - 1),
             mag_to_radius_settings: stars::MagnitudeToRadius::defaults(),
             deepsky_render_mag_decrease: 0.0,
+            deepsky_magnitude_limit: 13.0,
             render_labels: false,
             cloud_settings: crate::rendering::caspr::clouds::CloudSettings::default(),
+            tiled_stars_settings: crate::rendering::caspr::tiled_stars::TiledStarsSettings::default(),
+            camera_transition_settings: crate::rendering::caspr::camera_transition::CameraTransitionSettings::default(),
+            projection: sg_geometry::projection::Projection::default(),
+            camera_mode: crate::rendering::caspr::camera_mode::CameraMode::default(),
+            show_compass: true,
         }
     }
 }
@@ -71,13 +103,20 @@ impl SkySettingsRaw {
             star_files_to_not_render: hash_map_to_string(&sky_settings.stars_categories_active),
             line_files_to_not_render: hash_map_to_string(&sky_settings.lines_categories_active),
             deepsky_files_to_not_render: hash_map_to_string(&sky_settings.deepskies_categories_active),
+            shape_files_to_not_render: hash_map_to_string(&sky_settings.shapes_categories_active),
             markers_files_to_not_render: hash_map_to_string(&sky_settings.markers_categories_active),
             star_names_files_to_not_use: hash_map_to_string(&sky_settings.star_names_categories_active),
             mag_to_radius_id: sky_settings.mag_to_radius_id,
             mag_to_radius_settings: sky_settings.mag_to_radius_settings,
             deepsky_render_mag_decrease: sky_settings.deepsky_render_mag_decrease,
+            deepsky_magnitude_limit: sky_settings.deepsky_magnitude_limit,
             render_labels: sky_settings.render_labels,
             cloud_settings: sky_settings.cloud_settings,
+            tiled_stars_settings: sky_settings.tiled_stars_settings,
+            camera_transition_settings: sky_settings.camera_transition_settings,
+            projection: sky_settings.projection,
+            camera_mode: sky_settings.camera_mode,
+            show_compass: sky_settings.show_compass,
         }
     }
 }
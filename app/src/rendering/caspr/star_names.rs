@@ -1,5 +1,33 @@
 use serde::Deserialize;
 
+/// The letters used in the Bayer designations - the abbreviation used by the IAU catalogue of star names, the name used in the designations and the letter itself
+pub const GREEK_LETTERS: [(&str, &str, &str); 24] = [
+    ("alf", "alpha", "α"),
+    ("bet", "beta", "β"),
+    ("gam", "gamma", "γ"),
+    ("del", "delta", "δ"),
+    ("eps", "epsilon", "ε"),
+    ("zet", "zeta", "ζ"),
+    ("eta", "eta", "η"),
+    ("tet", "theta", "θ"),
+    ("iot", "iota", "ι"),
+    ("kap", "kappa", "κ"),
+    ("lam", "lambda", "λ"),
+    ("mu", "mu", "μ"),
+    ("nu", "nu", "ν"),
+    ("ksi", "xi", "ξ"),
+    ("omi", "omicron", "ο"),
+    ("pi", "pi", "π"),
+    ("rho", "rho", "ρ"),
+    ("sig", "sigma", "σ"),
+    ("tau", "tau", "τ"),
+    ("ups", "upsilon", "υ"),
+    ("phi", "phi", "φ"),
+    ("chi", "chi", "χ"),
+    ("psi", "psi", "ψ"),
+    ("ome", "omega", "ω"),
+];
+
 #[derive(Clone, Deserialize)]
 pub struct StarName {
     pub ra: angle::Deg<f32>,
@@ -60,4 +88,55 @@ impl StarName {
             None => None,
         }
     }
+
+    /// The Bayer letter of the star written the same way as in the sky objects files, for example "theta{1}" for the IAU id "tet01"
+    pub fn bayer_letter(&self) -> Option<String> {
+        let id = self.id.as_deref()?;
+        let letter_end = id.find(|c: char| c.is_ascii_digit()).unwrap_or(id.len());
+        let (_, letter, _) = GREEK_LETTERS.iter().find(|(abbreviation, _, _)| *abbreviation == &id[..letter_end])?;
+        let superscript = id[letter_end..].trim_start_matches('0');
+        Some(if superscript.is_empty() { letter.to_string() } else { format!("{letter}{{{superscript}}}") })
+    }
+
+    /// The IAU catalogue uses the Flamsteed number as the id of the stars without a Bayer letter
+    pub fn flamsteed_number(&self) -> Option<u32> {
+        self.id.as_deref()?.parse().ok()
+    }
+
+    /// Makes a question object out of a star with a Bayer or Flamsteed designation, so that it can be asked about even when no sky objects file contains it
+    pub fn to_question_object_raw(&self, constellation_genitive: &str) -> Option<crate::game::QuestionObjectRaw> {
+        let bayer_designation = self.bayer_letter().map(|letter| format!("{letter}{{ {constellation_genitive}}}"));
+        let flamsteed_designation = self.flamsteed_number().map(|number| format!("{number}{{ {constellation_genitive}}}"));
+        if bayer_designation.is_none() && flamsteed_designation.is_none() {
+            return None;
+        }
+        let hip = self.hip as u32;
+        Some(crate::game::QuestionObjectRaw {
+            object_id: 1_000_000 + hip as u64,
+            object_type: crate::game::ObjectType::Star(crate::game::StarType::Single),
+            dec: self.dec,
+            ra: self.ra,
+            proper_names: self.name.clone(),
+            bayer_designation,
+            flamsteed_designation,
+            hipparcos_number: Some(hip),
+            hd_number: None,
+            messier_number: None,
+            caldwell_number: None,
+            ngc_number: None,
+            ic_number: None,
+            constellations_abbreviations: self.con.clone(),
+            colour: None,
+            mag: Some(self.mag),
+            distance: None,
+            bv: None,
+            separation: None,
+            position_angle: None,
+            component_magnitudes: None,
+            variable_type: None,
+            period: None,
+            mag_max: None,
+            mag_min: None,
+        })
+    }
 }
@@ -0,0 +1,560 @@
+use angle::Angle;
+use eframe::egui;
+use egui::epaint::Color32;
+use nalgebra::Matrix3;
+use sg_geometry::projection::Projection;
+use std::{
+    io::{Read, Seek, SeekFrom},
+    sync::mpsc::{Receiver, Sender},
+};
+
+use super::stars::{MagnitudeToRadius, Star, StarRenderer};
+
+// The binary format of a tiled star catalogue (all numbers are little endian):
+// Header: magic "SGTS", version (u16), tile size in degrees (f32), number of tiles (u32)
+// Tile table: for every tile dec_min, dec_max, ra_min, ra_max (f32, degrees), offset of the first record (u64), number of records (u32), magnitude of the faintest star (f32)
+// Records: ra, dec, vmag, bv (f32 each, bv is NaN if it is not known), sorted by magnitude within each tile
+// The sky is split into declination bands of the tile size, each band is then split into tiles along the right ascension, with fewer tiles near the poles.
+const MAGIC: &[u8; 4] = b"SGTS";
+const VERSION: u16 = 1;
+const HEADER_SIZE: u64 = 14;
+const TILE_ENTRY_SIZE: u64 = 32;
+const RECORD_SIZE: usize = 16;
+/// How many records are read at once when a tile needs fainter stars
+const RECORDS_PER_READ: usize = 1024;
+/// How many stars of a catalogue are kept in memory at most (about 32 MB), the tiles looked at the longest time ago are dropped first
+const MAX_LOADED_STARS: usize = 2_000_000;
+pub const TILED_STARS_EXTENSION: &str = "sgts";
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy)]
+#[serde(default)]
+pub struct TiledStarsSettings {
+    pub enabled: bool,
+    /// The tiled catalogues are only rendered when the FOV is smaller than this
+    pub max_fov: f32,
+    /// The limiting magnitude when the FOV is exactly `max_fov`
+    pub limiting_magnitude: f32,
+    /// By how much the limiting magnitude increases every time the FOV is halved
+    pub magnitude_per_fov_halving: f32,
+    /// No stars fainter than this are ever rendered
+    pub faintest_magnitude: f32,
+}
+
+impl Default for TiledStarsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_fov: 30.0,
+            limiting_magnitude: 7.0,
+            magnitude_per_fov_halving: 1.0,
+            faintest_magnitude: 12.0,
+        }
+    }
+}
+
+impl TiledStarsSettings {
+    pub fn clamp(&mut self) {
+        self.max_fov = self.max_fov.clamp(0.0, 360.0);
+        if self.magnitude_per_fov_halving < 0.0 {
+            self.magnitude_per_fov_halving = 0.0;
+        }
+    }
+
+    /// The magnitude of the faintest star to be rendered at the given FOV, None if the tiled catalogues should not be rendered at all
+    pub fn limiting_magnitude(&self, fov: f32) -> Option<f32> {
+        if !self.enabled || fov >= self.max_fov || fov <= 0.0 {
+            return None;
+        }
+        Some((self.limiting_magnitude + self.magnitude_per_fov_halving * (self.max_fov / fov).log2()).min(self.faintest_magnitude))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TiledStar {
+    pub ra: angle::Deg<f32>,
+    pub dec: angle::Deg<f32>,
+    pub vmag: f32,
+    pub colour: Color32,
+}
+
+impl TiledStar {
+    fn from_record(record: &[u8]) -> Self {
+        let ra = read_f32(&record[0..4]);
+        let dec = read_f32(&record[4..8]);
+        let vmag = read_f32(&record[8..12]);
+        let bv = read_f32(&record[12..16]);
+        let colour = if bv.is_nan() { Color32::WHITE } else { Star::temperature_to_colour(Star::bv_to_temperature(bv)) };
+        Self {
+            ra: angle::Deg(ra),
+            dec: angle::Deg(dec),
+            vmag,
+            colour,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_renderer(
+        &self,
+        rotation_matrix: &Matrix3<f32>,
+        magnitude_to_radius_function: MagnitudeToRadius,
+        fov: angle::Deg<f32>,
+        zoom: f32,
+        viewport_rect: egui::Rect,
+        projection: Projection,
+        override_colour: Option<Color32>,
+    ) -> Option<StarRenderer> {
+        let radius = StarRenderer::magnitude_to_radius(magnitude_to_radius_function, self.vmag, fov);
+        if !StarRenderer::radius_enough_to_render(radius) {
+            return None;
+        }
+        let vec = sg_geometry::get_point_vector(self.ra, self.dec, rotation_matrix);
+        let (projected_point, is_within_bounds) = sg_geometry::project_point(&vec, zoom, viewport_rect, projection);
+        if !is_within_bounds {
+            return None;
+        }
+        // These stars are not question objects, so they do not need a real ID
+        Some(StarRenderer::new(0, radius, override_colour.unwrap_or(self.colour), projected_point, true, false))
+    }
+}
+
+struct Tile {
+    ra_min: f32,
+    ra_max: f32,
+    dec_min: f32,
+    dec_max: f32,
+    offset: u64,
+    count: u32,
+    faintest_magnitude: f32,
+    /// The angular distance from the centre of the tile to its furthest corner
+    radius: angle::Deg<f32>,
+    /// The brightest stars of the tile, always the first `stars.len()` records
+    stars: Vec<TiledStar>,
+    /// When the tile was last in view, counted in calls of `stars_in_view`
+    last_used: u64,
+    /// The magnitude up to which the stars were asked for from the loading thread, None if there is no request waiting
+    requested_to: Option<f32>,
+}
+
+impl Tile {
+    fn centre(&self) -> (angle::Deg<f32>, angle::Deg<f32>) {
+        (angle::Deg((self.ra_min + self.ra_max) / 2.0), angle::Deg((self.dec_min + self.dec_max) / 2.0))
+    }
+
+    fn is_fully_loaded(&self) -> bool {
+        self.stars.len() >= self.count as usize
+    }
+
+    /// Checks if all the stars up to the given magnitude have already been read
+    fn is_loaded_to(&self, magnitude: f32) -> bool {
+        self.is_fully_loaded() || self.stars.last().is_some_and(|star| star.vmag > magnitude)
+    }
+}
+
+/// A large star catalogue split into tiles, whose stars are only read from the disk once a part of the sky is looked at closely enough
+pub struct TiledStarCatalogue {
+    pub name: String,
+    pub override_colour: Option<Color32>,
+    path: std::path::PathBuf,
+    tiles: Vec<Tile>,
+    /// The tiles are read on this thread if it is set, otherwise right away when they are needed
+    loader: Option<Sender<TileRequest>>,
+    /// How many times the stars in view were asked for
+    views: u64,
+    loaded_stars: usize,
+    max_loaded_stars: usize,
+}
+
+/// Asks the loading thread for the stars of a tile up to the limiting magnitude, starting after the already loaded ones
+pub struct TileRequest {
+    catalogue: String,
+    path: std::path::PathBuf,
+    tile: usize,
+    offset: u64,
+    loaded: usize,
+    count: u32,
+    limiting_magnitude: f32,
+}
+
+/// The stars read by the loading thread, to be added after the first `start` stars of the tile
+pub struct LoadedTile {
+    pub catalogue: String,
+    tile: usize,
+    start: usize,
+    stars: Result<Vec<TiledStar>, String>,
+}
+
+impl TiledStarCatalogue {
+    /// Only reads the header and the tile table, the stars themselves are loaded as needed
+    pub fn open(path: std::path::PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+        let mut file = std::fs::File::open(&path)?;
+        let mut header = [0; HEADER_SIZE as usize];
+        file.read_exact(&mut header)?;
+        if &header[0..4] != MAGIC {
+            return Err(format!("{name} is not a tiled star catalogue").into());
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(format!("Unsupported version of the tiled star catalogue {name}: {version}").into());
+        }
+        let tiles_count = u32::from_le_bytes([header[10], header[11], header[12], header[13]]) as usize;
+        let mut table = vec![0; tiles_count * TILE_ENTRY_SIZE as usize];
+        file.read_exact(&mut table)?;
+        let tiles = table
+            .chunks_exact(TILE_ENTRY_SIZE as usize)
+            .map(|entry| {
+                let mut offset = [0; 8];
+                offset.copy_from_slice(&entry[16..24]);
+                let mut tile = Tile {
+                    dec_min: read_f32(&entry[0..4]),
+                    dec_max: read_f32(&entry[4..8]),
+                    ra_min: read_f32(&entry[8..12]),
+                    ra_max: read_f32(&entry[12..16]),
+                    offset: u64::from_le_bytes(offset),
+                    count: u32::from_le_bytes([entry[24], entry[25], entry[26], entry[27]]),
+                    faintest_magnitude: read_f32(&entry[28..32]),
+                    radius: angle::Deg(0.0),
+                    stars: Vec::new(),
+                    last_used: 0,
+                    requested_to: None,
+                };
+                let (ra, dec) = tile.centre();
+                tile.radius = [(tile.ra_min, tile.dec_min), (tile.ra_min, tile.dec_max), (tile.ra_max, tile.dec_min), (tile.ra_max, tile.dec_max)]
+                    .into_iter()
+                    .map(|(corner_ra, corner_dec)| sg_geometry::angular_distance((ra.to_rad(), dec.to_rad()), (angle::Deg(corner_ra).to_rad(), angle::Deg(corner_dec).to_rad())).to_deg())
+                    .fold(angle::Deg(0.0), |a, b| if b.value() > a.value() { b } else { a });
+                tile
+            })
+            .collect();
+        Ok(Self {
+            name,
+            override_colour: None,
+            path,
+            tiles,
+            loader: None,
+            views: 0,
+            loaded_stars: 0,
+            max_loaded_stars: MAX_LOADED_STARS,
+        })
+    }
+
+    /// From now on the missing stars are read on the loading thread, and `stars_in_view` only returns the stars loaded so far
+    pub fn load_in_background(&mut self, loader: Sender<TileRequest>) {
+        self.loader = Some(loader);
+    }
+
+    pub fn tiles_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Makes sure all the stars up to the given magnitude are loaded in the tiles overlapping the circle, and returns them.
+    /// The circle is given by its centre (ra, dec) and its radius. When loading in the background, the missing stars are only asked for and returned once they arrive.
+    pub fn stars_in_view(&mut self, centre: (angle::Deg<f32>, angle::Deg<f32>), radius: angle::Deg<f32>, limiting_magnitude: f32) -> Vec<TiledStar> {
+        self.views += 1;
+        let mut file = None;
+        let mut stars = Vec::new();
+        for (i, tile) in self.tiles.iter_mut().enumerate() {
+            if tile.count == 0 {
+                continue;
+            }
+            let (tile_ra, tile_dec) = tile.centre();
+            let distance = sg_geometry::angular_distance((centre.0.to_rad(), centre.1.to_rad()), (tile_ra.to_rad(), tile_dec.to_rad())).to_deg();
+            if distance.value() > radius.value() + tile.radius.value() {
+                continue;
+            }
+            tile.last_used = self.views;
+            if !tile.is_loaded_to(limiting_magnitude) {
+                if let Some(loader) = &self.loader {
+                    if tile.requested_to.is_none_or(|requested_to| requested_to < limiting_magnitude) {
+                        let request = TileRequest {
+                            catalogue: self.name.clone(),
+                            path: self.path.clone(),
+                            tile: i,
+                            offset: tile.offset,
+                            loaded: tile.stars.len(),
+                            count: tile.count,
+                            limiting_magnitude,
+                        };
+                        match loader.send(request) {
+                            Ok(()) => tile.requested_to = Some(limiting_magnitude),
+                            Err(err) => log::error!("The tiled stars loading thread has stopped: {err}"),
+                        }
+                    }
+                } else {
+                    if file.is_none() {
+                        match std::fs::File::open(&self.path) {
+                            Ok(opened) => file = Some(opened),
+                            Err(err) => {
+                                log::error!("Failed to open the tiled star catalogue {}: {err}", self.name);
+                                return stars;
+                            }
+                        }
+                    }
+                    if let Some(file) = &mut file {
+                        match read_records(file, tile.offset, tile.stars.len(), tile.count, limiting_magnitude) {
+                            Ok(loaded) => {
+                                self.loaded_stars += loaded.len();
+                                tile.stars.extend(loaded);
+                            }
+                            Err(err) => log::error!("Failed to load a tile of the tiled star catalogue {}: {err}", self.name),
+                        }
+                    }
+                }
+            }
+            stars.extend(tile.stars.iter().take_while(|star| star.vmag <= limiting_magnitude));
+        }
+        self.drop_unused_tiles();
+        stars
+    }
+
+    /// Adds the stars read by the loading thread to their tile
+    pub fn add_loaded_tile(&mut self, loaded: LoadedTile) {
+        let Some(tile) = self.tiles.get_mut(loaded.tile) else {
+            return;
+        };
+        tile.requested_to = None;
+        match loaded.stars {
+            // The tile might have been dropped or loaded further in the meantime, only the stars it does not have yet are added
+            Ok(stars) => {
+                if loaded.start <= tile.stars.len() {
+                    let new_stars = stars.into_iter().skip(tile.stars.len() - loaded.start);
+                    let before = tile.stars.len();
+                    tile.stars.extend(new_stars);
+                    self.loaded_stars += tile.stars.len() - before;
+                }
+            }
+            Err(err) => log::error!("Failed to load a tile of the tiled star catalogue {}: {err}", self.name),
+        }
+        self.drop_unused_tiles();
+    }
+
+    /// Keeps the number of loaded stars under the limit by dropping the stars of the tiles looked at the longest time ago, the tiles in the current view are kept
+    fn drop_unused_tiles(&mut self) {
+        while self.loaded_stars > self.max_loaded_stars {
+            let Some(tile) = self
+                .tiles
+                .iter_mut()
+                .filter(|tile| !tile.stars.is_empty() && tile.last_used < self.views)
+                .min_by_key(|tile| tile.last_used)
+            else {
+                break;
+            };
+            self.loaded_stars -= tile.stars.len();
+            tile.stars = Vec::new();
+        }
+    }
+
+    /// The magnitude of the faintest star in the whole catalogue
+    pub fn faintest_magnitude(&self) -> f32 {
+        self.tiles.iter().filter(|tile| tile.count > 0).map(|tile| tile.faintest_magnitude).fold(f32::NEG_INFINITY, f32::max)
+    }
+}
+
+/// Reads the records of a tile after the first `loaded` ones until a star fainter than the limiting magnitude is found or the tile runs out of stars
+fn read_records<R: Read + Seek>(reader: &mut R, offset: u64, loaded: usize, count: u32, limiting_magnitude: f32) -> std::io::Result<Vec<TiledStar>> {
+    reader.seek(SeekFrom::Start(offset + (loaded * RECORD_SIZE) as u64))?;
+    let mut stars: Vec<TiledStar> = Vec::new();
+    let mut buffer = vec![0; RECORDS_PER_READ * RECORD_SIZE];
+    while loaded + stars.len() < count as usize && stars.last().is_none_or(|star| star.vmag <= limiting_magnitude) {
+        let records = (count as usize - loaded - stars.len()).min(RECORDS_PER_READ);
+        let buffer = &mut buffer[..records * RECORD_SIZE];
+        reader.read_exact(buffer)?;
+        stars.extend(buffer.chunks_exact(RECORD_SIZE).map(TiledStar::from_record));
+    }
+    Ok(stars)
+}
+
+/// Starts the thread reading the tiles of all the catalogues. The thread stops once all the senders of the requests are dropped.
+pub fn spawn_loader() -> (Sender<TileRequest>, Receiver<LoadedTile>) {
+    let (request_sender, requests) = std::sync::mpsc::channel::<TileRequest>();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for request in requests {
+            let stars = std::fs::File::open(&request.path)
+                .and_then(|mut file| read_records(&mut file, request.offset, request.loaded, request.count, request.limiting_magnitude))
+                .map_err(|err| err.to_string());
+            let loaded = LoadedTile {
+                catalogue: request.catalogue,
+                tile: request.tile,
+                start: request.loaded,
+                stars,
+            };
+            if sender.send(loaded).is_err() {
+                break;
+            }
+        }
+    });
+    (request_sender, receiver)
+}
+
+fn read_f32(bytes: &[u8]) -> f32 {
+    f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Finds all the tiled star catalogues in the given folder
+pub fn open_catalogues(folder: &str) -> Vec<TiledStarCatalogue> {
+    let mut catalogues = Vec::new();
+    let Ok(files) = std::fs::read_dir(folder) else {
+        return catalogues;
+    };
+    for file in files.flatten() {
+        let path = file.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some(TILED_STARS_EXTENSION) {
+            continue;
+        }
+        match TiledStarCatalogue::open(path) {
+            Ok(catalogue) => catalogues.push(catalogue),
+            Err(err) => log::error!("Failed to open a tiled star catalogue: {err}"),
+        }
+    }
+    catalogues.sort_by(|a, b| a.name.cmp(&b.name));
+    catalogues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ra, dec, vmag, bv
+    type Record = (f32, f32, f32, f32);
+
+    /// Writes a catalogue in the same way as generators/tiled_stars/generate.py does
+    fn write_catalogue(tile_size: f32, stars: &[Record]) -> Vec<u8> {
+        let mut tiles: Vec<(f32, f32, f32, f32, Vec<Record>)> = Vec::new();
+        let bands = (180.0 / tile_size).ceil() as usize;
+        for band in 0..bands {
+            let dec_min = -90.0 + band as f32 * tile_size;
+            let dec_max = (dec_min + tile_size).min(90.0);
+            let band_centre = ((dec_min + dec_max) / 2.0).to_radians();
+            let ra_tiles = ((360.0 / tile_size) * band_centre.cos()).ceil().max(1.0) as usize;
+            let ra_size = 360.0 / ra_tiles as f32;
+            for ra_tile in 0..ra_tiles {
+                let ra_min = ra_tile as f32 * ra_size;
+                let mut tile_stars: Vec<Record> = stars
+                    .iter()
+                    .filter(|&&(ra, dec, _, _)| ra >= ra_min && ra < ra_min + ra_size && dec >= dec_min && (dec < dec_max || (dec_max == 90.0 && dec == 90.0)))
+                    .copied()
+                    .collect();
+                tile_stars.sort_by(|a, b| a.2.total_cmp(&b.2));
+                tiles.push((dec_min, dec_max, ra_min, ra_min + ra_size, tile_stars));
+            }
+        }
+        let mut data = Vec::new();
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&VERSION.to_le_bytes());
+        data.extend_from_slice(&tile_size.to_le_bytes());
+        data.extend_from_slice(&(tiles.len() as u32).to_le_bytes());
+        let mut offset = HEADER_SIZE + TILE_ENTRY_SIZE * tiles.len() as u64;
+        for (dec_min, dec_max, ra_min, ra_max, tile_stars) in &tiles {
+            for value in [dec_min, dec_max, ra_min, ra_max] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            data.extend_from_slice(&offset.to_le_bytes());
+            data.extend_from_slice(&(tile_stars.len() as u32).to_le_bytes());
+            data.extend_from_slice(&tile_stars.last().map_or(f32::NAN, |star| star.2).to_le_bytes());
+            offset += (tile_stars.len() * RECORD_SIZE) as u64;
+        }
+        for (_, _, _, _, tile_stars) in &tiles {
+            for &(ra, dec, vmag, bv) in tile_stars {
+                for value in [ra, dec, vmag, bv] {
+                    data.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn loads_only_the_needed_stars() {
+        let mut stars = vec![(10.0, 10.0, 5.0, 0.5), (11.0, 11.0, 9.0, f32::NAN), (200.0, -40.0, 6.0, 1.0)];
+        // Enough faint stars in one tile to need more than one read
+        for i in 0..(RECORDS_PER_READ + 10) {
+            stars.push((12.0, 12.0, 10.0 + i as f32 * 0.001, 0.0));
+        }
+        let path = std::env::temp_dir().join(format!("stellar-guesser-test-{}.{TILED_STARS_EXTENSION}", std::process::id()));
+        std::fs::write(&path, write_catalogue(15.0, &stars)).unwrap();
+        let mut catalogue = TiledStarCatalogue::open(path.clone()).unwrap();
+        assert!(catalogue.tiles_count() > 0);
+
+        let centre = (angle::Deg(10.0), angle::Deg(10.0));
+        let bright = catalogue.stars_in_view(centre, angle::Deg(3.0), 6.0);
+        assert_eq!(bright.len(), 1);
+        assert_eq!(bright[0].vmag, 5.0);
+        // The other side of the sky has not been touched
+        assert!(catalogue.tiles.iter().filter(|tile| tile.dec_max <= -30.0).all(|tile| tile.stars.is_empty()));
+
+        let faint = catalogue.stars_in_view(centre, angle::Deg(3.0), 20.0);
+        assert_eq!(faint.len(), 2 + RECORDS_PER_READ + 10);
+        assert!(faint.iter().any(|star| star.colour == Color32::WHITE));
+
+        let south = catalogue.stars_in_view((angle::Deg(200.0), angle::Deg(-40.0)), angle::Deg(1.0), 8.0);
+        assert_eq!(south.len(), 1);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    fn open_catalogue(name: &str, stars: &[Record]) -> TiledStarCatalogue {
+        let path = std::env::temp_dir().join(format!("stellar-guesser-test-{name}-{}.{TILED_STARS_EXTENSION}", std::process::id()));
+        std::fs::write(&path, write_catalogue(15.0, stars)).unwrap();
+        TiledStarCatalogue::open(path).unwrap()
+    }
+
+    #[test]
+    fn loads_tiles_in_background() {
+        let mut stars = vec![(10.0, 10.0, 5.0, 0.5), (11.0, 11.0, 9.0, f32::NAN)];
+        for i in 0..(RECORDS_PER_READ + 10) {
+            stars.push((12.0, 12.0, 10.0 + i as f32 * 0.001, 0.0));
+        }
+        let mut catalogue = open_catalogue("background", &stars);
+        let (loader, receiver) = spawn_loader();
+        catalogue.load_in_background(loader);
+
+        let centre = (angle::Deg(10.0), angle::Deg(10.0));
+        assert!(catalogue.stars_in_view(centre, angle::Deg(3.0), 6.0).is_empty());
+        // The tile is only asked for once while the stars are being read
+        assert!(catalogue.stars_in_view(centre, angle::Deg(3.0), 6.0).is_empty());
+        let loaded = receiver.recv_timeout(std::time::Duration::from_secs(10)).unwrap();
+        assert!(receiver.recv_timeout(std::time::Duration::from_millis(100)).is_err());
+        catalogue.add_loaded_tile(loaded);
+        let bright = catalogue.stars_in_view(centre, angle::Deg(3.0), 6.0);
+        assert_eq!(bright.len(), 1);
+        assert_eq!(bright[0].vmag, 5.0);
+
+        // Fainter stars are read after the ones already loaded, which are shown in the meantime
+        assert_eq!(catalogue.stars_in_view(centre, angle::Deg(3.0), 20.0).len(), RECORDS_PER_READ);
+        catalogue.add_loaded_tile(receiver.recv_timeout(std::time::Duration::from_secs(10)).unwrap());
+        assert_eq!(catalogue.stars_in_view(centre, angle::Deg(3.0), 20.0).len(), stars.len());
+        std::fs::remove_file(&catalogue.path).unwrap();
+    }
+
+    #[test]
+    fn drops_the_least_recently_viewed_tiles() {
+        let mut catalogue = open_catalogue("lru", &[(10.0, 10.0, 5.0, 0.5), (11.0, 11.0, 6.0, 0.5), (200.0, -40.0, 6.0, 1.0), (100.0, 50.0, 6.0, 1.0)]);
+        catalogue.max_loaded_stars = 2;
+        let north = (angle::Deg(10.0), angle::Deg(10.0));
+        let south = (angle::Deg(200.0), angle::Deg(-40.0));
+        assert_eq!(catalogue.stars_in_view(north, angle::Deg(3.0), 8.0).len(), 2);
+        assert_eq!(catalogue.stars_in_view(south, angle::Deg(1.0), 8.0).len(), 1);
+        assert_eq!(catalogue.loaded_stars, 1);
+        assert!(catalogue.tiles.iter().filter(|tile| tile.dec_min >= 0.0 && tile.dec_max <= 15.0).all(|tile| tile.stars.is_empty()));
+
+        // The tiles in view are kept even over the limit
+        catalogue.max_loaded_stars = 0;
+        assert_eq!(catalogue.stars_in_view(north, angle::Deg(3.0), 8.0).len(), 2);
+        assert_eq!(catalogue.loaded_stars, 2);
+        // And dropped once something else is looked at
+        assert_eq!(catalogue.stars_in_view((angle::Deg(100.0), angle::Deg(50.0)), angle::Deg(1.0), 8.0).len(), 1);
+        assert_eq!(catalogue.loaded_stars, 1);
+        std::fs::remove_file(&catalogue.path).unwrap();
+    }
+
+    #[test]
+    fn limiting_magnitude() {
+        let settings = TiledStarsSettings::default();
+        assert_eq!(settings.limiting_magnitude(settings.max_fov + 1.0), None);
+        assert_eq!(
+            settings.limiting_magnitude(settings.max_fov / 2.0),
+            Some(settings.limiting_magnitude + settings.magnitude_per_fov_halving)
+        );
+        assert_eq!(settings.limiting_magnitude(0.001), Some(settings.faintest_magnitude));
+    }
+}
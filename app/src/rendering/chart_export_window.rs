@@ -0,0 +1,256 @@
+use crate::{
+    export::{
+        self,
+        screenshot::{prepare_screenshot, save_screenshot},
+        sky_chart::build_chart,
+        worksheet::{generate_worksheet, save_worksheet, WorksheetFormat},
+        ChartFormat,
+    },
+    files,
+    localisation::tr,
+    public_constants, Application,
+};
+use eframe::egui;
+use sg_geometry::projection::Projection;
+
+impl Application {
+    pub fn render_chart_export_window(&mut self, ctx: &egui::Context) -> Option<egui::InnerResponse<Option<()>>> {
+        let mut opened = self.state.windows.chart_export.opened;
+        let response = egui::Window::new(tr("Export chart")).id(egui::Id::new("Export chart")).open(&mut opened).show(ctx, |ui| {
+            let state = &mut self.state.windows.chart_export;
+            let settings = &mut state.settings;
+            ui.checkbox(&mut settings.current_view, tr("Current view"))
+                .on_hover_text(tr("Export what is on the screen instead of the region given below"));
+            ui.add_enabled_ui(!settings.current_view, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut settings.centre_ra.0).speed(0.5).suffix("°"));
+                    ui.label(tr("Right ascension of the centre"));
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut settings.centre_dec.0).speed(0.5).suffix("°"));
+                    ui.label(tr("Declination of the centre"));
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut settings.fov).speed(0.5).suffix("°"));
+                    ui.label(tr("Field of view")).on_hover_text(tr("Measured across the diagonal of the page"));
+                });
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("Chart projection").selected_text(format!("{}", settings.projection)).show_ui(ui, |ui| {
+                        for val in Projection::variants() {
+                            ui.selectable_value(&mut settings.projection, val, format!("{val}"));
+                        }
+                    });
+                    ui.label(tr("Projection"));
+                });
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.magnitude_limit).speed(0.05));
+                ui.label(tr("Faintest stars"));
+            });
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.width).speed(1.0).suffix(" pt"));
+                ui.label("×");
+                ui.add(egui::DragValue::new(&mut settings.height).speed(1.0).suffix(" pt"));
+                ui.label(tr("Page size")).on_hover_text(tr("In points (1/72 of an inch), A4 is 842 × 595 pt"));
+            });
+            ui.checkbox(&mut settings.lines, tr("Lines"));
+            ui.checkbox(&mut settings.deepskies, tr("Deep-sky objects"));
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut settings.grid, tr("Coordinate grid"));
+                ui.add_enabled(settings.grid, egui::DragValue::new(&mut settings.grid_step).speed(0.5).suffix("°"));
+            });
+            ui.checkbox(&mut settings.blank, tr("Blank chart"))
+                .on_hover_text(tr("Leave out all the names, for practising with pen and paper"));
+            ui.add_enabled_ui(!settings.blank, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut settings.star_names_magnitude_limit).speed(0.05));
+                    ui.label(tr("Faintest stars with names"));
+                });
+            });
+            settings.clamp();
+            ui.separator();
+            ui.horizontal(|ui| {
+                for format in ChartFormat::variants() {
+                    ui.selectable_value(&mut state.format, format, format!("{format}"));
+                }
+            });
+            if ui.button(tr("Export")).clicked() {
+                let chart = build_chart(&self.cellestial_sphere, &state.settings, &[], &[]);
+                if let Some(path) = files::get_dir_opt(public_constants::CHARTS_FOLDER) {
+                    let extension = state.format.extension();
+                    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+                    let save_path_opt: Option<std::path::PathBuf> = {
+                        let dialog = rfd::FileDialog::new().add_filter(format!("{}", state.format), &[extension]).set_directory(path);
+                        dialog.save_file()
+                    };
+                    #[cfg(any(target_os = "android", target_os = "ios"))]
+                    let save_path_opt: Option<std::path::PathBuf> = {
+                        let mut save_path_intermediate = path;
+                        save_path_intermediate.push(format!("chart--{}.{extension}", chrono::Local::now().timestamp_millis()));
+                        Some(save_path_intermediate)
+                    };
+                    match save_path_opt {
+                        Some(save_path) => {
+                            if let Err(err) = export::save_chart(&chart, state.format, &save_path) {
+                                log::error!("Failed to save the chart: {err}");
+                            }
+                        }
+                        None => log::info!("Chart saving cancelled by the user"),
+                    }
+                }
+            }
+            ui.separator();
+            self.render_worksheet_section(ui);
+            ui.separator();
+            self.render_screenshot_section(ui);
+        });
+        self.state.windows.chart_export.opened = opened;
+        response
+    }
+
+    fn render_worksheet_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Worksheet"));
+        ui.label(tr("Print questions from a question pack, each on its own page with a chart, and a separate answer key"));
+        let state = &mut self.state.windows.chart_export;
+        if !self.game_handler.question_packs.contains_key(&state.worksheet_question_pack) {
+            state.worksheet_question_pack = self.game_handler.active_question_pack.clone();
+        }
+        let mut pack_names: Vec<&String> = self.game_handler.question_packs.keys().collect();
+        pack_names.sort();
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("Worksheet question pack")
+                .selected_text(state.worksheet_question_pack.clone())
+                .show_ui(ui, |ui| {
+                    for name in pack_names {
+                        ui.selectable_value(&mut state.worksheet_question_pack, name.clone(), name);
+                    }
+                });
+            ui.label(tr("Question pack"));
+        });
+        let settings = &mut state.worksheet;
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut settings.questions_count).range(1..=200));
+            ui.label(tr("Number of questions"));
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut settings.seed));
+            ui.label(tr("Seed")).on_hover_text(tr("The same seed always gives the same questions"));
+            if ui.button(tr("Random")).clicked() {
+                settings.seed = rand::random();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut settings.chart.fov).speed(0.5).suffix("°"));
+            ui.label(tr("Field of view of the charts"));
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut settings.chart.magnitude_limit).speed(0.05));
+            ui.label(tr("Faintest stars"));
+        });
+        ui.checkbox(&mut settings.chart.blank, tr("Blank charts"))
+            .on_hover_text(tr("Leave out all the names, so that the charts do not give the answers away"));
+        settings.chart.clamp();
+        ui.horizontal(|ui| {
+            for format in WorksheetFormat::variants() {
+                ui.selectable_value(&mut state.worksheet_format, format, format!("{format}"));
+            }
+        });
+        if ui.button(tr("Generate worksheet")).clicked() {
+            let Some(question_pack) = self.game_handler.question_packs.get(&state.worksheet_question_pack) else {
+                log::error!("Could not find the {} question pack", state.worksheet_question_pack);
+                return;
+            };
+            let worksheet = generate_worksheet(&self.cellestial_sphere, question_pack, &state.worksheet);
+            if worksheet.pages.is_empty() {
+                log::warn!("None of the questions of the {} question pack can be put on a worksheet", state.worksheet_question_pack);
+            }
+            if let Some(path) = files::get_dir_opt(public_constants::CHARTS_FOLDER) {
+                let extension = state.worksheet_format.extension();
+                #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+                let save_path_opt: Option<std::path::PathBuf> = {
+                    let dialog = rfd::FileDialog::new().add_filter(format!("{}", state.worksheet_format), &[extension]).set_directory(path);
+                    dialog.save_file()
+                };
+                #[cfg(any(target_os = "android", target_os = "ios"))]
+                let save_path_opt: Option<std::path::PathBuf> = {
+                    let mut save_path_intermediate = path;
+                    save_path_intermediate.push(format!("worksheet--{}.{extension}", chrono::Local::now().timestamp_millis()));
+                    Some(save_path_intermediate)
+                };
+                match save_path_opt {
+                    Some(save_path) => {
+                        if let Err(err) = save_worksheet(&worksheet, state.worksheet_format, &save_path) {
+                            log::error!("Failed to save the worksheet: {err}");
+                        }
+                    }
+                    None => log::info!("Worksheet saving cancelled by the user"),
+                }
+            }
+        }
+    }
+
+    fn render_screenshot_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Screenshot"));
+        ui.label(tr("Save the current view as a PNG image of any size, independent of the size of the window"));
+        let settings = &mut self.state.windows.chart_export.screenshot;
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut settings.width).speed(10.0).suffix(" px"));
+            ui.label("×");
+            ui.add(egui::DragValue::new(&mut settings.height).speed(10.0).suffix(" px"));
+            ui.label(tr("Resolution"));
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut settings.pixels_per_point).speed(0.05));
+            ui.label(tr("Scale"))
+                .on_hover_text(tr("How thick the lines and how large the text are, 1 is the size on a standard screen"));
+        });
+        ui.checkbox(&mut settings.answer_review, tr("Answer review"))
+            .on_hover_text(tr("Include the review of the answer when taking the screenshot after answering a question"));
+        settings.clamp();
+        let saving = self.threads_communication.screenshot.is_some();
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!saving, egui::Button::new(tr("Save screenshot"))).clicked() {
+                self.save_screenshot();
+            }
+            if saving {
+                ui.spinner();
+                ui.label(tr("Saving the screenshot"));
+            }
+        });
+    }
+
+    /// Asks where to save the screenshot, then lays it out here and leaves the rasterisation and the saving to another thread, as large images take seconds to rasterise
+    fn save_screenshot(&mut self) {
+        let Some(path) = files::get_dir_opt(public_constants::SCREENSHOTS_FOLDER) else {
+            return;
+        };
+        #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+        let save_path_opt: Option<std::path::PathBuf> = {
+            let dialog = rfd::FileDialog::new().add_filter("PNG", &["png"]).set_directory(path);
+            dialog.save_file()
+        };
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        let save_path_opt: Option<std::path::PathBuf> = {
+            let mut save_path_intermediate = path;
+            save_path_intermediate.push(format!("screenshot--{}.png", chrono::Local::now().timestamp_millis()));
+            Some(save_path_intermediate)
+        };
+        let Some(save_path) = save_path_opt else {
+            log::info!("Screenshot saving cancelled by the user");
+            return;
+        };
+        let settings = &self.state.windows.chart_export.screenshot;
+        let answer_review = if settings.answer_review { self.game_handler.get_answer_review() } else { None };
+        let screenshot = prepare_screenshot(&mut self.cellestial_sphere, &self.theme, answer_review, settings);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.threads_communication.screenshot = Some(receiver);
+        std::thread::spawn(move || {
+            let image = screenshot.rasterise();
+            let result = save_screenshot(&image, &save_path).map(|_| save_path).map_err(|err| err.to_string());
+            if let Err(err) = sender.send(result) {
+                log::error!("Failed to send the result of saving the screenshot: {}", err);
+            }
+        });
+    }
+}
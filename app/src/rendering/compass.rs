@@ -0,0 +1,45 @@
+use crate::renderer::CellestialSphere;
+use eframe::egui;
+use nalgebra::Vector3;
+
+const COMPASS_RADIUS: f32 = 24.0;
+/// Distance of the centre of the compass from the bottom left corner of the screen
+const COMPASS_OFFSET: f32 = 40.0;
+
+/// The direction on the screen in which the tangent vector (at the centre of the view, without rotations) points
+fn screen_direction(cellestial_sphere: &CellestialSphere, tangent: &Vector3<f32>) -> egui::Vec2 {
+    let camera_vector = cellestial_sphere.rotation * tangent;
+    egui::vec2(camera_vector.x, camera_vector.y).normalized()
+}
+
+/// Draws arrows showing where north and east are at the centre of the view
+pub fn render_compass(cellestial_sphere: &CellestialSphere, painter: &egui::Painter) {
+    let centre = cellestial_sphere.view_centre().normalize();
+    let Some(north) = (Vector3::z() - centre * centre.z).try_normalize(1e-4) else {
+        // Looking right at a pole, every direction is south (or north)
+        return;
+    };
+    let east = centre.cross(&north);
+    let north = screen_direction(cellestial_sphere, &north);
+    let east = screen_direction(cellestial_sphere, &east);
+
+    let visuals = painter.ctx().style().visuals.clone();
+    let compass_centre = cellestial_sphere.viewport_rect.left_bottom() + egui::vec2(COMPASS_OFFSET, -COMPASS_OFFSET);
+    painter.circle(
+        compass_centre,
+        COMPASS_RADIUS,
+        visuals.extreme_bg_color.gamma_multiply(0.6),
+        egui::Stroke::new(1.0, visuals.weak_text_color()),
+    );
+    painter.arrow(compass_centre, north * COMPASS_RADIUS * 0.8, egui::Stroke::new(2.0, visuals.strong_text_color()));
+    painter.line_segment([compass_centre, compass_centre + east * COMPASS_RADIUS * 0.6], egui::Stroke::new(1.0, visuals.text_color()));
+    let font = egui::FontId::proportional(12.0);
+    painter.text(
+        compass_centre + north * (COMPASS_RADIUS + 8.0),
+        egui::Align2::CENTER_CENTER,
+        "N",
+        font.clone(),
+        visuals.strong_text_color(),
+    );
+    painter.text(compass_centre + east * (COMPASS_RADIUS + 8.0), egui::Align2::CENTER_CENTER, "E", font, visuals.text_color());
+}
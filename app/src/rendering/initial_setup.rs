@@ -1,6 +1,6 @@
 use eframe::egui;
 
-use crate::Application;
+use crate::{localisation::tr, Application};
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub enum InitialSetupStage {
@@ -34,15 +34,15 @@ pub fn render_initial_setup(app: &mut Application, ctx: &egui::Context, availabl
             modal.area(modal_area).show(ctx, |ui| {
                 ui.set_width(modal_width);
                 ui.set_max_height(modal_height);
-                ui.heading("Onboarding");
+                ui.heading(tr("Onboarding"));
                 egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
-                    ui.label("The following screens will guide you through some essential setup and information you may find useful. Everything that gets configured now can later be changed in the settings window, where you can also find the rest of the settings that are not shown here.")
+                    ui.label(tr("The following screens will guide you through some essential setup and information you may find useful. Everything that gets configured now can later be changed in the settings window, where you can also find the rest of the settings that are not shown here."))
                 });
                 egui::Sides::new().show(
                     ui,
                     |_ui| {},
                     |ui| {
-                        if ui.button("Next").clicked() {
+                        if ui.button(tr("Next")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Keyboard;
                         }
                     },
@@ -55,7 +55,7 @@ pub fn render_initial_setup(app: &mut Application, ctx: &egui::Context, availabl
             modal.area(modal_area).show(ctx, |ui| {
                 ui.set_width(modal_width);
                 ui.set_max_height(modal_height);
-                ui.heading("Onboarding - Keyboard setup");
+                ui.heading(tr("Onboarding - Keyboard setup"));
                 egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
                     app.render_application_settings_input_subwindow(ui);
                 });
@@ -63,10 +63,10 @@ pub fn render_initial_setup(app: &mut Application, ctx: &egui::Context, availabl
                     ui,
                     |_ui| {},
                     |ui| {
-                        if ui.button("Next").clicked() {
+                        if ui.button(tr("Next")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Community;
                         }
-                        if ui.button("Back").clicked() {
+                        if ui.button(tr("Back")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Introduction;
                         }
                     },
@@ -79,7 +79,7 @@ pub fn render_initial_setup(app: &mut Application, ctx: &egui::Context, availabl
             modal.area(modal_area).show(ctx, |ui| {
                 ui.set_width(modal_width);
                 ui.set_max_height(modal_height);
-                ui.heading("Onboarding - Community");
+                ui.heading(tr("Onboarding - Community"));
                 egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
                     crate::rendering::feedback_and_help_window::render_feedback_and_support_window_inner(ui);
                 });
@@ -87,10 +87,10 @@ pub fn render_initial_setup(app: &mut Application, ctx: &egui::Context, availabl
                     ui,
                     |_ui| {},
                     |ui| {
-                        if ui.button("Next").clicked() {
+                        if ui.button(tr("Next")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Credits;
                         }
-                        if ui.button("Back").clicked() {
+                        if ui.button(tr("Back")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Keyboard;
                         }
                     },
@@ -103,16 +103,16 @@ pub fn render_initial_setup(app: &mut Application, ctx: &egui::Context, availabl
             modal.area(modal_area).show(ctx, |ui| {
                 ui.set_width(modal_width);
                 ui.set_max_height(modal_height);
-                ui.heading("Onboarding - Credits");
+                ui.heading(tr("Onboarding - Credits"));
                 sg_credits::ui::render_credits_inner(ui);
                 egui::Sides::new().show(
                     ui,
                     |_ui| {},
                     |ui| {
-                        if ui.button("Finish").clicked() {
+                        if ui.button(tr("Finish")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Finished;
                         }
-                        if ui.button("Back").clicked() {
+                        if ui.button(tr("Back")).clicked() {
                             app.initial_setup_stage = InitialSetupStage::Community;
                         }
                     },
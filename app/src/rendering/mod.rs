@@ -1,10 +1,15 @@
 pub mod app_info_window;
 pub mod caspr;
+pub mod chart_export_window;
+pub mod compass;
 pub mod credits_window;
 pub mod feedback_and_help_window;
 pub mod initial_setup;
+pub mod night_vision;
+pub mod object_info_window;
 pub mod question_display;
 pub mod rendering_handler;
+pub mod search_window;
 pub mod settings_window;
 pub mod stats_window;
 mod testing_window;
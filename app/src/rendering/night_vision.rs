@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use eframe::egui;
+use egui::{epaint, Color32, Shape};
+
+/// The dimmest allowed brightness cap, so that the screen does not go completely black
+pub const MIN_BRIGHTNESS: f32 = 0.05;
+
+/// Turns the colour into a red of the same luminance, with the brightness scaled down so that white becomes the brightest allowed red
+pub fn night_vision_colour(colour: Color32, brightness_cap: f32) -> Color32 {
+    if colour == Color32::PLACEHOLDER {
+        return colour;
+    }
+    // The colours are premultiplied, which the luminance (being a linear combination of the channels) does not mind
+    let luminance = 0.2126 * colour.r() as f32 + 0.7152 * colour.g() as f32 + 0.0722 * colour.b() as f32;
+    let red = (luminance * brightness_cap.clamp(MIN_BRIGHTNESS, 1.0)).round().clamp(0.0, 255.0) as u8;
+    Color32::from_rgba_premultiplied(red, 0, 0, colour.a())
+}
+
+fn map_path_stroke(stroke: &mut epaint::PathStroke, brightness_cap: f32) {
+    if let epaint::ColorMode::Solid(colour) = &mut stroke.color {
+        *colour = night_vision_colour(*colour, brightness_cap);
+    }
+}
+
+/// Recolours everything the shape draws, including text and images (textures are multiplied by the red tint, so only their red channel is left)
+pub fn night_vision_shape(shape: &mut Shape, brightness_cap: f32) {
+    let map = |colour: &mut Color32| *colour = night_vision_colour(*colour, brightness_cap);
+    match shape {
+        Shape::Noop | Shape::Callback(_) => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                night_vision_shape(shape, brightness_cap);
+            }
+        }
+        Shape::Circle(circle) => {
+            map(&mut circle.fill);
+            map(&mut circle.stroke.color);
+        }
+        Shape::Ellipse(ellipse) => {
+            map(&mut ellipse.fill);
+            map(&mut ellipse.stroke.color);
+        }
+        Shape::LineSegment { stroke, .. } => map(&mut stroke.color),
+        Shape::Path(path) => {
+            map(&mut path.fill);
+            map_path_stroke(&mut path.stroke, brightness_cap);
+        }
+        Shape::Rect(rect) => {
+            map(&mut rect.fill);
+            map(&mut rect.stroke.color);
+        }
+        Shape::Text(text) => {
+            map(&mut text.fallback_color);
+            map(&mut text.underline.color);
+            if let Some(colour) = &mut text.override_text_color {
+                map(colour);
+            }
+            let galley = Arc::make_mut(&mut text.galley);
+            for row in &mut galley.rows {
+                for vertex in &mut row.visuals.mesh.vertices {
+                    map(&mut vertex.color);
+                }
+            }
+        }
+        Shape::Mesh(mesh) => {
+            for vertex in &mut Arc::make_mut(mesh).vertices {
+                map(&mut vertex.color);
+            }
+        }
+        Shape::QuadraticBezier(bezier) => {
+            map(&mut bezier.fill);
+            map_path_stroke(&mut bezier.stroke, brightness_cap);
+        }
+        Shape::CubicBezier(bezier) => {
+            map(&mut bezier.fill);
+            map_path_stroke(&mut bezier.stroke, brightness_cap);
+        }
+    }
+}
+
+/// Recolours everything painted so far in this frame. It has to be called after everything else is drawn.
+pub fn apply_night_vision(ctx: &egui::Context, brightness_cap: f32) {
+    // The panels are painted onto the background layer, which is not among the areas
+    let mut layer_ids = vec![egui::LayerId::background()];
+    layer_ids.extend(ctx.memory(|memory| memory.layer_ids().collect::<Vec<egui::LayerId>>()));
+    ctx.graphics_mut(|graphics| {
+        for layer_id in layer_ids {
+            if let Some(paint_list) = graphics.get_mut(layer_id) {
+                for index in 0..paint_list.next_idx().0 {
+                    paint_list.mutate_shape(egui::layers::ShapeIdx(index), |clipped_shape| night_vision_shape(&mut clipped_shape.shape, brightness_cap));
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours_become_dim_red() {
+        assert_eq!(night_vision_colour(Color32::WHITE, 0.5), Color32::from_rgb(128, 0, 0));
+        assert_eq!(night_vision_colour(Color32::BLACK, 0.5), Color32::BLACK);
+        // Blue is darker than green, so it stays darker
+        assert!(night_vision_colour(Color32::BLUE, 1.0).r() < night_vision_colour(Color32::GREEN, 1.0).r());
+        // Transparency is kept
+        assert_eq!(night_vision_colour(Color32::TRANSPARENT, 1.0), Color32::TRANSPARENT);
+        assert_eq!(night_vision_colour(Color32::from_white_alpha(100), 1.0).a(), 100);
+    }
+
+    #[test]
+    fn whole_frame_is_red() {
+        let ctx = egui::Context::default();
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(800.0, 600.0))),
+            ..Default::default()
+        };
+        let run = |input: egui::RawInput| {
+            ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.painter().circle_filled(egui::pos2(100.0, 100.0), 10.0, Color32::LIGHT_BLUE);
+                    ui.label("Sky");
+                });
+                egui::Window::new("Window").show(ctx, |ui| ui.colored_label(Color32::GREEN, "Text"));
+                apply_night_vision(ctx, 0.5);
+            })
+        };
+        // Windows are only measured in the first frame, so the second one is checked
+        run(input.clone());
+        let output = run(input);
+        let mut shapes = output.shapes.into_iter().map(|clipped_shape| clipped_shape.shape).collect::<Vec<Shape>>();
+        assert!(!shapes.is_empty());
+        let mut colours = Vec::new();
+        let mut texts = Vec::new();
+        while let Some(shape) = shapes.pop() {
+            match shape {
+                Shape::Vec(inner) => shapes.extend(inner),
+                Shape::Circle(circle) => colours.extend([circle.fill, circle.stroke.color]),
+                Shape::Rect(rect) => colours.extend([rect.fill, rect.stroke.color]),
+                Shape::Text(text) => {
+                    texts.push(text.galley.job.text.clone());
+                    colours.push(text.fallback_color);
+                    colours.extend(text.galley.rows.iter().flat_map(|row| row.visuals.mesh.vertices.iter().map(|vertex| vertex.color)));
+                }
+                _ => {}
+            }
+        }
+        assert!(texts.contains(&"Text".to_string()), "The window was not drawn: {texts:?}");
+        // The placeholder is replaced by the (already red) fallback colour of the text when drawing
+        colours.retain(|colour| *colour != Color32::PLACEHOLDER);
+        assert!(colours.iter().any(|colour| colour.r() > 0));
+        assert!(colours.iter().all(|colour| colour.g() == 0 && colour.b() == 0 && colour.r() <= 128), "{colours:?}");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    enums::RendererCategory,
+    game::{ObjectType, QuestionObject},
+    rendering::caspr::{
+        markers::game_markers::{GameMarker, GameMarkerType},
+        stars::StarRenderer,
+    },
+    Application,
+};
+use eframe::egui;
+
+/// How far from an object (in points) a click can be to still select it
+pub const SELECTION_DISTANCE: f32 = 20.0;
+const SELECTION_MARKER_RADIUS: f32 = 12.0;
+
+impl Application {
+    /// Selects the object closest to the screen position, or clears the selection if there is none
+    pub fn select_object_at(&mut self, screen_pos: egui::Pos2) {
+        let sphere = &self.cellestial_sphere;
+        let magnitude_to_radius = sphere.sky_settings.mag_to_radius_settings[sphere.sky_settings.mag_to_radius_id];
+        let object_id = sphere
+            .object_at_screen_pos(screen_pos, SELECTION_DISTANCE, |object| match object.object_type {
+                // Stars too faint to be seen can not be clicked on
+                ObjectType::Star(_) => object
+                    .mag
+                    .is_some_and(|mag| StarRenderer::radius_enough_to_render(StarRenderer::magnitude_to_radius(magnitude_to_radius, mag, angle::Deg(sphere.fov)))),
+                ObjectType::Deepsky(_) => true,
+            })
+            .map(|object| object.object_id);
+        self.select_object(object_id);
+    }
+
+    /// Shows the information about the object and highlights it, None clears the selection
+    pub fn select_object(&mut self, object_id: Option<u64>) {
+        let object = object_id.and_then(|object_id| find_question_object(&self.cellestial_sphere.question_objects, object_id));
+        self.cellestial_sphere.game_markers.selection = object.map(|object| {
+            GameMarker::new(
+                GameMarkerType::Selection,
+                object.ra,
+                object.dec,
+                2.0,
+                SELECTION_MARKER_RADIUS,
+                true,
+                false,
+                &self.theme.game_visuals.game_markers_colours,
+            )
+        });
+        self.cellestial_sphere.game_markers.selection_lines = Vec::new();
+        self.state.windows.object_info.object_id = object.map(|object| object.object_id);
+        self.state.windows.object_info.opened = object.is_some();
+        self.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+    }
+
+    pub fn render_object_info_window(&mut self, ctx: &egui::Context) -> Option<egui::InnerResponse<Option<()>>> {
+        let object = self
+            .state
+            .windows
+            .object_info
+            .object_id
+            .and_then(|object_id| find_question_object(&self.cellestial_sphere.question_objects, object_id))?;
+        let mut opened = self.state.windows.object_info.opened;
+        let response = egui::Window::new("Object information").open(&mut opened).show(ctx, |ui| {
+            ui.heading(object.display_name());
+            egui::Grid::new("object_info_grid").num_columns(2).striped(true).show(ui, |ui| {
+                let mut row = |label: &str, value: String| {
+                    ui.label(label);
+                    ui.label(value);
+                    ui.end_row();
+                };
+                row("Object type", object.type_name());
+                if object.proper_names_full.len() > 1 || (object.proper_names_full.len() == 1 && object.display_name() != object.proper_names_full[0]) {
+                    row("Proper names", object.proper_names_full.join(", "));
+                }
+                let designations = object.designations();
+                if !designations.is_empty() {
+                    row("Designations", designations.join(", "));
+                }
+                if let Some(mag) = object.mag {
+                    row("Magnitude", format!("{mag:.2}"));
+                }
+                if let Some(bv) = object.bv {
+                    row("B-V colour index", format!("{bv:.2}"));
+                }
+                // Unknown distances are stored as negative numbers
+                if let Some(distance) = object.distance.filter(|distance| *distance > 0.0) {
+                    row("Distance", format!("{distance} ly"));
+                }
+                let constellations = object
+                    .constellations_abbreviations
+                    .iter()
+                    .filter(|abbreviation| !abbreviation.is_empty())
+                    .map(|abbreviation| {
+                        match self
+                            .cellestial_sphere
+                            .constellations
+                            .get(&abbreviation.to_lowercase())
+                            .map(|constellation| constellation.display_name())
+                        {
+                            Some(name) => format!("{name} ({abbreviation})"),
+                            None => abbreviation.to_owned(),
+                        }
+                    })
+                    .collect::<Vec<String>>();
+                if !constellations.is_empty() {
+                    row("Constellation", constellations.join(", "));
+                }
+                row("Coordinates", format!("ra = {:.3}°; dec = {:.3}°", object.ra.0, object.dec.0));
+            });
+            for image in &object.images {
+                ui.add(egui::Image::new(&image.path).max_width(600.0));
+                if let Some(image_source) = &image.source {
+                    ui.hyperlink_to("Image source", image_source);
+                }
+            }
+        });
+        if !opened {
+            self.select_object(None);
+        }
+        response
+    }
+}
+
+pub fn find_question_object(objects: &[QuestionObject], object_id: u64) -> Option<&QuestionObject> {
+    // The objects are sorted by their IDs when loaded
+    objects.binary_search_by_key(&object_id, |object| object.object_id).ok().map(|i| &objects[i])
+}
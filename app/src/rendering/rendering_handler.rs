@@ -48,6 +48,24 @@ impl Application {
                 [response.response.rect.left(), response.response.rect.bottom()],
             ]);
         }
+        if let Some(response) = self.render_object_info_window(ctx) {
+            window_rectangles.push([
+                [response.response.rect.right(), response.response.rect.top()],
+                [response.response.rect.left(), response.response.rect.bottom()],
+            ]);
+        }
+        if let Some(response) = self.render_search_window(ctx) {
+            window_rectangles.push([
+                [response.response.rect.right(), response.response.rect.top()],
+                [response.response.rect.left(), response.response.rect.bottom()],
+            ]);
+        }
+        if let Some(response) = self.render_chart_export_window(ctx) {
+            window_rectangles.push([
+                [response.response.rect.right(), response.response.rect.top()],
+                [response.response.rect.left(), response.response.rect.bottom()],
+            ]);
+        }
         if let Some(response) = self.render_testing_window(ctx) {
             window_rectangles.push([
                 [response.response.rect.right(), response.response.rect.top()],
@@ -60,6 +78,9 @@ impl Application {
 
                 let painter = ui.painter();
                 self.cellestial_sphere.render_sky(painter);
+                if self.cellestial_sphere.sky_settings.show_compass {
+                    crate::rendering::compass::render_compass(&self.cellestial_sphere, painter);
+                }
             })
             .response
             .interact(egui::Sense::click_and_drag());
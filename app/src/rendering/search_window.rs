@@ -0,0 +1,77 @@
+use crate::{
+    enums::RendererCategory,
+    rendering::{caspr::search::SearchTarget, object_info_window::find_question_object},
+    Application,
+};
+use eframe::egui;
+use nalgebra::Matrix3;
+
+const SUGGESTIONS_COUNT: usize = 10;
+const CONSTELLATION_BORDER_WIDTH: f32 = 2.0;
+/// When the view is wider than this (in degrees), the camera zooms in when going to an object
+const GO_TO_MAX_FOV: f32 = 60.0;
+
+impl Application {
+    /// Smoothly moves the camera to the object or the constellation and highlights it
+    pub fn go_to(&mut self, target: &SearchTarget) {
+        let zoom = (self.cellestial_sphere.fov > GO_TO_MAX_FOV).then(|| self.cellestial_sphere.fov_to_zoom(GO_TO_MAX_FOV));
+        match target {
+            SearchTarget::Object(object_id) => {
+                self.select_object(Some(*object_id));
+                if let Some(object) = find_question_object(&self.cellestial_sphere.question_objects, *object_id) {
+                    let point = sg_geometry::get_point_vector(object.ra, object.dec, &Matrix3::identity());
+                    self.cellestial_sphere.transition_to_point(&point, zoom);
+                }
+            }
+            SearchTarget::Constellation(abbreviation) => {
+                self.select_object(None);
+                let Some(constellation) = self.cellestial_sphere.constellations.get(abbreviation) else {
+                    log::error!("Could not find the {abbreviation} constellation");
+                    return;
+                };
+                let centre = constellation.centre();
+                self.cellestial_sphere.game_markers.selection_lines = constellation.border_lines(CONSTELLATION_BORDER_WIDTH);
+                self.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
+                if let Some(centre) = centre {
+                    self.cellestial_sphere.transition_to_point(&centre, zoom);
+                }
+            }
+        }
+    }
+
+    pub fn render_search_window(&mut self, ctx: &egui::Context) -> Option<egui::InnerResponse<Option<()>>> {
+        let mut opened = self.state.windows.search.opened;
+        let mut chosen = None;
+        let response = egui::Window::new("Search").open(&mut opened).show(ctx, |ui| {
+            let query = &mut self.state.windows.search.query;
+            let text_edit = ui.add(egui::TextEdit::singleline(query).hint_text("M42, Betelgeuse, alpha Orionis, Orion..."));
+            let submitted = text_edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let results = self.cellestial_sphere.search_index.search(query, SUGGESTIONS_COUNT);
+            if !query.is_empty() && results.is_empty() {
+                ui.label("Nothing was found");
+            }
+            for entry in &results {
+                let description = match &entry.target {
+                    SearchTarget::Object(object_id) => match find_question_object(&self.cellestial_sphere.question_objects, *object_id) {
+                        Some(object) if object.display_name() != entry.name => format!("{} - {}, {}", entry.name, object.display_name(), object.type_name().to_lowercase()),
+                        Some(object) => format!("{} - {}", entry.name, object.type_name().to_lowercase()),
+                        None => entry.name.clone(),
+                    },
+                    SearchTarget::Constellation(_) => format!("{} - constellation", entry.name),
+                };
+                if ui.selectable_label(false, description).clicked() {
+                    chosen = Some(entry.target.clone());
+                }
+            }
+            // Pressing enter goes to the best match
+            if submitted && chosen.is_none() {
+                chosen = results.first().map(|entry| entry.target.clone());
+            }
+        });
+        self.state.windows.search.opened = opened;
+        if let Some(target) = chosen {
+            self.go_to(&target);
+        }
+        response
+    }
+}
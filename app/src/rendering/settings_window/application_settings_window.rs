@@ -1,6 +1,14 @@
 use eframe::egui;
 
-use crate::{files, public_constants, structs::state::windows::settings::ApplicationSettingsSubWindow, Application};
+use crate::{
+    files,
+    keybindings::{Action, KeyBinding},
+    localisation::{self, tr, tr_args, Language},
+    public_constants,
+    rendering::{night_vision, settings_window::theme_editor_window},
+    structs::state::windows::settings::ApplicationSettingsSubWindow,
+    Application,
+};
 
 impl Application {
     pub fn render_application_settings_window(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
@@ -8,12 +16,22 @@ impl Application {
             ui.selectable_value(
                 &mut self.state.windows.settings.application_settings.subwindow,
                 ApplicationSettingsSubWindow::Input,
-                ApplicationSettingsSubWindow::Input.as_ref(),
+                tr(ApplicationSettingsSubWindow::Input.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.application_settings.subwindow,
                 ApplicationSettingsSubWindow::Theme,
-                ApplicationSettingsSubWindow::Theme.as_ref(),
+                tr(ApplicationSettingsSubWindow::Theme.as_ref()),
+            );
+            ui.selectable_value(
+                &mut self.state.windows.settings.application_settings.subwindow,
+                ApplicationSettingsSubWindow::ThemeEditor,
+                tr(ApplicationSettingsSubWindow::ThemeEditor.as_ref()),
+            );
+            ui.selectable_value(
+                &mut self.state.windows.settings.application_settings.subwindow,
+                ApplicationSettingsSubWindow::Language,
+                tr(ApplicationSettingsSubWindow::Language.as_ref()),
             );
         });
         ui.separator();
@@ -22,18 +40,40 @@ impl Application {
             .show(ui, |ui| match self.state.windows.settings.application_settings.subwindow {
                 ApplicationSettingsSubWindow::Input => self.render_application_settings_input_subwindow(ui),
                 ApplicationSettingsSubWindow::Theme => self.render_application_settings_theme_subwindow(ctx, ui),
+                ApplicationSettingsSubWindow::ThemeEditor => self.render_theme_editor(ctx, ui),
+                ApplicationSettingsSubWindow::Language => self.render_application_settings_language_subwindow(ui),
             });
     }
 
+    pub fn render_application_settings_language_subwindow(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Language"));
+        ui.label(tr(
+            "The language of the user interface and of the names of the constellations and objects. Answers are accepted in all the languages.",
+        ));
+        let mut language = localisation::language();
+        egui::ComboBox::from_id_salt("Language").selected_text(language.to_string()).show_ui(ui, |ui| {
+            for variant in Language::variants() {
+                ui.selectable_value(&mut language, variant, variant.to_string());
+            }
+        });
+        if language != localisation::language() {
+            localisation::set_language(language);
+        }
+    }
+
     pub fn render_application_settings_input_subwindow(&mut self, ui: &mut egui::Ui) {
         #[cfg(target_os = "android")]
         let previous_display_onscreen_keyboard = self.input.settings.display_onscreen_keyboard;
 
-        ui.heading("Input method");
-        ui.checkbox(&mut self.input.settings.display_onscreen_keyboard, "Use on-screen keyboard");
-        ui.label("You may use this keyboard as a replacement to the default input method provided by your system, both on mobile and desktop.");
-        ui.label("It is known that some devices experience issues with the native keyboard on Android. To get around this, you may choose to use an alternative keyboard built into this application, which does not experience those issues. However, it will be different to what you are used to, so it is up to each user to decide. To help you make the correct choice, please consider and test out the following:\n - If you only want to play the game and answer questions, you will most likely only need the letters and numbers. You can try typing them into the text box below to see if they work as expected.\n - If you want to also edit questions packs or do other \"advanced\" things, you may also need some special characters. The exact set will depend on your use case, so you may find out that some characters are misbehaving later. For now, you can try typing in the following set of characters: \"():,.'");
+        ui.heading(tr("Input method"));
+        ui.checkbox(&mut self.input.settings.display_onscreen_keyboard, tr("Use on-screen keyboard"));
+        ui.label(tr(
+            "You may use this keyboard as a replacement to the default input method provided by your system, both on mobile and desktop.",
+        ));
+        ui.label(tr("It is known that some devices experience issues with the native keyboard on Android. To get around this, you may choose to use an alternative keyboard built into this application, which does not experience those issues. However, it will be different to what you are used to, so it is up to each user to decide. To help you make the correct choice, please consider and test out the following:\n - If you only want to play the game and answer questions, you will most likely only need the letters and numbers. You can try typing them into the text box below to see if they work as expected.\n - If you want to also edit questions packs or do other \"advanced\" things, you may also need some special characters. The exact set will depend on your use case, so you may find out that some characters are misbehaving later. For now, you can try typing in the following set of characters: \"():,.'"));
         ui.text_edit_singleline(&mut self.state.windows.settings.application_settings.test_input);
+        ui.separator();
+        self.render_keybindings(ui);
 
         #[cfg(target_os = "android")]
         if !previous_display_onscreen_keyboard && self.input.settings.display_onscreen_keyboard {
@@ -41,10 +81,69 @@ impl Application {
         }
     }
 
+    fn render_keybindings(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Keyboard shortcuts"));
+        ui.label(tr("Click 'Add' and press the new key combination, or click a shortcut to remove it. Shortcuts without Alt or Ctrl do not work while typing into a text field. The middle mouse button always works as 'Next'."));
+        if let Some(action) = self.state.windows.settings.application_settings.rebinding {
+            // The key press is taken out of the events, so that it does not also trigger the action it is bound to
+            let pressed = ui.ctx().input_mut(|i| {
+                let index = i.events.iter().position(|event| matches!(event, egui::Event::Key { pressed: true, repeat: false, .. }))?;
+                match i.events.remove(index) {
+                    egui::Event::Key { key, modifiers, .. } => Some((key, modifiers)),
+                    _ => None,
+                }
+            });
+            if let Some((key, modifiers)) = pressed {
+                if key != egui::Key::Escape || modifiers.any() {
+                    let binding = KeyBinding::from_event(key, modifiers);
+                    let bindings = self.input.settings.keybindings.get_mut(action);
+                    if !bindings.contains(&binding) {
+                        bindings.push(binding);
+                    }
+                }
+                self.state.windows.settings.application_settings.rebinding = None;
+            }
+        }
+        egui::Grid::new("Keyboard shortcuts").striped(true).show(ui, |ui| {
+            for action in Action::variants() {
+                ui.label(action.to_string());
+                ui.horizontal(|ui| {
+                    let mut to_remove = None;
+                    for (index, binding) in self.input.settings.keybindings.get(action).iter().enumerate() {
+                        let conflicts = self.input.settings.keybindings.conflicts(action, binding);
+                        let mut text = egui::RichText::new(binding.to_string());
+                        if !conflicts.is_empty() {
+                            text = text.color(ui.visuals().warn_fg_color);
+                        }
+                        let mut response = ui.button(text);
+                        if !conflicts.is_empty() {
+                            response = response.on_hover_text(tr_args("Also bound to: {0}", &[&conflicts.iter().map(|action| action.to_string()).collect::<Vec<String>>().join(", ")]));
+                        }
+                        if response.clicked() {
+                            to_remove = Some(index);
+                        }
+                    }
+                    if let Some(index) = to_remove {
+                        self.input.settings.keybindings.get_mut(action).remove(index);
+                    }
+                    if self.state.windows.settings.application_settings.rebinding == Some(action) {
+                        ui.label(tr("Press a key (Escape to cancel)"));
+                    } else if ui.button(tr("Add")).clicked() {
+                        self.state.windows.settings.application_settings.rebinding = Some(action);
+                    }
+                    if ui.button(tr("Reset")).on_hover_text(tr("Restore the default shortcuts")).clicked() {
+                        self.input.settings.keybindings.reset(action);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+    }
+
     pub fn render_application_settings_theme_subwindow(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
         let previous_theme_name = self.theme.name.clone();
         let mut selected_theme_name = self.theme.name.clone();
-        ui.label("Theme: ");
+        ui.label(tr("Theme: "));
         egui::ComboBox::from_id_salt("Theme: ").selected_text(&self.theme.name).show_ui(ui, |ui| {
             ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
             let mut themes = self.themes.themes_names().collect::<Vec<&String>>();
@@ -56,18 +155,38 @@ impl Application {
         if selected_theme_name != previous_theme_name {
             match self.themes.get(&selected_theme_name) {
                 Some(theme) => {
+                    for (field, key) in theme.game_visuals.unknown_keys(&self.cellestial_sphere) {
+                        log::warn!("The theme {selected_theme_name} has a colour for {key} in {field}, which is not loaded");
+                    }
                     self.apply_theme(ctx, theme.clone());
                 }
                 None => log::error!("Failed to get the selected theme: {selected_theme_name}"),
             }
         }
-        ui.heading("Export theme");
-        ui.label("Export the current settings into a theme");
         ui.horizontal(|ui| {
-            ui.label("Theme name: ");
+            ui.label(tr("Game marker colours: ")).on_hover_text(tr(
+                "The colour-blind safe palettes keep the markers distinguishable. The markers also differ in their shapes, which are shown in the theme editor.",
+            ));
+            let mut game_markers_colours = self.theme.game_visuals.game_markers_colours.clone();
+            theme_editor_window::render_palette_selector(ui, "Game marker colours: ", &mut game_markers_colours);
+            if game_markers_colours != self.theme.game_visuals.game_markers_colours {
+                let mut theme = self.theme.clone();
+                theme.game_visuals.game_markers_colours = game_markers_colours;
+                self.apply_theme(ctx, theme);
+            }
+        });
+        ui.heading(tr("Night vision"));
+        ui.checkbox(&mut self.graphics_settings.night_vision, tr("Night vision"))
+            .on_hover_text(tr("Show everything in dim red to keep your eyes adapted to the dark when observing"));
+        ui.add(egui::Slider::new(&mut self.graphics_settings.night_vision_brightness, night_vision::MIN_BRIGHTNESS..=1.0).text(tr("Maximum brightness")))
+            .on_hover_text(tr("How bright the brightest red is. The screen brightness of the device lowers it further."));
+        ui.heading(tr("Export theme"));
+        ui.label(tr("Export the current settings into a theme"));
+        ui.horizontal(|ui| {
+            ui.label(tr("Theme name: "));
             ui.text_edit_singleline(&mut self.theme.name);
         });
-        if ui.button("Export").clicked() {
+        if ui.button(tr("Export")).clicked() {
             if let Some(path) = files::get_dir_opt(public_constants::THEMES_FOLDER) {
                 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
                 let save_path_opt: Option<std::path::PathBuf> = {
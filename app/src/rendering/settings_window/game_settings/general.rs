@@ -1,18 +1,19 @@
 use crate::{
     enums::{GameStage, RendererCategory},
+    localisation::tr,
     Application,
 };
 use eframe::egui;
 
 impl Application {
     pub fn render_game_settings_general_subwindow(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.game_handler.game_settings.is_scored_mode, "Play in scored mode?");
+        ui.checkbox(&mut self.game_handler.game_settings.is_scored_mode, tr("Play in scored mode?"));
         ui.add(
             egui::Slider::new(&mut self.game_handler.game_settings.no_of_questions, 1..=self.game_handler.possible_no_of_questions)
-                .text("Number of questions")
+                .text(tr("Number of questions"))
                 .logarithmic(true),
         );
-        if ui.button("Reset questions").clicked() {
+        if ui.button(tr("Reset questions")).clicked() {
             self.game_handler.stage = GameStage::NotStartedYet;
             self.game_handler.reset_used_questions(&mut self.cellestial_sphere);
 
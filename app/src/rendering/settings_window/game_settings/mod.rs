@@ -1,4 +1,4 @@
-use crate::{enums::RendererCategory, structs::state::windows::settings::GameSettingsSubWindow, Application};
+use crate::{enums::RendererCategory, localisation::tr, structs::state::windows::settings::GameSettingsSubWindow, Application};
 use eframe::egui;
 
 pub mod general;
@@ -11,17 +11,17 @@ impl Application {
             ui.selectable_value(
                 &mut self.state.windows.settings.game_settings.subwindow,
                 GameSettingsSubWindow::General,
-                GameSettingsSubWindow::General.as_ref(),
+                tr(GameSettingsSubWindow::General.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.game_settings.subwindow,
                 GameSettingsSubWindow::Questions,
-                GameSettingsSubWindow::Questions.as_ref(),
+                tr(GameSettingsSubWindow::Questions.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.game_settings.subwindow,
                 GameSettingsSubWindow::Constellations,
-                GameSettingsSubWindow::Constellations.as_ref(),
+                tr(GameSettingsSubWindow::Constellations.as_ref()),
             );
         });
         ui.separator();
@@ -37,7 +37,7 @@ impl Application {
             GameSettingsSubWindow::Constellations => {
                 let mut abbrev_to_name = std::collections::HashMap::new();
                 for constellation in self.cellestial_sphere.constellations.values() {
-                    abbrev_to_name.insert(constellation.abbreviation.clone(), constellation.possible_names[1].clone());
+                    abbrev_to_name.insert(constellation.abbreviation.clone(), constellation.display_name());
                 }
                 sg_game_constellations::ui::render_constellations_settings_subwindow(
                     ui,
@@ -1,4 +1,9 @@
 use crate::{
+    game::{
+        questions,
+        scoring::{ScoreCurve, ScoreStep, ScoringRules},
+    },
+    localisation::{tr, tr_args},
     public_constants,
     structs::state::windows::settings::{GameSettingsQuestionsSubWindow, GameSettingsType},
     Application,
@@ -10,7 +15,7 @@ impl Application {
     pub fn render_game_settings_questions_subwindow(&mut self, ui: &mut egui::Ui, tolerance_changed: &mut bool) {
         let prev_active_pack = self.game_handler.active_question_pack.clone();
         ui.horizontal(|ui| {
-            eframe::egui::ComboBox::from_label("Select question pack")
+            eframe::egui::ComboBox::new("Select question pack", tr("Select question pack"))
                 .selected_text(&self.game_handler.active_question_pack)
                 .show_ui(ui, |ui| {
                     ui.style_mut().wrap_mode = Some(eframe::egui::TextWrapMode::Extend);
@@ -23,7 +28,7 @@ impl Application {
                 });
             let removed_group = ui
                 .add_enabled_ui(self.game_handler.question_packs.keys().len() != 0, |ui| {
-                    if ui.button("Remove pack").clicked() {
+                    if ui.button(tr("Remove pack")).clicked() {
                         if let Some(pack) = self.game_handler.question_packs.get(&self.game_handler.active_question_pack) {
                             let remove = if let Some(path) = &pack.file_path {
                                 let path_buf = std::path::PathBuf::from(path);
@@ -71,19 +76,13 @@ impl Application {
                 self.state.windows.settings.game_settings.settings_type = GameSettingsType::Advanced;
                 self.state.windows.settings.game_settings.query = self.game_handler.question_packs.get(&self.game_handler.active_question_pack).unwrap().query.clone();
                 self.state.windows.settings.game_settings.question_pack_new_description = self.game_handler.question_packs.get(&self.game_handler.active_question_pack).unwrap().description.clone();
-                let new_questions = self
-                    .cellestial_sphere
-                    .generate_questions(&self.game_handler.question_packs.get(&self.game_handler.active_question_pack).unwrap().question_objects);
-                self.game_handler.possible_no_of_questions = new_questions.len() as u32;
-                self.game_handler.question_catalog = new_questions;
-                self.game_handler.reset_used_questions(&mut self.cellestial_sphere);
-                self.game_handler.current_question = 0;
-                self.game_handler.stage = crate::enums::GameStage::NotStartedYet;
-                self.game_handler.question_number_text = String::new();
+                self.game_handler.reload_question_catalog(&mut self.cellestial_sphere);
             }
             if ui
-                .button("Add default packs")
-                .on_hover_text("Will add the default question packs. If a question pack with a name of a default pack exists already, that default pack will not be added.")
+                .button(tr("Add default packs"))
+                .on_hover_text(tr(
+                    "Will add the default question packs. If a question pack with a name of a default pack exists already, that default pack will not be added.",
+                ))
                 .clicked()
             {
                 for (name, pack) in crate::game::questions_filter::default_packs() {
@@ -98,7 +97,7 @@ impl Application {
             if self.testing_mode {
                 let active_pack = self.game_handler.question_packs.get(&self.game_handler.active_question_pack);
                 ui.add_enabled_ui(active_pack.is_some(), |ui| {
-                    if ui.button("Print out the question pack").clicked() {
+                    if ui.button(tr("Print out the question pack")).clicked() {
                         if let Some(pack) = active_pack {
                             let name = format!(r##"String::from(r#"{}"#)"##, self.game_handler.active_question_pack);
                             let query = format!(
@@ -119,18 +118,25 @@ impl Application {
                             for (question_type, objects) in &pack.question_objects {
                                 let settings = match question_type {
                                     crate::game::questions::QuestionType::AngularSeparation(small_settings) => format!("QuestionType::AngularSeparation(angular_separation::{small_settings:?}))"),
+                                    crate::game::questions::QuestionType::CompareBrightness(small_settings) => format!("QuestionType::CompareBrightness(compare_brightness::{small_settings:?})"),
+                                    crate::game::questions::QuestionType::ConstellationGenitive(small_settings) => {
+                                        format!("QuestionType::ConstellationGenitive(constellation_genitive::{small_settings:?})")
+                                    }
                                     crate::game::questions::QuestionType::FindThisObject(small_settings) => format!("QuestionType::FindThisObject(find_this_object::{small_settings:?})"),
                                     crate::game::questions::QuestionType::GuessDec(small_settings) => format!("QuestionType::GuessDec(guess_ra_dec::{small_settings:?})"),
                                     crate::game::questions::QuestionType::GuessRa(small_settings) => format!("QuestionType::GuessRa(guess_ra_dec::{small_settings:?})"),
                                     crate::game::questions::QuestionType::GuessTheMagnitude(small_settings) => format!("QuestionType::GuessTheMagnitude(guess_the_magnitude::{small_settings:?})"),
+                                    crate::game::questions::QuestionType::GuessTheSeparation(small_settings) => format!("QuestionType::GuessTheSeparation(guess_the_separation::{small_settings:?})"),
                                     crate::game::questions::QuestionType::MarkMissingObject(small_settings) => format!("QuestionType::MarkMissingObject(mark_missing_object::{small_settings:?})"),
                                     crate::game::questions::QuestionType::WhatIsThisObject(small_settings) => format!("QuestionType::WhatIsThisObject(which_object_is_here::{small_settings:?})"),
+                                    crate::game::questions::QuestionType::WhichBayerLetter(small_settings) => format!("QuestionType::WhichBayerLetter(which_bayer_letter::{small_settings:?})"),
                                     crate::game::questions::QuestionType::WhichConstellationIsThisPointIn(small_settings) => {
                                         format!("QuestionType::WhichConstellationIsThisPointIn(which_constellation_is_point_in::{small_settings:?})")
                                     }
                                     crate::game::questions::QuestionType::WhichObjectIsMissing(small_settings) => {
                                         format!("QuestionType::WhichObjectIsMissing(which_object_is_missing::{small_settings:?})")
                                     }
+                                    crate::game::questions::QuestionType::WhichVariableStar(small_settings) => format!("QuestionType::WhichVariableStar(which_variable_star::{small_settings:?})"),
                                 };
                                 question_objects.push(format!("({settings}, vec!{objects:?})"));
                             }
@@ -144,7 +150,7 @@ impl Application {
             }
         });
         ui.horizontal(|ui| {
-            ui.label("Question pack name");
+            ui.label(tr("Question pack name"));
             ui.text_edit_singleline(&mut self.state.windows.settings.game_settings.question_pack_new_name);
             self.state.windows.settings.game_settings.question_pack_new_name = self
                 .state
@@ -157,7 +163,7 @@ impl Application {
                 .replace(crate::game::game_handler::QUESTION_PACK_QUESTIONS_DIV, "")
                 .replace(crate::game::game_handler::QUESTION_PACK_QUESTIONS_PARTS_DIV, "");
         });
-        ui.label("Question pack description");
+        ui.label(tr("Question pack description"));
         ui.add(eframe::egui::TextEdit::multiline(&mut self.state.windows.settings.game_settings.question_pack_new_description).desired_rows(2));
         self.state.windows.settings.game_settings.question_pack_new_description = self
             .state
@@ -173,26 +179,26 @@ impl Application {
 
         let mut can_evaluate = true;
         let mut settings_all = Vec::new();
-        ui.collapsing("Edit question pack", |ui| {
+        ui.collapsing(tr("Edit question pack"), |ui| {
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut self.state.windows.settings.game_settings.settings_type, GameSettingsType::Basic, GameSettingsType::Basic.as_ref());
+                ui.selectable_value(&mut self.state.windows.settings.game_settings.settings_type, GameSettingsType::Basic, tr(GameSettingsType::Basic.as_ref()));
                 ui.selectable_value(
                     &mut self.state.windows.settings.game_settings.settings_type,
                     GameSettingsType::Advanced,
-                    GameSettingsType::Advanced.as_ref(),
+                    tr(GameSettingsType::Advanced.as_ref()),
                 );
             });
             ui.separator();
 
             match self.state.windows.settings.game_settings.settings_type {
                 GameSettingsType::Basic => {
-                    ui.colored_label(egui::Color32::YELLOW, "Warning: If the question pack was defined using the 'Advanced' tab, you must edit it there. The settings below will not match because the 'Advanced' tab provides much more control over question packs.");
+                    ui.colored_label(egui::Color32::YELLOW, tr("Warning: If the question pack was defined using the 'Advanced' tab, you must edit it there. The settings below will not match because the 'Advanced' tab provides much more control over question packs."));
                     ui.horizontal(|ui| {
                         // If adding new question types, make sure that the picker gets collapsed into a combo box on appropriately wide/narrow screens
                         if self.screen_width.narrow() {
-                            ui.label("Question type: ");
+                            ui.label(tr("Question type: "));
                             egui::ComboBox::from_id_salt("Question type: ")
-                                .selected_text(format!("{}", self.state.windows.settings.game_settings.questions_subwindow.subwindow))
+                                .selected_text(tr(self.state.windows.settings.game_settings.questions_subwindow.subwindow.as_ref()))
                                 .show_ui(ui, |ui: &mut egui::Ui| {
                                     ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
                                     self.render_question_type_picker(ui);
@@ -209,37 +215,42 @@ impl Application {
                         GameSettingsQuestionsSubWindow::GuessTheAngularDistance => self.render_game_settings_angular_distance_subwindow(ui),
                         GameSettingsQuestionsSubWindow::GuessTheCoordinates => self.render_game_settings_coordinates_subwindow(ui),
                         GameSettingsQuestionsSubWindow::GuessTheMagnitude => self.render_game_settings_magnitude_subwindow(ui),
+                        GameSettingsQuestionsSubWindow::GuessTheSeparation => self.render_game_settings_separation_subwindow(ui),
                         GameSettingsQuestionsSubWindow::MarkMissingObject => self.render_game_settings_mark_missing_object_subwindow(ui, tolerance_changed),
                         GameSettingsQuestionsSubWindow::WhichObjectIsMissing => self.render_game_settings_which_object_is_missing_subwindow(ui),
+                        GameSettingsQuestionsSubWindow::WhichVariableStar => self.render_game_settings_which_variable_star_subwindow(ui),
+                        GameSettingsQuestionsSubWindow::ConstellationGenitive => self.render_game_settings_constellation_genitive_subwindow(ui),
+                        GameSettingsQuestionsSubWindow::WhichBayerLetter => self.render_game_settings_which_bayer_letter_subwindow(ui),
+                        GameSettingsQuestionsSubWindow::CompareBrightness => self.render_game_settings_compare_brightness_subwindow(ui),
                     }
 
                     self.state.windows.settings.game_settings.generated_query = self.generate_query_from_basic();
 
                     ui.separator();
-                    ui.label("Generated query:");
+                    ui.label(tr("Generated query:"));
                     ui.label(egui::RichText::new(&self.state.windows.settings.game_settings.generated_query).code());
                     self.state.windows.settings.game_settings.internal_query = self.state.windows.settings.game_settings.generated_query.clone();
                 }
                 GameSettingsType::Advanced => {
-                    ui.collapsing("Query guide", |ui| {
-                        egui::CollapsingHeader::new("Overview").default_open(true).show(ui, |ui| {
-                            ui.label("The query defines all of the questions in the question pack. On each line of the query (so separated by a line break inserted by pressing the Enter key) there is first a question type declaration along with its settings, and then an objects filter. The question pack is then constructing by going through all of these question type - filter pairs, and adding questions of said question type (with the declared settings) to the pack for each object in your catalogue which matches the object filter. By including one question type multiple times in the query, one can have different settings for different objects, or have one object included multiple times.");
-                            ui.label("Example:");
+                    ui.collapsing(tr("Query guide"), |ui| {
+                        egui::CollapsingHeader::new(tr("Overview")).id_salt("Overview").default_open(true).show(ui, |ui| {
+                            ui.label(tr("The query defines all of the questions in the question pack. On each line of the query (so separated by a line break inserted by pressing the Enter key) there is first a question type declaration along with its settings, and then an objects filter. The question pack is then constructing by going through all of these question type - filter pairs, and adding questions of said question type (with the declared settings) to the pack for each object in your catalogue which matches the object filter. By including one question type multiple times in the query, one can have different settings for different objects, or have one object included multiple times."));
+                            ui.label(tr("Example:"));
                             ui.label(egui::RichText::new(concat!(r#"FIND_THIS_OBJECT({..., "replay_incorrect":true}): CATALOGUE_DESIGNATION(MESSIER:1)"#, "\n", r#"FIND_THIS_OBJECT({..., "replay_incorrect":false}): CATALOGUE_DESIGNATION(MESSIER:2)"#)).code());
-                            ui.label("In the example above, the first line defines a question type where the player is asked to mark an object in the sky and if the answer is incorrect, the question will be repeated later. For the sake of example, this behaviour would only be present for finding Messier 1. The second line also adds a question type for marking objects in the sky, but this time incorrectly answered questions will not be asked again. This behaviour would only be present for finding Messier 2.");
+                            ui.label(tr("In the example above, the first line defines a question type where the player is asked to mark an object in the sky and if the answer is incorrect, the question will be repeated later. For the sake of example, this behaviour would only be present for finding Messier 1. The second line also adds a question type for marking objects in the sky, but this time incorrectly answered questions will not be asked again. This behaviour would only be present for finding Messier 2."));
                         });
-                        egui::CollapsingHeader::new("Question types and settings").default_open(true).show(ui, |ui| {
-                            ui.label("There are several different question types:\n - ANGULAR_SEPARATION: Asks the player to guess the angular distance between two objects\n - FIND_THIS_OBJECT: Asks the player to mark a given object in the sky\n - GUESS_DEC, GUESS_RA: Asks the player to guess the declination/right ascension (respectively) of an object marked in the sky\n - GUESS_THE_MAGNITUDE: Asks the player to guess the magnitude of an object marked in the sky\n - MARK_MISSING_OBJECT: Asks the player to mark the position of an object hidden from the sky (for example a star that is not rendered for the duration of answering the question) \n - WHAT_IS_THIS_OBJECT: Asks the player to give a designation (name, Messier number, ...) of an object marked in the sky\n - WHICH_CONSTELLATION_IS_THIS_POINT_IN: Asks the player to identify which constellation the point marked in the sky is\n - WHICH_OBJECT_IS_MISSING: Asks the player to give a designation (name, Messier number, ...) of an object hidden from the sky");
-                            ui.label("The syntax for initiating a question type is `<name>({<settings>}):`, for example:");
+                        egui::CollapsingHeader::new(tr("Question types and settings")).id_salt("Question types and settings").default_open(true).show(ui, |ui| {
+                            ui.label(tr("There are several different question types:\n - ANGULAR_SEPARATION: Asks the player to guess the angular distance between two objects\n - COMPARE_BRIGHTNESS: Asks the player to order two or three stars marked in the sky from the brightest to the faintest\n - CONSTELLATION_GENITIVE: Asks the player to give the genitive of the constellation of an object, as used in the designations of stars\n - FIND_THIS_OBJECT: Asks the player to mark a given object in the sky\n - GUESS_DEC, GUESS_RA: Asks the player to guess the declination/right ascension (respectively) of an object marked in the sky\n - GUESS_THE_MAGNITUDE: Asks the player to guess the magnitude of an object marked in the sky\n - GUESS_THE_SEPARATION: Asks the player to guess the separation of the components of a double star marked in the sky\n - MARK_MISSING_OBJECT: Asks the player to mark the position of an object hidden from the sky (for example a star that is not rendered for the duration of answering the question) \n - WHAT_IS_THIS_OBJECT: Asks the player to give a designation (name, Messier number, ...) of an object marked in the sky\n - WHICH_BAYER_LETTER: Asks the player to give the Bayer letter of a star marked in the sky\n - WHICH_CONSTELLATION_IS_THIS_POINT_IN: Asks the player to identify which constellation the point marked in the sky is\n - WHICH_OBJECT_IS_MISSING: Asks the player to give a designation (name, Messier number, ...) of an object hidden from the sky\n - WHICH_VARIABLE_STAR: Describes the variability of a star and asks the player to name it"));
+                            ui.label(tr("The syntax for initiating a question type is `<name>({<settings>}):`, for example:"));
                             ui.label(egui::RichText::new(r#"FIND_THIS_OBJECT({..., "replay_incorrect":true}):"#).code());
-                            ui.label("Each question type comes with its own settings. The best way to get a list of them is to go into the 'Basic' tab, enable the corresponding question type, and look at the generated query. All settings will be there. Another option is to just leave the settings blank, so only having the curly braces in the definition, and look at the error(s). However, please be careful when using this technique as some settings have defaults so their absence may not cause errors. Always look at the parsed query to check if you are doing what you think you are doing. It is in just a slightly different format and corresponds directly to the structure used to evaluate the query.");
+                            ui.label(tr("Each question type comes with its own settings. The best way to get a list of them is to go into the 'Basic' tab, enable the corresponding question type, and look at the generated query. All settings will be there. Another option is to just leave the settings blank, so only having the curly braces in the definition, and look at the error(s). However, please be careful when using this technique as some settings have defaults so their absence may not cause errors. Always look at the parsed query to check if you are doing what you think you are doing. It is in just a slightly different format and corresponds directly to the structure used to evaluate the query."));
                         });
-                        egui::CollapsingHeader::new("Filters").default_open(true).show(ui, |ui| {
-                            ui.label("Filters come after the colon of the question type definition and dictate which objects will be used to create questions of said type and settings. In the end all of the options are collapsed into a single true/false value for each object. A question with an object is created if (and only if) the value is true. Omitting filters and only having a question definition includes all objects.");
-                            ui.label("There are many different filter options. The syntax is always the same: `<name>(<arguments>)`. Arguments are comma-separated. For example:");
+                        egui::CollapsingHeader::new(tr("Filters")).id_salt("Filters").default_open(true).show(ui, |ui| {
+                            ui.label(tr("Filters come after the colon of the question type definition and dictate which objects will be used to create questions of said type and settings. In the end all of the options are collapsed into a single true/false value for each object. A question with an object is created if (and only if) the value is true. Omitting filters and only having a question definition includes all objects."));
+                            ui.label(tr("There are many different filter options. The syntax is always the same: `<name>(<arguments>)`. Arguments are comma-separated. For example:"));
                             ui.label(egui::RichText::new(r#"CATALOGUE_DESIGNATION(MESSIER:1, MESSIER:2)"#).code());
                             ui.label(format!("{}{}{}{}",
-                                concat!(
+                                tr(concat!(
                                     "List of filter expressions and their descriptions:\n",
                                     " - AND(expression_1, expression_2, ...): Evaluates to true if and only if all of the inner expressions also evaluate to true. Takes at least one argument.\n",
                                     " - OR(expression_1, expression_2, ...): Evaluates to true if and only if at least one of the inner expressions evaluates to true. Takes at least one argument.\n",
@@ -249,23 +260,27 @@ impl Application {
                                     " - RA(value_1, value_2): Evaluates to true if and only if the right ascension of the object is between value_1 and value_2 (in hours). Takes exactly two real numbers as arguments.\n",
                                     " - CONSTELLATION(value_1, value_2, ...): Evaluates to true if and only if the object is in at least one of the constellations listed. Takes at least one constellation abbreviation as arguments.\n",
                                     " - CONSTELLATION_GROUP(value_1, value_2, ...): A shorthand for CONSTELLATION(all constellations in the listed groups). Takes at least one constellation group name as arguments.\n"
-                                ),
-                                format_args!(" - CATALOGUE(value_1, value_2, ...): Evaluates to true if and only if the object is present in at least one of the listed catalogues. Takes at least one catalogue as arguments. Valid catalogues are: {}\n", crate::game::questions_filter::parser::VALID_CATALOGUES.join(", ")),
-                                format_args!(" - TYPE(value_1, value_2, ...): Evaluates to true if and only if the object is of at least one of the types listed. Takes at least one object type as arguments. Valid object types are: {}\n", crate::game::ALLOWED_TYPES),
-                                concat!(
+                                )),
+                                tr_args(" - CATALOGUE(value_1, value_2, ...): Evaluates to true if and only if the object is present in at least one of the listed catalogues. Takes at least one catalogue as arguments. Valid catalogues are: {0}\n", &[&crate::game::questions_filter::parser::VALID_CATALOGUES.join(", ")]),
+                                tr_args(" - TYPE(value_1, value_2, ...): Evaluates to true if and only if the object is of at least one of the types listed. Takes at least one object type as arguments. Valid object types are: {0}\n", &[&crate::game::ALLOWED_TYPES]),
+                                tr(concat!(
                                     " - MAG_BELOW(value): Evaluates to true if and only if the magnitude of the object is known and is lower than the value passed in. Takes exactly one real number as an argument.\n",
                                     " - MAG_ABOVE(value): Evaluates to true if and only if the magnitude of the object is known and is greater than the value passed in. Takes exactly one real number as an argument.\n",
                                     " - MAG(value_1, value_2): Evaluates to true if and only if the magnitude of the object is known and is between value_1 and value_2. Takes exactly two real numbers as arguments.\n",
                                     " - OBJECT_ID(value_1, value_2, ...): Evaluates to true if and only if the internal id of the object matches at least one of the listed ones. Takes at least one whole number as arguments.\n",
                                     " - CATALOGUE_DESIGNATION(value_1, value_2, ...): Evaluates to true if and only if at least one of the designations listed matches the object. The designation is in the format `<catalogue name>:<designation>`, for example `MESSIER:75` would be Messier 75 and `PROPER_NAME:Vega` would be Vega. See above for valid catalogues. Takes at least one whole number as arguments.\n",
-                                )
+                                    " - SEPARATION(value_1, value_2): Evaluates to true if and only if the object is a double star with the separation of its components between value_1 and value_2 (in arcseconds). Takes exactly two real numbers as arguments.\n",
+                                    " - VARIABLE(value_1, value_2, ...): Evaluates to true if and only if the object is a variable star of at least one of the listed types. The types are the abbreviations of the General Catalogue of Variable Stars (for example M, DCEP, EA, SR) and match all their subtypes (EA matches EA, E matches EA, EB and EW), ANY matches all variable stars. Takes at least one argument.\n",
+                                    " - PERIOD(value_1, value_2): Evaluates to true if and only if the object is a variable star with a known period between value_1 and value_2 (in days). Takes exactly two real numbers as arguments.\n",
+                                    " - BRIGHTEST_IN_CONSTELLATION(value): Evaluates to true if and only if the object is a star among the `value` brightest stars of its constellation (counting all the stars down to about the sixth magnitude, not only the question objects). Takes exactly one whole number as an argument.\n",
+                                ))
                             ));
                         });
-                        ui.label("In general, you can usually take a look onto the generated query in the 'Basic' tab, which showcases the basics of the query syntax. However, please note that the 'Basic' tab has limited options and will not showcase all of the features. You may also find that some queries have redundant parts - they are generated automatically.");
-                        ui.label(egui::RichText::new("Always look at the parsed query at the bottom of the window. It has a slightly different syntax, but corresponds directly to the internal structure which will be used to evaluate the query. You may find out you are sometimes doing something else than you thought :D It also includes potential errors which will usually guide you on how to fix them.").strong());
+                        ui.label(tr("In general, you can usually take a look onto the generated query in the 'Basic' tab, which showcases the basics of the query syntax. However, please note that the 'Basic' tab has limited options and will not showcase all of the features. You may also find that some queries have redundant parts - they are generated automatically."));
+                        ui.label(egui::RichText::new(tr("Always look at the parsed query at the bottom of the window. It has a slightly different syntax, but corresponds directly to the internal structure which will be used to evaluate the query. You may find out you are sometimes doing something else than you thought :D It also includes potential errors which will usually guide you on how to fix them.")).strong());
                     });
                     ui.separator();
-                    ui.label("Enter the questions query here:");
+                    ui.label(tr("Enter the questions query here:"));
                     ui.add(egui::TextEdit::multiline(&mut self.state.windows.settings.game_settings.query).desired_width(f32::INFINITY));
                     self.state.windows.settings.game_settings.internal_query = self.state.windows.settings.game_settings.query.clone();
                 }
@@ -294,14 +309,14 @@ impl Application {
                     let query = spl.pop().unwrap(); //.replace(":", "");
                     match crate::game::questions_filter::parser::Parser::new(query).parse(&self.game_handler.constellation_groups_settings.constellation_groups) {
                         Ok(Some(crate::game::questions_filter::parser::Node::Keyword(ast))) => (format!("{ast:?}"), Ok(Some(ast))),
-                        Ok(Some(crate::game::questions_filter::parser::Node::Value(_))) | Ok(None) => (String::from("No restrictions"), Ok(None)),
+                        Ok(Some(crate::game::questions_filter::parser::Node::Value(_))) | Ok(None) => (tr("No restrictions"), Ok(None)),
                         Err(err) => {
                             can_evaluate = false;
-                            (format!("Error when parsing the query: {err}"), Err(""))
+                            (tr_args("Error when parsing the query: {0}", &[&err]), Err(""))
                         }
                     }
                 } else {
-                    (String::from("No restrictions"), Ok(None))
+                    (tr("No restrictions"), Ok(None))
                 };
                 let mut joined = spl.join("");
                 if joined.trim().ends_with(")") {
@@ -332,15 +347,15 @@ impl Application {
             }
             let joined = text_parts.join("\n");
             let replaced = joined.replace("SmallSettings {", "{");
-            ui.label("Parsed query:");
+            ui.label(tr("Parsed query:"));
             ui.label(egui::RichText::new(replaced).code());
         });
         ui.add_enabled_ui(can_evaluate, |ui| {
             ui.horizontal(|ui| {
                 let save_button = if self.game_handler.question_packs.contains_key(&self.state.windows.settings.game_settings.question_pack_new_name) {
-                    ui.button("Evaluate and save")
+                    ui.button(tr("Evaluate and save"))
                 } else {
-                    ui.button("Evaluate and create new pack")
+                    ui.button(tr("Evaluate and create new pack"))
                 };
                 if save_button.clicked() {
                     let res = self.cellestial_sphere.evaluate_questions_query(&settings_all);
@@ -355,7 +370,7 @@ impl Application {
                     );
                     self.game_handler.active_question_pack = self.state.windows.settings.game_settings.question_pack_new_name.clone();
                 }
-                let export_button = ui.button("Evaluate and export");
+                let export_button = ui.button(tr("Evaluate and export"));
                 if export_button.clicked() {
                     let res = self.cellestial_sphere.evaluate_questions_query(&settings_all);
                     if let Some(path) = crate::files::get_dir_opt(public_constants::QUESTION_PACKS_FOLDER) {
@@ -408,25 +423,13 @@ impl Application {
                     }
                 }
                 if save_button.clicked() || export_button.clicked() {
-                    let new_questions = if let Some(active_pack) = self.game_handler.question_packs.get(&self.game_handler.active_question_pack) {
-                        self.cellestial_sphere.generate_questions(&active_pack.question_objects)
-                    } else {
-                        Vec::new()
-                    };
-                    self.game_handler.possible_no_of_questions = new_questions.len() as u32;
-                    self.game_handler.question_catalog = new_questions;
-                    self.game_handler.reset_used_questions(&mut self.cellestial_sphere);
-                    self.game_handler.current_question = 0;
-                    self.game_handler.stage = crate::enums::GameStage::NotStartedYet;
-                    self.game_handler.question_number_text = String::new();
+                    self.game_handler.reload_question_catalog(&mut self.cellestial_sphere);
                 }
             });
         });
     }
 
     fn generate_query_from_basic(&self) -> String {
-        use crate::game::questions;
-
         let active_constellations = self
             .game_handler
             .constellation_groups_settings
@@ -452,6 +455,8 @@ impl Application {
                 ask_bayer: false,
                 ask_flamsteed: false,
                 ask_proper: false,
+                scoring: self.game_handler.questions_settings.find_this_object.scoring.clone(),
+                hints: questions::find_this_object::default_hints(),
             };
             let mut settings_catalogues = Vec::new();
             if self.game_handler.questions_settings.find_this_object.show_messiers {
@@ -506,6 +511,7 @@ impl Application {
                 accept_proper: true,
                 accept_bayer: true,
                 accept_flamsteed: true,
+                scoring: self.game_handler.questions_settings.what_is_this_object.scoring.clone(),
             };
             let mut settings_catalogues = Vec::new();
             if self.game_handler.questions_settings.what_is_this_object.show_messiers {
@@ -544,6 +550,7 @@ impl Application {
         if self.game_handler.questions_settings.what_constellation_is_this_point_in.show {
             let question_settings = questions::which_constellation_is_point_in::SmallSettings {
                 rotate_to_point: self.game_handler.questions_settings.what_constellation_is_this_point_in.rotate_to_point,
+                scoring: self.game_handler.questions_settings.what_constellation_is_this_point_in.scoring.clone(),
             };
             let settings = if self.game_handler.questions_settings.what_constellation_is_this_point_in.limit_to_toggled_constellations {
                 format!(": CONSTELLATION({active_constellations})")
@@ -557,6 +564,7 @@ impl Application {
         if self.game_handler.questions_settings.angular_separation.show {
             let question_settings = questions::angular_separation::SmallSettings {
                 rotate_to_midpoint: self.game_handler.questions_settings.angular_separation.rotate_to_midpoint,
+                scoring: self.game_handler.questions_settings.angular_separation.scoring.clone(),
             };
             let settings = if self.game_handler.questions_settings.angular_separation.limit_to_toggled_constellations {
                 format!(": CONSTELLATION({active_constellations})")
@@ -570,6 +578,7 @@ impl Application {
         if self.game_handler.questions_settings.guess_rad_dec.show {
             let question_settings = questions::guess_ra_dec::SmallSettings {
                 rotate_to_point: self.game_handler.questions_settings.guess_rad_dec.rotate_to_point,
+                scoring: self.game_handler.questions_settings.guess_rad_dec.scoring.clone(),
             };
             let settings = if self.game_handler.questions_settings.guess_rad_dec.limit_to_toggled_constellations {
                 format!(": CONSTELLATION({active_constellations})")
@@ -585,6 +594,7 @@ impl Application {
             let question_settings = questions::guess_the_magnitude::SmallSettings {
                 rotate_to_point: self.game_handler.questions_settings.guess_the_magnitude.rotate_to_point,
                 replay_incorrect: self.game_handler.questions_settings.guess_the_magnitude.replay_incorrect,
+                scoring: self.game_handler.questions_settings.guess_the_magnitude.scoring.clone(),
             };
             let mut settings = format!("MAG_BELOW({})", self.game_handler.questions_settings.guess_the_magnitude.magnitude_cutoff);
             if self.game_handler.questions_settings.guess_the_magnitude.limit_to_toggled_constellations {
@@ -594,11 +604,26 @@ impl Application {
                 query_parts.push(format!("GUESS_THE_MAGNITUDE({question_settings}): {settings}"));
             }
         }
+        if self.game_handler.questions_settings.guess_the_separation.show {
+            let question_settings = questions::guess_the_separation::SmallSettings {
+                rotate_to_point: self.game_handler.questions_settings.guess_the_separation.rotate_to_point,
+                replay_incorrect: self.game_handler.questions_settings.guess_the_separation.replay_incorrect,
+                scoring: self.game_handler.questions_settings.guess_the_separation.scoring.clone(),
+            };
+            let mut settings = String::from("SEPARATION(0, 100000)");
+            if self.game_handler.questions_settings.guess_the_separation.limit_to_toggled_constellations {
+                settings = format!("AND({settings}, CONSTELLATION({active_constellations}))");
+            };
+            if let Ok(question_settings) = serde_json::to_string(&question_settings) {
+                query_parts.push(format!("GUESS_THE_SEPARATION({question_settings}): {settings}"));
+            }
+        }
         if self.game_handler.questions_settings.mark_missing_object.show {
             let question_settings = questions::mark_missing_object::SmallSettings {
                 correctness_threshold: self.game_handler.questions_settings.mark_missing_object.correctness_threshold,
                 rotate_to_answer: self.game_handler.questions_settings.mark_missing_object.rotate_to_correct_point,
                 replay_incorrect: self.game_handler.questions_settings.mark_missing_object.replay_incorrect,
+                scoring: self.game_handler.questions_settings.mark_missing_object.scoring.clone(),
             };
             let mut settings_catalogues = Vec::new();
             if self.game_handler.questions_settings.mark_missing_object.show_messiers {
@@ -647,6 +672,7 @@ impl Application {
                 accept_proper: true,
                 accept_bayer: true,
                 accept_flamsteed: true,
+                scoring: self.game_handler.questions_settings.which_object_is_missing.scoring.clone(),
             };
             let mut settings_catalogues = Vec::new();
             if self.game_handler.questions_settings.which_object_is_missing.show_messiers {
@@ -682,6 +708,70 @@ impl Application {
                 }
             };
         }
+        if self.game_handler.questions_settings.which_variable_star.show {
+            let question_settings = questions::which_variable_star::SmallSettings {
+                rotate_to_answer: self.game_handler.questions_settings.which_variable_star.rotate_to_answer,
+                replay_incorrect: self.game_handler.questions_settings.which_variable_star.replay_incorrect,
+                show_constellation: self.game_handler.questions_settings.which_variable_star.show_constellation,
+                scoring: self.game_handler.questions_settings.which_variable_star.scoring.clone(),
+            };
+            let mut settings = String::from("VARIABLE(ANY)");
+            if self.game_handler.questions_settings.which_variable_star.limit_to_toggled_constellations {
+                settings = format!("AND({settings}, CONSTELLATION({active_constellations}))");
+            };
+            if let Ok(question_settings) = serde_json::to_string(&question_settings) {
+                query_parts.push(format!("WHICH_VARIABLE_STAR({question_settings}): {settings}"));
+            }
+        }
+        if self.game_handler.questions_settings.constellation_genitive.show {
+            let question_settings = questions::constellation_genitive::SmallSettings {
+                replay_incorrect: self.game_handler.questions_settings.constellation_genitive.replay_incorrect,
+                scoring: self.game_handler.questions_settings.constellation_genitive.scoring.clone(),
+            };
+            let settings = if self.game_handler.questions_settings.constellation_genitive.limit_to_toggled_constellations {
+                format!(": CONSTELLATION({active_constellations})")
+            } else {
+                String::new()
+            };
+            if let Ok(question_settings) = serde_json::to_string(&question_settings) {
+                query_parts.push(format!("CONSTELLATION_GENITIVE({question_settings}){settings}"));
+            }
+        }
+        if self.game_handler.questions_settings.which_bayer_letter.show {
+            let question_settings = questions::which_bayer_letter::SmallSettings {
+                rotate_to_point: self.game_handler.questions_settings.which_bayer_letter.rotate_to_point,
+                replay_incorrect: self.game_handler.questions_settings.which_bayer_letter.replay_incorrect,
+                scoring: self.game_handler.questions_settings.which_bayer_letter.scoring.clone(),
+            };
+            let mut settings = format!(
+                "AND(TYPE(STAR), CATALOGUE(BAYER), BRIGHTEST_IN_CONSTELLATION({}))",
+                self.game_handler.questions_settings.which_bayer_letter.brightest_in_constellation
+            );
+            if self.game_handler.questions_settings.which_bayer_letter.limit_to_toggled_constellations {
+                settings = format!("AND({settings}, CONSTELLATION({active_constellations}))");
+            };
+            if let Ok(question_settings) = serde_json::to_string(&question_settings) {
+                query_parts.push(format!("WHICH_BAYER_LETTER({question_settings}): {settings}"));
+            }
+        }
+        if self.game_handler.questions_settings.compare_brightness.show {
+            let question_settings = questions::compare_brightness::SmallSettings {
+                stars_per_question: self.game_handler.questions_settings.compare_brightness.stars_per_question,
+                min_magnitude_difference: self.game_handler.questions_settings.compare_brightness.min_magnitude_difference,
+                max_magnitude_difference: self.game_handler.questions_settings.compare_brightness.max_magnitude_difference,
+                max_separation: self.game_handler.questions_settings.compare_brightness.max_separation,
+                rotate_to_stars: self.game_handler.questions_settings.compare_brightness.rotate_to_stars,
+                replay_incorrect: self.game_handler.questions_settings.compare_brightness.replay_incorrect,
+                scoring: self.game_handler.questions_settings.compare_brightness.scoring.clone(),
+            };
+            let mut settings = format!("AND(TYPE(STAR), MAG_BELOW({}))", self.game_handler.questions_settings.compare_brightness.magnitude_cutoff);
+            if self.game_handler.questions_settings.compare_brightness.limit_to_toggled_constellations {
+                settings = format!("AND({settings}, CONSTELLATION({active_constellations}))");
+            };
+            if let Ok(question_settings) = serde_json::to_string(&question_settings) {
+                query_parts.push(format!("COMPARE_BRIGHTNESS({question_settings}): {settings}"));
+            }
+        }
         let query = query_parts.join("\n");
         query.replace("SmallSettings {", "{")
     }
@@ -691,190 +781,510 @@ impl Application {
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::FindThisObject,
-            GameSettingsQuestionsSubWindow::FindThisObject.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::FindThisObject.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::WhatIsThisObject,
-            GameSettingsQuestionsSubWindow::WhatIsThisObject.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::WhatIsThisObject.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::WhichConstellationIsThisPointIn,
-            GameSettingsQuestionsSubWindow::WhichConstellationIsThisPointIn.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::WhichConstellationIsThisPointIn.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::GuessTheAngularDistance,
-            GameSettingsQuestionsSubWindow::GuessTheAngularDistance.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::GuessTheAngularDistance.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::GuessTheCoordinates,
-            GameSettingsQuestionsSubWindow::GuessTheCoordinates.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::GuessTheCoordinates.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::GuessTheMagnitude,
-            GameSettingsQuestionsSubWindow::GuessTheMagnitude.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::GuessTheMagnitude.as_ref()),
+        );
+        ui.selectable_value(
+            &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
+            GameSettingsQuestionsSubWindow::GuessTheSeparation,
+            tr(GameSettingsQuestionsSubWindow::GuessTheSeparation.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::MarkMissingObject,
-            GameSettingsQuestionsSubWindow::MarkMissingObject.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::MarkMissingObject.as_ref()),
         );
         ui.selectable_value(
             &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
             GameSettingsQuestionsSubWindow::WhichObjectIsMissing,
-            GameSettingsQuestionsSubWindow::WhichObjectIsMissing.as_ref(),
+            tr(GameSettingsQuestionsSubWindow::WhichObjectIsMissing.as_ref()),
+        );
+        ui.selectable_value(
+            &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
+            GameSettingsQuestionsSubWindow::WhichVariableStar,
+            tr(GameSettingsQuestionsSubWindow::WhichVariableStar.as_ref()),
+        );
+        ui.selectable_value(
+            &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
+            GameSettingsQuestionsSubWindow::ConstellationGenitive,
+            tr(GameSettingsQuestionsSubWindow::ConstellationGenitive.as_ref()),
+        );
+        ui.selectable_value(
+            &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
+            GameSettingsQuestionsSubWindow::WhichBayerLetter,
+            tr(GameSettingsQuestionsSubWindow::WhichBayerLetter.as_ref()),
+        );
+        ui.selectable_value(
+            &mut self.state.windows.settings.game_settings.questions_subwindow.subwindow,
+            GameSettingsQuestionsSubWindow::CompareBrightness,
+            tr(GameSettingsQuestionsSubWindow::CompareBrightness.as_ref()),
         );
     }
 
     fn render_game_settings_find_this_object_subwindow(&mut self, ui: &mut egui::Ui, tolerance_changed: &mut bool) {
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show, "Show the 'Find this object' questions");
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show, tr("Show the 'Find this object' questions"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.find_this_object.rotate_to_correct_point,
-            "Rotate to the correct point after answering",
+            tr("Rotate to the correct point after answering"),
         )
-        .on_hover_text("Whether or not to rotate the view so that the correct point is in the centre of the screen after answering");
+        .on_hover_text(tr("Whether or not to rotate the view so that the correct point is in the centre of the screen after answering"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.find_this_object.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
-        );
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_messiers, "Ask about Messier objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_caldwells, "Ask about Caldwell objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_ngcs, "Ask about NGC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_ics, "Ask about IC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_bayer, "Ask about stars with Bayer designations");
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_starnames, "Ask about named stars");
-        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.find_this_object.magnitude_cutoff, 0.0..=20.0).text("Star magnitude cutoff"));
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_messiers, tr("Ask about Messier objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_caldwells, tr("Ask about Caldwell objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_ngcs, tr("Ask about NGC objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_ics, tr("Ask about IC objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_bayer, tr("Ask about stars with Bayer designations"));
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.show_starnames, tr("Ask about named stars"));
+        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.find_this_object.magnitude_cutoff, 0.0..=20.0).text(tr("Star magnitude cutoff")));
         let mut correctness_threshold_inner = self.game_handler.questions_settings.find_this_object.correctness_threshold.value();
         let correctness_threshold_widget = ui.add(
             egui::Slider::new(&mut correctness_threshold_inner, 0.0..=180.0)
-                .text("Correctness threshold (degrees)")
+                .text(tr("Correctness threshold (degrees)"))
                 .logarithmic(true),
         );
         self.game_handler.questions_settings.find_this_object.correctness_threshold = angle::Deg(correctness_threshold_inner);
         *tolerance_changed |= correctness_threshold_widget.changed();
-        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.replay_incorrect, "Replay incorrectly answered questions");
+        ui.checkbox(&mut self.game_handler.questions_settings.find_this_object.replay_incorrect, tr("Replay incorrectly answered questions"));
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.find_this_object.scoring,
+                questions::find_this_object::default_scoring(),
+                "°",
+                true,
+            )
+        });
     }
 
     fn render_game_settings_mark_missing_object_subwindow(&mut self, ui: &mut egui::Ui, tolerance_changed: &mut bool) {
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show, "Show the 'Mark the missing object' questions");
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show, tr("Show the 'Mark the missing object' questions"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.mark_missing_object.rotate_to_correct_point,
-            "Rotate to the correct point after answering",
+            tr("Rotate to the correct point after answering"),
         )
-        .on_hover_text("Whether or not to rotate the view so that the correct point is in the centre of the screen after answering");
+        .on_hover_text(tr("Whether or not to rotate the view so that the correct point is in the centre of the screen after answering"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.mark_missing_object.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
-        );
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_bayer, "Ask stars with Bayer designations");
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_starnames, "Ask named stars");
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_messiers, "Ask Messier objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_caldwells, "Ask Caldwell objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_ngcs, "Ask NGC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_ics, "Ask IC objects");
-        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.mark_missing_object.magnitude_cutoff, 0.0..=20.0).text("Magnitude cutoff"));
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_bayer, tr("Ask stars with Bayer designations"));
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_starnames, tr("Ask named stars"));
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_messiers, tr("Ask Messier objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_caldwells, tr("Ask Caldwell objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_ngcs, tr("Ask NGC objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.show_ics, tr("Ask IC objects"));
+        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.mark_missing_object.magnitude_cutoff, 0.0..=20.0).text(tr("Magnitude cutoff")));
         let mut correctness_threshold_inner = self.game_handler.questions_settings.mark_missing_object.correctness_threshold.value();
         let correctness_threshold_widget = ui.add(
             egui::Slider::new(&mut correctness_threshold_inner, 0.0..=180.0)
-                .text("Correctness threshold (degrees)")
+                .text(tr("Correctness threshold (degrees)"))
                 .logarithmic(true),
         );
         self.game_handler.questions_settings.mark_missing_object.correctness_threshold = angle::Deg(correctness_threshold_inner);
         *tolerance_changed |= correctness_threshold_widget.changed();
-        ui.checkbox(&mut self.game_handler.questions_settings.mark_missing_object.replay_incorrect, "Replay incorrectly answered questions");
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.mark_missing_object.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.mark_missing_object.scoring,
+                questions::mark_missing_object::default_scoring(),
+                "°",
+                false,
+            )
+        });
     }
 
     fn render_game_settings_which_object_is_missing_subwindow(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show, "Show the 'Which object is missing' questions");
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_object_is_missing.show,
+            tr("Show the 'Which object is missing' questions"),
+        );
         ui.checkbox(
             &mut self.game_handler.questions_settings.which_object_is_missing.rotate_to_answer,
-            "Rotate to the correct point after answering",
+            tr("Rotate to the correct point after answering"),
         )
-        .on_hover_text("Whether or not to rotate the view so that the correct point is in the centre of the screen after answering");
+        .on_hover_text(tr("Whether or not to rotate the view so that the correct point is in the centre of the screen after answering"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.which_object_is_missing.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_messiers, tr("Ask about Messier objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_caldwells, tr("Ask about Caldwell objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_ngcs, tr("Ask about NGC objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_ics, tr("Ask about IC objects"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_object_is_missing.show_bayer,
+            tr("Ask about stars with Bayer designations"),
         );
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_messiers, "Ask about Messier objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_caldwells, "Ask about Caldwell objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_ngcs, "Ask about NGC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_ics, "Ask about IC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_bayer, "Ask about stars with Bayer designations");
-        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_starnames, "Ask about named stars");
-        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.which_object_is_missing.magnitude_cutoff, 0.0..=20.0).text("Star magnitude cutoff"));
+        ui.checkbox(&mut self.game_handler.questions_settings.which_object_is_missing.show_starnames, tr("Ask about named stars"));
+        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.which_object_is_missing.magnitude_cutoff, 0.0..=20.0).text(tr("Star magnitude cutoff")));
         ui.checkbox(
             &mut self.game_handler.questions_settings.which_object_is_missing.replay_incorrect,
-            "Replay incorrectly answered questions",
+            tr("Replay incorrectly answered questions"),
         );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.which_object_is_missing.scoring,
+                questions::which_object_is_missing::default_scoring(),
+                "",
+                false,
+            )
+        });
     }
 
     fn render_game_settings_what_is_this_object_subwindow(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show, "Show the 'What is this object' questions");
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.rotate_to_point, "Rotate to the point in question")
-            .on_hover_text("Whether or not to rotate the view so that the point in question is in the centre of the screen");
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show, tr("Show the 'What is this object' questions"));
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.rotate_to_point, tr("Rotate to the point in question"))
+            .on_hover_text(tr("Whether or not to rotate the view so that the point in question is in the centre of the screen"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.what_is_this_object.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
-        );
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_messiers, "Ask about Messier objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_caldwells, "Ask about Caldwell objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_ngcs, "Ask about NGC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_ics, "Ask about IC objects");
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_bayer, "Ask about stars with Bayer designations");
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_starnames, "Ask about named stars");
-        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.what_is_this_object.magnitude_cutoff, 0.0..=20.0).text("Star magnitude cutoff"));
-        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.replay_incorrect, "Replay incorrectly answered questions");
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_messiers, tr("Ask about Messier objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_caldwells, tr("Ask about Caldwell objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_ngcs, tr("Ask about NGC objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_ics, tr("Ask about IC objects"));
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_bayer, tr("Ask about stars with Bayer designations"));
+        ui.checkbox(&mut self.game_handler.questions_settings.what_is_this_object.show_starnames, tr("Ask about named stars"));
+        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.what_is_this_object.magnitude_cutoff, 0.0..=20.0).text(tr("Star magnitude cutoff")));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.what_is_this_object.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.what_is_this_object.scoring,
+                questions::which_object_is_here::default_scoring(),
+                "",
+                false,
+            )
+        });
     }
 
     fn render_game_settings_guess_the_constellation_subwindow(&mut self, ui: &mut egui::Ui) {
         ui.checkbox(
             &mut self.game_handler.questions_settings.what_constellation_is_this_point_in.show,
-            "Show the 'Which constellation is this point in' questions",
+            tr("Show the 'Which constellation is this point in' questions"),
         );
         ui.checkbox(
             &mut self.game_handler.questions_settings.what_constellation_is_this_point_in.rotate_to_point,
-            "Rotate to the point in question",
+            tr("Rotate to the point in question"),
         )
-        .on_hover_text("Whether or not to rotate the view so that the point in question is in the centre of the screen");
+        .on_hover_text(tr("Whether or not to rotate the view so that the point in question is in the centre of the screen"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.what_constellation_is_this_point_in.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
+            tr("Limit to objects from toggled constellations"),
         );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.what_constellation_is_this_point_in.scoring,
+                questions::which_constellation_is_point_in::default_scoring(),
+                "",
+                false,
+            )
+        });
     }
 
     fn render_game_settings_angular_distance_subwindow(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.game_handler.questions_settings.angular_separation.show, "Show the 'What is the angle between..' questions");
-        ui.checkbox(&mut self.game_handler.questions_settings.angular_separation.rotate_to_midpoint, "Rotate to the midpoint")
-            .on_hover_text("Whether or not to rotate the view so that the point in the middle between the points in question is in the centre of the screen");
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.angular_separation.show,
+            tr("Show the 'What is the angle between..' questions"),
+        );
+        ui.checkbox(&mut self.game_handler.questions_settings.angular_separation.rotate_to_midpoint, tr("Rotate to the midpoint"))
+            .on_hover_text(tr(
+                "Whether or not to rotate the view so that the point in the middle between the points in question is in the centre of the screen",
+            ));
         ui.checkbox(
             &mut self.game_handler.questions_settings.angular_separation.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
+            tr("Limit to objects from toggled constellations"),
         );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.angular_separation.scoring,
+                questions::angular_separation::default_scoring(),
+                "%",
+                false,
+            )
+        });
     }
 
     fn render_game_settings_coordinates_subwindow(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.game_handler.questions_settings.guess_rad_dec.show, "Show the 'What is the RA/DEC..' questions");
-        ui.checkbox(&mut self.game_handler.questions_settings.guess_rad_dec.rotate_to_point, "Rotate to the point in question")
-            .on_hover_text("Whether or not to rotate the view so that the point in question is in the centre of the screen");
+        ui.checkbox(&mut self.game_handler.questions_settings.guess_rad_dec.show, tr("Show the 'What is the RA/DEC..' questions"));
+        ui.checkbox(&mut self.game_handler.questions_settings.guess_rad_dec.rotate_to_point, tr("Rotate to the point in question"))
+            .on_hover_text(tr("Whether or not to rotate the view so that the point in question is in the centre of the screen"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.guess_rad_dec.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
+            tr("Limit to objects from toggled constellations"),
         );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.guess_rad_dec.scoring,
+                questions::guess_ra_dec::default_scoring(),
+                "°",
+                false,
+            )
+        });
     }
 
     fn render_game_settings_magnitude_subwindow(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.game_handler.questions_settings.guess_the_magnitude.show, "Show the 'Guess the magnitude' questions");
-        ui.checkbox(&mut self.game_handler.questions_settings.guess_the_magnitude.rotate_to_point, "Rotate to the object in question")
-            .on_hover_text("Whether or not to rotate the view so that the object in question is in the centre of the screen");
+        ui.checkbox(&mut self.game_handler.questions_settings.guess_the_magnitude.show, tr("Show the 'Guess the magnitude' questions"));
+        ui.checkbox(&mut self.game_handler.questions_settings.guess_the_magnitude.rotate_to_point, tr("Rotate to the object in question"))
+            .on_hover_text(tr("Whether or not to rotate the view so that the object in question is in the centre of the screen"));
         ui.checkbox(
             &mut self.game_handler.questions_settings.guess_the_magnitude.limit_to_toggled_constellations,
-            "Limit to objects from toggled constellations",
+            tr("Limit to objects from toggled constellations"),
         );
-        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.guess_the_magnitude.magnitude_cutoff, 0.0..=20.0).text("Star magnitude cutoff"));
-        ui.checkbox(&mut self.game_handler.questions_settings.guess_the_magnitude.replay_incorrect, "Replay incorrectly answered questions");
+        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.guess_the_magnitude.magnitude_cutoff, 0.0..=20.0).text(tr("Star magnitude cutoff")));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.guess_the_magnitude.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.guess_the_magnitude.scoring,
+                questions::guess_the_magnitude::default_scoring(),
+                " mag",
+                false,
+            )
+        });
+    }
+
+    fn render_game_settings_separation_subwindow(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.game_handler.questions_settings.guess_the_separation.show, tr("Show the 'Guess the separation' questions"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.guess_the_separation.rotate_to_point,
+            tr("Rotate to the double star in question"),
+        )
+        .on_hover_text(tr("Whether or not to rotate the view so that the double star in question is in the centre of the screen"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.guess_the_separation.limit_to_toggled_constellations,
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.guess_the_separation.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.guess_the_separation.scoring,
+                questions::guess_the_separation::default_scoring(),
+                " %",
+                false,
+            )
+        });
+    }
+
+    fn render_game_settings_which_variable_star_subwindow(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_variable_star.show,
+            tr("Show the 'Which variable star is this' questions"),
+        );
+        ui.checkbox(&mut self.game_handler.questions_settings.which_variable_star.rotate_to_answer, tr("Rotate to the star after answering"))
+            .on_hover_text(tr("Whether or not to rotate the view so that the star in question is in the centre of the screen after answering"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_variable_star.limit_to_toggled_constellations,
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_variable_star.show_constellation,
+            tr("Tell the constellation of the star"),
+        );
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_variable_star.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.which_variable_star.scoring,
+                questions::which_variable_star::default_scoring(),
+                "",
+                false,
+            )
+        });
+    }
+
+    fn render_game_settings_constellation_genitive_subwindow(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.constellation_genitive.show,
+            tr("Show the 'What is the genitive of this constellation' questions"),
+        )
+        .on_hover_text(tr("The genitive is the form of the latin name used in the designations of stars, e.g. 'Orionis' in 'alpha Orionis'"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.constellation_genitive.limit_to_toggled_constellations,
+            tr("Limit to toggled constellations"),
+        );
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.constellation_genitive.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.constellation_genitive.scoring,
+                questions::constellation_genitive::default_scoring(),
+                "",
+                false,
+            )
+        });
+    }
+
+    fn render_game_settings_which_bayer_letter_subwindow(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.game_handler.questions_settings.which_bayer_letter.show, tr("Show the 'Which Bayer letter' questions"));
+        ui.checkbox(&mut self.game_handler.questions_settings.which_bayer_letter.rotate_to_point, tr("Rotate to the star"))
+            .on_hover_text(tr("Whether or not to rotate the view so that the star in question is in the centre of the screen"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_bayer_letter.limit_to_toggled_constellations,
+            tr("Limit to objects from toggled constellations"),
+        );
+        ui.add(egui::Slider::new(&mut self.game_handler.questions_settings.which_bayer_letter.brightest_in_constellation, 1..=24).text(tr("Brightest stars of each constellation")))
+            .on_hover_text(tr("Only the stars among this many brightest stars of their constellation are asked about"));
+        ui.checkbox(
+            &mut self.game_handler.questions_settings.which_bayer_letter.replay_incorrect,
+            tr("Replay incorrectly answered questions"),
+        );
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(
+                ui,
+                &mut self.game_handler.questions_settings.which_bayer_letter.scoring,
+                questions::which_bayer_letter::default_scoring(),
+                "",
+                false,
+            )
+        });
+    }
+
+    fn render_game_settings_compare_brightness_subwindow(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.game_handler.questions_settings.compare_brightness;
+        ui.checkbox(&mut settings.show, tr("Show the 'Compare the brightness' questions"));
+        ui.checkbox(&mut settings.rotate_to_stars, tr("Rotate to the stars"))
+            .on_hover_text(tr("Whether or not to rotate the view so that the compared stars are in the centre of the screen"));
+        ui.checkbox(&mut settings.limit_to_toggled_constellations, tr("Limit to objects from toggled constellations"));
+        ui.add(egui::Slider::new(&mut settings.magnitude_cutoff, 0.0..=20.0).text(tr("Star magnitude cutoff")));
+        ui.add(egui::Slider::new(&mut settings.stars_per_question, 2..=3).text(tr("Stars in each question")));
+        ui.add(egui::Slider::new(&mut settings.min_magnitude_difference, 0.0..=5.0).text(tr("Minimal magnitude difference")))
+            .on_hover_text(tr("The smaller the difference between the stars, the harder it is to tell which one is brighter"));
+        ui.add(egui::Slider::new(&mut settings.max_magnitude_difference, 0.0..=10.0).text(tr("Maximal magnitude difference")));
+        if settings.max_magnitude_difference < settings.min_magnitude_difference {
+            settings.max_magnitude_difference = settings.min_magnitude_difference;
+        }
+        ui.add(egui::Slider::new(&mut settings.max_separation, 1.0..=180.0).text(tr("Maximal separation of the stars (°)")));
+        ui.checkbox(&mut settings.replay_incorrect, tr("Replay incorrectly answered questions"));
+        ui.collapsing(tr("Scoring"), |ui| {
+            render_scoring_settings(ui, &mut settings.scoring, questions::compare_brightness::default_scoring(), "", false)
+        });
+    }
+}
+
+/// Lets the player change how many points the questions are worth. `unit` is the unit of the error of an answer, questions whose answers are either correct or not have no unit and only award points for a correct answer.
+fn render_scoring_settings(ui: &mut egui::Ui, scoring: &mut ScoringRules, default: ScoringRules, unit: &str, hints: bool) {
+    if !unit.is_empty() {
+        let curve_name = |curve: &ScoreCurve| match curve {
+            ScoreCurve::Stepwise(_) => tr("Points in steps by the error"),
+            ScoreCurve::Continuous { .. } => tr("Points decreasing with the error"),
+            ScoreCurve::Correct { .. } => tr("Points for a correct answer"),
+        };
+        let max_points = scoring.max_points() as f32 - scoring.time_bonus.max_points;
+        let stepwise = match &default.curve {
+            ScoreCurve::Stepwise(steps) => ScoreCurve::Stepwise(steps.clone()),
+            _ => ScoringRules::stepwise([1.0, 2.0, 5.0]).curve,
+        };
+        let continuous = ScoreCurve::Continuous {
+            max_points,
+            max_error: match &scoring.curve {
+                ScoreCurve::Stepwise(steps) => steps.iter().map(|step| step.below).fold(0.0, f32::max),
+                ScoreCurve::Continuous { max_error, .. } => *max_error,
+                ScoreCurve::Correct { .. } => 1.0,
+            },
+        };
+        let correct = ScoreCurve::Correct { points: max_points };
+        egui::ComboBox::from_id_salt("Scoring curve").selected_text(curve_name(&scoring.curve)).show_ui(ui, |ui| {
+            for curve in [stepwise, continuous, correct] {
+                let selected = std::mem::discriminant(&curve) == std::mem::discriminant(&scoring.curve);
+                if ui.selectable_label(selected, curve_name(&curve)).clicked() && !selected {
+                    scoring.curve = curve;
+                }
+            }
+        });
+    }
+    match &mut scoring.curve {
+        ScoreCurve::Stepwise(steps) => {
+            let mut removed = None;
+            for (i, step) in steps.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut step.points).speed(0.1).range(0.0..=100.0).suffix(tr(" points")));
+                    ui.label(tr("for an error below"));
+                    ui.add(egui::DragValue::new(&mut step.below).speed(0.1).range(0.0..=f32::MAX).suffix(unit));
+                    if ui.button(tr("Remove")).clicked() {
+                        removed = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = removed {
+                steps.remove(i);
+            }
+            if ui.button(tr("Add a step")).clicked() {
+                let below = steps.last().map(|step| step.below * 2.0).unwrap_or(1.0);
+                steps.push(ScoreStep { below, points: 1.0 });
+            }
+            // The first step the error is below is used, so they have to stay sorted
+            steps.sort_by(|a, b| a.below.total_cmp(&b.below));
+        }
+        ScoreCurve::Continuous { max_points, max_error } => {
+            ui.add(egui::DragValue::new(max_points).speed(0.1).range(0.0..=100.0).prefix(tr("Up to ")).suffix(tr(" points")));
+            ui.add(egui::DragValue::new(max_error).speed(0.1).range(0.0..=f32::MAX).prefix(tr("Nothing for an error of ")).suffix(unit));
+        }
+        ScoreCurve::Correct { points } => {
+            ui.add(egui::DragValue::new(points).speed(0.1).range(0.0..=100.0).suffix(tr(" points")));
+        }
+    }
+    ui.add(egui::Slider::new(&mut scoring.time_bonus.max_points, 0.0..=10.0).text(tr("Time bonus (points)")))
+        .on_hover_text(tr("Extra points for a quick answer, decreasing to nothing at the time limit below"));
+    ui.add(egui::Slider::new(&mut scoring.time_bonus.within_seconds, 1.0..=300.0).text(tr("Time limit of the bonus (seconds)")));
+    if hints {
+        let mut hint_penalty = scoring.hint_penalty * 100.0;
+        ui.add(egui::Slider::new(&mut hint_penalty, 0.0..=100.0).text(tr("Points lost for every hint (%)")));
+        scoring.hint_penalty = hint_penalty / 100.0;
+    }
+    ui.label(scoring.describe(unit));
+    if ui.button(tr("Reset the scoring")).clicked() {
+        *scoring = default;
     }
 }
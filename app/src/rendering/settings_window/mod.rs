@@ -1,18 +1,19 @@
-use crate::{structs::state::windows::settings::SettingsSubWindow, Application};
+use crate::{localisation::tr, structs::state::windows::settings::SettingsSubWindow, Application};
 use eframe::egui;
 
 pub mod application_settings_window;
 pub mod game_settings;
 pub mod sky_settings_window;
+pub mod theme_editor_window;
 
 impl Application {
     pub fn render_settings_window(&mut self, ctx: &egui::Context) -> Option<egui::InnerResponse<Option<()>>> {
         let mut opened = self.state.windows.settings.opened;
-        let response = egui::Window::new("Settings").open(&mut opened).show(ctx, |ui| {
+        let response = egui::Window::new(tr("Settings")).id(egui::Id::new("Settings")).open(&mut opened).show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut self.state.windows.settings.subwindow, SettingsSubWindow::Application, SettingsSubWindow::Application.as_ref());
-                ui.selectable_value(&mut self.state.windows.settings.subwindow, SettingsSubWindow::Game, SettingsSubWindow::Game.as_ref());
-                ui.selectable_value(&mut self.state.windows.settings.subwindow, SettingsSubWindow::Sky, SettingsSubWindow::Sky.as_ref());
+                ui.selectable_value(&mut self.state.windows.settings.subwindow, SettingsSubWindow::Application, tr(SettingsSubWindow::Application.as_ref()));
+                ui.selectable_value(&mut self.state.windows.settings.subwindow, SettingsSubWindow::Game, tr(SettingsSubWindow::Game.as_ref()));
+                ui.selectable_value(&mut self.state.windows.settings.subwindow, SettingsSubWindow::Sky, tr(SettingsSubWindow::Sky.as_ref()));
             });
             ui.separator();
             match self.state.windows.settings.subwindow {
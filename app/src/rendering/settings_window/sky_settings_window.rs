@@ -1,11 +1,13 @@
 use std::collections::HashSet;
 
 use eframe::egui;
+use sg_geometry::projection::Projection;
 
 use crate::{
     enums::{LightPollution, RendererCategory},
+    localisation::{tr, tr_args},
     renderer::CellestialSphere,
-    rendering::caspr::{markers::game_markers::GameMarker, stars},
+    rendering::caspr::{camera_mode::CameraMode, camera_transition::Easing, markers::game_markers::GameMarker, stars},
     structs::state::windows::settings::SkySettingsSubWindow,
     Application,
 };
@@ -16,27 +18,32 @@ impl Application {
             ui.selectable_value(
                 &mut self.state.windows.settings.sky_settings.subwindow,
                 SkySettingsSubWindow::General,
-                SkySettingsSubWindow::General.as_ref(),
+                tr(SkySettingsSubWindow::General.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.sky_settings.subwindow,
                 SkySettingsSubWindow::Stars,
-                SkySettingsSubWindow::Stars.as_ref(),
+                tr(SkySettingsSubWindow::Stars.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.sky_settings.subwindow,
                 SkySettingsSubWindow::Deepsky,
-                SkySettingsSubWindow::Deepsky.as_ref(),
+                tr(SkySettingsSubWindow::Deepsky.as_ref()),
+            );
+            ui.selectable_value(
+                &mut self.state.windows.settings.sky_settings.subwindow,
+                SkySettingsSubWindow::Shapes,
+                tr(SkySettingsSubWindow::Shapes.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.sky_settings.subwindow,
                 SkySettingsSubWindow::Lines,
-                SkySettingsSubWindow::Lines.as_ref(),
+                tr(SkySettingsSubWindow::Lines.as_ref()),
             );
             ui.selectable_value(
                 &mut self.state.windows.settings.sky_settings.subwindow,
                 SkySettingsSubWindow::Markers,
-                SkySettingsSubWindow::Markers.as_ref(),
+                tr(SkySettingsSubWindow::Markers.as_ref()),
             );
         });
         ui.separator();
@@ -46,6 +53,7 @@ impl Application {
                 SkySettingsSubWindow::General => self.render_sky_settings_general_subwindow(ui),
                 SkySettingsSubWindow::Stars => self.render_sky_settings_stars_subwindow(ui),
                 SkySettingsSubWindow::Deepsky => self.render_sky_settings_deepsky_subwindow(ui),
+                SkySettingsSubWindow::Shapes => self.render_sky_settings_shapes_subwindow(ui),
                 SkySettingsSubWindow::Lines => self.render_sky_settings_lines_subwindow(ui),
                 SkySettingsSubWindow::Markers => self.render_sky_settings_markers_subwindow(ui),
             });
@@ -53,8 +61,8 @@ impl Application {
 
     pub fn render_sky_settings_general_subwindow(&mut self, ui: &mut egui::Ui) {
         let prev_light_pollution = self.cellestial_sphere.light_pollution_place;
-        ui.label("Light pollution level: ")
-            .on_hover_text("These settings are made to reflect how the sky looks in different locations for a person with an average eyesight.");
+        ui.label(tr("Light pollution level: "))
+            .on_hover_text(tr("These settings are made to reflect how the sky looks in different locations for a person with an average eyesight."));
         egui::ComboBox::from_id_salt("Light pollution level: ")
             .selected_text(format!("{}", self.cellestial_sphere.light_pollution_place))
             .show_ui(ui, |ui| {
@@ -69,37 +77,66 @@ impl Application {
             self.cellestial_sphere.sky_settings.mag_to_radius_settings[self.cellestial_sphere.sky_settings.mag_to_radius_id] = settings;
         }
         ui.separator();
+        let mut projection = self.cellestial_sphere.sky_settings.projection;
+        ui.label(tr("Projection")).on_hover_text(tr(
+            "How the sky is drawn onto the screen. Printed star charts are usually stereographic or rectangular (equirectangular).",
+        ));
+        egui::ComboBox::from_id_salt("Projection").selected_text(format!("{projection}")).show_ui(ui, |ui| {
+            for val in Projection::variants() {
+                ui.selectable_value(&mut projection, val, format!("{val}"));
+            }
+        });
+        if projection != self.cellestial_sphere.sky_settings.projection {
+            self.cellestial_sphere.set_projection(projection);
+        }
+        let mut camera_mode = self.cellestial_sphere.sky_settings.camera_mode;
+        ui.label(tr("Camera")).on_hover_text(tr(
+            "How the sky turns when dragged. There is no observer on the Earth, so 'up' can only be the north celestial pole, not the zenith.",
+        ));
+        egui::ComboBox::from_id_salt("Camera mode").selected_text(format!("{camera_mode}")).show_ui(ui, |ui| {
+            for val in CameraMode::variants() {
+                ui.selectable_value(&mut camera_mode, val, format!("{val}")).on_hover_text(val.explanation());
+            }
+        });
+        if camera_mode != self.cellestial_sphere.sky_settings.camera_mode {
+            self.cellestial_sphere.set_camera_mode(camera_mode);
+        }
+        ui.checkbox(&mut self.cellestial_sphere.sky_settings.show_compass, tr("Show the compass"))
+            .on_hover_text(tr("Arrows in the corner of the screen pointing to the north and east at the centre of the view"));
+        ui.separator();
         let previous_enabled = self.cellestial_sphere.sky_settings.cloud_settings.enabled;
         let previous_coverage = self.cellestial_sphere.sky_settings.cloud_settings.coverage;
         let previous_thickness = self.cellestial_sphere.sky_settings.cloud_settings.thickness;
         let previous_iterations = self.cellestial_sphere.sky_settings.cloud_settings.iterations;
-        ui.label("Cloudiness")
-            .on_hover_text("These settings dictate what the maximum increase in magnitude (decrease in brightness) should be due to clouds and how the clouds should look like");
+        ui.label(tr("Cloudiness")).on_hover_text(tr(
+            "These settings dictate what the maximum increase in magnitude (decrease in brightness) should be due to clouds and how the clouds should look like",
+        ));
 
         ui.horizontal(|ui| {
             ui.add(egui::Checkbox::without_text(&mut self.cellestial_sphere.sky_settings.cloud_settings.recalculate_on_change));
-            ui.label("Recalculate on change of settings").on_hover_text("Should the clouds be recalculated when settings change?");
+            ui.label(tr("Recalculate on change of settings"))
+                .on_hover_text(tr("Should the clouds be recalculated when settings change?"));
         });
         ui.horizontal(|ui| {
             ui.add(egui::Checkbox::without_text(&mut self.cellestial_sphere.sky_settings.cloud_settings.enabled));
-            ui.label("Enabled").on_hover_text("Should there be any clouds?");
+            ui.label(tr("Enabled")).on_hover_text(tr("Should there be any clouds?"));
         });
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.cellestial_sphere.sky_settings.cloud_settings.coverage).speed(0.02));
-            ui.label("Coverage").on_hover_text("How much of the sky (a fraction from 0 to 1) should be covered in clouds?");
+            ui.label(tr("Coverage")).on_hover_text(tr("How much of the sky (a fraction from 0 to 1) should be covered in clouds?"));
         });
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.cellestial_sphere.sky_settings.cloud_settings.thickness).speed(0.1))
-                .on_hover_text("How thick should the clouds be? More specifically, what should the maximum increase in magnitude due to clouds be?");
-            ui.label("Thickness");
+                .on_hover_text(tr("How thick should the clouds be? More specifically, what should the maximum increase in magnitude due to clouds be?"));
+            ui.label(tr("Thickness"));
         });
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.cellestial_sphere.sky_settings.cloud_settings.iterations).speed(0.1))
-                .on_hover_text("How detailed should the clouds be? Higher values lead to more structured clouds (1 corresponds to essentially blobs on the sky, 8 and higher actually look like clouds) but at the cost of longer computation times.");
-            ui.label("Level of detail");
+                .on_hover_text(tr("How detailed should the clouds be? Higher values lead to more structured clouds (1 corresponds to essentially blobs on the sky, 8 and higher actually look like clouds) but at the cost of longer computation times."));
+            ui.label(tr("Level of detail"));
         });
 
-        let recalculate = ui.button("Apply settings").clicked();
+        let recalculate = ui.button(tr("Apply settings")).clicked();
 
         self.cellestial_sphere.sky_settings.cloud_settings.clamp();
 
@@ -120,15 +157,35 @@ impl Application {
                 self.cellestial_sphere.init_single_renderer_group(RendererCategory::Stars, &star_set_name);
             }
         }
+        ui.separator();
+        ui.label(tr("Camera movement")).on_hover_text(tr(
+            "How the camera moves when it is turned automatically, for example to the answer of a question or to a searched object",
+        ));
+        let camera_transition_settings = &mut self.cellestial_sphere.sky_settings.camera_transition_settings;
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut camera_transition_settings.duration).speed(0.05).suffix(" s"));
+            ui.label(tr("Duration")).on_hover_text(tr("How long the movement takes, 0 moves the camera instantly"));
+        });
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("Camera movement easing")
+                .selected_text(format!("{}", camera_transition_settings.easing))
+                .show_ui(ui, |ui| {
+                    for easing in Easing::variants() {
+                        ui.selectable_value(&mut camera_transition_settings.easing, easing, format!("{easing}"));
+                    }
+                });
+            ui.label(tr("Easing")).on_hover_text(tr("How the camera speeds up and slows down during the movement"));
+        });
+        camera_transition_settings.clamp();
     }
 
     pub fn render_sky_settings_stars_subwindow(&mut self, ui: &mut egui::Ui) {
-        let override_rule_changed = ui.checkbox(&mut self.graphics_settings.use_overriden_star_colour, "Override the default star colour").changed();
+        let override_rule_changed = ui.checkbox(&mut self.graphics_settings.use_overriden_star_colour, tr("Override the default star colour")).changed();
         self.theme.game_visuals.use_overriden_star_colour = self.graphics_settings.use_overriden_star_colour;
         let override_colour_changed = ui
             .horizontal(|ui| {
                 let changed = ui.color_edit_button_srgba(&mut self.theme.game_visuals.override_star_colour).changed();
-                ui.label("Override star colour");
+                ui.label(tr("Override star colour"));
                 changed
             })
             .inner;
@@ -144,16 +201,20 @@ impl Application {
                     star.override_colour = colour;
                 }
             }
+            for catalogue in &mut self.cellestial_sphere.tiled_stars {
+                catalogue.override_colour = colour;
+            }
             let keys = self.cellestial_sphere.stars.keys().cloned().collect::<Vec<String>>();
             for star_set_name in keys {
                 self.cellestial_sphere.init_single_renderer_group(RendererCategory::Stars, &star_set_name);
             }
+            self.cellestial_sphere.init_tiled_star_renderers();
         }
 
         let mut reinit_stars = false;
         let prev_mag_to_rad_fn_id = self.cellestial_sphere.sky_settings.mag_to_radius_id;
         ui.horizontal(|ui| {
-            ui.label("Magnitude to radius function: ");
+            ui.label(tr("Magnitude to radius function: "));
             egui::ComboBox::from_id_salt("Magnitude to radius function: ")
                 .selected_text(self.cellestial_sphere.sky_settings.mag_to_radius_settings[self.cellestial_sphere.sky_settings.mag_to_radius_id].name())
                 .show_ui(ui, |ui: &mut egui::Ui| {
@@ -180,14 +241,18 @@ impl Application {
             stars::MagnitudeToRadius::Linear { mag_scale, mag_offset } => {
                 let prev_mag_offset = *mag_offset;
                 let prev_mag_scale = *mag_scale;
-                ui.horizontal_wrapped(|ui| ui.label("The following two values affect the size of the stars via the following formula: radius = mag_scale * (mag_offset - magnitude)"));
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr(
+                        "The following two values affect the size of the stars via the following formula: radius = mag_scale * (mag_offset - magnitude)",
+                    ))
+                });
                 ui.horizontal(|ui| {
                     ui.add(egui::DragValue::new(mag_offset).speed(0.03));
-                    ui.label("Magnitude offset (mag_offset)");
+                    ui.label(tr("Magnitude offset (mag_offset)"));
                 });
                 ui.horizontal(|ui| {
                     ui.add(egui::DragValue::new(mag_scale).speed(0.01));
-                    ui.label("Magnitude scale (mag_scale)");
+                    ui.label(tr("Magnitude scale (mag_scale)"));
                 });
                 if prev_mag_offset != *mag_offset || prev_mag_scale != *mag_scale {
                     self.cellestial_sphere.light_pollution_place = CellestialSphere::mag_settings_to_light_pollution_place(
@@ -201,21 +266,25 @@ impl Application {
                 let prev_r0 = *r_0;
                 let prev_n = *n;
                 let prev_o = *o;
-                ui.horizontal_wrapped(|ui| ui.label("The following three values affect the size of the stars via the following formula: radius = r_0 * ln(180°*n/fov) * 10^(-o*magnitude)"));
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr(
+                        "The following three values affect the size of the stars via the following formula: radius = r_0 * ln(180°*n/fov) * 10^(-o*magnitude)",
+                    ))
+                });
                 ui.horizontal(|ui| {
                     ui.add(egui::DragValue::new(r_0).speed(0.03));
-                    ui.label("r_0 (a size multiplier)");
+                    ui.label(tr("r_0 (a size multiplier)"));
                 });
                 ui.horizontal(|ui| {
                     ui.add(egui::DragValue::new(n).speed(0.01));
-                    ui.label("n (how much does the size change (proportionally) when changing the FOV; higher values of n cause smaller changes)");
+                    ui.label(tr("n (how much does the size change (proportionally) when changing the FOV; higher values of n cause smaller changes)"));
                 });
                 if *n < 2.0 {
                     *n = 2.0;
                 }
                 ui.horizontal(|ui| {
                     ui.add(egui::DragValue::new(o).speed(0.001));
-                    ui.label("o (how much does the size change (proportionally) when changing the magnitude");
+                    ui.label(tr("o (how much does the size change (proportionally) when changing the magnitude"));
                 });
                 if prev_r0 != *r_0 || prev_n != *n || prev_o != *o {
                     self.cellestial_sphere.light_pollution_place = CellestialSphere::mag_settings_to_light_pollution_place(
@@ -231,7 +300,7 @@ impl Application {
         let mut newly_inactive_star_groups = Vec::new();
         for (name, active) in &mut self.cellestial_sphere.sky_settings.stars_categories_active {
             let active_before = *active;
-            ui.checkbox(active, format!("Render stars from the {name} file"));
+            ui.checkbox(active, tr_args("Render stars from the {0} file", &[name]));
             if !active_before && *active {
                 newly_active_star_groups.push(name.to_owned());
             } else if active_before && !*active {
@@ -239,31 +308,83 @@ impl Application {
             }
         }
 
+        if !self.cellestial_sphere.tiled_stars.is_empty() {
+            ui.separator();
+            ui.label(tr("Faint stars")).on_hover_text(tr(
+                "Large star catalogues (found in the tiled-stars folder) whose stars are only loaded and rendered when zoomed in on a part of the sky",
+            ));
+            let previous_settings = self.cellestial_sphere.sky_settings.tiled_stars_settings;
+            let settings = &mut self.cellestial_sphere.sky_settings.tiled_stars_settings;
+            ui.checkbox(&mut settings.enabled, tr("Render faint stars"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.max_fov).speed(0.5).suffix("°"));
+                ui.label(tr("Maximum FOV"))
+                    .on_hover_text(tr("The faint stars are only rendered when the field of view is smaller than this"));
+            });
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.limiting_magnitude).speed(0.05));
+                ui.label(tr("Limiting magnitude at the maximum FOV"));
+            });
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.magnitude_per_fov_halving).speed(0.05));
+                ui.label(tr("Limiting magnitude increase"))
+                    .on_hover_text(tr("By how much the limiting magnitude increases every time the field of view is halved"));
+            });
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.faintest_magnitude).speed(0.05));
+                ui.label(tr("Faintest magnitude")).on_hover_text(tr("Stars fainter than this are never rendered, no matter the zoom"));
+            });
+            settings.clamp();
+            let settings = *settings;
+            if previous_settings.enabled != settings.enabled
+                || previous_settings.max_fov != settings.max_fov
+                || previous_settings.limiting_magnitude != settings.limiting_magnitude
+                || previous_settings.magnitude_per_fov_halving != settings.magnitude_per_fov_halving
+                || previous_settings.faintest_magnitude != settings.faintest_magnitude
+            {
+                reinit_stars = true;
+            }
+        }
+
         for name in &newly_active_star_groups {
             self.cellestial_sphere.init_single_renderer_group(RendererCategory::Stars, name);
         }
         for name in &newly_inactive_star_groups {
             self.cellestial_sphere.deinit_single_renderer_group(RendererCategory::Stars, name);
         }
+        if !newly_active_star_groups.is_empty() || !newly_inactive_star_groups.is_empty() {
+            // The tiled catalogues are toggled in the same way as the other star files
+            self.cellestial_sphere.init_tiled_star_renderers();
+        }
         if reinit_stars {
             self.cellestial_sphere.reinit_renderer_category(RendererCategory::Stars);
         }
     }
 
     pub fn render_sky_settings_deepsky_subwindow(&mut self, ui: &mut egui::Ui) {
+        let mut reinit_deepskies = false;
         ui.horizontal(|ui| {
-            ui.add(egui::DragValue::new(&mut self.cellestial_sphere.sky_settings.deepsky_render_mag_decrease).speed(0.1));
-            ui.label("Magnitude decrease")
-                .on_hover_text("By how much should the magnitude of the deepsky objects be decreased for rendering - this way the objects can be made to be seen even without zooming in");
+            reinit_deepskies |= ui.add(egui::DragValue::new(&mut self.cellestial_sphere.sky_settings.deepsky_render_mag_decrease).speed(0.1)).changed();
+            ui.label(tr("Magnitude decrease")).on_hover_text(tr(
+                "By how much should the magnitude of the deepsky objects be decreased for rendering - this way the objects can be made to be seen even without zooming in",
+            ));
+        });
+        ui.horizontal(|ui| {
+            reinit_deepskies |= ui
+                .add(egui::DragValue::new(&mut self.cellestial_sphere.sky_settings.deepsky_magnitude_limit).speed(0.1).range(-2.0..=20.0))
+                .changed();
+            ui.label(tr("Magnitude limit")).on_hover_text(tr(
+                "The faintest deepsky objects shown when zoomed out - fainter objects appear as you zoom in. Objects without a known magnitude are always shown.",
+            ));
         });
 
-        ui.checkbox(&mut self.cellestial_sphere.sky_settings.render_labels, "Render labels");
+        ui.checkbox(&mut self.cellestial_sphere.sky_settings.render_labels, tr("Render labels"));
 
         let mut deepsky_groups_to_init = HashSet::new();
         let mut deepsky_groups_to_deinit = HashSet::new();
         for (name, deepskies_set) in &mut self.cellestial_sphere.deepskies {
             ui.heading(name);
-            if ui.checkbox(&mut deepskies_set.active, format!("Render deepsky objects from the {name} file")).changed() {
+            if ui.checkbox(&mut deepskies_set.active, tr_args("Render deepsky objects from the {0} file", &[name])).changed() {
                 if deepskies_set.active {
                     deepsky_groups_to_init.insert(name.to_owned());
                 } else {
@@ -272,7 +393,7 @@ impl Application {
                 self.cellestial_sphere.sky_settings.deepskies_categories_active.insert(name.to_owned(), deepskies_set.active);
             }
             ui.horizontal(|ui| {
-                ui.label("Marker colour: ");
+                ui.label(tr("Marker colour: "));
                 if ui.color_edit_button_srgba(&mut deepskies_set.colour).changed() {
                     deepsky_groups_to_init.insert(name.to_owned());
                 }
@@ -285,6 +406,39 @@ impl Application {
         for name in &deepsky_groups_to_deinit {
             self.cellestial_sphere.deinit_single_renderer_group(RendererCategory::Deepskies, name);
         }
+        if reinit_deepskies {
+            self.cellestial_sphere.reinit_renderer_category(RendererCategory::Deepskies);
+        }
+    }
+
+    pub fn render_sky_settings_shapes_subwindow(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("Outlines of extended objects like galaxies and nebulae, and of the Milky Way"));
+        let mut shape_groups_to_init = HashSet::new();
+        let mut shape_groups_to_deinit = HashSet::new();
+        for (name, shapes_set) in &mut self.cellestial_sphere.shapes {
+            ui.heading(name);
+            if ui.checkbox(&mut shapes_set.active, tr_args("Render shapes from the {0} file", &[name])).changed() {
+                if shapes_set.active {
+                    shape_groups_to_init.insert(name.to_owned());
+                } else {
+                    shape_groups_to_deinit.insert(name.to_owned());
+                }
+                self.cellestial_sphere.sky_settings.shapes_categories_active.insert(name.to_owned(), shapes_set.active);
+            }
+            ui.horizontal(|ui| {
+                ui.label(tr("Outline colour: "));
+                if ui.color_edit_button_srgba(&mut shapes_set.colour).changed() {
+                    shape_groups_to_init.insert(name.to_owned());
+                }
+            });
+            self.theme.game_visuals.shapes_colours.insert(name.clone(), shapes_set.colour);
+        }
+        for name in &shape_groups_to_init {
+            self.cellestial_sphere.init_single_renderer_group(RendererCategory::Shapes, name);
+        }
+        for name in &shape_groups_to_deinit {
+            self.cellestial_sphere.deinit_single_renderer_group(RendererCategory::Shapes, name);
+        }
     }
 
     pub fn render_sky_settings_lines_subwindow(&mut self, ui: &mut egui::Ui) {
@@ -292,7 +446,7 @@ impl Application {
         let mut line_groups_to_deinit = HashSet::new();
         for (name, lines_set) in &mut self.cellestial_sphere.lines {
             ui.heading(name);
-            if ui.checkbox(&mut lines_set.active, format!("Render lines from the {name} file")).changed() {
+            if ui.checkbox(&mut lines_set.active, tr_args("Render lines from the {0} file", &[name])).changed() {
                 if lines_set.active {
                     line_groups_to_init.insert(name.to_owned());
                 } else {
@@ -301,7 +455,7 @@ impl Application {
                 self.cellestial_sphere.sky_settings.lines_categories_active.insert(name.to_owned(), lines_set.active);
             }
             ui.horizontal(|ui| {
-                ui.label("Line colour: ");
+                ui.label(tr("Line colour: "));
                 if ui.color_edit_button_srgba(&mut lines_set.colour).changed() {
                     line_groups_to_init.insert(name.to_owned());
                 }
@@ -318,27 +472,44 @@ impl Application {
 
     pub fn render_sky_settings_markers_subwindow(&mut self, ui: &mut egui::Ui) {
         let mut game_markers_changed = false;
-        ui.heading("Game markers");
+        ui.heading(tr("Game markers"));
         ui.horizontal(|ui| {
-            ui.label("Guess marker colour: ");
+            ui.label(tr("Guess marker colour: "));
             game_markers_changed |= ui.color_edit_button_srgba(&mut self.theme.game_visuals.game_markers_colours.exact).changed();
         });
         ui.horizontal(|ui| {
-            ui.label("Tolerance marker colour: ");
+            ui.label(tr("Tolerance marker colour: "));
             game_markers_changed |= ui.color_edit_button_srgba(&mut self.theme.game_visuals.game_markers_colours.tolerance).changed();
         });
         ui.horizontal(|ui| {
-            ui.label("Task marker colour: ");
+            ui.label(tr("Task marker colour: "));
             game_markers_changed |= ui.color_edit_button_srgba(&mut self.theme.game_visuals.game_markers_colours.task).changed();
         });
         ui.horizontal(|ui| {
-            ui.label("Corrent answer marker colour: ");
+            ui.label(tr("Correct answer marker colour: "));
             game_markers_changed |= ui.color_edit_button_srgba(&mut self.theme.game_visuals.game_markers_colours.correct_answer).changed();
         });
+        ui.horizontal(|ui| {
+            ui.label(tr("Hint colour: "));
+            game_markers_changed |= ui.color_edit_button_srgba(&mut self.theme.game_visuals.game_markers_colours.hint).changed();
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Selected object marker colour: "));
+            game_markers_changed |= ui.color_edit_button_srgba(&mut self.theme.game_visuals.game_markers_colours.selection).changed();
+        });
         if game_markers_changed {
-            for marker in self.cellestial_sphere.game_markers.markers.iter_mut() {
+            for marker in self
+                .cellestial_sphere
+                .game_markers
+                .markers
+                .iter_mut()
+                .chain(self.cellestial_sphere.game_markers.hints.iter_mut())
+                .chain(self.cellestial_sphere.game_markers.selection.iter_mut())
+            {
                 marker.colour = GameMarker::get_colour(marker.marker_type, &self.theme.game_visuals.game_markers_colours);
             }
+            self.cellestial_sphere.game_markers.hint_lines_colour = self.theme.game_visuals.game_markers_colours.hint;
+            self.cellestial_sphere.game_markers.selection_lines_colour = self.theme.game_visuals.game_markers_colours.selection;
             self.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, "game");
         }
         ui.separator();
@@ -346,7 +517,7 @@ impl Application {
         let mut marker_groups_to_deinit = HashSet::new();
         for (name, markers_set) in &mut self.cellestial_sphere.markers {
             ui.heading(name);
-            if ui.checkbox(&mut markers_set.active, format!("Render markers from the {name} file")).changed() {
+            if ui.checkbox(&mut markers_set.active, tr_args("Render markers from the {0} file", &[name])).changed() {
                 if markers_set.active {
                     marker_groups_to_init.insert(name.to_owned());
                 } else {
@@ -355,7 +526,7 @@ impl Application {
                 self.cellestial_sphere.sky_settings.markers_categories_active.insert(name.to_owned(), markers_set.active);
             }
             ui.horizontal(|ui| {
-                ui.label("Marker colour: ");
+                ui.label(tr("Marker colour: "));
                 if ui.color_edit_button_srgba(&mut markers_set.colour).changed() {
                     marker_groups_to_init.insert(name.to_owned());
                 }
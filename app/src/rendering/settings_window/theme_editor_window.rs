@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use eframe::egui;
+use egui::Color32;
+
+use crate::{
+    localisation::{tr, tr_args},
+    rendering::{
+        caspr::markers::game_markers::GameMarkerType,
+        themes::{GameMarkersColours, GameMarkersPalette},
+    },
+    Application,
+};
+
+/// Colour pickers for all the colours in the map, sorted by the name of the file they belong to
+fn render_colours(ui: &mut egui::Ui, id: &str, colours: &mut HashMap<String, Color32>) {
+    let mut names = colours.keys().cloned().collect::<Vec<String>>();
+    names.sort();
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        for name in names {
+            if let Some(colour) = colours.get_mut(&name) {
+                ui.label(&name);
+                ui.color_edit_button_srgba(colour);
+                ui.end_row();
+            }
+        }
+    });
+}
+
+/// A selection of the preset colours of the game markers
+pub fn render_palette_selector(ui: &mut egui::Ui, id: &str, colours: &mut GameMarkersColours) {
+    let current_palette = GameMarkersPalette::of(colours);
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(current_palette.map(|palette| palette.to_string()).unwrap_or_else(|| tr("Custom")))
+        .show_ui(ui, |ui| {
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+            for palette in GameMarkersPalette::variants() {
+                if ui.selectable_label(current_palette == Some(palette), palette.to_string()).clicked() {
+                    *colours = palette.colours();
+                }
+            }
+        });
+}
+
+/// Draws the marker the way it looks in the sky, so that the shapes can be learnt
+fn render_marker_preview(ui: &mut egui::Ui, marker_type: GameMarkerType, colour: Color32) {
+    for circular in [true, false] {
+        let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(24.0), egui::Sense::hover());
+        marker_type.shape(circular).paint(ui.painter(), rect.center(), 8.0, egui::Stroke::new(2.0, colour));
+    }
+}
+
+fn render_game_markers_colours(ui: &mut egui::Ui, colours: &mut GameMarkersColours) {
+    ui.horizontal(|ui| {
+        ui.label(tr("Palette"));
+        render_palette_selector(ui, "Theme editor palette", colours);
+    });
+    egui::Grid::new("Theme editor game markers").striped(true).show(ui, |ui| {
+        for marker_type in GameMarkerType::variants() {
+            let colour = match marker_type {
+                GameMarkerType::Exact => &mut colours.exact,
+                GameMarkerType::Tolerance => &mut colours.tolerance,
+                GameMarkerType::Task => &mut colours.task,
+                GameMarkerType::CorrectAnswer => &mut colours.correct_answer,
+                GameMarkerType::Hint => &mut colours.hint,
+                GameMarkerType::Selection => &mut colours.selection,
+            };
+            ui.label(marker_type.to_string());
+            ui.color_edit_button_srgba(colour);
+            ui.horizontal(|ui| render_marker_preview(ui, marker_type, *colour));
+            ui.end_row();
+        }
+    });
+}
+
+impl Application {
+    pub fn render_theme_editor(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.label(tr(
+            "Changes are applied immediately. They are kept until another theme is selected, so save them as a new theme to keep them for later.",
+        ));
+        let mut theme = self.theme.clone();
+
+        ui.heading(tr("Save"));
+        let state = &mut self.state.windows.settings.application_settings;
+        ui.horizontal(|ui| {
+            ui.label(tr("New theme name: "));
+            ui.text_edit_singleline(&mut state.new_theme_name);
+        });
+        let name_taken = self.themes.get(&state.new_theme_name).is_some();
+        if name_taken {
+            ui.colored_label(ui.visuals().warn_fg_color, tr("A theme of this name already exists"));
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!state.new_theme_name.trim().is_empty() && !name_taken, egui::Button::new(tr("Save as new theme")))
+                .clicked()
+            {
+                let mut new_theme = theme.clone();
+                new_theme.name = state.new_theme_name.trim().to_string();
+                state.theme_save_result = Some(match self.themes.save_as_new(&new_theme) {
+                    Ok(path) => {
+                        theme.name = new_theme.name;
+                        Ok(tr_args("Saved to {0}", &[&path.display()]))
+                    }
+                    Err(err) => {
+                        log::error!("Failed to save the theme: {err}");
+                        Err(err)
+                    }
+                });
+            }
+            if ui.button(tr("Revert")).on_hover_text(tr("Discard the changes made since the theme was selected")).clicked() {
+                match self.themes.get(&theme.name) {
+                    Some(saved_theme) => theme = saved_theme.clone(),
+                    None => log::warn!("The theme {} is not saved, so its changes cannot be reverted", theme.name),
+                }
+            }
+        });
+        match &state.theme_save_result {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(err)) => {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            None => {}
+        }
+
+        let unknown_keys = theme.game_visuals.unknown_keys(&self.cellestial_sphere);
+        if !unknown_keys.is_empty() {
+            ui.heading(tr("Problems"));
+            ui.colored_label(ui.visuals().warn_fg_color, tr("The theme has colours for files which are not loaded (they may have been mistyped):"));
+            for (field, key) in &unknown_keys {
+                ui.label(format!("{field}: {key}"));
+            }
+            if ui.button(tr("Remove them")).clicked() {
+                theme.game_visuals.remove_unknown_keys(&self.cellestial_sphere);
+            }
+        }
+
+        ui.heading(tr("Sky"));
+        ui.horizontal(|ui| {
+            ui.label(tr("Default colour"));
+            ui.color_edit_button_srgba(&mut theme.game_visuals.default_colour)
+                .on_hover_text(tr("The colour of objects which are neither in the theme nor have their own colour"));
+        });
+        ui.collapsing(tr("Lines"), |ui| render_colours(ui, "Theme editor lines", &mut theme.game_visuals.lines_colours));
+        ui.collapsing(tr("Markers"), |ui| render_colours(ui, "Theme editor markers", &mut theme.game_visuals.markers_colours));
+        ui.collapsing(tr("Deepsky objects"), |ui| render_colours(ui, "Theme editor deepskies", &mut theme.game_visuals.deepskies_colours));
+        ui.collapsing(tr("Shapes"), |ui| render_colours(ui, "Theme editor shapes", &mut theme.game_visuals.shapes_colours));
+        ui.collapsing(tr("Game markers"), |ui| render_game_markers_colours(ui, &mut theme.game_visuals.game_markers_colours));
+
+        ui.heading(tr("Interface"));
+        ui.collapsing(tr("Interface colours and shapes"), |ui| theme.egui_visuals.ui(ui));
+
+        if theme != self.theme {
+            self.apply_theme(ctx, theme);
+        }
+    }
+}
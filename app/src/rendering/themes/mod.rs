@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use eframe::egui;
 use egui::Color32;
 
-use crate::{enums::RendererCategory, Application};
+use crate::{enums::RendererCategory, files, public_constants, renderer::CellestialSphere, Application};
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
 pub struct Theme {
@@ -49,6 +49,14 @@ impl Theme {
                     [("messier-catalogue.csv", [107, 238, 255, 255]), ("caldwell-catalogue.csv", [107, 255, 191, 255])]
                         .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
                 ),
+                shapes_colours: HashMap::from(
+                    [
+                        ("messier-shapes.csv", [107, 238, 255, 255]),
+                        ("caldwell-shapes.csv", [107, 255, 191, 255]),
+                        ("milky-way.csv", [140, 140, 180, 255]),
+                    ]
+                    .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
+                ),
             },
             egui_visuals: egui::Visuals::dark(),
         }
@@ -94,6 +102,14 @@ impl Theme {
                     [("messier-catalogue.csv", [107, 238, 255, 255]), ("caldwell-catalogue.csv", [107, 255, 191, 255])]
                         .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
                 ),
+                shapes_colours: HashMap::from(
+                    [
+                        ("messier-shapes.csv", [107, 238, 255, 255]),
+                        ("caldwell-shapes.csv", [107, 255, 191, 255]),
+                        ("milky-way.csv", [140, 140, 180, 255]),
+                    ]
+                    .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
+                ),
             },
             egui_visuals,
         }
@@ -125,6 +141,43 @@ impl ThemesHandler {
     pub fn from_hash_map(data: HashMap<String, Theme>) -> Self {
         Self { data }
     }
+
+    /// Writes the theme into a new file in the themes folder and adds it to the available themes. Fails if a theme of the same name already exists.
+    pub fn save_as_new(&mut self, theme: &Theme) -> Result<std::path::PathBuf, String> {
+        if theme.name.trim().is_empty() {
+            return Err("The theme has no name".into());
+        }
+        if self.data.contains_key(&theme.name) {
+            return Err(format!("A theme called '{}' already exists", theme.name));
+        }
+        let mut path = files::get_dir_opt(public_constants::THEMES_FOLDER).ok_or("Failed to get the themes folder")?;
+        if !path.exists() {
+            std::fs::create_dir_all(&path).map_err(|err| format!("Failed to create the themes folder: {err}"))?;
+        }
+        path.push(format!("{}.json", theme_file_name(&theme.name)));
+        if path.exists() {
+            return Err(format!("The file {} already exists", path.display()));
+        }
+        let theme_str = serde_json::to_string_pretty(theme).map_err(|err| format!("Failed to serialize the theme: {err}"))?;
+        std::fs::write(&path, theme_str).map_err(|err| format!("Failed to save the theme: {err}"))?;
+        self.insert(theme.name.clone(), theme.clone());
+        Ok(path)
+    }
+}
+
+/// A file name (without the extension) for a theme of the given name, with only the characters that are safe on every platform
+pub fn theme_file_name(theme_name: &str) -> String {
+    theme_name
+        .trim()
+        .chars()
+        .map(|character| {
+            if character.is_alphanumeric() || character == '-' || character == '_' {
+                character.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
@@ -140,6 +193,40 @@ pub struct Visuals {
     pub game_markers_colours: GameMarkersColours,
     #[serde(default)]
     pub deepskies_colours: HashMap<String, Color32>,
+    #[serde(default)]
+    pub shapes_colours: HashMap<String, Color32>,
+}
+
+impl Visuals {
+    /// Keys of the colours which do not belong to any of the loaded files (most likely typos in a theme file), together with the name of the field they are in
+    pub fn unknown_keys(&self, cellestial_sphere: &CellestialSphere) -> Vec<(&'static str, String)> {
+        let mut unknown_keys = Vec::new();
+        let fields = [
+            ("lines_colours", &self.lines_colours, cellestial_sphere.lines.keys().collect::<Vec<&String>>()),
+            ("markers_colours", &self.markers_colours, cellestial_sphere.markers.keys().collect()),
+            ("deepskies_colours", &self.deepskies_colours, cellestial_sphere.deepskies.keys().collect()),
+            ("shapes_colours", &self.shapes_colours, cellestial_sphere.shapes.keys().collect()),
+        ];
+        for (field, colours, known_keys) in fields {
+            let mut unknown = colours.keys().filter(|key| !known_keys.contains(key)).cloned().collect::<Vec<String>>();
+            unknown.sort();
+            unknown_keys.extend(unknown.into_iter().map(|key| (field, key)));
+        }
+        unknown_keys
+    }
+
+    /// Removes the colours returned by `unknown_keys`
+    pub fn remove_unknown_keys(&mut self, cellestial_sphere: &CellestialSphere) {
+        for (field, key) in self.unknown_keys(cellestial_sphere) {
+            let colours = match field {
+                "lines_colours" => &mut self.lines_colours,
+                "markers_colours" => &mut self.markers_colours,
+                "deepskies_colours" => &mut self.deepskies_colours,
+                _ => &mut self.shapes_colours,
+            };
+            colours.remove(&key);
+        }
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
@@ -148,6 +235,18 @@ pub struct GameMarkersColours {
     pub tolerance: Color32,
     pub task: Color32,
     pub correct_answer: Color32,
+    #[serde(default = "default_hint_colour")]
+    pub hint: Color32,
+    #[serde(default = "default_selection_colour")]
+    pub selection: Color32,
+}
+
+fn default_hint_colour() -> Color32 {
+    Color32::LIGHT_BLUE
+}
+
+fn default_selection_colour() -> Color32 {
+    Color32::LIGHT_GREEN
 }
 
 impl Default for GameMarkersColours {
@@ -157,8 +256,67 @@ impl Default for GameMarkersColours {
             tolerance: Color32::LIGHT_RED,
             task: Color32::YELLOW,
             correct_answer: Color32::YELLOW,
+            hint: default_hint_colour(),
+            selection: default_selection_colour(),
+        }
+    }
+}
+
+/// Preset colours of the game markers. Apart from the default, they are chosen to stay distinguishable with the common kinds of colour blindness.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameMarkersPalette {
+    Default,
+    /// The palette by Masataka Okabe and Kei Ito
+    OkabeIto,
+    /// The bright qualitative palette by Paul Tol
+    TolBright,
+}
+
+impl GameMarkersPalette {
+    pub fn variants() -> [Self; 3] {
+        [Self::Default, Self::OkabeIto, Self::TolBright]
+    }
+
+    pub fn colours(&self) -> GameMarkersColours {
+        match self {
+            Self::Default => GameMarkersColours::default(),
+            Self::OkabeIto => GameMarkersColours {
+                exact: Color32::from_rgb(213, 94, 0),
+                tolerance: Color32::from_rgb(230, 159, 0),
+                task: Color32::from_rgb(86, 180, 233),
+                correct_answer: Color32::from_rgb(0, 158, 115),
+                hint: Color32::from_rgb(240, 228, 66),
+                selection: Color32::from_rgb(204, 121, 167),
+            },
+            Self::TolBright => GameMarkersColours {
+                exact: Color32::from_rgb(238, 102, 119),
+                tolerance: Color32::from_rgb(170, 51, 119),
+                task: Color32::from_rgb(204, 187, 68),
+                correct_answer: Color32::from_rgb(102, 204, 238),
+                hint: Color32::from_rgb(34, 136, 51),
+                selection: Color32::from_rgb(187, 187, 187),
+            },
         }
     }
+
+    /// The palette the colours come from, if they were not changed since
+    pub fn of(colours: &GameMarkersColours) -> Option<Self> {
+        Self::variants().into_iter().find(|palette| &palette.colours() == colours)
+    }
+}
+
+impl std::fmt::Display for GameMarkersPalette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Default => "Default",
+                Self::OkabeIto => "Okabe-Ito (colour-blind safe)",
+                Self::TolBright => "Paul Tol bright (colour-blind safe)",
+            }
+        )
+    }
 }
 
 pub fn default_themes() -> ThemesHandler {
@@ -202,12 +360,21 @@ pub fn default_themes() -> ThemesHandler {
                 game_markers_colours: GameMarkersColours {
                     task: Color32::from_rgba_unmultiplied(255, 155, 0, 255),
                     correct_answer: Color32::from_rgba_unmultiplied(255, 155, 0, 255),
+                    selection: Color32::from_rgba_unmultiplied(0, 160, 60, 255),
                     ..Default::default()
                 },
                 deepskies_colours: HashMap::from(
                     [("messier-catalogue.csv", [62, 211, 228, 255]), ("caldwell-catalogue.csv", [75, 227, 165, 255])]
                         .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
                 ),
+                shapes_colours: HashMap::from(
+                    [
+                        ("messier-shapes.csv", [62, 211, 228, 255]),
+                        ("caldwell-shapes.csv", [75, 227, 165, 255]),
+                        ("milky-way.csv", [120, 120, 160, 255]),
+                    ]
+                    .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
+                ),
             },
             egui_visuals: egui::Visuals::light(),
         },
@@ -250,12 +417,21 @@ pub fn default_themes() -> ThemesHandler {
                 game_markers_colours: GameMarkersColours {
                     task: Color32::from_rgba_unmultiplied(255, 155, 0, 255),
                     correct_answer: Color32::from_rgba_unmultiplied(255, 155, 0, 255),
+                    selection: Color32::from_rgba_unmultiplied(0, 160, 60, 255),
                     ..Default::default()
                 },
                 deepskies_colours: HashMap::from(
                     [("messier-catalogue.csv", [62, 211, 228, 255]), ("caldwell-catalogue.csv", [75, 227, 165, 255])]
                         .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
                 ),
+                shapes_colours: HashMap::from(
+                    [
+                        ("messier-shapes.csv", [62, 211, 228, 255]),
+                        ("caldwell-shapes.csv", [75, 227, 165, 255]),
+                        ("milky-way.csv", [120, 120, 160, 255]),
+                    ]
+                    .map(|(n, c)| (n.to_string(), Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]))),
+                ),
             },
             egui_visuals,
         },
@@ -270,10 +446,10 @@ impl Application {
         for (name, deepskies) in &mut self.cellestial_sphere.deepskies {
             match self.theme.game_visuals.deepskies_colours.get(name) {
                 Some(colour) => {
-                    deepskies.colour = *colour;
-                    if deepskies.active {
+                    if deepskies.colour != *colour && deepskies.active {
                         deepskies_to_reinit.push(name.clone());
                     }
+                    deepskies.colour = *colour;
                 }
                 None => {
                     self.theme.game_visuals.deepskies_colours.insert(name.clone(), deepskies.colour);
@@ -283,14 +459,31 @@ impl Application {
         for name in deepskies_to_reinit {
             self.cellestial_sphere.init_single_renderer_group(RendererCategory::Deepskies, &name);
         }
+        let mut shapes_to_reinit = Vec::new();
+        for (name, shapes) in &mut self.cellestial_sphere.shapes {
+            match self.theme.game_visuals.shapes_colours.get(name) {
+                Some(colour) => {
+                    if shapes.colour != *colour && shapes.active {
+                        shapes_to_reinit.push(name.clone());
+                    }
+                    shapes.colour = *colour;
+                }
+                None => {
+                    self.theme.game_visuals.shapes_colours.insert(name.clone(), shapes.colour);
+                }
+            }
+        }
+        for name in shapes_to_reinit {
+            self.cellestial_sphere.init_single_renderer_group(RendererCategory::Shapes, &name);
+        }
         let mut lines_to_reinit = Vec::new();
         for (name, lines) in &mut self.cellestial_sphere.lines {
             match self.theme.game_visuals.lines_colours.get(name) {
                 Some(colour) => {
-                    lines.colour = *colour;
-                    if lines.active {
+                    if lines.colour != *colour && lines.active {
                         lines_to_reinit.push(name.clone());
                     }
+                    lines.colour = *colour;
                 }
                 None => {
                     self.theme.game_visuals.lines_colours.insert(name.clone(), lines.colour);
@@ -304,10 +497,10 @@ impl Application {
         for (name, markers) in &mut self.cellestial_sphere.markers {
             match self.theme.game_visuals.markers_colours.get(name) {
                 Some(colour) => {
-                    markers.colour = *colour;
-                    if markers.active {
+                    if markers.colour != *colour && markers.active {
                         markers_to_reinit.push(name.clone());
                     }
+                    markers.colour = *colour;
                 }
                 None => {
                     self.theme.game_visuals.markers_colours.insert(name.clone(), markers.colour);
@@ -317,6 +510,28 @@ impl Application {
         for name in markers_to_reinit {
             self.cellestial_sphere.init_single_renderer_group(RendererCategory::Markers, &name);
         }
+        self.cellestial_sphere.game_markers.recolour(&self.theme.game_visuals.game_markers_colours);
         ctx.set_visuals(self.theme.egui_visuals.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_file_names_are_safe() {
+        assert_eq!(theme_file_name("Dark"), "dark");
+        assert_eq!(theme_file_name(" My theme: v2/red "), "my_theme__v2_red");
+        assert_eq!(theme_file_name("Tmavý"), "tmavý");
+    }
+
+    #[test]
+    fn palettes_are_recognised() {
+        for palette in GameMarkersPalette::variants() {
+            assert_eq!(GameMarkersPalette::of(&palette.colours()), Some(palette));
+        }
+        let light_theme = default_themes().get("Light").cloned().expect("No light theme");
+        assert_eq!(GameMarkersPalette::of(&light_theme.game_visuals.game_markers_colours), None);
+    }
+}
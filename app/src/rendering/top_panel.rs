@@ -1,4 +1,4 @@
-use crate::{enums::LightPollution, Application};
+use crate::{enums::LightPollution, localisation::tr, Application};
 use eframe::egui;
 
 impl Application {
@@ -14,7 +14,7 @@ impl Application {
                     });
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if self.screen_width.narrow() {
-                            ui.menu_button("Settings & Controls", |ui| {
+                            ui.menu_button(tr("Settings & Controls"), |ui| {
                                 if self.screen_width.very_narrow() {
                                     render_left_controls(self, ui);
                                 }
@@ -34,38 +34,67 @@ impl Application {
 
 fn render_right_controls(app: &mut crate::application::Application, ui: &mut egui::Ui) {
     let app_info_btn = ui
-        .add(egui::Button::new(egui::RichText::new("App info").text_style(egui::TextStyle::Body)))
-        .on_hover_text("Show information about the application");
+        .add(egui::Button::new(egui::RichText::new(tr("App info")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Show information about the application"));
     if app_info_btn.clicked() {
         app.state.windows.app_info.opened = true;
     }
     let feedback_and_support_btn = ui
-        .add(egui::Button::new(egui::RichText::new("Feedback and support").text_style(egui::TextStyle::Body)))
-        .on_hover_text("Show information about how to leave feedback and get support regarding the application");
+        .add(egui::Button::new(egui::RichText::new(tr("Feedback and support")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Show information about how to leave feedback and get support regarding the application"));
     if feedback_and_support_btn.clicked() {
         app.state.windows.feedback_and_help.opened = true;
     }
     let credits_btn = ui
-        .add(egui::Button::new(egui::RichText::new("Credits").text_style(egui::TextStyle::Body)))
-        .on_hover_text("Show the credits for the resources used by this application");
+        .add(egui::Button::new(egui::RichText::new(tr("Credits")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Show the credits for the resources used by this application"));
     if credits_btn.clicked() {
         app.state.windows.credits.opened = true;
     }
     let stats_btn: egui::Response = ui
-        .add(egui::Button::new(egui::RichText::new("Statistics").text_style(egui::TextStyle::Body)))
-        .on_hover_text("Show your statistics");
+        .add(egui::Button::new(egui::RichText::new(tr("Statistics")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Show your statistics"));
     if stats_btn.clicked() {
         app.state.windows.stats.opened = true;
     }
     let settings_btn = ui
-        .add(egui::Button::new(egui::RichText::new("Settings").text_style(egui::TextStyle::Body)))
-        .on_hover_text("Show the settings");
+        .add(egui::Button::new(egui::RichText::new(tr("Settings")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Show the settings"));
     if settings_btn.clicked() {
         app.state.windows.settings.opened = true;
     }
+    let search_btn = ui
+        .add(egui::Button::new(egui::RichText::new(tr("Search")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Find an object or a constellation by its name or designation"));
+    if search_btn.clicked() {
+        app.state.windows.search.opened = true;
+    }
+    let chart_export_btn = ui
+        .add(egui::Button::new(egui::RichText::new(tr("Export chart")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Save a star chart as SVG or PDF, a printable worksheet or a high-resolution screenshot"));
+    if chart_export_btn.clicked() {
+        app.state.windows.chart_export.opened = true;
+    }
+    let exploration_btn = ui
+        .add(egui::Button::new(egui::RichText::new(tr("Explore")).text_style(egui::TextStyle::Body)).selected(app.state.exploration_mode))
+        .on_hover_text(tr("When exploring, clicking on an object shows information about it instead of placing a marker"));
+    if exploration_btn.clicked() {
+        app.state.exploration_mode = !app.state.exploration_mode;
+        if !app.state.exploration_mode {
+            app.select_object(None);
+        }
+    }
+    let night_vision_btn = ui
+        .add(egui::Button::new(egui::RichText::new(tr("Night vision")).text_style(egui::TextStyle::Body)).selected(app.graphics_settings.night_vision))
+        .on_hover_text(tr(
+            "Show everything in dim red to keep your eyes adapted to the dark when observing. The brightness can be set in the theme settings.",
+        ));
+    if night_vision_btn.clicked() {
+        app.graphics_settings.night_vision = !app.graphics_settings.night_vision;
+    }
     let game_question_btn = ui
-        .add(egui::Button::new(egui::RichText::new("Question").text_style(egui::TextStyle::Body)))
-        .on_hover_text("Show the question");
+        .add(egui::Button::new(egui::RichText::new(tr("Question")).text_style(egui::TextStyle::Body)))
+        .on_hover_text(tr("Show the question"));
     if game_question_btn.clicked() {
         app.state.windows.game_question.opened = true;
     }
@@ -89,8 +118,8 @@ fn render_left_controls(app: &mut crate::application::Application, ui: &mut egui
     ));
     let prev_light_pollution: LightPollution = app.cellestial_sphere.light_pollution_place;
     ui.horizontal(|ui| {
-        ui.label("Light pollution level: ")
-            .on_hover_text("These settings are made to reflect how the sky looks in different locations for a person with an average eyesight.");
+        ui.label(tr("Light pollution level: "))
+            .on_hover_text(tr("These settings are made to reflect how the sky looks in different locations for a person with an average eyesight."));
         egui::ComboBox::from_id_salt("Light pollution level: ")
             .selected_text(format!("{}", app.cellestial_sphere.light_pollution_place))
             .show_ui(ui, |ui| {
@@ -1,11 +1,24 @@
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct GraphicsSettings {
     pub use_overriden_star_colour: bool,
+    /// Shows everything in dim red, so that looking at the screen does not ruin the dark adaptation of the eyes
+    #[serde(default)]
+    pub night_vision: bool,
+    /// The brightness of the brightest red in the night vision mode, between 0 and 1
+    #[serde(default = "default_night_vision_brightness")]
+    pub night_vision_brightness: f32,
+}
+
+fn default_night_vision_brightness() -> f32 {
+    0.5
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for GraphicsSettings {
     fn default() -> Self {
-        Self { use_overriden_star_colour: false }
+        Self {
+            use_overriden_star_colour: false,
+            night_vision: false,
+            night_vision_brightness: default_night_vision_brightness(),
+        }
     }
 }
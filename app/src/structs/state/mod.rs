@@ -4,6 +4,8 @@ pub mod windows;
 pub struct State {
     pub start_timestamp: i64,
     pub time_spent_start: i64,
+    /// Clicking on the sky shows information about the closest object instead of placing a marker
+    pub exploration_mode: bool,
 
     pub windows: windows::WindowsState,
 }
@@ -13,6 +15,7 @@ impl State {
         Self {
             start_timestamp,
             time_spent_start,
+            exploration_mode: false,
             windows: windows::WindowsState::default(),
         }
     }
@@ -3,6 +3,10 @@ use crate::Application;
 #[derive(Default)]
 pub struct ThreadsCommunication {
     pub check_updates: Option<CheckUpdates>,
+    /// Receives the path of the saved screenshot once it is rasterised and saved in its own thread
+    pub screenshot: Option<std::sync::mpsc::Receiver<Result<std::path::PathBuf, String>>>,
+    /// Receives the stars of the tiled catalogues read in their own thread
+    pub tiled_stars: Option<std::sync::mpsc::Receiver<crate::rendering::caspr::tiled_stars::LoadedTile>>,
 }
 
 pub enum CheckUpdatesShowPopup {
@@ -17,6 +21,27 @@ pub struct CheckUpdates {
 
 impl Application {
     pub fn receive_threads_messages(&mut self) {
+        if let Some(receiver) = &self.threads_communication.tiled_stars {
+            self.cellestial_sphere.add_loaded_tiles(receiver.try_iter());
+        }
+        if let Some(receiver) = &self.threads_communication.screenshot {
+            match receiver.try_recv() {
+                Ok(Ok(path)) => {
+                    log::info!("Saved the screenshot to {}", path.display());
+                    self.threads_communication.screenshot = None;
+                }
+                Ok(Err(err)) => {
+                    log::error!("Failed to save the screenshot: {}", err);
+                    self.toasts.error("Failed to save the screenshot").duration(Some(std::time::Duration::from_secs(15)));
+                    self.threads_communication.screenshot = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    log::error!("The screenshot thread stopped without saving the screenshot");
+                    self.threads_communication.screenshot = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {}
+            }
+        }
         if let Some(check_updates_setup) = &self.threads_communication.check_updates {
             if let Ok(response_res) = check_updates_setup.receiver.try_recv() {
                 match response_res {
@@ -0,0 +1,32 @@
+use crate::export::{
+    screenshot::ScreenshotSettings,
+    sky_chart::ChartSettings,
+    worksheet::{WorksheetFormat, WorksheetSettings},
+    ChartFormat,
+};
+
+pub struct ChartExportWindowState {
+    pub opened: bool,
+    pub settings: ChartSettings,
+    pub format: ChartFormat,
+    pub worksheet: WorksheetSettings,
+    pub worksheet_format: WorksheetFormat,
+    /// The name of the question pack the worksheet is generated from, the active one when empty
+    pub worksheet_question_pack: String,
+    pub screenshot: ScreenshotSettings,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ChartExportWindowState {
+    fn default() -> Self {
+        Self {
+            opened: false,
+            settings: ChartSettings::default(),
+            format: ChartFormat::Pdf,
+            worksheet: WorksheetSettings::default(),
+            worksheet_format: WorksheetFormat::Pdf,
+            worksheet_question_pack: String::new(),
+            screenshot: ScreenshotSettings::default(),
+        }
+    }
+}
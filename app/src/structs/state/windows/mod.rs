@@ -1,15 +1,21 @@
 pub mod app_info;
+pub mod chart_export;
 pub mod credits;
 pub mod feedback_and_help;
+pub mod object_info;
 pub mod question;
+pub mod search;
 pub mod settings;
 pub mod stats;
 pub mod testing;
 
 pub struct WindowsState {
     pub app_info: app_info::AppInfoWindowState,
+    pub chart_export: chart_export::ChartExportWindowState,
     pub credits: credits::CreditsWindowState,
     pub feedback_and_help: feedback_and_help::FeedbackAndHelpWindowState,
+    pub object_info: object_info::ObjectInfoWindowState,
+    pub search: search::SearchWindowState,
     pub settings: settings::SettingsWindowState,
     pub stats: stats::StatsWindowState,
     pub game_question: question::QuestionWindowState,
@@ -21,8 +27,11 @@ impl Default for WindowsState {
     fn default() -> Self {
         Self {
             app_info: app_info::AppInfoWindowState::default(),
+            chart_export: chart_export::ChartExportWindowState::default(),
             credits: credits::CreditsWindowState::default(),
             feedback_and_help: feedback_and_help::FeedbackAndHelpWindowState::default(),
+            object_info: object_info::ObjectInfoWindowState::default(),
+            search: search::SearchWindowState::default(),
             settings: settings::SettingsWindowState::default(),
             stats: stats::StatsWindowState::default(),
             game_question: question::QuestionWindowState::default(),
@@ -0,0 +1,12 @@
+pub struct ObjectInfoWindowState {
+    pub opened: bool,
+    /// The object shown in the window
+    pub object_id: Option<u64>,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ObjectInfoWindowState {
+    fn default() -> Self {
+        Self { opened: false, object_id: None }
+    }
+}
@@ -0,0 +1,11 @@
+pub struct SearchWindowState {
+    pub opened: bool,
+    pub query: String,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for SearchWindowState {
+    fn default() -> Self {
+        Self { opened: false, query: String::new() }
+    }
+}
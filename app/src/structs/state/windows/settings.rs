@@ -93,6 +93,12 @@ pub struct ApplicationSettingsWindowState {
     pub subwindow: ApplicationSettingsSubWindow,
 
     pub test_input: String,
+    /// The action for which a new key binding is being recorded
+    pub rebinding: Option<crate::keybindings::Action>,
+    /// The name under which the edited theme gets saved
+    pub new_theme_name: String,
+    /// The outcome of the last attempt to save a theme, shown in the theme editor
+    pub theme_save_result: Option<Result<String, String>>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -101,6 +107,9 @@ impl Default for ApplicationSettingsWindowState {
         Self {
             subwindow: ApplicationSettingsSubWindow::Input,
             test_input: String::new(),
+            rebinding: None,
+            new_theme_name: String::new(),
+            theme_save_result: None,
         }
     }
 }
@@ -159,8 +168,13 @@ pub enum GameSettingsQuestionsSubWindow {
     GuessTheAngularDistance,
     GuessTheCoordinates,
     GuessTheMagnitude,
+    GuessTheSeparation,
     MarkMissingObject,
     WhichObjectIsMissing,
+    WhichVariableStar,
+    ConstellationGenitive,
+    WhichBayerLetter,
+    CompareBrightness,
 }
 
 impl AsRef<str> for GameSettingsQuestionsSubWindow {
@@ -172,8 +186,13 @@ impl AsRef<str> for GameSettingsQuestionsSubWindow {
             Self::GuessTheAngularDistance => "Guess the angular distance",
             Self::GuessTheCoordinates => "Guess the coordinates",
             Self::GuessTheMagnitude => "Guess the magnitude",
+            Self::GuessTheSeparation => "Guess the separation",
             Self::MarkMissingObject => "Mark missing object",
             Self::WhichObjectIsMissing => "Which object is missing",
+            Self::WhichVariableStar => "Which variable star is this",
+            Self::ConstellationGenitive => "Constellation genitives",
+            Self::WhichBayerLetter => "Which Bayer letter",
+            Self::CompareBrightness => "Compare the brightness",
         }
     }
 }
@@ -189,6 +208,7 @@ pub enum SkySettingsSubWindow {
     General,
     Stars,
     Deepsky,
+    Shapes,
     Lines,
     Markers,
 }
@@ -199,6 +219,7 @@ impl AsRef<str> for SkySettingsSubWindow {
             Self::General => "General",
             Self::Stars => "Stars",
             Self::Deepsky => "Deepsky objects",
+            Self::Shapes => "Shapes",
             Self::Lines => "Lines",
             Self::Markers => "Markers",
         }
@@ -215,6 +236,8 @@ impl Display for SkySettingsSubWindow {
 pub enum ApplicationSettingsSubWindow {
     Input,
     Theme,
+    ThemeEditor,
+    Language,
 }
 
 impl AsRef<str> for ApplicationSettingsSubWindow {
@@ -222,6 +245,8 @@ impl AsRef<str> for ApplicationSettingsSubWindow {
         match *self {
             Self::Input => "Input",
             Self::Theme => "Theme",
+            Self::ThemeEditor => "Theme editor",
+            Self::Language => "Language",
         }
     }
 }
@@ -0,0 +1,1085 @@
+use angle::Angle;
+use image::RgbaImage;
+use std::collections::HashMap;
+use stellar_guesser::{
+    enums::GameStage,
+    export::screenshot::{render_screenshot, ScreenshotSettings},
+    game::{
+        game_handler::{Answer, GameHandler},
+        questions::{
+            angular_separation, compare_brightness, constellation_genitive, guess_ra_dec, guess_the_magnitude, guess_the_separation, which_bayer_letter, which_constellation_is_point_in,
+            which_object_is_here, which_variable_star, QuestionType,
+        },
+        questions_filter::{
+            parser::{Node, Parser},
+            QuestionPack,
+        },
+        scoring::Score,
+    },
+    localisation,
+    renderer::CellestialSphere,
+    rendering::{
+        caspr::{camera_mode::CameraMode, search::SearchTarget},
+        themes::Theme,
+    },
+};
+
+/// Upper bound on the number of questions played in a single game, so that a broken game cannot loop forever
+const MAX_QUESTIONS: usize = 10_000;
+
+fn setup() -> (CellestialSphere, GameHandler, Theme) {
+    let mut theme = Theme::dark();
+    let mut cellestial_sphere = CellestialSphere::load(None, &mut theme).expect("Failed to load the cellestial sphere");
+    cellestial_sphere.init();
+    let mut game_handler = GameHandler::init(&mut cellestial_sphere, None, false);
+    game_handler.game_settings.is_scored_mode = true;
+    game_handler.game_settings.no_of_questions = u32::MAX;
+    (cellestial_sphere, game_handler, theme)
+}
+
+fn activate_pack(cellestial_sphere: &mut CellestialSphere, game_handler: &mut GameHandler, name: &str, pack: QuestionPack) {
+    game_handler.question_packs.insert(name.to_owned(), pack);
+    game_handler.active_question_pack = name.to_owned();
+    game_handler.reload_question_catalog(cellestial_sphere);
+}
+
+fn activate_default_pack(cellestial_sphere: &mut CellestialSphere, game_handler: &mut GameHandler, name: &str) {
+    let (_, pack) = stellar_guesser::game::questions_filter::default_packs()
+        .into_iter()
+        .find(|(pack_name, _)| pack_name == name)
+        .expect("Default pack not found");
+    activate_pack(cellestial_sphere, game_handler, name, pack);
+}
+
+fn query_pack(cellestial_sphere: &CellestialSphere, query: &str, question_types: Vec<QuestionType>) -> QuestionPack {
+    let mut queries = Vec::new();
+    for question_type in question_types {
+        match Parser::new(query).parse(&HashMap::new()) {
+            Ok(Some(Node::Keyword(keyword))) => queries.push((Some(keyword), question_type)),
+            other => panic!("Failed to parse the query '{query}': {other:?}"),
+        }
+    }
+    QuestionPack {
+        query: query.to_owned(),
+        question_objects: cellestial_sphere.evaluate_questions_query(&queries),
+        description: String::new(),
+        file_path: None,
+    }
+}
+
+/// Plays the whole active pack, answering each question with whatever `answer` returns for the correct answer. Returns the number of questions played.
+fn play(cellestial_sphere: &mut CellestialSphere, game_handler: &mut GameHandler, theme: &Theme, answer: impl Fn(Answer) -> Answer) -> usize {
+    game_handler.start_game(cellestial_sphere, theme);
+    let mut played = 0;
+    while !game_handler.no_more_questions() {
+        assert_eq!(game_handler.stage, GameStage::Guessing);
+        let correct_answer = game_handler.get_correct_answer(cellestial_sphere).expect("The question has no correct answer");
+        game_handler.submit_answer(cellestial_sphere, theme, answer(correct_answer));
+        game_handler.check_answer(cellestial_sphere, theme);
+        assert_eq!(game_handler.stage, GameStage::Checked);
+        assert!(game_handler.get_answer_review().is_some());
+        game_handler.next_part(cellestial_sphere, theme);
+        played += 1;
+        assert!(played < MAX_QUESTIONS, "The game did not end");
+    }
+    played
+}
+
+/// A viewport of the given size in the top left corner of the window
+fn viewport(width: f32, height: f32) -> eframe::egui::Rect {
+    eframe::egui::Rect::from_min_size(eframe::egui::pos2(0.0, 0.0), eframe::egui::vec2(width, height))
+}
+
+/// Renders what is in the viewport of the sphere, pixel for pixel and without any answer review
+fn render_view(cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> RgbaImage {
+    let settings = ScreenshotSettings {
+        width: cellestial_sphere.viewport_rect.width() as u32,
+        height: cellestial_sphere.viewport_rect.height() as u32,
+        pixels_per_point: 1.0,
+        ..Default::default()
+    };
+    render_screenshot(cellestial_sphere, theme, None, &settings)
+}
+
+/// The number of pixels of the rendered view which are not the background
+fn drawn_pixels(cellestial_sphere: &mut CellestialSphere, theme: &Theme) -> usize {
+    let background = theme.egui_visuals.panel_fill.to_array();
+    render_view(cellestial_sphere, theme).pixels().filter(|pixel| pixel.0 != background).count()
+}
+
+/// Turns the camera to the given position, zooming in or out if a zoom is given, without waiting for the camera transition
+fn look_at(cellestial_sphere: &mut CellestialSphere, ra: angle::Deg<f32>, dec: angle::Deg<f32>, zoom: Option<f32>) {
+    let point = sg_geometry::get_point_vector(ra, dec, &nalgebra::Matrix3::identity());
+    cellestial_sphere.transition_to_point(&point, zoom).unwrap();
+    cellestial_sphere.update_camera_transition(1);
+    cellestial_sphere.update_camera_transition(i64::MAX);
+}
+
+#[test]
+fn mark_messiers_perfectly() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Mark Messiers (accurately)");
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.stage, GameStage::NoMoreQuestions);
+    assert_eq!(game_handler.get_score(), 3 * questions as u32);
+    assert_eq!(game_handler.get_possible_score(), 3 * questions as u32);
+}
+
+#[test]
+fn mark_messiers_slightly_off() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Mark Messiers (accurately)");
+    let questions = game_handler.question_catalog.len();
+
+    // 0.3° away along the declination gives 2 points and is still within the 1° correctness threshold, so no question is replayed
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| match answer {
+        Answer::Position { ra, dec } => Answer::Position {
+            ra,
+            dec: if dec.0 > 0.0 { angle::Deg(dec.0 - 0.3) } else { angle::Deg(dec.0 + 0.3) },
+        },
+        Answer::Text(_) => panic!("Expected a position answer"),
+    });
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), 2 * questions as u32);
+    assert_eq!(game_handler.get_possible_score(), 3 * questions as u32);
+}
+
+#[test]
+fn marker_questions_count_towards_the_possible_score() {
+    use stellar_guesser::game::questions::{find_this_object, mark_missing_object};
+
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let pack = query_pack(
+        &cellestial_sphere,
+        "CATALOGUE_DESIGNATION(MESSIER:31)",
+        vec![
+            QuestionType::FindThisObject(find_this_object::SmallSettings {
+                rotate_to_answer: false,
+                replay_incorrect: false,
+                ask_messier: true,
+                ..Default::default()
+            }),
+            QuestionType::MarkMissingObject(mark_missing_object::SmallSettings {
+                rotate_to_answer: false,
+                replay_incorrect: false,
+                ..Default::default()
+            }),
+        ],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Andromeda", pack);
+    game_handler.questions_settings.find_this_object.replay_incorrect = false;
+    assert_eq!(game_handler.question_catalog.len(), 2);
+
+    // Answers far off are worth nothing, but their points are still possible
+    let far_off = |answer| match answer {
+        Answer::Position { ra, dec } => Answer::Position { ra, dec: angle::Deg(dec.0 - 30.0) },
+        Answer::Text(_) => panic!("Expected a position answer"),
+    };
+    assert_eq!(play(&mut cellestial_sphere, &mut game_handler, &theme, far_off), 2);
+    assert_eq!(game_handler.get_score(), 0);
+    assert_eq!(game_handler.get_possible_score(), 6);
+
+    // Outside of the scored mode nothing is counted
+    game_handler.game_settings.is_scored_mode = false;
+    game_handler.reset_used_questions(&mut cellestial_sphere);
+    game_handler.start_game(&mut cellestial_sphere, &theme);
+    for _ in 0..2 {
+        let correct_answer = game_handler.get_correct_answer(&cellestial_sphere).expect("A question should be asked");
+        game_handler.submit_answer(&mut cellestial_sphere, &theme, correct_answer);
+        game_handler.check_answer(&mut cellestial_sphere, &theme);
+        game_handler.next_part(&mut cellestial_sphere, &theme);
+    }
+    assert_eq!(game_handler.get_possible_score(), 0);
+}
+
+#[test]
+fn recognise_messiers() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Recognise marked Messiers");
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), questions as u32);
+    assert_eq!(game_handler.get_possible_score(), questions as u32);
+}
+
+#[test]
+fn text_questions_answered_correctly() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let pack = query_pack(
+        &cellestial_sphere,
+        "CATALOGUE(MESSIER)",
+        vec![
+            QuestionType::GuessRa(guess_ra_dec::SmallSettings {
+                rotate_to_point: false,
+                scoring: guess_ra_dec::default_scoring(),
+            }),
+            QuestionType::GuessDec(guess_ra_dec::SmallSettings {
+                rotate_to_point: false,
+                scoring: guess_ra_dec::default_scoring(),
+            }),
+            QuestionType::GuessTheMagnitude(guess_the_magnitude::SmallSettings {
+                rotate_to_point: false,
+                replay_incorrect: false,
+                scoring: guess_the_magnitude::default_scoring(),
+            }),
+            QuestionType::AngularSeparation(angular_separation::SmallSettings {
+                rotate_to_midpoint: false,
+                scoring: angular_separation::default_scoring(),
+            }),
+        ],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Text questions", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), 3 * questions as u32);
+    assert_eq!(game_handler.get_possible_score(), 3 * questions as u32);
+}
+
+#[test]
+fn constellations_answered_correctly() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let pack = query_pack(
+        &cellestial_sphere,
+        "CATALOGUE(MESSIER)",
+        vec![QuestionType::WhichConstellationIsThisPointIn(which_constellation_is_point_in::SmallSettings {
+            rotate_to_point: false,
+            scoring: which_constellation_is_point_in::default_scoring(),
+        })],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Constellations", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), questions as u32);
+    assert_eq!(game_handler.get_possible_score(), questions as u32);
+}
+
+#[test]
+fn wrong_answers_score_nothing() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let pack = query_pack(
+        &cellestial_sphere,
+        "CATALOGUE(MESSIER)",
+        vec![QuestionType::GuessTheMagnitude(guess_the_magnitude::SmallSettings {
+            rotate_to_point: false,
+            replay_incorrect: false,
+            scoring: guess_the_magnitude::default_scoring(),
+        })],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Magnitudes", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| match answer {
+        Answer::Text(magnitude) => Answer::Text((magnitude.parse::<f32>().unwrap() + 5.0).to_string()),
+        Answer::Position { .. } => panic!("Expected a text answer"),
+    });
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), 0);
+    assert_eq!(game_handler.get_possible_score(), 3 * questions as u32);
+}
+
+#[test]
+fn scored_mode_ends_the_game() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Mark Messiers (accurately)");
+    game_handler.game_settings.no_of_questions = 5;
+
+    play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(game_handler.stage, GameStage::ScoredModeFinished);
+    assert_eq!(game_handler.get_score(), game_handler.get_possible_score());
+}
+
+#[test]
+fn hints_cost_points() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Mark Messiers (accurately)");
+    game_handler.game_settings.no_of_questions = 20;
+
+    game_handler.start_game(&mut cellestial_sphere, &theme);
+    let mut played = 0;
+    while !game_handler.no_more_questions() {
+        // Every Messier object lies in a constellation, so the first hint is always available
+        let hint = game_handler.use_hint(&mut cellestial_sphere, &theme);
+        assert!(hint.is_some());
+        let correct_answer = game_handler.get_correct_answer(&cellestial_sphere).unwrap();
+        game_handler.submit_answer(&mut cellestial_sphere, &theme, correct_answer);
+        game_handler.check_answer(&mut cellestial_sphere, &theme);
+        let (_, review) = game_handler.get_answer_review().unwrap();
+        assert!(review.contains(&hint.unwrap()));
+        game_handler.next_part(&mut cellestial_sphere, &theme);
+        played += 1;
+        assert!(played < MAX_QUESTIONS, "The game did not end");
+    }
+    // A perfect answer is worth 3 points, one hint takes away 20% of them
+    assert_eq!(game_handler.get_score(), 2 * played as u32);
+    assert_eq!(game_handler.get_possible_score(), 3 * played as u32);
+}
+
+#[test]
+fn clicking_selects_the_nearest_object() {
+    let (mut cellestial_sphere, _, _) = setup();
+    cellestial_sphere.viewport_rect = viewport(800.0, 600.0);
+    let m42 = cellestial_sphere.question_objects.iter().find(|object| object.messier_number == Some(42)).expect("M42 not found");
+    let (ra, dec) = (m42.ra, m42.dec);
+    let point = sg_geometry::get_point_vector(ra, dec, &nalgebra::Matrix3::identity());
+    cellestial_sphere.look_at_point(&point).unwrap();
+    cellestial_sphere.init_renderers();
+
+    let centre = eframe::egui::pos2(400.0, 300.0);
+    let selected = cellestial_sphere.object_at_screen_pos(centre, 20.0, |object| object.messier_number.is_some());
+    assert_eq!(selected.and_then(|object| object.messier_number), Some(42));
+    // Nothing is selected when clicking too far away from all objects
+    assert!(cellestial_sphere.object_at_screen_pos(centre, 20.0, |_| false).is_none());
+    assert!(cellestial_sphere.objects_within(ra, dec, angle::Deg(1.0)).iter().any(|(object, _)| object.messier_number == Some(42)));
+}
+
+#[test]
+fn searching_moves_the_camera_to_the_object() {
+    let (mut cellestial_sphere, _, _) = setup();
+    cellestial_sphere.viewport_rect = viewport(800.0, 600.0);
+    let m42 = cellestial_sphere.question_objects.iter().find(|object| object.messier_number == Some(42)).expect("M42 not found");
+    let (object_id, ra, dec) = (m42.object_id, m42.ra, m42.dec);
+    for query in ["M42", "m 42", "NGC 1976", "great orion nebula"] {
+        let found = cellestial_sphere.search_index.search(query, 1);
+        assert_eq!(found.first().map(|entry| &entry.target), Some(&SearchTarget::Object(object_id)), "{query}");
+    }
+    assert!(matches!(
+        cellestial_sphere.search_index.search("Orion", 1).first().map(|entry| &entry.target),
+        Some(SearchTarget::Constellation(abbreviation)) if abbreviation == "ori"
+    ));
+
+    let point = sg_geometry::get_point_vector(ra, dec, &nalgebra::Matrix3::identity());
+    cellestial_sphere.transition_to_point(&point, Some(cellestial_sphere.fov_to_zoom(30.0))).unwrap();
+    assert!(cellestial_sphere.update_camera_transition(1));
+    assert!(cellestial_sphere.camera_transition.is_some());
+    assert!(cellestial_sphere.update_camera_transition(i64::MAX));
+    assert!(cellestial_sphere.camera_transition.is_none());
+    assert!(!cellestial_sphere.update_camera_transition(i64::MAX));
+    let centre = cellestial_sphere.project_screen_pos(eframe::egui::pos2(400.0, 300.0)).normalize();
+    assert!(centre.dot(&point.normalize()) > 0.9999);
+    assert!((cellestial_sphere.fov - 30.0).abs() < 1e-3);
+
+    // Dragging cancels the transition
+    cellestial_sphere.transition_to_point(&-point, None).unwrap();
+    cellestial_sphere.update_camera_transition(0);
+    cellestial_sphere.cancel_camera_transition();
+    assert!(!cellestial_sphere.update_camera_transition(i64::MAX));
+    assert!(cellestial_sphere.project_screen_pos(eframe::egui::pos2(400.0, 300.0)).normalize().dot(&point.normalize()) > 0.9999);
+}
+
+#[test]
+fn stars_near_the_edge_are_drawn_in_every_projection() {
+    use stellar_guesser::rendering::caspr::stars::MagnitudeToRadius;
+
+    let (mut cellestial_sphere, _, theme) = setup();
+    for lines in cellestial_sphere.lines.values_mut() {
+        lines.active = false;
+    }
+    for shapes in cellestial_sphere.shapes.values_mut() {
+        shapes.active = false;
+    }
+    cellestial_sphere.sky_settings.tiled_stars_settings.enabled = false;
+    // Every star is drawn at least a few pixels big, whatever the FOV
+    let id = cellestial_sphere.sky_settings.mag_to_radius_id;
+    cellestial_sphere.sky_settings.mag_to_radius_settings[id] = MagnitudeToRadius::Linear { mag_scale: 0.5, mag_offset: 12.0 };
+    let (width, height) = (320, 180);
+    cellestial_sphere.viewport_rect = viewport(width as f32, height as f32);
+    let background = theme.egui_visuals.panel_fill.to_array();
+    for projection in sg_geometry::projection::Projection::variants() {
+        cellestial_sphere.set_projection(projection);
+        let mut checked = 0;
+        for (ra, zoom) in [0.0, 90.0, 180.0, 270.0].into_iter().flat_map(|ra| [(ra, 0.8), (ra, 1.5)]) {
+            look_at(&mut cellestial_sphere, angle::Deg(ra), angle::Deg(20.0), Some(zoom));
+            let sky = render_view(&mut cellestial_sphere, &theme);
+            // The stars in the outer fifth of the screen on each side
+            for star in cellestial_sphere.stars.values().flatten() {
+                let vector = sg_geometry::get_point_vector(star.ra, star.dec, cellestial_sphere.rotation.matrix());
+                let (screen_pos, _) = sg_geometry::project_point(&vector, cellestial_sphere.zoom, cellestial_sphere.viewport_rect, projection);
+                let (x, y) = (screen_pos.x.round() as i64, screen_pos.y.round() as i64);
+                let inside = (2..width as i64 - 2).contains(&x) && (2..height as i64 - 2).contains(&y);
+                let near_the_edge = x < width as i64 / 5 || x > width as i64 * 4 / 5 || y < height as i64 / 5 || y > height as i64 * 4 / 5;
+                if !inside || !near_the_edge || projection.project(&vector).is_none() {
+                    continue;
+                }
+                let drawn = (x - 1..=x + 1).any(|x| (y - 1..=y + 1).any(|y| sky.get_pixel(x as u32, y as u32).0 != background));
+                assert!(drawn, "A star at {screen_pos:?} was not drawn in the {projection} projection");
+                checked += 1;
+            }
+        }
+        assert!(checked > 0, "No star near the edge in the {projection} projection");
+    }
+}
+
+#[test]
+fn blank_chart_has_no_names() {
+    use stellar_guesser::export::{
+        chart::ChartElement,
+        sky_chart::{build_chart, ChartMarker, ChartSettings},
+    };
+
+    let (cellestial_sphere, _, _) = setup();
+    let m42 = cellestial_sphere.question_objects.iter().find(|object| object.messier_number == Some(42)).expect("M42 not found");
+    let settings = ChartSettings {
+        current_view: false,
+        centre_ra: m42.ra,
+        centre_dec: m42.dec,
+        fov: 60.0,
+        ..Default::default()
+    };
+    let markers = [ChartMarker {
+        ra: m42.ra,
+        dec: m42.dec,
+        label: String::from("1"),
+    }];
+    let texts = |blank: bool| {
+        let chart = build_chart(&cellestial_sphere, &ChartSettings { blank, ..settings.clone() }, &markers, &[]);
+        assert!(
+            chart.elements.iter().any(|element| matches!(element, ChartElement::Line { dashed: false, .. })),
+            "No lines on the chart"
+        );
+        chart
+            .elements
+            .into_iter()
+            .filter_map(|element| match element {
+                ChartElement::Text { text, .. } => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+    };
+    let labelled = texts(false);
+    assert!(labelled.contains(&String::from("M 42")));
+    assert!(labelled.contains(&String::from("ORION")));
+    // The marker sits in the middle of the page
+    let blank = texts(true);
+    assert!(blank.contains(&String::from("1")));
+    assert!(!blank.iter().any(|text| text == "M 42" || text == "ORION"));
+}
+
+#[test]
+fn worksheets_are_reproducible() {
+    use stellar_guesser::export::worksheet::{generate_worksheet, WorksheetSettings};
+
+    let (cellestial_sphere, _, _) = setup();
+    let (_, pack) = stellar_guesser::game::questions_filter::default_packs()
+        .into_iter()
+        .find(|(pack_name, _)| pack_name == "Recognise marked Messiers")
+        .expect("Default pack not found");
+    let settings = WorksheetSettings {
+        questions_count: 5,
+        seed: 42,
+        ..Default::default()
+    };
+    let answers = |settings: &WorksheetSettings| {
+        let worksheet = generate_worksheet(&cellestial_sphere, &pack, settings);
+        assert_eq!(worksheet.pages.len(), settings.questions_count);
+        worksheet.answer_key[0].to_svg()
+    };
+    let answer_key = answers(&settings);
+    assert!(answer_key.contains("1. "));
+    assert!(answer_key.contains("5. "));
+    assert!(!answer_key.contains("6. "));
+    assert_eq!(answer_key, answers(&settings));
+    assert_ne!(answer_key, answers(&WorksheetSettings { seed: 7, ..settings.clone() }));
+}
+
+#[test]
+fn screenshots_do_not_depend_on_the_window() {
+    let (mut cellestial_sphere, _, theme) = setup();
+    let window = viewport(800.0, 600.0);
+    cellestial_sphere.viewport_rect = window;
+    cellestial_sphere.init_renderers();
+    let m42 = cellestial_sphere.question_objects.iter().find(|object| object.messier_number == Some(42)).expect("M42 not found");
+    let point = sg_geometry::get_point_vector(m42.ra, m42.dec, &nalgebra::Matrix3::identity());
+    cellestial_sphere.look_at_point(&point).unwrap();
+
+    let settings = ScreenshotSettings {
+        width: 320,
+        height: 180,
+        pixels_per_point: 1.0,
+        ..Default::default()
+    };
+    let sky = render_screenshot(&mut cellestial_sphere, &theme, None, &settings);
+    assert_eq!(sky.dimensions(), (320, 180));
+    assert_eq!(cellestial_sphere.viewport_rect, window);
+    let background = theme.egui_visuals.panel_fill.to_array();
+    assert!(sky.pixels().all(|pixel| pixel.0[3] == 255));
+    assert!(sky.pixels().any(|pixel| pixel.0 != background), "Nothing of the sky was drawn");
+
+    let reviewed = render_screenshot(&mut cellestial_sphere, &theme, Some(("Correct!", "The object was M 42.")), &settings);
+    assert_ne!(sky, reviewed);
+    // The review is in the top left corner
+    assert_eq!(sky.get_pixel(319, 179), reviewed.get_pixel(319, 179));
+}
+
+#[test]
+fn shapes_are_hidden_with_their_objects() {
+    let (mut cellestial_sphere, _, theme) = setup();
+    let milky_way = cellestial_sphere.shapes.get("milky-way.csv").expect("The Milky Way was not loaded");
+    assert_eq!(milky_way.shapes.len(), 2);
+    assert!(milky_way.shapes.iter().all(|shape| shape.object_id.is_none() && shape.vertices.len() > 3));
+    let m31 = cellestial_sphere.shapes["messier-shapes.csv"]
+        .shapes
+        .iter()
+        .find(|shape| shape.object_id == Some(31))
+        .expect("M31 has no shape");
+    assert!(m31.vertices.len() > 3);
+
+    // The lines would cover most of the view, so they are left out
+    for lines in cellestial_sphere.lines.values_mut() {
+        lines.active = false;
+    }
+    cellestial_sphere.viewport_rect = viewport(320.0, 180.0);
+    let zoom = cellestial_sphere.fov_to_zoom(8.0);
+    look_at(&mut cellestial_sphere, angle::Deg(10.7), angle::Deg(41.3), Some(zoom));
+    assert!(drawn_pixels(&mut cellestial_sphere, &theme) > 0);
+    cellestial_sphere.disable_single_renderer(31);
+    let without_m31 = drawn_pixels(&mut cellestial_sphere, &theme);
+    cellestial_sphere.enable_single_renderer(31);
+    assert!(without_m31 < drawn_pixels(&mut cellestial_sphere, &theme));
+}
+
+#[test]
+fn culling_keeps_long_lines_and_hidden_objects() {
+    let (mut cellestial_sphere, _, theme) = setup();
+    cellestial_sphere.viewport_rect = viewport(320.0, 180.0);
+
+    // A point on the longest constellation line far from its middle, with both of its ends off the screen
+    let connections = cellestial_sphere.lines.get("constellation-connections.csv").expect("The constellation lines were not loaded");
+    let line = connections.lines.iter().max_by(|a, b| a.half_length().value().total_cmp(&b.half_length().value())).unwrap();
+    assert!(line.half_length().to_deg().value() > 10.0);
+    let start = nalgebra::Unit::new_normalize(sg_geometry::get_point_vector(line.ra_start, line.dec_start, &nalgebra::Matrix3::identity()));
+    let end = nalgebra::Unit::new_normalize(sg_geometry::get_point_vector(line.ra_end, line.dec_end, &nalgebra::Matrix3::identity()));
+    let (dec, ra) = sg_geometry::cartesian_to_spherical(start.slerp(&end, 0.12).into_inner());
+    for (name, lines) in cellestial_sphere.lines.iter_mut() {
+        lines.active = name == "constellation-connections.csv";
+    }
+    let zoom = cellestial_sphere.fov_to_zoom(2.0);
+    look_at(&mut cellestial_sphere, ra.to_deg(), dec.to_deg(), Some(zoom));
+    let with_lines = drawn_pixels(&mut cellestial_sphere, &theme);
+    cellestial_sphere.lines.get_mut("constellation-connections.csv").unwrap().active = false;
+    assert!(drawn_pixels(&mut cellestial_sphere, &theme) < with_lines, "The line was culled");
+
+    // A hidden object stays hidden after going off the screen and coming back
+    let zoom = cellestial_sphere.fov_to_zoom(8.0);
+    look_at(&mut cellestial_sphere, angle::Deg(10.7), angle::Deg(41.3), Some(zoom));
+    let with_m31 = drawn_pixels(&mut cellestial_sphere, &theme);
+    cellestial_sphere.disable_single_renderer(31);
+    let without_m31 = drawn_pixels(&mut cellestial_sphere, &theme);
+    assert!(without_m31 < with_m31);
+    look_at(&mut cellestial_sphere, angle::Deg(190.7), angle::Deg(-41.3), Some(zoom));
+    look_at(&mut cellestial_sphere, angle::Deg(10.7), angle::Deg(41.3), Some(zoom));
+    assert_eq!(drawn_pixels(&mut cellestial_sphere, &theme), without_m31);
+    cellestial_sphere.enable_single_renderer(31);
+    assert_eq!(drawn_pixels(&mut cellestial_sphere, &theme), with_m31);
+}
+
+#[test]
+fn faint_deepskies_are_culled_until_zoomed_in() {
+    let (mut cellestial_sphere, _, theme) = setup();
+    let object_ids: std::collections::HashSet<u64> = cellestial_sphere.question_objects.iter().map(|object| object.object_id).collect();
+    assert_eq!(object_ids.len(), cellestial_sphere.question_objects.len(), "An object was loaded more than once");
+
+    for lines in cellestial_sphere.lines.values_mut() {
+        lines.active = false;
+    }
+    for shapes in cellestial_sphere.shapes.values_mut() {
+        shapes.active = false;
+    }
+    cellestial_sphere.viewport_rect = viewport(320.0, 180.0);
+    // The Virgo cluster, full of galaxies around the 9th and 10th magnitude
+    let zoom = cellestial_sphere.fov_to_zoom(100.0);
+    look_at(&mut cellestial_sphere, angle::Deg(187.0), angle::Deg(12.0), Some(zoom));
+    cellestial_sphere.sky_settings.deepsky_magnitude_limit = 8.0;
+    let bright_only = drawn_pixels(&mut cellestial_sphere, &theme);
+    cellestial_sphere.sky_settings.deepsky_magnitude_limit = 13.0;
+    assert!(bright_only < drawn_pixels(&mut cellestial_sphere, &theme));
+}
+
+#[test]
+fn line_shortcuts_toggle_the_lines() {
+    use stellar_guesser::keybindings::Action;
+
+    let (mut cellestial_sphere, _, _) = setup();
+    for (action, name) in Action::variants().into_iter().filter_map(|action| Some((action, action.toggled_lines()?))) {
+        let active = cellestial_sphere.lines.get(name).unwrap_or_else(|| panic!("No lines are loaded from {name}")).active;
+        cellestial_sphere.toggle_lines(name);
+        assert_eq!(cellestial_sphere.lines[name].active, !active, "{action:?} did not toggle {name}");
+        assert_eq!(cellestial_sphere.sky_settings.lines_categories_active[name], !active);
+        cellestial_sphere.toggle_lines(name);
+        assert_eq!(cellestial_sphere.lines[name].active, active);
+    }
+}
+
+#[test]
+fn double_and_variable_stars() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let separation = guess_the_separation::SmallSettings {
+        rotate_to_point: false,
+        replay_incorrect: false,
+        scoring: guess_the_separation::default_scoring(),
+    };
+    let variable = which_variable_star::SmallSettings {
+        rotate_to_answer: false,
+        ..Default::default()
+    };
+
+    let object_ids = |query: &str| {
+        query_pack(&cellestial_sphere, query, vec![QuestionType::GuessTheSeparation(separation.clone())]).question_objects[0]
+            .1
+            .clone()
+    };
+    // Albireo (34.4") is too wide, Izar (2.9") is within the range
+    let close_doubles = object_ids("SEPARATION(2, 10)");
+    assert!(close_doubles.contains(&1072105));
+    assert!(!close_doubles.contains(&1095947));
+    // Mira is a Mira variable, Algol is an eclipsing binary
+    let miras = object_ids("VARIABLE(M)");
+    assert!(miras.contains(&1010826));
+    assert!(!miras.contains(&1014576));
+    assert!(object_ids("VARIABLE(E)").contains(&1014576));
+    let periods = object_ids("PERIOD(300, 350)");
+    assert!(periods.contains(&1010826));
+    assert!(!periods.contains(&1014576));
+
+    let pack = query_pack(&cellestial_sphere, "SEPARATION(0, 100000)", vec![QuestionType::GuessTheSeparation(separation)]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Double stars", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), 3 * questions as u32);
+
+    let pack = query_pack(&cellestial_sphere, "VARIABLE(ANY)", vec![QuestionType::WhichVariableStar(variable)]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Variable stars", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), questions as u32);
+}
+
+#[test]
+fn playable_without_a_mouse() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Mark Messiers (accurately)");
+    game_handler.start_game(&mut cellestial_sphere, &theme);
+
+    // Giving up checks the question without any answer
+    game_handler.give_up(&mut cellestial_sphere, &theme);
+    assert_eq!(game_handler.stage, GameStage::Checked);
+    assert_eq!(game_handler.get_score(), 0);
+    assert_eq!(game_handler.get_possible_score(), 3);
+    game_handler.next_part(&mut cellestial_sphere, &theme);
+
+    // A marker placed anywhere and then moved onto the object counts as a single guess
+    let Some(Answer::Position { ra, dec }) = game_handler.get_correct_answer(&cellestial_sphere) else {
+        panic!("Expected a position answer");
+    };
+    let away_dec = if dec.0 > 0.0 { dec.0 - 10.0 } else { dec.0 + 10.0 };
+    game_handler.place_marker(&mut cellestial_sphere, &theme, angle::Deg(away_dec).to_rad(), ra.to_rad());
+    game_handler.move_last_marker(&mut cellestial_sphere, &theme, dec.to_rad(), ra.to_rad());
+    assert_eq!(game_handler.guess_marker_positions.len(), 1);
+    game_handler.check_answer(&mut cellestial_sphere, &theme);
+    assert_eq!(game_handler.get_score(), 3);
+}
+
+#[test]
+fn touch_gestures_follow_the_fingers() {
+    let (mut cellestial_sphere, _, _) = setup();
+    cellestial_sphere.viewport_rect = viewport(800.0, 600.0);
+    let centre = eframe::egui::pos2(400.0, 300.0);
+    let screen_pos_of = |cellestial_sphere: &CellestialSphere, [dec, ra]: [angle::Rad<f32>; 2]| cellestial_sphere.dec_ra_to_screen_pos(dec.to_deg(), ra.to_deg()).expect("The point left the screen");
+
+    // Moving both fingers moves the sky with them
+    let point = cellestial_sphere.screen_pos_to_dec_ra(centre);
+    cellestial_sphere.apply_touch_gesture(centre, eframe::egui::vec2(50.0, 0.0), 1.0, angle::Rad(0.0));
+    assert!((screen_pos_of(&cellestial_sphere, point) - eframe::egui::pos2(450.0, 300.0)).length() < 1.0);
+
+    // Pinching zooms around the fingers rather than around the centre of the screen
+    let fingers = eframe::egui::pos2(600.0, 200.0);
+    let point = cellestial_sphere.screen_pos_to_dec_ra(fingers);
+    let zoom = cellestial_sphere.get_zoom();
+    cellestial_sphere.apply_touch_gesture(fingers, eframe::egui::Vec2::ZERO, 1.5, angle::Rad(0.0));
+    assert!((cellestial_sphere.get_zoom() - 1.5 * zoom).abs() < 1e-3 * zoom);
+    assert!((screen_pos_of(&cellestial_sphere, point) - fingers).length() < 1.0);
+
+    // Turning the fingers clockwise turns the sky clockwise (the y axis points down on the screen)
+    let point = cellestial_sphere.screen_pos_to_dec_ra(centre + eframe::egui::vec2(100.0, 0.0));
+    cellestial_sphere.apply_touch_gesture(centre, eframe::egui::Vec2::ZERO, 1.0, angle::Rad(0.1));
+    let offset = screen_pos_of(&cellestial_sphere, point) - centre;
+    assert!((offset.y.atan2(offset.x) - 0.1).abs() < 0.01, "The sky turned by {} rad", offset.y.atan2(offset.x));
+}
+
+#[test]
+fn north_up_camera_never_rolls() {
+    let (mut cellestial_sphere, _, _) = setup();
+    cellestial_sphere.viewport_rect = viewport(800.0, 600.0);
+    let centre = eframe::egui::pos2(400.0, 300.0);
+    cellestial_sphere.roll_around(centre, angle::Rad(0.7));
+    cellestial_sphere.set_camera_mode(CameraMode::NorthUp);
+    // The north celestial pole is straight above the centre of the screen
+    let assert_north_up = |cellestial_sphere: &CellestialSphere| {
+        let pole = cellestial_sphere.rotation * nalgebra::Vector3::z();
+        assert!(pole.x.abs() < 1e-4 && pole.y <= 1e-4, "The north pole is at {pole:?} in camera space");
+    };
+    assert_north_up(&cellestial_sphere);
+
+    for (from, to) in [
+        (eframe::egui::vec2(-200.0, 50.0), eframe::egui::vec2(150.0, -120.0)),
+        (eframe::egui::vec2(0.0, -250.0), eframe::egui::vec2(0.0, 250.0)),
+    ] {
+        let initial = cellestial_sphere.project_screen_pos(centre + from);
+        let end = cellestial_sphere.project_screen_pos(centre + to);
+        cellestial_sphere.rotate_between_points(&initial, &end);
+        assert_north_up(&cellestial_sphere);
+    }
+
+    let rotation = cellestial_sphere.rotation;
+    cellestial_sphere.roll_around(centre, angle::Rad(0.5));
+    assert_eq!(cellestial_sphere.rotation, rotation);
+
+    look_at(&mut cellestial_sphere, angle::Deg(83.8), angle::Deg(-5.4), None);
+    assert_north_up(&cellestial_sphere);
+    let point = sg_geometry::get_point_vector(angle::Deg(83.8), angle::Deg(-5.4), &nalgebra::Matrix3::identity());
+    assert!(cellestial_sphere.view_centre().normalize().dot(&point.normalize()) > 0.9999);
+}
+
+#[test]
+fn themes_report_unknown_keys() {
+    let (cellestial_sphere, _, mut theme) = setup();
+    assert_eq!(theme.game_visuals.unknown_keys(&cellestial_sphere), Vec::new());
+
+    theme.game_visuals.lines_colours.insert("ecliptc.csv".into(), eframe::egui::Color32::RED);
+    theme.game_visuals.markers_colours.insert("celestial-poles.csv".into(), eframe::egui::Color32::RED);
+    assert_eq!(
+        theme.game_visuals.unknown_keys(&cellestial_sphere),
+        vec![("lines_colours", "ecliptc.csv".to_string()), ("markers_colours", "celestial-poles.csv".to_string())]
+    );
+    theme.game_visuals.remove_unknown_keys(&cellestial_sphere);
+    assert_eq!(theme.game_visuals.unknown_keys(&cellestial_sphere), Vec::new());
+    assert!(theme.game_visuals.lines_colours.contains_key("ecliptic.csv"));
+
+    // Saving never overwrites an existing theme
+    let mut themes = stellar_guesser::rendering::themes::default_themes();
+    assert!(themes.save_as_new(&theme).is_err());
+}
+
+#[test]
+fn constellations_answered_in_czech() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    localisation::set_language(localisation::Language::Czech);
+    let pack = query_pack(
+        &cellestial_sphere,
+        "CATALOGUE(MESSIER)",
+        vec![QuestionType::WhichConstellationIsThisPointIn(which_constellation_is_point_in::SmallSettings {
+            rotate_to_point: false,
+            scoring: which_constellation_is_point_in::default_scoring(),
+        })],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Constellations", pack);
+    // [abbreviation, latin name, czech name]
+    let czech_names: HashMap<String, String> = cellestial_sphere
+        .constellations
+        .values()
+        .map(|constellation| (constellation.possible_names[0].clone(), constellation.possible_names[2].clone()))
+        .collect();
+
+    game_handler.start_game(&mut cellestial_sphere, &theme);
+    let mut played = 0;
+    while !game_handler.no_more_questions() {
+        let Some(Answer::Text(abbreviation)) = game_handler.get_correct_answer(&cellestial_sphere) else {
+            panic!("Expected a text answer");
+        };
+        // The case and the diacritics do not matter
+        let answer = czech_names[&abbreviation].to_uppercase();
+        game_handler.submit_answer(&mut cellestial_sphere, &theme, Answer::Text(answer));
+        game_handler.check_answer(&mut cellestial_sphere, &theme);
+        let (heading, _) = game_handler.get_answer_review().unwrap();
+        assert_eq!(heading, "Správně!");
+        game_handler.next_part(&mut cellestial_sphere, &theme);
+        played += 1;
+        assert!(played < MAX_QUESTIONS, "The game did not end");
+    }
+    assert_eq!(game_handler.get_score(), played as u32);
+    localisation::set_language(localisation::Language::English);
+}
+
+#[test]
+fn find_this_object_speaks_the_chosen_language() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    activate_default_pack(&mut cellestial_sphere, &mut game_handler, "Mark Messiers (accurately)");
+    game_handler.game_settings.no_of_questions = 20;
+
+    // A perfect answer is worth 3 points, one hint takes away 20% of them
+    let score = Score {
+        base: 3.0,
+        time_bonus: 0.0,
+        hint_penalty: 0.6,
+    };
+    for (language, correct) in [(localisation::Language::English, "Correct!"), (localisation::Language::Czech, "Správně!")] {
+        localisation::set_language(language);
+        let constellation_hints: Vec<String> = cellestial_sphere
+            .constellations
+            .values()
+            .map(|constellation| localisation::tr_args("The object is in {0}.", &[&constellation.display_name()]))
+            .collect();
+        game_handler.start_game(&mut cellestial_sphere, &theme);
+        let mut played = 0;
+        while !game_handler.no_more_questions() {
+            let hint = game_handler.use_hint(&mut cellestial_sphere, &theme).unwrap();
+            assert!(constellation_hints.contains(&hint), "The hint '{hint}' does not name the constellation in {language}");
+            let correct_answer = game_handler.get_correct_answer(&cellestial_sphere).unwrap();
+            game_handler.submit_answer(&mut cellestial_sphere, &theme, correct_answer);
+            game_handler.check_answer(&mut cellestial_sphere, &theme);
+            let (heading, review) = game_handler.get_answer_review().unwrap();
+            assert_eq!(heading, correct);
+            assert!(review.contains(&score.to_string()), "The review '{review}' is not in {language}");
+            game_handler.next_part(&mut cellestial_sphere, &theme);
+            played += 1;
+            assert!(played < MAX_QUESTIONS, "The game did not end");
+        }
+    }
+    localisation::set_language(localisation::Language::English);
+}
+
+#[test]
+fn designations_accept_constellation_abbreviations() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let settings = which_object_is_here::SmallSettings {
+        rotate_to_point: false,
+        replay_incorrect: false,
+        accept_messier: false,
+        accept_caldwell: false,
+        accept_ngc: false,
+        accept_ic: false,
+        accept_hip: false,
+        accept_hd: false,
+        accept_proper: false,
+        accept_bayer: true,
+        accept_flamsteed: false,
+        scoring: which_object_is_here::default_scoring(),
+    };
+    // The filter also accepts the abbreviation in place of the genitive
+    let pack = query_pack(&cellestial_sphere, "CATALOGUE_DESIGNATION(BAYER:beta Ori)", vec![QuestionType::WhatIsThisObject(settings)]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Rigel", pack);
+    assert_eq!(game_handler.question_catalog.len(), 1);
+
+    for answer in ["beta Orionis", "beta Ori", "Beta ORI"] {
+        game_handler.reset_used_questions(&mut cellestial_sphere);
+        game_handler.start_game(&mut cellestial_sphere, &theme);
+        game_handler.submit_answer(&mut cellestial_sphere, &theme, Answer::Text(answer.to_string()));
+        game_handler.check_answer(&mut cellestial_sphere, &theme);
+        let (heading, review) = game_handler.get_answer_review().unwrap();
+        assert_eq!(heading, "Correct!", "'{answer}' was not accepted: {review}");
+    }
+}
+
+#[test]
+fn constellation_genitives_answered_correctly() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    assert!(cellestial_sphere.constellations.values().all(|constellation| !constellation.genitive.is_empty()));
+    let pack = query_pack(
+        &cellestial_sphere,
+        "CONSTELLATION(ORI, UMA, CVN)",
+        vec![QuestionType::ConstellationGenitive(constellation_genitive::SmallSettings {
+            replay_incorrect: false,
+            scoring: constellation_genitive::default_scoring(),
+        })],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Genitives", pack);
+    // Only one question per constellation, however many objects lie in it
+    assert_eq!(game_handler.question_catalog.len(), 3);
+
+    let genitives = std::cell::RefCell::new(Vec::new());
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| {
+        if let Answer::Text(genitive) = &answer {
+            genitives.borrow_mut().push(genitive.clone());
+        }
+        answer
+    });
+    assert_eq!(played, 3);
+    assert_eq!(game_handler.get_score(), 3);
+    let mut genitives = genitives.into_inner();
+    genitives.sort();
+    assert_eq!(genitives, vec!["Canum Venaticorum", "Orionis", "Ursae Majoris"]);
+}
+
+#[test]
+fn bayer_letters_of_the_brightest_stars() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let settings = which_bayer_letter::SmallSettings {
+        rotate_to_point: false,
+        replay_incorrect: false,
+        scoring: which_bayer_letter::default_scoring(),
+    };
+    let object_ids = |query: &str| {
+        query_pack(&cellestial_sphere, query, vec![QuestionType::WhichBayerLetter(settings.clone())]).question_objects[0]
+            .1
+            .clone()
+    };
+    // Acamar and Alcor are only in the catalogue of star names, Alcor has a Flamsteed number instead of a Bayer letter
+    assert_eq!(object_ids("CATALOGUE_DESIGNATION(BAYER:theta1 Eri)"), vec![1013847]);
+    assert_eq!(object_ids("CATALOGUE_DESIGNATION(FLAMSTEED:80 UMa)"), vec![1065477]);
+    // Deneb, then Sadr and Aljanah are the brightest stars of Cygnus
+    assert_eq!(object_ids("AND(CONSTELLATION(CYG),BRIGHTEST_IN_CONSTELLATION(1))"), vec![1102098]);
+    assert_eq!(object_ids("AND(CONSTELLATION(CYG),BRIGHTEST_IN_CONSTELLATION(3))"), vec![1100453, 1102098, 1102488]);
+
+    let pack = query_pack(
+        &cellestial_sphere,
+        "AND(CATALOGUE(BAYER),BRIGHTEST_IN_CONSTELLATION(3),CONSTELLATION(ORI,CYG,ERI))",
+        vec![QuestionType::WhichBayerLetter(settings.clone())],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Bayer letters", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+    let played = play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer);
+    assert_eq!(played, questions);
+    assert_eq!(game_handler.get_score(), questions as u32);
+
+    let pack = query_pack(&cellestial_sphere, "OBJECT_ID(1013847)", vec![QuestionType::WhichBayerLetter(settings)]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Acamar", pack);
+    for answer in ["theta1", "θ1", "Theta 1", "theta"] {
+        game_handler.reset_used_questions(&mut cellestial_sphere);
+        game_handler.start_game(&mut cellestial_sphere, &theme);
+        game_handler.submit_answer(&mut cellestial_sphere, &theme, Answer::Text(answer.to_string()));
+        game_handler.check_answer(&mut cellestial_sphere, &theme);
+        let (heading, review) = game_handler.get_answer_review().unwrap();
+        assert_eq!(heading, "Correct!", "'{answer}' was not accepted: {review}");
+    }
+}
+
+#[test]
+fn brightness_compared_per_pair() {
+    let (mut cellestial_sphere, mut game_handler, theme) = setup();
+    let settings = |stars_per_question| compare_brightness::SmallSettings {
+        stars_per_question,
+        rotate_to_stars: false,
+        replay_incorrect: false,
+        ..Default::default()
+    };
+
+    let pack = query_pack(&cellestial_sphere, "AND(TYPE(STAR),MAG_BELOW(3))", vec![QuestionType::CompareBrightness(settings(2))]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Pairs", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+    assert_eq!(play(&mut cellestial_sphere, &mut game_handler, &theme, |answer| answer), questions);
+    assert_eq!(game_handler.get_score(), questions as u32);
+
+    // Swapping the two brightest of three stars gets only the other two pairs right
+    let pack = query_pack(&cellestial_sphere, "AND(TYPE(STAR),MAG_BELOW(3))", vec![QuestionType::CompareBrightness(settings(3))]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Triples", pack);
+    let questions = game_handler.question_catalog.len();
+    assert!(questions > 0);
+    let swapped = |answer| match answer {
+        Answer::Text(order) => {
+            let mut letters = order.chars().collect::<Vec<char>>();
+            letters.swap(0, 1);
+            Answer::Text(letters.into_iter().collect())
+        }
+        other => other,
+    };
+    assert_eq!(play(&mut cellestial_sphere, &mut game_handler, &theme, swapped), questions);
+    assert_eq!(game_handler.get_score(), 2 * questions as u32);
+    assert_eq!(game_handler.possible_score, 3 * questions as u32);
+
+    // Gamma Cassiopeiae is brighter than Achird until the clouds dim it
+    let pack = query_pack(&cellestial_sphere, "OR(OBJECT_ID(1004427),OBJECT_ID(1003821))", vec![QuestionType::CompareBrightness(settings(2))]);
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Gamma Cassiopeiae and Achird", pack);
+    game_handler.start_game(&mut cellestial_sphere, &theme);
+    let Some(Answer::Text(clear)) = game_handler.get_correct_answer(&cellestial_sphere) else {
+        panic!("There is no question about Gamma Cassiopeiae and Achird");
+    };
+    for star in cellestial_sphere.stars.values_mut().flatten().filter(|star| star.object_id == 1004427) {
+        star.magnitude_offset = 2.0;
+    }
+    let Some(Answer::Text(cloudy)) = game_handler.get_correct_answer(&cellestial_sphere) else {
+        panic!("There is no question about Gamma Cassiopeiae and Achird");
+    };
+    assert_eq!(cloudy, clear.chars().rev().collect::<String>());
+}
+
+/// The named stars with the drawn Deneb as the only star on the sky. Deneb and the other named stars are only in the IAU catalogue of star names, while the drawn Deneb comes from a catalogue with its own object IDs.
+fn named_stars_with_drawn_deneb() -> (CellestialSphere, Theme) {
+    const HEADER: &str = "object_id,object_category,object_type,dec,ra,proper_names,bayer_designation,flamsteed_designation,hipparcos_number,hd_number,messier_number,caldwell_number,ngc_number,ic_number,constellations_abbreviations,mag,bv,distance,colour,object_type_text";
+    let drawn_stars = format!("{HEADER}\n7,Star,Single,45.280339,310.357980,,,,,,,,,,Cyg,1.25,,,,Star\n");
+    let read = |path: &str| std::fs::read_to_string(path).unwrap_or_else(|err| panic!("Failed to read {path}: {err}"));
+    let sky_data = vec![
+        ("sky objects", vec![["drawn-stars.csv".to_owned(), drawn_stars]]),
+        ("star names", vec![["IAU-CSN.csv".to_owned(), read("./sphere/named-stars/IAU-CSN.csv")]]),
+    ];
+    let sky_files = vec![["constellation names".to_owned(), read("./data/constellations.csv")]];
+    let mut theme = Theme::dark();
+    let mut cellestial_sphere = CellestialSphere::from_sky_data(None, &mut theme, None, sky_data, sky_files).expect("Failed to load the cellestial sphere");
+    cellestial_sphere.init();
+    (cellestial_sphere, theme)
+}
+
+#[test]
+fn brightness_of_named_stars_follows_the_drawn_stars() {
+    let (mut cellestial_sphere, theme) = named_stars_with_drawn_deneb();
+    let mut game_handler = GameHandler::init(&mut cellestial_sphere, None, false);
+    game_handler.game_settings.is_scored_mode = true;
+
+    let deneb = cellestial_sphere.question_objects.iter().find(|object| object.object_id == 1102098).expect("Deneb not found");
+    let (deneb_ra, deneb_dec) = (deneb.ra, deneb.dec);
+    let pack = query_pack(
+        &cellestial_sphere,
+        "OR(OBJECT_ID(1102098),OBJECT_ID(1100453))",
+        vec![QuestionType::CompareBrightness(compare_brightness::SmallSettings {
+            rotate_to_stars: false,
+            replay_incorrect: false,
+            ..Default::default()
+        })],
+    );
+    activate_pack(&mut cellestial_sphere, &mut game_handler, "Deneb and Sadr", pack);
+    game_handler.start_game(&mut cellestial_sphere, &theme);
+    let Some(Answer::Text(clear)) = game_handler.get_correct_answer(&cellestial_sphere) else {
+        panic!("There is no question about Deneb and Sadr");
+    };
+    assert_eq!(cellestial_sphere.magnitude_offset(deneb_ra, deneb_dec), 0.0);
+
+    // Dimming the drawn Deneb makes it fainter than Sadr
+    for star in cellestial_sphere.stars.values_mut().flatten() {
+        star.magnitude_offset = 2.0;
+    }
+    assert_eq!(cellestial_sphere.magnitude_offset(deneb_ra, deneb_dec), 2.0);
+    let Some(Answer::Text(cloudy)) = game_handler.get_correct_answer(&cellestial_sphere) else {
+        panic!("There is no question about Deneb and Sadr");
+    };
+    assert_eq!(cloudy, clear.chars().rev().collect::<String>());
+
+    game_handler.submit_answer(&mut cellestial_sphere, &theme, Answer::Text(cloudy));
+    game_handler.check_answer(&mut cellestial_sphere, &theme);
+    let (heading, review) = game_handler.get_answer_review().unwrap();
+    assert_eq!(heading, "Correct!");
+    assert!(review.contains("mag without the clouds"), "{review}");
+    assert!(review.ends_with(&compare_brightness::default_scoring().review_text_per_pair(1, "")), "{review}");
+}
+
+#[test]
+fn missing_named_stars_are_not_drawn() {
+    let (mut cellestial_sphere, theme) = named_stars_with_drawn_deneb();
+    let deneb = cellestial_sphere.question_objects.iter().find(|object| object.object_id == 1102098).expect("Deneb not found");
+    let (ra, dec) = (deneb.ra, deneb.dec);
+    cellestial_sphere.viewport_rect = viewport(320.0, 180.0);
+    let zoom = cellestial_sphere.fov_to_zoom(8.0);
+    look_at(&mut cellestial_sphere, ra, dec, Some(zoom));
+    assert!(drawn_pixels(&mut cellestial_sphere, &theme) > 0);
+    cellestial_sphere.disable_single_renderer(1102098);
+    assert_eq!(drawn_pixels(&mut cellestial_sphere, &theme), 0);
+    cellestial_sphere.enable_single_renderer(1102098);
+    assert!(drawn_pixels(&mut cellestial_sphere, &theme) > 0);
+}
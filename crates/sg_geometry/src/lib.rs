@@ -6,6 +6,10 @@ use spherical_geometry::SphericalPoint;
 use std::f32::consts::PI;
 
 pub mod intersections;
+pub mod projection;
+pub mod spatial_index;
+
+use projection::Projection;
 
 // const POLYGONLIMIT: f32 = 180.0;
 const VIEWPORT_OFFSET: f32 = 10.0;
@@ -82,14 +86,16 @@ pub fn get_point_vector(ra: angle::Deg<f32>, dec: angle::Deg<f32>, rotation_matr
     rotation_matrix * Vector3::new((de_s * ra_c) as f32, (de_s * ra_s) as f32, (de_c) as f32)
 }
 
-pub fn project_point(vector: &Vector3<f32>, zoom: f32, viewport_rect: egui::Rect) -> (egui::Pos2, bool) {
-    let scale_factor = 1.0 - vector[2];
+pub fn project_point(vector: &Vector3<f32>, zoom: f32, viewport_rect: egui::Rect, projection: Projection) -> (egui::Pos2, bool) {
+    let Some(plane_coordinates) = projection.project(vector) else {
+        return (egui::Pos2::new(0.0, 0.0), false);
+    };
 
     let rect_size = Vector2::new(viewport_rect.max[0] - viewport_rect.min[0], viewport_rect.max[1] - viewport_rect.min[1]);
 
     let screen_ratio = 2.0 / (rect_size[0] * rect_size[0] + rect_size[1] * rect_size[1]).sqrt();
 
-    let point_coordinates = Vector2::new(vector[0] * zoom / scale_factor, vector[1] * zoom / scale_factor);
+    let point_coordinates = plane_coordinates * zoom;
 
     let final_coordinates = egui::Pos2::new(point_coordinates[0] / screen_ratio + rect_size[0] / 2.0, point_coordinates[1] / screen_ratio + rect_size[1] / 2.0);
 
@@ -105,26 +111,40 @@ pub fn project_point(vector: &Vector3<f32>, zoom: f32, viewport_rect: egui::Rect
     )
 }
 
-//something is broken over here and I have no idea what it is...
-pub fn cast_onto_sphere(viewport_rect: &egui::Rect, screen_position: &egui::Pos2, rotation: nalgebra::Rotation3<f32>, zoom: f32) -> Vector3<f32> {
+/// The position on the plane of the projection corresponding to the position on the screen
+fn screen_to_plane(viewport_rect: &egui::Rect, screen_position: &egui::Pos2, zoom: f32) -> Vector2<f32> {
     let rect_size = Vector2::new(viewport_rect.max[0] - viewport_rect.min[0], viewport_rect.max[1] - viewport_rect.min[1]);
 
     let screen_ratio = 2.0 / (rect_size[0] * rect_size[0] + rect_size[1] * rect_size[1]).sqrt();
 
-    let plane_coordinates = Vector2::new((screen_position[0] - rect_size[0] / 2.0) * screen_ratio, (screen_position[1] - rect_size[1] / 2.0) * screen_ratio);
+    Vector2::new((screen_position[0] - rect_size[0] / 2.0) * screen_ratio, (screen_position[1] - rect_size[1] / 2.0) * screen_ratio) / zoom
+}
 
-    cast_onto_sphere_plane_position(rotation, zoom, plane_coordinates)
+/// The point on the sphere (without the rotation) shown at the position on the screen. Positions outside of the projected sphere are moved onto its edge.
+pub fn cast_onto_sphere(viewport_rect: &egui::Rect, screen_position: &egui::Pos2, rotation: nalgebra::Rotation3<f32>, zoom: f32, projection: Projection) -> Vector3<f32> {
+    rotation.inverse() * projection.unproject_clamped(&screen_to_plane(viewport_rect, screen_position, zoom))
 }
 
-pub fn cast_onto_sphere_plane_position(rotation: nalgebra::Rotation3<f32>, zoom: f32, plane_coordinates: Vector2<f32>) -> Vector3<f32> {
-    let scaling_factor = zoom * zoom + plane_coordinates[0] * plane_coordinates[0] + plane_coordinates[1] * plane_coordinates[1];
+/// Like `cast_onto_sphere`, but None if the position on the screen is outside of the projected sphere
+pub fn try_cast_onto_sphere(viewport_rect: &egui::Rect, screen_position: &egui::Pos2, rotation: nalgebra::Rotation3<f32>, zoom: f32, projection: Projection) -> Option<Vector3<f32>> {
+    projection.unproject(&screen_to_plane(viewport_rect, screen_position, zoom)).map(|vector| rotation.inverse() * vector)
+}
 
-    rotation.matrix().try_inverse().expect("FUCK")
-        * Vector3::new(
-            2.0 * zoom * zoom * plane_coordinates[0] / scaling_factor,
-            2.0 * zoom * zoom * plane_coordinates[1] / scaling_factor,
-            -(zoom * zoom - plane_coordinates[0] * plane_coordinates[0] - plane_coordinates[1] * plane_coordinates[1]) * zoom / (scaling_factor),
-        )
+/// The rotation looking at the point (without rotations) with the north celestial pole up and east to the left, like on printed star charts.
+/// Near the poles, where north is not defined, the 0h meridian is up instead.
+pub fn north_up_rotation(point: &Vector3<f32>) -> Option<nalgebra::Rotation3<f32>> {
+    let centre = point.try_normalize(f32::EPSILON)?;
+    let pole = Vector3::new(0.0, 0.0, 1.0);
+    let north = (pole - centre * pole.dot(&centre))
+        .try_normalize(1e-4)
+        .or_else(|| (Vector3::new(1.0, 0.0, 0.0) - centre * centre.x).try_normalize(1e-4))?;
+    let east = centre.cross(&north);
+    // The rows map the vectors onto the camera space, where the camera looks along -z, x goes to the right and y down the screen
+    Some(nalgebra::Rotation3::from_matrix_unchecked(Matrix3::from_rows(&[
+        (-east).transpose(),
+        (-north).transpose(),
+        (-centre).transpose(),
+    ])))
 }
 
 /** Returns a (dec, ra) pair (both in radians) */
@@ -144,8 +164,8 @@ pub fn cartesian_to_spherical(vector: Vector3<f32>) -> (angle::Rad<f32>, angle::
     }
     (angle::Rad(dec), angle::Rad(ra))
 }
-pub fn cast_onto_sphere_dec_ra(viewport_rect: &egui::Rect, screen_position: &egui::Pos2, rotation: nalgebra::Rotation3<f32>, zoom: f32) -> [angle::Rad<f32>; 2] {
-    let sphere_position = cast_onto_sphere(viewport_rect, screen_position, rotation, zoom);
+pub fn cast_onto_sphere_dec_ra(viewport_rect: &egui::Rect, screen_position: &egui::Pos2, rotation: nalgebra::Rotation3<f32>, zoom: f32, projection: Projection) -> [angle::Rad<f32>; 2] {
+    let sphere_position = cast_onto_sphere(viewport_rect, screen_position, rotation, zoom, projection);
     let (dec, ra) = cartesian_to_spherical(sphere_position);
     [dec, ra]
 }
@@ -167,10 +187,19 @@ pub fn angular_distance(initial_position: (angle::Rad<f32>, angle::Rad<f32>), fi
 
     let b = PI / 2.0 - i_dec.value();
     let c = PI / 2.0 - f_dec.value();
-    angle::Rad((b.cos() * c.cos() + b.sin() * c.sin() * (i_ra - f_ra).cos()).acos())
+    // Rounding errors can push the cosine slightly outside of [-1; 1] for (almost) identical points, which would make acos return NaN
+    angle::Rad((b.cos() * c.cos() + b.sin() * c.sin() * (i_ra - f_ra).cos()).clamp(-1.0, 1.0).acos())
 
     // (i_dec.cos() * f_dec.cos() + i_dec.sin() * i_dec.sin() * (i_ra - f_ra).cos()).acos()
 }
+/// Returns the (ra, dec) of the point `distance` away from (ra, dec) in the direction of `bearing` (measured from the north towards the east)
+pub fn offset_point(ra: angle::Deg<f32>, dec: angle::Deg<f32>, distance: angle::Deg<f32>, bearing: angle::Deg<f32>) -> (angle::Deg<f32>, angle::Deg<f32>) {
+    let (ra, dec) = (ra.to_rad().value(), dec.to_rad().value());
+    let (distance, bearing) = (distance.to_rad().value(), bearing.to_rad().value());
+    let final_dec = (dec.sin() * distance.cos() + dec.cos() * distance.sin() * bearing.cos()).clamp(-1.0, 1.0).asin();
+    let final_ra = ra + (bearing.sin() * distance.sin() * dec.cos()).atan2(distance.cos() - dec.sin() * final_dec.sin());
+    (angle::Deg(final_ra.to_degrees().rem_euclid(360.0)), angle::Deg(final_dec.to_degrees()))
+}
 /// Returns a (ra, dec) pair, both in degrees
 pub fn generate_random_point(rng: &mut ThreadRng) -> (angle::Deg<f32>, angle::Deg<f32>) {
     // Generate a random right ascension as normal
@@ -220,7 +249,11 @@ mod tests {
     #[test]
     fn angular_distance() {
         let max_delta = angle::Deg(0.01);
-        let tests = vec![((angle::Deg(15.7), angle::Deg(96.3)), (angle::Deg(73.2), angle::Deg(93.9)), angle::Deg(57.52))];
+        let tests = vec![
+            ((angle::Deg(15.7), angle::Deg(96.3)), (angle::Deg(73.2), angle::Deg(93.9)), angle::Deg(57.52)),
+            ((angle::Deg(41.269), angle::Deg(10.685)), (angle::Deg(41.269), angle::Deg(10.685)), angle::Deg(0.0)),
+            ((angle::Deg(-5.391), angle::Deg(83.822)), (angle::Deg(-5.391), angle::Deg(83.822)), angle::Deg(0.0)),
+        ];
         for ((dec_1, ra_1), (dec_2, ra_2), expected_res) in tests {
             let dec_1 = dec_1.to_rad();
             let ra_1 = ra_1.to_rad();
@@ -235,6 +268,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn offset_point() {
+        let max_delta = angle::Deg(0.01);
+        for (ra, dec, distance, bearing) in [(10.0, 20.0, 5.0, 0.0), (350.0, -40.0, 12.5, 77.0), (123.0, 85.0, 10.0, 180.0), (0.0, 0.0, 30.0, 270.0)] {
+            let (final_ra, final_dec) = super::offset_point(angle::Deg(ra), angle::Deg(dec), angle::Deg(distance), angle::Deg(bearing));
+            let res = super::angular_distance((angle::Deg(ra).to_rad(), angle::Deg(dec).to_rad()), (final_ra.to_rad(), final_dec.to_rad())).to_deg();
+            assert!((res - angle::Deg(distance)).abs() <= max_delta);
+        }
+        let (_, final_dec) = super::offset_point(angle::Deg(10.0), angle::Deg(20.0), angle::Deg(5.0), angle::Deg(0.0));
+        assert!((final_dec - angle::Deg(25.0)).abs() <= max_delta);
+    }
+
     #[test]
     fn vec_to_dec_ra() {
         let max_delta = angle::Deg(0.002);
@@ -260,4 +305,21 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn north_up_rotation() {
+        let identity = nalgebra::Matrix3::identity();
+        for (ra, dec) in [(0.0, 0.0), (83.8, -5.4), (250.0, 60.0), (10.0, 90.0), (10.0, -90.0)] {
+            let centre = super::get_point_vector(angle::Deg(ra), angle::Deg(dec), &identity);
+            let rotation = super::north_up_rotation(&centre).unwrap();
+            assert!((rotation * centre - nalgebra::Vector3::new(0.0, 0.0, -1.0)).magnitude() < 1e-5);
+            if dec.abs() < 90.0 {
+                // North is up (towards -y) and east (higher right ascension) is to the left (towards -x)
+                let north = rotation * super::get_point_vector(angle::Deg(ra), angle::Deg(dec + 1.0), &identity);
+                let east = rotation * super::get_point_vector(angle::Deg(ra + 1.0), angle::Deg(dec), &identity);
+                assert!(north.y < 0.0 && north.x.abs() < 1e-4);
+                assert!(east.x < 0.0 && east.y.abs() < 1e-3);
+            }
+        }
+    }
 }
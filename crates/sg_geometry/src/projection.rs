@@ -0,0 +1,236 @@
+use nalgebra::{Vector2, Vector3};
+use std::f32::consts::{FRAC_PI_2, PI, SQRT_2};
+
+/// A way of mapping the sphere onto the plane of the screen.
+/// All of them work in the space of the camera, which looks along -z, with x going to the right and y going down the screen.
+/// The plane coordinates are scaled so that near the centre of the view they are half of the angular distance (in radians) from it, so a zoom gives roughly the same field of view in all of them.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Projection {
+    /// Keeps the shapes of small areas (circles stay circles), used by most star charts
+    Stereographic,
+    /// Keeps great circles straight, but can only show less than a half of the sphere
+    Gnomonic,
+    /// The sphere as seen from far away, shows a half of the sphere
+    Orthographic,
+    /// A rectangular chart of the longitude and latitude relative to the view
+    Equirectangular,
+    /// Keeps the areas, shows the whole sphere in an ellipse
+    HammerAitoff,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Projection {
+    fn default() -> Self {
+        Self::Stereographic
+    }
+}
+
+impl std::fmt::Display for Projection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stereographic => write!(f, "Stereographic"),
+            Self::Gnomonic => write!(f, "Gnomonic"),
+            Self::Orthographic => write!(f, "Orthographic"),
+            Self::Equirectangular => write!(f, "Equirectangular"),
+            Self::HammerAitoff => write!(f, "Hammer-Aitoff"),
+        }
+    }
+}
+
+/// The smallest and the largest zoom searched through when looking for the zoom giving a field of view
+const ZOOM_SEARCH_RANGE: (f32, f32) = (1e-3, 1e4);
+
+impl Projection {
+    pub fn variants() -> [Self; 5] {
+        [Self::Stereographic, Self::Gnomonic, Self::Orthographic, Self::Equirectangular, Self::HammerAitoff]
+    }
+
+    /// Projects a unit vector in the space of the camera onto the plane, None if the projection can not show the point
+    pub fn project(self, vector: &Vector3<f32>) -> Option<Vector2<f32>> {
+        match self {
+            Self::Stereographic => {
+                let scale_factor = 1.0 - vector.z;
+                if scale_factor <= f32::EPSILON {
+                    return None;
+                }
+                Some(Vector2::new(vector.x, vector.y) / scale_factor)
+            }
+            Self::Gnomonic => {
+                if vector.z >= -f32::EPSILON {
+                    return None;
+                }
+                Some(Vector2::new(vector.x, vector.y) / (-2.0 * vector.z))
+            }
+            Self::Orthographic => {
+                if vector.z > 0.0 {
+                    return None;
+                }
+                Some(Vector2::new(vector.x, vector.y) / 2.0)
+            }
+            Self::Equirectangular => {
+                let (longitude, latitude) = Self::longitude_latitude(vector);
+                Some(Vector2::new(longitude, latitude) / 2.0)
+            }
+            Self::HammerAitoff => {
+                let (longitude, latitude) = Self::longitude_latitude(vector);
+                let denominator = (1.0 + latitude.cos() * (longitude / 2.0).cos()).sqrt();
+                if denominator <= f32::EPSILON {
+                    return None;
+                }
+                Some(Vector2::new(SQRT_2 * latitude.cos() * (longitude / 2.0).sin() / denominator, latitude.sin() / (SQRT_2 * denominator)))
+            }
+        }
+    }
+
+    /// The unit vector in the space of the camera projected onto the point of the plane, None if the point is outside of the projected sphere
+    pub fn unproject(self, point: &Vector2<f32>) -> Option<Vector3<f32>> {
+        match self {
+            Self::Stereographic => {
+                let distance_squared = point.magnitude_squared();
+                let scaling_factor = 1.0 + distance_squared;
+                Some(Vector3::new(2.0 * point.x / scaling_factor, 2.0 * point.y / scaling_factor, -(1.0 - distance_squared) / scaling_factor))
+            }
+            Self::Gnomonic => Some(Vector3::new(2.0 * point.x, 2.0 * point.y, -1.0).normalize()),
+            Self::Orthographic => {
+                let point = 2.0 * point;
+                let distance_squared = point.magnitude_squared();
+                if distance_squared > 1.0 {
+                    return None;
+                }
+                Some(Vector3::new(point.x, point.y, -(1.0 - distance_squared).sqrt()))
+            }
+            Self::Equirectangular => {
+                let (longitude, latitude) = (2.0 * point.x, 2.0 * point.y);
+                if longitude.abs() > PI || latitude.abs() > FRAC_PI_2 {
+                    return None;
+                }
+                Some(Self::from_longitude_latitude(longitude, latitude))
+            }
+            Self::HammerAitoff => {
+                let (x, y) = (2.0 * point.x, 2.0 * point.y);
+                let ellipse = (x / 4.0).powi(2) + (y / 2.0).powi(2);
+                if ellipse > 0.5 {
+                    return None;
+                }
+                let z = (1.0 - ellipse).sqrt();
+                let longitude = 2.0 * (z * x).atan2(2.0 * (2.0 * z * z - 1.0));
+                let latitude = (z * y).clamp(-1.0, 1.0).asin();
+                Some(Self::from_longitude_latitude(longitude, latitude))
+            }
+        }
+    }
+
+    /// Like `unproject`, but points outside of the projected sphere are moved towards the centre onto its edge
+    pub fn unproject_clamped(self, point: &Vector2<f32>) -> Vector3<f32> {
+        if let Some(vector) = self.unproject(point) {
+            return vector;
+        }
+        let (mut inside, mut outside) = (0.0, 1.0);
+        for _ in 0..24 {
+            let middle = (inside + outside) / 2.0;
+            if self.unproject(&(point * middle)).is_some() {
+                inside = middle;
+            } else {
+                outside = middle;
+            }
+        }
+        self.unproject(&(point * inside)).unwrap_or(Vector3::new(0.0, 0.0, -1.0))
+    }
+
+    /// Whether the projection tears the line between the two points apart, so it should not be drawn as a single segment.
+    /// This happens to the projections of the whole sphere when the line crosses the meridian behind the camera.
+    pub fn breaks_line(self, start: &Vector3<f32>, end: &Vector3<f32>) -> bool {
+        match self {
+            Self::Stereographic | Self::Gnomonic | Self::Orthographic => false,
+            Self::Equirectangular | Self::HammerAitoff => start.x * end.x < 0.0 && start.z + end.z > 0.0,
+        }
+    }
+
+    /// The field of view (in degrees) across the diagonal of the screen, where the corners of the screen are 1 / zoom away from the centre of the plane
+    pub fn zoom_to_fov(self, zoom: f32) -> f32 {
+        let corner = Vector2::new(1.0, 1.0) / (SQRT_2 * zoom);
+        match self.unproject(&corner) {
+            Some(vector) => 2.0 * (-vector.normalize().z).clamp(-1.0, 1.0).acos().to_degrees(),
+            None => 360.0,
+        }
+    }
+
+    /// The zoom giving the field of view (in degrees), the inverse of `zoom_to_fov`
+    pub fn fov_to_zoom(self, fov: f32) -> f32 {
+        // The field of view gets smaller as the zoom grows, so the zoom can be found by bisection
+        let (mut low, mut high) = (ZOOM_SEARCH_RANGE.0.ln(), ZOOM_SEARCH_RANGE.1.ln());
+        for _ in 0..48 {
+            let middle = (low + high) / 2.0;
+            if self.zoom_to_fov(middle.exp()) > fov {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        ((low + high) / 2.0).exp()
+    }
+
+    /// The longitude (to the right) and the latitude (down the screen) relative to the direction of the camera
+    fn longitude_latitude(vector: &Vector3<f32>) -> (f32, f32) {
+        let vector = vector.normalize();
+        (vector.x.atan2(-vector.z), vector.y.clamp(-1.0, 1.0).asin())
+    }
+
+    fn from_longitude_latitude(longitude: f32, latitude: f32) -> Vector3<f32> {
+        Vector3::new(latitude.cos() * longitude.sin(), latitude.sin(), -latitude.cos() * longitude.cos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unproject_inverts_project() {
+        let mut rng = rand::thread_rng();
+        for projection in Projection::variants() {
+            for _ in 0..1000 {
+                let (ra, dec) = crate::generate_random_point(&mut rng);
+                let vector = crate::get_point_vector(ra, dec, &nalgebra::Matrix3::identity());
+                // Stay away from the edges of the projections, where the precision gets poor
+                let limit = match projection {
+                    Projection::Gnomonic | Projection::Orthographic => -0.1,
+                    Projection::Stereographic | Projection::Equirectangular | Projection::HammerAitoff => 0.9,
+                };
+                if vector.z > limit {
+                    continue;
+                }
+                let point = projection.project(&vector).unwrap();
+                let unprojected = projection.unproject(&point).unwrap();
+                assert!((unprojected - vector).magnitude() < 1e-3, "{projection}: {vector:?} -> {point:?} -> {unprojected:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn stereographic_fov_matches_the_original_formula() {
+        for zoom in [0.5, 1.0, 3.0_f32.sqrt(), 10.0] {
+            let original = 4.0 * (1.0 / zoom).atan() / PI * 180.0;
+            assert!((Projection::Stereographic.zoom_to_fov(zoom) - original).abs() < 1e-3);
+            for projection in Projection::variants() {
+                let fov = projection.zoom_to_fov(zoom);
+                if fov < 360.0 {
+                    assert!((projection.fov_to_zoom(fov) - zoom).abs() / zoom < 1e-3, "{projection} {zoom}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hidden_points() {
+        let behind = Vector3::new(0.0, 0.0, 1.0);
+        assert!(Projection::Gnomonic.project(&behind).is_none());
+        assert!(Projection::Orthographic.project(&behind).is_none());
+        assert!(Projection::Equirectangular.project(&behind).is_some());
+        assert!(Projection::Orthographic.unproject(&Vector2::new(1.0, 0.0)).is_none());
+        let clamped = Projection::Orthographic.unproject_clamped(&Vector2::new(1.0, 0.0));
+        assert!((clamped - Vector3::new(1.0, 0.0, 0.0)).magnitude() < 1e-3);
+        assert!(Projection::HammerAitoff.breaks_line(&Vector3::new(0.1, 0.0, 0.99), &Vector3::new(-0.1, 0.0, 0.99)));
+        assert!(!Projection::HammerAitoff.breaks_line(&Vector3::new(0.1, 0.0, -0.99), &Vector3::new(-0.1, 0.0, -0.99)));
+    }
+}
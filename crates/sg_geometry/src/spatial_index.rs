@@ -0,0 +1,172 @@
+use angle::Angle;
+use nalgebra::Vector3;
+
+/// The number of cells along each edge of a cube face used by default, giving cells roughly 5.6° across
+pub const DEFAULT_RESOLUTION: usize = 16;
+
+/// A spatial index of points on the unit sphere.
+/// The sphere is projected onto the faces of a cube, each of which is split into a grid of cells.
+/// Since the edges of the cells are great circles, a cell is contained in the circle around its centre going through its furthest corner, which is what the lookups use.
+pub struct CubeMapIndex<T> {
+    resolution: usize,
+    cells: Vec<Vec<(Vector3<f32>, T)>>,
+    cell_centres: Vec<Vector3<f32>>,
+    /// The angular distance from the centre of a cell to its furthest corner, in radians
+    cell_radii: Vec<f32>,
+}
+
+impl<T> CubeMapIndex<T> {
+    pub fn new(resolution: usize) -> Self {
+        let resolution = resolution.max(1);
+        let mut cell_centres = Vec::with_capacity(6 * resolution * resolution);
+        let mut cell_radii = Vec::with_capacity(6 * resolution * resolution);
+        for face in 0..6 {
+            for i in 0..resolution {
+                for j in 0..resolution {
+                    let to_face_coordinate = |cell: f32| 2.0 * cell / resolution as f32 - 1.0;
+                    let centre = face_vector(face, to_face_coordinate(i as f32 + 0.5), to_face_coordinate(j as f32 + 0.5)).normalize();
+                    let radius = [(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (1.0, 1.0)]
+                        .into_iter()
+                        .map(|(di, dj)| angle_between(&centre, &face_vector(face, to_face_coordinate(i as f32 + di), to_face_coordinate(j as f32 + dj)).normalize()))
+                        .fold(0.0, f32::max);
+                    cell_centres.push(centre);
+                    cell_radii.push(radius);
+                }
+            }
+        }
+        Self {
+            resolution,
+            cells: (0..6 * resolution * resolution).map(|_| Vec::new()).collect(),
+            cell_centres,
+            cell_radii,
+        }
+    }
+
+    pub fn from_points(resolution: usize, points: impl IntoIterator<Item = (Vector3<f32>, T)>) -> Self {
+        let mut index = Self::new(resolution);
+        for (point, value) in points {
+            index.insert(point, value);
+        }
+        index
+    }
+
+    /// The point does not have to be normalised
+    pub fn insert(&mut self, point: Vector3<f32>, value: T) {
+        let point = point.normalize();
+        if point.iter().any(|coordinate| coordinate.is_nan()) {
+            return;
+        }
+        let cell = self.cell_of(&point);
+        self.cells[cell].push((point, value));
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.iter().map(|cell| cell.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(|cell| cell.is_empty())
+    }
+
+    fn cell_of(&self, point: &Vector3<f32>) -> usize {
+        let (x, y, z) = (point.x.abs(), point.y.abs(), point.z.abs());
+        let (face, major, u, v) = if x >= y && x >= z {
+            (if point.x >= 0.0 { 0 } else { 1 }, x, point.y, point.z)
+        } else if y >= z {
+            (if point.y >= 0.0 { 2 } else { 3 }, y, point.x, point.z)
+        } else {
+            (if point.z >= 0.0 { 4 } else { 5 }, z, point.x, point.y)
+        };
+        let to_cell = |coordinate: f32| ((((coordinate / major) + 1.0) / 2.0 * self.resolution as f32) as usize).min(self.resolution - 1);
+        face * self.resolution * self.resolution + to_cell(u) * self.resolution + to_cell(v)
+    }
+
+    /// All the points in the cells touching the circle. Some of them may lie outside of it, but none inside of the circle are left out, which is all that is needed for culling.
+    pub fn candidates_within(&self, centre: &Vector3<f32>, radius: angle::Rad<f32>) -> impl Iterator<Item = &(Vector3<f32>, T)> {
+        let centre = centre.normalize();
+        let radius = radius.value();
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(i, cell)| !cell.is_empty() && angle_between(&centre, &self.cell_centres[*i]) <= radius + self.cell_radii[*i])
+            .flat_map(|(_, cell)| cell.iter())
+    }
+
+    /// All the points inside of the circle
+    pub fn within(&self, centre: &Vector3<f32>, radius: angle::Rad<f32>) -> impl Iterator<Item = &(Vector3<f32>, T)> {
+        let normalised_centre = centre.normalize();
+        let min_cos = radius.value().min(std::f32::consts::PI).cos();
+        self.candidates_within(centre, radius).filter(move |(point, _)| point.dot(&normalised_centre) >= min_cos)
+    }
+
+    /// The closest point not further than `max_radius` away, together with its distance
+    pub fn nearest(&self, point: &Vector3<f32>, max_radius: angle::Rad<f32>) -> Option<(&T, angle::Rad<f32>)> {
+        let normalised_point = point.normalize();
+        self.within(point, max_radius)
+            .map(|(other, value)| (value, angle_between(&normalised_point, other)))
+            .min_by(|(_, distance_1), (_, distance_2)| distance_1.total_cmp(distance_2))
+            .map(|(value, distance)| (value, angle::Rad(distance)))
+    }
+}
+
+/// A point on the face of the cube with the given coordinates on that face (both between -1 and 1)
+fn face_vector(face: usize, u: f32, v: f32) -> Vector3<f32> {
+    match face {
+        0 => Vector3::new(1.0, u, v),
+        1 => Vector3::new(-1.0, u, v),
+        2 => Vector3::new(u, 1.0, v),
+        3 => Vector3::new(u, -1.0, v),
+        4 => Vector3::new(u, v, 1.0),
+        _ => Vector3::new(u, v, -1.0),
+    }
+}
+
+/// Both vectors have to be normalised
+fn angle_between(a: &Vector3<f32>, b: &Vector3<f32>) -> f32 {
+    a.dot(b).clamp(-1.0, 1.0).acos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = rand::thread_rng();
+        let points: Vec<Vector3<f32>> = (0..5000)
+            .map(|_| {
+                let (ra, dec) = crate::generate_random_point(&mut rng);
+                crate::get_point_vector(ra, dec, &nalgebra::Matrix3::identity())
+            })
+            .collect();
+        let index = CubeMapIndex::from_points(8, points.iter().enumerate().map(|(i, point)| (*point, i)));
+        assert_eq!(index.len(), points.len());
+
+        for (query, radius) in [(0, 0.05), (1, 0.3), (2, 1.0), (3, 3.0)] {
+            let centre = points[query];
+            let mut expected: Vec<usize> = (0..points.len()).filter(|&i| angle_between(&centre, &points[i].normalize()) <= radius).collect();
+            let mut found: Vec<usize> = index.within(&centre, angle::Rad(radius)).map(|(_, i)| *i).collect();
+            expected.sort();
+            found.sort();
+            assert_eq!(expected, found);
+        }
+
+        let (nearest, distance) = index.nearest(&points[42], angle::Rad(0.1)).unwrap();
+        assert_eq!(*nearest, 42);
+        assert!(distance.value() < 1e-3);
+    }
+
+    #[test]
+    fn points_on_the_edges() {
+        let mut index = CubeMapIndex::new(4);
+        for (i, point) in [Vector3::new(1.0, 1.0, 1.0), Vector3::new(-1.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0), Vector3::new(1.0, -1.0, 0.0)]
+            .into_iter()
+            .enumerate()
+        {
+            index.insert(point, i);
+        }
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.nearest(&Vector3::new(0.0, 0.01, -1.0), angle::Rad(0.1)).map(|(i, _)| *i), Some(2));
+        assert_eq!(index.nearest(&Vector3::new(0.0, 1.0, 0.0), angle::Rad(0.1)), None);
+    }
+}
@@ -0,0 +1,154 @@
+"""
+Converts the OpenNGC catalogue (NGC.csv from https://github.com/mattiaverga/OpenNGC) into a sky objects file with all the NGC and IC objects.
+Objects which are already in the other sky objects files (like the Messier and Caldwell catalogues) keep their object IDs, the app then loads them only once.
+The other objects get new object IDs following the largest one already in use.
+The result should be put into the sphere/sky-objects folder next to the executable, under a name sorted after the other catalogues (ngc-ic-catalogue.csv).
+
+Usage: python generate.py NGC.csv ngc-ic-catalogue.csv [--sky-objects ../../app/sphere/sky-objects] [--max-magnitude 16.0]
+"""
+import argparse
+import csv
+import os
+
+COLUMNS = [
+    "object_id",
+    "object_category",
+    "object_type",
+    "dec",
+    "ra",
+    "proper_names",
+    "bayer_designation",
+    "flamsteed_designation",
+    "hipparcos_number",
+    "hd_number",
+    "messier_number",
+    "caldwell_number",
+    "ngc_number",
+    "ic_number",
+    "constellations_abbreviations",
+    "mag",
+    "bv",
+    "distance",
+    "colour",
+    "object_type_text",
+]
+COLOUR = "8FA8C8FF"
+
+# OpenNGC object types, those missing here (stars, duplicates, nonexistent objects) are skipped
+TYPES = {
+    "G": ("Galaxy", "Galaxy"),
+    "GPair": ("Galaxy", "Galaxy Pair"),
+    "GTrpl": ("Galaxy", "Galaxy Triplet"),
+    "GGroup": ("Galaxy", "Galaxy Group"),
+    "OCl": ("OpenCluster", "Open Cluster"),
+    "Cl+N": ("OpenCluster", "Cluster with Nebula"),
+    "GCl": ("GlobularCluster", "Globular Cluster"),
+    "PN": ("PlanetaryNebula", "Planetary Nebula"),
+    "HII": ("DiffuseNebula", "HII Region"),
+    "EmN": ("DiffuseNebula", "Emission Nebula"),
+    "RfN": ("DiffuseNebula", "Reflection Nebula"),
+    "Neb": ("Nebula", "Nebula"),
+    "DrkN": ("DarkNebula", "Dark Nebula"),
+    "SNR": ("SupernovaRemnant", "Supernova Remnant"),
+    "*Ass": ("StarCloud", "Association of Stars"),
+}
+
+
+def parse_sexagesimal(value):
+    """Parses '12:34:56.7' or '-12:34:56.7' into a number of hours or degrees"""
+    sign = -1.0 if value.startswith("-") else 1.0
+    parts = [float(part) for part in value.lstrip("+-").split(":")]
+    return sign * (parts[0] + parts[1] / 60.0 + parts[2] / 3600.0)
+
+
+def parse_designation(name):
+    """Splits 'NGC0224' or 'IC0434' into the catalogue and the number, None for components like 'NGC0080 NED01' or 'NGC2362A'"""
+    for catalogue in ("NGC", "IC"):
+        if name.startswith(catalogue) and name[len(catalogue) :].isdigit():
+            return catalogue, int(name[len(catalogue) :])
+    return None
+
+
+def existing_objects(folder, output):
+    """The object IDs of the NGC and IC objects in the other sky objects files, and the largest object ID in use"""
+    ids = {}
+    max_id = 0
+    for file_name in sorted(os.listdir(folder)):
+        path = os.path.join(folder, file_name)
+        if not file_name.endswith(".csv") or os.path.abspath(path) == os.path.abspath(output):
+            continue
+        with open(path, newline="") as file:
+            for row in csv.DictReader(file):
+                object_id = int(row["object_id"])
+                max_id = max(max_id, object_id)
+                for catalogue, column in (("NGC", "ngc_number"), ("IC", "ic_number")):
+                    if row.get(column):
+                        ids.setdefault((catalogue, int(row[column])), object_id)
+    return ids, max_id
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Converts the OpenNGC catalogue into a sky objects file")
+    parser.add_argument("input")
+    parser.add_argument("output")
+    parser.add_argument("--sky-objects", default=os.path.join(os.path.dirname(__file__), "..", "..", "app", "sphere", "sky-objects"), help="The folder with the other sky objects files")
+    parser.add_argument("--max-magnitude", type=float, default=None, help="Objects fainter than this are skipped, objects without a magnitude are always kept")
+    args = parser.parse_args()
+
+    ids, max_id = existing_objects(args.sky_objects, args.output)
+    rows = []
+    skipped = 0
+    reused = 0
+    with open(args.input, newline="", encoding="utf-8") as file:
+        for row in csv.DictReader(file, delimiter=";"):
+            designation = parse_designation(row["Name"])
+            object_type = TYPES.get(row["Type"])
+            if designation is None or object_type is None:
+                skipped += 1
+                continue
+            try:
+                ra = parse_sexagesimal(row["RA"]) * 15.0
+                dec = parse_sexagesimal(row["Dec"])
+            except (ValueError, IndexError):
+                skipped += 1
+                continue
+            mag = row["V-Mag"] or row["B-Mag"]
+            if args.max_magnitude is not None and mag and float(mag) > args.max_magnitude:
+                continue
+            catalogue, number = designation
+            object_id = ids.get(designation)
+            if object_id is None:
+                max_id += 1
+                object_id = max_id
+            else:
+                reused += 1
+            rows.append(
+                {
+                    "object_id": object_id,
+                    "object_category": "Deepsky",
+                    "object_type": object_type[0],
+                    "dec": f"{dec:.8f}",
+                    "ra": f"{ra:.8f}",
+                    "proper_names": ";".join(name.strip() for name in row["Common names"].split(",") if name.strip()),
+                    "messier_number": int(row["M"]) if row["M"] else "",
+                    "ngc_number": number if catalogue == "NGC" else "",
+                    "ic_number": number if catalogue == "IC" else "",
+                    "constellations_abbreviations": row["Const"],
+                    "mag": mag,
+                    "colour": COLOUR,
+                    "object_type_text": object_type[1],
+                }
+            )
+    if skipped > 0:
+        print(f"Skipped {skipped} rows which are not deepsky objects or could not be parsed")
+
+    rows.sort(key=lambda row: row["object_id"])
+    with open(args.output, "w", newline="") as file:
+        writer = csv.DictWriter(file, fieldnames=COLUMNS, restval="", lineterminator="\n")
+        writer.writeheader()
+        writer.writerows(rows)
+    print(f"Written {len(rows)} objects, {reused} of them already in the other files")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,88 @@
+"""
+Converts a large star catalogue in a CSV file (for example a Tycho-2 subset) into the tiled binary format read by app/src/rendering/caspr/tiled_stars.rs.
+The CSV file needs the columns ra, dec (both in degrees), vmag and optionally bv.
+The result should be put into the sphere/tiled-stars folder next to the executable.
+
+Usage: python generate.py input.csv output.sgts [--tile-size 10] [--min-magnitude 6.0] [--max-magnitude 12.0]
+"""
+import argparse
+import csv
+import math
+import struct
+
+MAGIC = b"SGTS"
+VERSION = 1
+HEADER_FORMAT = "<4sHfI"
+TILE_FORMAT = "<ffffQIf"
+RECORD_FORMAT = "<ffff"
+
+
+def make_tiles(tile_size):
+    """Splits the sky into declination bands, each of them split into roughly square tiles along the right ascension"""
+    tiles = []
+    bands = math.ceil(180.0 / tile_size)
+    for band in range(bands):
+        dec_min = -90.0 + band * tile_size
+        dec_max = min(dec_min + tile_size, 90.0)
+        band_centre = math.radians((dec_min + dec_max) / 2.0)
+        ra_tiles = max(1, math.ceil((360.0 / tile_size) * math.cos(band_centre)))
+        ra_size = 360.0 / ra_tiles
+        band_tiles = []
+        for ra_tile in range(ra_tiles):
+            ra_min = ra_tile * ra_size
+            band_tiles.append({"dec_min": dec_min, "dec_max": dec_max, "ra_min": ra_min, "ra_max": ra_min + ra_size, "stars": []})
+        tiles.append(band_tiles)
+    return tiles
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Converts a star catalogue into a tiled star catalogue")
+    parser.add_argument("input")
+    parser.add_argument("output")
+    parser.add_argument("--tile-size", type=float, default=10.0, help="The size of a tile in degrees")
+    parser.add_argument("--min-magnitude", type=float, default=6.0, help="Stars brighter than this are skipped, since they are already in the main star catalogue")
+    parser.add_argument("--max-magnitude", type=float, default=12.0, help="Stars fainter than this are skipped")
+    args = parser.parse_args()
+
+    tiles = make_tiles(args.tile_size)
+    skipped = 0
+    with open(args.input, newline="") as file:
+        for row in csv.DictReader(file):
+            try:
+                ra = float(row["ra"]) % 360.0
+                dec = float(row["dec"])
+                vmag = float(row["vmag"])
+            except (KeyError, ValueError):
+                skipped += 1
+                continue
+            if vmag < args.min_magnitude or vmag > args.max_magnitude:
+                continue
+            try:
+                bv = float(row.get("bv") or "nan")
+            except ValueError:
+                bv = float("nan")
+            band = min(int((dec + 90.0) // args.tile_size), len(tiles) - 1)
+            band_tiles = tiles[band]
+            ra_tile = min(int(ra // (360.0 / len(band_tiles))), len(band_tiles) - 1)
+            band_tiles[ra_tile]["stars"].append((ra, dec, vmag, bv))
+    if skipped > 0:
+        print(f"Skipped {skipped} rows that could not be parsed")
+
+    tiles = [tile for band_tiles in tiles for tile in band_tiles]
+    offset = struct.calcsize(HEADER_FORMAT) + struct.calcsize(TILE_FORMAT) * len(tiles)
+    with open(args.output, "wb") as file:
+        file.write(struct.pack(HEADER_FORMAT, MAGIC, VERSION, args.tile_size, len(tiles)))
+        for tile in tiles:
+            # The stars are sorted by their magnitude, so only the start of a tile has to be read for bright limiting magnitudes
+            tile["stars"].sort(key=lambda star: star[2])
+            faintest = tile["stars"][-1][2] if tile["stars"] else float("nan")
+            file.write(struct.pack(TILE_FORMAT, tile["dec_min"], tile["dec_max"], tile["ra_min"], tile["ra_max"], offset, len(tile["stars"]), faintest))
+            offset += struct.calcsize(RECORD_FORMAT) * len(tile["stars"])
+        for tile in tiles:
+            for star in tile["stars"]:
+                file.write(struct.pack(RECORD_FORMAT, *star))
+    print(f"Written {sum(len(tile['stars']) for tile in tiles)} stars in {len(tiles)} tiles")
+
+
+if __name__ == "__main__":
+    main()